@@ -0,0 +1,166 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    macros::MacroParam,
+    random::noise,
+    ui::{Ui, UiSineWaveData},
+};
+
+// Each drifted param with its noise channel, drift range and clamp.
+const DRIFTED: [(MacroParam, u32, f32, f32, f32); 6] = [
+    (MacroParam::CenterX, 0, 0.15, 0.05, 0.95),
+    (MacroParam::CenterY, 1, 0.15, 0.05, 0.95),
+    (MacroParam::InnerRadius, 2, 0.15, 0.05, 1.),
+    (MacroParam::Amplitude, 3, 0.03, 0., 0.1),
+    (MacroParam::Thickness, 4, 0.01, 0.01, 0.1),
+    (MacroParam::Speed, 5, 0.01, -0.1, 0.1),
+];
+
+// The values a wave drifts around and what the drift last wrote over them. A value that no longer
+// matches what was written was changed elsewhere, by a preset load, a cue or a slider, and becomes
+// the new base instead of being overwritten.
+#[derive(Clone, Copy)]
+struct Drifted {
+    base: [f32; 6],
+    written: [f32; 6],
+}
+
+impl Drifted {
+    fn base(drifted: Option<Self>, wave: &UiSineWaveData) -> [f32; 6] {
+        std::array::from_fn(|i| {
+            let current = DRIFTED[i].0.value(wave);
+            match drifted {
+                Some(drifted) if drifted.written[i] == current => drifted.base[i],
+                _ => current,
+            }
+        })
+    }
+}
+
+pub(crate) struct Ambient {
+    last_input: Instant,
+    started: Instant,
+    last_cycle: Instant,
+    active: bool,
+    drifted: Vec<Option<Drifted>>,
+}
+
+impl Default for Ambient {
+    fn default() -> Self {
+        let now = Instant::now();
+
+        Self {
+            last_input: now,
+            started: now,
+            last_cycle: now,
+            active: false,
+            drifted: Vec::new(),
+        }
+    }
+}
+
+impl Ambient {
+    pub(crate) fn register_input(&mut self) {
+        self.last_input = Instant::now();
+    }
+
+    pub(crate) fn update(&mut self, ui: &mut Ui) {
+        let settings = &ui.ambient;
+        let idle = Duration::from_secs_f32(settings.idle_minutes.max(0.) * 60.);
+        let active = settings.enabled || (settings.idle && self.last_input.elapsed() >= idle);
+
+        ui.ambient.active = active;
+
+        if !active {
+            self.restore(ui);
+            return;
+        }
+
+        let now = Instant::now();
+        if !self.active {
+            self.active = true;
+            self.started = now;
+            self.last_cycle = now;
+        }
+
+        let cycle = Duration::from_secs_f32(ui.ambient.cycle_minutes.max(0.1) * 60.);
+        if ui.ambient.cycle_presets && now - self.last_cycle >= cycle {
+            self.last_cycle = now;
+            Self::cycle_preset(ui);
+        }
+
+        let time = (now - self.started).as_secs_f32() * ui.ambient.drift_speed;
+        let drift = Drift {
+            seed: ui.ambient.seed,
+            time,
+            intensity: ui.ambient.intensity,
+        };
+
+        self.drifted.resize(ui.waves.0.len(), None);
+        for (i, (wave, drifted)) in ui.waves.0.iter_mut().zip(&mut self.drifted).enumerate() {
+            *drifted = wave.init.then(|| {
+                let base = Drifted::base(*drifted, wave);
+                drift.apply(i as u32, base, wave)
+            });
+        }
+    }
+
+    fn cycle_preset(ui: &mut Ui) {
+        let presets = ui.presets.names.clone();
+        if presets.is_empty() {
+            return;
+        }
+
+        let next = presets
+            .iter()
+            .position(|name| *name == ui.presets.name)
+            .map_or(0, |i| (i + 1) % presets.len());
+
+        ui.load_preset(&presets[next]);
+    }
+
+    // Puts back the base of every value the drift still owns; anything replaced since is kept.
+    fn restore(&mut self, ui: &mut Ui) {
+        if !std::mem::take(&mut self.active) {
+            return;
+        }
+
+        for (wave, drifted) in ui.waves.0.iter_mut().zip(self.drifted.drain(..)) {
+            let Some(drifted) = drifted else {
+                continue;
+            };
+            for (i, (param, ..)) in DRIFTED.into_iter().enumerate() {
+                if param.value(wave) == drifted.written[i] {
+                    *param.field(wave) = drifted.base[i];
+                }
+            }
+        }
+    }
+}
+
+struct Drift {
+    seed: u32,
+    time: f32,
+    intensity: f32,
+}
+
+impl Drift {
+    fn apply(&self, wave: u32, base: [f32; 6], target: &mut UiSineWaveData) -> Drifted {
+        let written = std::array::from_fn(|i| {
+            let (param, channel, range, min, max) = DRIFTED[i];
+            if target.locks.contains(param) {
+                return base[i];
+            }
+
+            let channel = wave * 8 + channel;
+            let drift = noise(self.seed, channel, self.time) - noise(self.seed, channel, 0.);
+            (base[i] + range * self.intensity * drift).clamp(min, max)
+        });
+
+        for (i, (param, ..)) in DRIFTED.into_iter().enumerate() {
+            *param.field(target) = written[i];
+        }
+
+        Drifted { base, written }
+    }
+}
@@ -1,47 +1,138 @@
-use winit::{application::ApplicationHandler, event::WindowEvent, window::Window};
+use std::time::Instant;
 
-use crate::render::Render;
+use winit::{
+    application::ApplicationHandler,
+    event::WindowEvent,
+    event_loop::{ActiveEventLoop, ControlFlow},
+    window::{Fullscreen, Window},
+};
+
+#[cfg(target_os = "linux")]
+use crate::wallpaper;
+use crate::{
+    args::Args,
+    branding,
+    kiosk::{self, Kiosk},
+    render::Render,
+    widget,
+};
 
-#[derive(Default)]
 pub(crate) enum App {
     Initialized {
-        render: Render,
+        render: Box<Render>,
+        args: Box<Args>,
+        kiosk: Option<Kiosk>,
+    },
+    Uninitialized {
+        args: Box<Args>,
+        retry: Option<Instant>,
     },
-    #[default]
-    Uninitialized,
 }
 
 impl App {
-    fn is_initialized(&self) -> bool {
-        !matches!(self, Self::Uninitialized)
+    pub(crate) fn new(args: Args) -> Self {
+        Self::Uninitialized {
+            args: Box::new(args),
+            retry: None,
+        }
+    }
+
+    // Kiosk installs run unattended, so a lost device tears the renderer down and builds a new one.
+    fn restart(&mut self, event_loop: &ActiveEventLoop) {
+        let Self::Initialized { render, args, .. } = self else {
+            return;
+        };
+
+        log::warn!("Restarting the renderer");
+        render.save_ui_state();
+        *self = Self::Uninitialized {
+            args: args.clone(),
+            retry: None,
+        };
+        self.resumed(event_loop);
     }
 }
 
 impl ApplicationHandler for App {
     fn resumed(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
-        if self.is_initialized() {
+        let Self::Uninitialized { args, retry } = self else {
             return;
-        }
+        };
+        *retry = None;
+
+        #[cfg(target_os = "linux")]
+        let attributes = if args.wallpaper {
+            match wallpaper::window_attributes(event_loop) {
+                Ok(attributes) => attributes,
+                Err(err) => {
+                    log::error!("{err:#}");
+                    event_loop.exit();
+                    return;
+                }
+            }
+        } else if args.widget {
+            widget::window_attributes()
+        } else {
+            Window::default_attributes().with_title(branding::TITLE)
+        };
+        #[cfg(not(target_os = "linux"))]
+        let attributes = if args.widget {
+            widget::window_attributes()
+        } else {
+            Window::default_attributes().with_title(branding::TITLE)
+        };
+        let attributes = attributes.with_window_icon(branding::icon());
+        let attributes = if args.kiosk.is_some() {
+            attributes.with_fullscreen(Some(Fullscreen::Borderless(None)))
+        } else {
+            attributes
+        };
 
         let window = event_loop
-            .create_window(Window::default_attributes())
+            .create_window(attributes)
             .expect("Failed to create Window");
 
-        let render = pollster::block_on(Render::new(window)).expect("Failed to create render");
+        let render = match pollster::block_on(Render::new(window, args)) {
+            Ok(render) => render,
+            Err(err) if args.kiosk.is_some() => {
+                log::error!(
+                    "Failed to create render, retrying in {:?}: {err:#}",
+                    kiosk::RESTART_DELAY
+                );
+                *retry = Some(Instant::now() + kiosk::RESTART_DELAY);
+                return;
+            }
+            Err(err) => panic!("Failed to create render: {err:#}"),
+        };
 
-        *self = Self::Initialized { render };
+        *self = Self::Initialized {
+            render: Box::new(render),
+            kiosk: args.kiosk.clone().map(Kiosk::new),
+            args: args.clone(),
+        };
     }
 
     fn window_event(
         &mut self,
         event_loop: &winit::event_loop::ActiveEventLoop,
-        window_id: winit::window::WindowId,
+        _window_id: winit::window::WindowId,
         event: winit::event::WindowEvent,
     ) {
-        let Self::Initialized { render } = self else {
+        let Self::Initialized { render, kiosk, .. } = self else {
             return;
         };
 
+        if let Some(kiosk) = kiosk {
+            if kiosk.exit_requested(&event) {
+                event_loop.exit();
+                return;
+            }
+            if matches!(event, WindowEvent::CloseRequested) {
+                log::info!("Ignoring close request in kiosk mode");
+                return;
+            }
+        }
+
         render.handle_ui_inputs(&event);
 
         match event {
@@ -51,14 +142,56 @@ impl ApplicationHandler for App {
             WindowEvent::Resized(physical_size) => {
                 render.resize(physical_size);
             }
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                render.scale_factor_changed(scale_factor);
+            }
 
-            WindowEvent::RedrawRequested => match render.render() {
-                Ok(()) => {}
-                Err(_) => {
+            WindowEvent::RedrawRequested => {
+                let result = render.render();
+                if kiosk.is_some() && (result.is_err() || render.device_lost()) {
+                    if let Err(err) = result {
+                        log::error!("Render failed: {err:#}");
+                    }
+                    self.restart(event_loop);
+                } else if result.is_err() {
                     event_loop.exit();
                 }
-            },
+            }
             _ => (),
         }
     }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        if let Self::Uninitialized {
+            retry: Some(retry), ..
+        } = self
+        {
+            if Instant::now() >= *retry {
+                self.resumed(event_loop);
+            } else {
+                event_loop.set_control_flow(ControlFlow::WaitUntil(*retry));
+            }
+            return;
+        }
+
+        let Self::Initialized { render, .. } = self else {
+            return;
+        };
+
+        let Some(deadline) = render.frame_deadline() else {
+            return;
+        };
+
+        if Instant::now() >= deadline {
+            render.request_redraw();
+        } else {
+            event_loop.set_control_flow(ControlFlow::WaitUntil(deadline));
+        }
+    }
+
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        if let Self::Initialized { render, .. } = self {
+            render.save_ui_state();
+        }
+    }
 }
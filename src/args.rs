@@ -0,0 +1,381 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow, bail};
+use wgpu::TextureFormat;
+
+use crate::{
+    color_space::{self, HdrRequest, OffScreenFormat},
+    kiosk::{self, Chord},
+};
+
+const WALLPAPER_FPS: f32 = 15.;
+const DUMP_DIR: &str = "frame-dump";
+const BATCH_DIR: &str = "batch";
+const DEFAULT_SHUTTER: f32 = 180.;
+
+#[derive(Clone, Debug)]
+pub(crate) struct BatchArgs {
+    pub(crate) presets: PathBuf,
+    pub(crate) out: PathBuf,
+    pub(crate) thumbnail: Option<u32>,
+    pub(crate) video: Option<f32>,
+    pub(crate) video_width: Option<u32>,
+    pub(crate) motion_blur: u32,
+    pub(crate) shutter: f32,
+    pub(crate) audio: Option<PathBuf>,
+}
+
+impl BatchArgs {
+    fn parse(mut raw: impl Iterator<Item = String>) -> Result<Self> {
+        let mut presets = None;
+        let mut out = None;
+        let mut thumbnail = None;
+        let mut video = None;
+        let mut video_width = None;
+        let mut motion_blur = 1;
+        let mut shutter = DEFAULT_SHUTTER;
+        let mut audio = None;
+
+        while let Some(arg) = raw.next() {
+            match arg.as_str() {
+                "--presets" => {
+                    let dir = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--presets` expects a directory"))?;
+                    presets = Some(PathBuf::from(dir));
+                }
+                "--out" => {
+                    let dir = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--out` expects a directory"))?;
+                    out = Some(PathBuf::from(dir));
+                }
+                "--thumbnail" => {
+                    let size = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--thumbnail` expects a width in pixels"))?;
+                    let size = size
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|size| (16..=8192).contains(size))
+                        .ok_or_else(|| anyhow!("Invalid thumbnail width `{size}`"))?;
+                    thumbnail = Some(size);
+                }
+                "--video" => {
+                    let duration = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--video` expects a duration such as `5s`"))?;
+                    let seconds = duration
+                        .strip_suffix('s')
+                        .unwrap_or(&duration)
+                        .parse::<f32>()
+                        .ok()
+                        .filter(|seconds| *seconds > 0. && *seconds <= 600.)
+                        .ok_or_else(|| anyhow!("Invalid video duration `{duration}`"))?;
+                    video = Some(seconds);
+                }
+                "--video-width" => {
+                    let size = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--video-width` expects a width in pixels"))?;
+                    let size = size
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|size| (16..=8192).contains(size))
+                        .ok_or_else(|| anyhow!("Invalid video width `{size}`"))?;
+                    video_width = Some(size);
+                }
+                "--motion-blur" => {
+                    let samples = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--motion-blur` expects a sample count"))?;
+                    motion_blur = samples
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|samples| (1..=64).contains(samples))
+                        .ok_or_else(|| anyhow!("Invalid motion blur sample count `{samples}`"))?;
+                }
+                "--shutter" => {
+                    let angle = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--shutter` expects an angle in degrees"))?;
+                    shutter = angle
+                        .parse::<f32>()
+                        .ok()
+                        .filter(|angle| (0. ..=360.).contains(angle))
+                        .ok_or_else(|| anyhow!("Invalid shutter angle `{angle}`"))?;
+                }
+                "--audio" => {
+                    let path = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--audio` expects an audio file"))?;
+                    audio = Some(PathBuf::from(path));
+                }
+                _ => bail!("Unknown batch argument `{arg}`"),
+            }
+        }
+
+        let presets = presets.ok_or_else(|| anyhow!("`batch` requires `--presets`"))?;
+        if thumbnail.is_none() && video.is_none() {
+            bail!("`batch` requires `--thumbnail` and/or `--video`");
+        }
+        if audio.is_some() && video.is_none() {
+            bail!("`--audio` requires `--video`");
+        }
+        if video_width.is_some() && video.is_none() {
+            bail!("`--video-width` requires `--video`");
+        }
+        if audio.is_some() && !cfg!(feature = "audio") {
+            bail!("`--audio` requires building with the `audio` feature");
+        }
+
+        Ok(Self {
+            presets,
+            out: out.unwrap_or_else(|| PathBuf::from(BATCH_DIR)),
+            thumbnail,
+            video,
+            video_width,
+            motion_blur,
+            shutter,
+            audio,
+        })
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+pub(crate) struct Args {
+    pub(crate) safe_mode: bool,
+    pub(crate) low_power: bool,
+    pub(crate) http: Option<String>,
+    pub(crate) wallpaper: bool,
+    pub(crate) widget: bool,
+    pub(crate) kiosk: Option<Chord>,
+    pub(crate) fps: Option<f32>,
+    pub(crate) unorm_surface: bool,
+    pub(crate) format: Option<TextureFormat>,
+    pub(crate) view_format: Option<TextureFormat>,
+    pub(crate) off_screen_format: Option<OffScreenFormat>,
+    pub(crate) hdr: Option<HdrRequest>,
+    pub(crate) dump_frame: Option<u32>,
+    pub(crate) dump_dir: Option<PathBuf>,
+    pub(crate) cpu_render: Option<PathBuf>,
+    pub(crate) cpu_size: Option<[u32; 2]>,
+    pub(crate) preset: Option<String>,
+    pub(crate) stream: Option<String>,
+    pub(crate) show_control: Option<String>,
+    pub(crate) project: Option<PathBuf>,
+    pub(crate) batch: Option<BatchArgs>,
+}
+
+impl Args {
+    pub(crate) fn parse() -> Result<Self> {
+        let mut args = Self::default();
+        let mut raw = std::env::args().skip(1).peekable();
+
+        if raw.next_if(|arg| arg == "batch").is_some() {
+            let mut flags = Vec::new();
+            for arg in raw {
+                match arg.as_str() {
+                    "--safe-mode" => args.safe_mode = true,
+                    _ => flags.push(arg),
+                }
+            }
+            args.batch = Some(BatchArgs::parse(flags.into_iter())?);
+            return Ok(args);
+        }
+
+        let mut kiosk = false;
+        let mut kiosk_chord = None;
+
+        while let Some(arg) = raw.next() {
+            match arg.as_str() {
+                "--safe-mode" => args.safe_mode = true,
+                "--low-power" => args.low_power = true,
+                "--http" => {
+                    let address = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--http` expects an address"))?;
+                    args.http = Some(address);
+                }
+                "--wallpaper" => args.wallpaper = true,
+                "--widget" => args.widget = true,
+                "--kiosk" => kiosk = true,
+                "--kiosk-chord" => {
+                    let chord = raw.next().ok_or_else(|| {
+                        anyhow!(
+                            "`--kiosk-chord` expects a chord such as `{}`",
+                            kiosk::DEFAULT_CHORD
+                        )
+                    })?;
+                    kiosk_chord = Some(Chord::parse(&chord)?);
+                }
+                "--fps" => {
+                    let fps = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--fps` expects a frame rate"))?;
+                    let fps = fps
+                        .parse::<f32>()
+                        .ok()
+                        .filter(|fps| *fps > 0.)
+                        .ok_or_else(|| anyhow!("Invalid frame rate `{fps}`"))?;
+                    args.fps = Some(fps);
+                }
+                "--surface-format" => {
+                    let format = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--surface-format` expects `srgb` or `unorm`"))?;
+                    args.unorm_surface = match format.as_str() {
+                        "srgb" => false,
+                        "unorm" => true,
+                        _ => bail!("Invalid surface format `{format}`"),
+                    };
+                }
+                "--format" | "--view-format" => {
+                    let name = raw.next().ok_or_else(|| {
+                        anyhow!(
+                            "`{arg}` expects one of {}",
+                            color_space::surface_format_names()
+                        )
+                    })?;
+                    let format = color_space::parse_surface_format(&name)
+                        .ok_or_else(|| anyhow!("Invalid surface format `{name}`"))?;
+                    if arg == "--format" {
+                        args.format = Some(format);
+                    } else {
+                        args.view_format = Some(format);
+                    }
+                }
+                "--offscreen-format" => {
+                    let format = raw.next().ok_or_else(|| {
+                        anyhow!("`--offscreen-format` expects `rgba8`, `rgba8-srgb` or `rgba16f`")
+                    })?;
+                    args.off_screen_format = Some(
+                        OffScreenFormat::parse(&format)
+                            .ok_or_else(|| anyhow!("Invalid off-screen format `{format}`"))?,
+                    );
+                }
+                "--hdr" => {
+                    let mode = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--hdr` expects `auto`, `scrgb` or `pq`"))?;
+                    args.hdr = Some(
+                        HdrRequest::parse(&mode)
+                            .ok_or_else(|| anyhow!("Invalid HDR mode `{mode}`"))?,
+                    );
+                }
+                "--dump-frame" => {
+                    let frame = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--dump-frame` expects a frame number"))?;
+                    let frame = frame
+                        .parse::<u32>()
+                        .map_err(|_| anyhow!("Invalid frame number `{frame}`"))?;
+                    args.dump_frame = Some(frame);
+                }
+                "--dump-dir" => {
+                    let dir = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--dump-dir` expects a directory"))?;
+                    args.dump_dir = Some(PathBuf::from(dir));
+                }
+                "--cpu-render" => {
+                    let path = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--cpu-render` expects an output path"))?;
+                    args.cpu_render = Some(PathBuf::from(path));
+                }
+                "--cpu-size" => {
+                    let size = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--cpu-size` expects a size such as `320x180`"))?;
+                    let parsed = size
+                        .split_once('x')
+                        .and_then(|(width, height)| {
+                            Some([width.parse().ok()?, height.parse().ok()?])
+                        })
+                        .filter(|size: &[u32; 2]| {
+                            size.iter().all(|side| (16..=4096).contains(side))
+                        })
+                        .ok_or_else(|| anyhow!("Invalid size `{size}`"))?;
+                    args.cpu_size = Some(parsed);
+                }
+                "--preset" => {
+                    let preset = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--preset` expects a preset name or path"))?;
+                    args.preset = Some(preset);
+                }
+                "--stream" => {
+                    let source = raw.next().ok_or_else(|| {
+                        anyhow!("`--stream` expects `stdin`, `udp:ADDRESS` or a path")
+                    })?;
+                    args.stream = Some(source);
+                }
+                "--show-control" => {
+                    let source = raw.next().ok_or_else(|| {
+                        anyhow!("`--show-control` expects a MIDI device path or `udp:ADDRESS`")
+                    })?;
+                    args.show_control = Some(source);
+                }
+                "--project" => {
+                    let dir = raw
+                        .next()
+                        .ok_or_else(|| anyhow!("`--project` expects a directory"))?;
+                    args.project = Some(PathBuf::from(dir));
+                }
+                _ => bail!("Unknown argument `{arg}`"),
+            }
+        }
+
+        if args.http.is_some() && !cfg!(feature = "http") {
+            bail!("`--http` requires building with the `http` feature");
+        }
+
+        if args.wallpaper && !cfg!(target_os = "linux") {
+            bail!("`--wallpaper` is only supported on Linux");
+        }
+
+        if kiosk_chord.is_some() && !kiosk {
+            bail!("`--kiosk-chord` requires `--kiosk`");
+        }
+
+        if kiosk && args.wallpaper {
+            bail!("`--kiosk` cannot be combined with `--wallpaper`");
+        }
+
+        if args.widget && (kiosk || args.wallpaper) {
+            bail!("`--widget` cannot be combined with `--kiosk` or `--wallpaper`");
+        }
+
+        if kiosk {
+            args.kiosk = Some(match kiosk_chord {
+                Some(chord) => chord,
+                None => Chord::parse(kiosk::DEFAULT_CHORD)?,
+            });
+        }
+
+        if args.hdr.is_some() && args.format.is_some() {
+            bail!("`--hdr` picks the surface format itself and cannot be combined with `--format`");
+        }
+
+        if args.dump_dir.is_some() && args.dump_frame.is_none() {
+            bail!("`--dump-dir` requires `--dump-frame`");
+        }
+
+        if args.cpu_size.is_some() && args.cpu_render.is_none() {
+            bail!("`--cpu-size` requires `--cpu-render`");
+        }
+
+        Ok(args)
+    }
+
+    pub(crate) fn dump_dir(&self) -> PathBuf {
+        self.dump_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DUMP_DIR))
+    }
+
+    pub(crate) fn frame_rate(&self) -> Option<f32> {
+        self.fps.or(self.wallpaper.then_some(WALLPAPER_FPS))
+    }
+}
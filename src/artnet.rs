@@ -0,0 +1,329 @@
+#[cfg(feature = "network")]
+use std::{
+    net::{ToSocketAddrs, UdpSocket},
+    sync::{
+        Arc,
+        atomic::{AtomicU8, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "network")]
+use anyhow::{Result, anyhow};
+#[cfg(feature = "network")]
+use wgpu::{
+    Buffer, BufferDescriptor, BufferUsages, COPY_BYTES_PER_ROW_ALIGNMENT, Extent3d, MapMode,
+    Origin3d, PollType, TexelCopyBufferInfo, TexelCopyBufferLayout, TexelCopyTextureInfo,
+    TextureAspect, TextureFormat,
+};
+use wgpu::{CommandEncoder, Device, Texture};
+
+use crate::ui::UiArtNet;
+#[cfg(feature = "network")]
+use crate::{color, screenshot, ui::ArtNetPoint};
+
+#[cfg(feature = "network")]
+const DMX_CHANNELS: usize = 512;
+#[cfg(feature = "network")]
+const SAMPLE_STRIDE: u64 = COPY_BYTES_PER_ROW_ALIGNMENT as u64;
+
+#[cfg(feature = "network")]
+const MAP_PENDING: u8 = 0;
+#[cfg(feature = "network")]
+const MAP_READY: u8 = 1;
+#[cfg(feature = "network")]
+const MAP_FAILED: u8 = 2;
+
+// How a sampled texel turns into 8-bit DMX levels, matching what a screenshot of the target shows.
+#[cfg(feature = "network")]
+#[derive(Clone, Copy)]
+enum Texel {
+    Rgba8,
+    Bgra8,
+    Rgba16Float,
+    Rgb10a2,
+}
+
+#[cfg(feature = "network")]
+impl Texel {
+    fn new(format: TextureFormat) -> Option<Self> {
+        match format {
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => Some(Self::Rgba8),
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => Some(Self::Bgra8),
+            TextureFormat::Rgba16Float => Some(Self::Rgba16Float),
+            TextureFormat::Rgb10a2Unorm => Some(Self::Rgb10a2),
+            _ => None,
+        }
+    }
+
+    fn rgb(self, bytes: &[u8]) -> [u8; 3] {
+        match self {
+            Self::Rgba8 => [bytes[0], bytes[1], bytes[2]],
+            Self::Bgra8 => [bytes[2], bytes[1], bytes[0]],
+            Self::Rgba16Float => std::array::from_fn(|i| {
+                let half = u16::from_le_bytes([bytes[2 * i], bytes[2 * i + 1]]);
+                let linear = screenshot::half_to_f32(half).clamp(0., 1.);
+                (color::linear_to_srgb(linear) * 255.).round() as u8
+            }),
+            Self::Rgb10a2 => {
+                let packed = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                std::array::from_fn(|i| (((packed >> (10 * i)) & 0x3ff) >> 2) as u8)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "network")]
+enum ReadbackState {
+    Idle,
+    Recorded,
+    Mapping(Arc<AtomicU8>),
+}
+
+#[cfg(feature = "network")]
+struct Readback {
+    buffer: Buffer,
+    capacity: usize,
+    points: Vec<ArtNetPoint>,
+    texel: Texel,
+    state: ReadbackState,
+}
+
+#[cfg(feature = "network")]
+impl Readback {
+    fn new(capacity: usize, device: &Device) -> Self {
+        Self {
+            buffer: device.create_buffer(&BufferDescriptor {
+                label: Some("Art-Net Readback Buffer"),
+                size: capacity as u64 * SAMPLE_STRIDE,
+                usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            }),
+            capacity,
+            points: Vec::new(),
+            texel: Texel::Rgba8,
+            state: ReadbackState::Idle,
+        }
+    }
+}
+
+#[cfg(feature = "network")]
+pub(crate) struct ArtNet {
+    socket: Option<UdpSocket>,
+    sequence: u8,
+    readback: Option<Readback>,
+    last_sample: Instant,
+}
+
+#[cfg(not(feature = "network"))]
+#[derive(Default)]
+pub(crate) struct ArtNet {}
+
+#[cfg(feature = "network")]
+impl Default for ArtNet {
+    fn default() -> Self {
+        Self {
+            socket: None,
+            sequence: 0,
+            readback: None,
+            last_sample: Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "network")]
+impl ArtNet {
+    pub(crate) fn receive(&mut self, settings: &mut UiArtNet, device: &Device) {
+        let Some(readback) = &mut self.readback else {
+            return;
+        };
+
+        let ReadbackState::Mapping(map_state) = &readback.state else {
+            return;
+        };
+
+        let _ = device.poll(PollType::Poll);
+        match map_state.load(Ordering::Acquire) {
+            MAP_PENDING => return,
+            MAP_FAILED => {
+                self.readback = None;
+                settings.status = Some("Art-Net: failed to read back the frame".to_owned());
+                return;
+            }
+            _ => {}
+        }
+
+        let mut dmx = [0u8; DMX_CHANNELS];
+        {
+            let mapped = readback.buffer.slice(..).get_mapped_range();
+            for (i, point) in readback.points.iter().enumerate() {
+                let offset = i * SAMPLE_STRIDE as usize;
+                let rgb = readback.texel.rgb(&mapped[offset..]);
+
+                let start = usize::from(point.channel.max(1)) - 1;
+                for (channel, value) in dmx.iter_mut().skip(start).zip(rgb) {
+                    *channel = value;
+                }
+            }
+        }
+        readback.buffer.unmap();
+        readback.state = ReadbackState::Idle;
+
+        settings.status = match self.send(settings, &dmx) {
+            Ok(()) => None,
+            Err(err) => Some(format!("Art-Net: {err}")),
+        };
+    }
+
+    pub(crate) fn record(
+        &mut self,
+        settings: &mut UiArtNet,
+        texture: &Texture,
+        encoder: &mut CommandEncoder,
+        device: &Device,
+    ) {
+        if !settings.enabled || settings.points.is_empty() {
+            return;
+        }
+
+        let interval = Duration::from_secs_f32(1. / settings.rate.max(1.));
+        if self.last_sample.elapsed() < interval {
+            return;
+        }
+
+        let Some(texel) = Texel::new(texture.format()) else {
+            settings.status = Some(format!(
+                "Art-Net: sampling {:?} targets is not supported",
+                texture.format()
+            ));
+            return;
+        };
+
+        let count = settings.points.len();
+        let readback = match self.readback.take() {
+            Some(readback) if !matches!(readback.state, ReadbackState::Idle) => {
+                self.readback = Some(readback);
+                return;
+            }
+            Some(readback) if readback.capacity >= count => readback,
+            _ => Readback::new(count, device),
+        };
+        let readback = self.readback.insert(readback);
+
+        let size = texture.size();
+        for (i, point) in settings.points.iter().enumerate() {
+            let [x, y] = point.position.map(|v| v.clamp(0., 1.));
+
+            encoder.copy_texture_to_buffer(
+                TexelCopyTextureInfo {
+                    texture,
+                    mip_level: 0,
+                    origin: Origin3d {
+                        x: (x * (size.width - 1) as f32).round() as u32,
+                        y: (y * (size.height - 1) as f32).round() as u32,
+                        z: 0,
+                    },
+                    aspect: TextureAspect::All,
+                },
+                TexelCopyBufferInfo {
+                    buffer: &readback.buffer,
+                    layout: TexelCopyBufferLayout {
+                        offset: i as u64 * SAMPLE_STRIDE,
+                        bytes_per_row: Some(SAMPLE_STRIDE as u32),
+                        rows_per_image: None,
+                    },
+                },
+                Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+
+        readback.points = settings.points.clone();
+        readback.texel = texel;
+        readback.state = ReadbackState::Recorded;
+        self.last_sample = Instant::now();
+    }
+
+    pub(crate) fn submitted(&mut self) {
+        let Some(readback) = &mut self.readback else {
+            return;
+        };
+
+        if !matches!(readback.state, ReadbackState::Recorded) {
+            return;
+        }
+
+        let map_state = Arc::new(AtomicU8::new(MAP_PENDING));
+        let callback_state = map_state.clone();
+        readback
+            .buffer
+            .slice(..)
+            .map_async(MapMode::Read, move |result| {
+                let state = if result.is_ok() {
+                    MAP_READY
+                } else {
+                    MAP_FAILED
+                };
+                callback_state.store(state, Ordering::Release);
+            });
+
+        readback.state = ReadbackState::Mapping(map_state);
+    }
+
+    fn send(&mut self, settings: &UiArtNet, dmx: &[u8; DMX_CHANNELS]) -> Result<()> {
+        let target = settings
+            .target
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| anyhow!("Could not resolve `{}`", settings.target))?;
+
+        let socket = match &self.socket {
+            Some(socket) => socket,
+            None => {
+                let socket = UdpSocket::bind("0.0.0.0:0")?;
+                socket.set_broadcast(true)?;
+                socket.set_nonblocking(true)?;
+                self.socket.insert(socket)
+            }
+        };
+
+        self.sequence = self.sequence.wrapping_add(1).max(1);
+
+        let mut packet = Vec::with_capacity(18 + DMX_CHANNELS);
+        packet.extend_from_slice(b"Art-Net\0");
+        packet.extend_from_slice(&0x5000u16.to_le_bytes());
+        packet.extend_from_slice(&14u16.to_be_bytes());
+        packet.push(self.sequence);
+        packet.push(0);
+        packet.extend_from_slice(&(settings.universe & 0x7fff).to_le_bytes());
+        packet.extend_from_slice(&(DMX_CHANNELS as u16).to_be_bytes());
+        packet.extend_from_slice(dmx);
+
+        socket.send_to(&packet, target)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "network"))]
+impl ArtNet {
+    pub(crate) fn receive(&mut self, settings: &mut UiArtNet, _device: &Device) {
+        settings.status = settings
+            .enabled
+            .then(|| "Art-Net requires building with the `network` feature".to_owned());
+    }
+
+    pub(crate) fn record(
+        &mut self,
+        _settings: &mut UiArtNet,
+        _texture: &Texture,
+        _encoder: &mut CommandEncoder,
+        _device: &Device,
+    ) {
+    }
+
+    pub(crate) fn submitted(&mut self) {}
+}
@@ -0,0 +1,103 @@
+pub(crate) mod analysis;
+#[cfg(feature = "audio")]
+pub(crate) mod player;
+pub(crate) mod response;
+pub(crate) mod synth;
+
+#[cfg(feature = "audio")]
+use crate::{
+    audio::{analysis::FFT_SIZE, player::Player, synth::Synth},
+    pipelines::spectrum::GPU_FFT_SIZE,
+};
+use crate::{
+    audio::{
+        analysis::{Analyzer, AudioFeatures},
+        synth::UiSynth,
+    },
+    ui::UiSineWaveData,
+};
+
+const WAVEFORM_RELEASE: f32 = 0.8;
+
+pub(crate) struct AudioFrame {
+    pub(crate) samples: Vec<f32>,
+    pub(crate) sample_rate: u32,
+}
+
+#[derive(Default)]
+pub(crate) struct Audio {
+    analyzer: Analyzer,
+    waveform: Vec<f32>,
+    gpu_analysis: bool,
+    pending: Option<AudioFrame>,
+    #[cfg(feature = "audio")]
+    pub(crate) player: Player,
+    #[cfg(feature = "audio")]
+    synth: Synth,
+}
+
+impl Audio {
+    pub(crate) fn set_gpu_analysis(&mut self, enabled: bool) {
+        self.gpu_analysis = enabled;
+    }
+
+    pub(crate) fn update(&mut self) {
+        self.pending = None;
+
+        #[cfg(feature = "audio")]
+        {
+            self.player.poll();
+
+            let len = if self.gpu_analysis {
+                GPU_FFT_SIZE
+            } else {
+                FFT_SIZE
+            };
+            if let Some((samples, sample_rate)) = self.player.recent_samples(len) {
+                self.waveform = samples[samples.len().saturating_sub(FFT_SIZE)..].to_vec();
+                if self.gpu_analysis {
+                    self.pending = Some(AudioFrame {
+                        samples,
+                        sample_rate,
+                    });
+                } else {
+                    self.analyzer.process(&samples, sample_rate);
+                }
+                return;
+            }
+        }
+
+        self.analyzer.decay();
+        for sample in &mut self.waveform {
+            *sample *= WAVEFORM_RELEASE;
+        }
+    }
+
+    pub(crate) fn sonify(&mut self, settings: &mut UiSynth, waves: &[UiSineWaveData]) {
+        #[cfg(feature = "audio")]
+        self.synth.update(settings, waves);
+
+        #[cfg(not(feature = "audio"))]
+        let _ = (settings, waves);
+    }
+
+    pub(crate) fn take_pending(&mut self) -> Option<AudioFrame> {
+        self.pending.take()
+    }
+
+    pub(crate) fn apply_gpu_analysis(&mut self, bins: &[f32], bands: [f32; 4]) {
+        self.analyzer.apply(bins, bands);
+    }
+
+    pub(crate) fn features(&self) -> &AudioFeatures {
+        self.analyzer.features()
+    }
+
+    pub(crate) fn waveform(&self) -> &[f32] {
+        &self.waveform
+    }
+
+    pub(crate) fn spectrum(&self) -> &[f32] {
+        self.analyzer.bins()
+    }
+}
@@ -0,0 +1,282 @@
+#![cfg_attr(not(feature = "audio"), allow(dead_code))]
+
+use std::{collections::VecDeque, f32::consts::PI};
+
+pub(crate) const FFT_SIZE: usize = 1024;
+pub(crate) const SPECTRUM_BINS: usize = 512;
+
+const SPECTRUM_LOW: f32 = 20.;
+const SPECTRUM_RANGE: f32 = 1000.;
+
+const BEAT_HISTORY: usize = 43;
+const BEAT_THRESHOLD: f32 = 1.4;
+const BEAT_DECAY: f32 = 0.85;
+const RELEASE: f32 = 0.8;
+
+#[derive(Clone, Copy, Default, Debug)]
+pub(crate) struct AudioFeatures {
+    pub(crate) level: f32,
+    pub(crate) bass: f32,
+    pub(crate) mid: f32,
+    pub(crate) treble: f32,
+    pub(crate) beat: f32,
+}
+
+impl AudioFeatures {
+    pub(crate) fn scaled(&self, amount: f32) -> Self {
+        Self {
+            level: self.level * amount,
+            bass: self.bass * amount,
+            mid: self.mid * amount,
+            treble: self.treble * amount,
+            beat: self.beat * amount,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct AudioSnapshot {
+    pub(crate) features: AudioFeatures,
+    pub(crate) spectrum: Vec<f32>,
+    pub(crate) waveform: Vec<f32>,
+}
+
+// The whole file analyzed up front at a fixed rate, so a non-realtime export sees exactly the
+// features a live run would have heard at each instant.
+pub(crate) struct OfflineAnalysis {
+    samples: Vec<f32>,
+    sample_rate: u32,
+    rate: f32,
+    features: Vec<AudioFeatures>,
+    spectra: Vec<Vec<f32>>,
+}
+
+impl OfflineAnalysis {
+    pub(crate) fn new(samples: Vec<f32>, sample_rate: u32, rate: f32, seconds: f32) -> Self {
+        let length = samples.len() as f32 / sample_rate as f32;
+        let count = (length.min(seconds) * rate).ceil() as usize + 1;
+
+        let mut analyzer = Analyzer::default();
+        let mut features = Vec::with_capacity(count);
+        let mut spectra = Vec::with_capacity(count);
+        for i in 0..count {
+            let end = Self::sample_index(i, rate, sample_rate).min(samples.len());
+            let start = end.saturating_sub(FFT_SIZE);
+            analyzer.process(&samples[start..end], sample_rate);
+            features.push(analyzer.features);
+            spectra.push(analyzer.bins.clone());
+        }
+
+        Self {
+            samples,
+            sample_rate,
+            rate,
+            features,
+            spectra,
+        }
+    }
+
+    fn sample_index(i: usize, rate: f32, sample_rate: u32) -> usize {
+        (i as f64 / rate as f64 * sample_rate as f64).round() as usize
+    }
+
+    // Past the end of the file the scene hears silence rather than the last analyzed frame.
+    pub(crate) fn snapshot(&self, seconds: f32) -> AudioSnapshot {
+        let i = (seconds.max(0.) * self.rate).round() as usize;
+        let (Some(features), Some(spectrum)) = (self.features.get(i), self.spectra.get(i)) else {
+            return AudioSnapshot {
+                features: AudioFeatures::default(),
+                spectrum: vec![0.; SPECTRUM_BINS],
+                waveform: Vec::new(),
+            };
+        };
+
+        let end = Self::sample_index(i, self.rate, self.sample_rate).min(self.samples.len());
+        let start = end.saturating_sub(FFT_SIZE);
+        AudioSnapshot {
+            features: *features,
+            spectrum: spectrum.clone(),
+            waveform: self.samples[start..end].to_vec(),
+        }
+    }
+}
+
+pub(crate) struct Analyzer {
+    window: Vec<f32>,
+    re: Vec<f32>,
+    im: Vec<f32>,
+    spectrum: Vec<f32>,
+    bins: Vec<f32>,
+    bass_history: VecDeque<f32>,
+    features: AudioFeatures,
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        let window = (0..FFT_SIZE)
+            .map(|i| 0.5 - 0.5 * (2. * PI * i as f32 / (FFT_SIZE - 1) as f32).cos())
+            .collect();
+
+        Self {
+            window,
+            re: vec![0.; FFT_SIZE],
+            im: vec![0.; FFT_SIZE],
+            spectrum: vec![0.; FFT_SIZE / 2],
+            bins: vec![0.; SPECTRUM_BINS],
+            bass_history: VecDeque::with_capacity(BEAT_HISTORY),
+            features: AudioFeatures::default(),
+        }
+    }
+}
+
+impl Analyzer {
+    pub(crate) fn features(&self) -> &AudioFeatures {
+        &self.features
+    }
+
+    pub(crate) fn bins(&self) -> &[f32] {
+        &self.bins
+    }
+
+    pub(crate) fn process(&mut self, samples: &[f32], sample_rate: u32) {
+        let offset = FFT_SIZE.saturating_sub(samples.len());
+        let samples = &samples[samples.len().saturating_sub(FFT_SIZE)..];
+
+        self.re.fill(0.);
+        self.im.fill(0.);
+        for (i, sample) in samples.iter().enumerate() {
+            self.re[offset + i] = sample * self.window[offset + i];
+        }
+
+        fft(&mut self.re, &mut self.im);
+
+        let norm = 4. / FFT_SIZE as f32;
+        for (bin, magnitude) in self.spectrum.iter_mut().enumerate() {
+            *magnitude = (self.re[bin] * self.re[bin] + self.im[bin] * self.im[bin]).sqrt() * norm;
+        }
+
+        let bin_width = sample_rate as f32 / FFT_SIZE as f32;
+        for (i, value) in self.bins.iter_mut().enumerate() {
+            let (low, high) = log_bin_range(i, bin_width, self.spectrum.len());
+            let peak = self.spectrum[low..high].iter().copied().fold(0., f32::max);
+            *value = peak.min(1.).sqrt();
+        }
+
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / FFT_SIZE as f32).sqrt();
+
+        let bass = self.band(20., 250., sample_rate);
+        let mid = self.band(250., 4000., sample_rate);
+        let treble = self.band(4000., 16000., sample_rate);
+
+        self.update_features(rms, bass, mid, treble);
+    }
+
+    pub(crate) fn apply(&mut self, bins: &[f32], [rms, bass, mid, treble]: [f32; 4]) {
+        for (value, bin) in self.bins.iter_mut().zip(bins) {
+            *value = *bin;
+        }
+
+        self.update_features(rms, bass.min(1.), mid.min(1.), treble.min(1.));
+    }
+
+    fn update_features(&mut self, rms: f32, bass: f32, mid: f32, treble: f32) {
+        let average = if self.bass_history.is_empty() {
+            0.
+        } else {
+            self.bass_history.iter().sum::<f32>() / self.bass_history.len() as f32
+        };
+
+        if self.bass_history.len() == BEAT_HISTORY {
+            self.bass_history.pop_front();
+        }
+        self.bass_history.push_back(bass);
+
+        let features = &mut self.features;
+        features.level = smooth(features.level, (rms * 2f32.sqrt()).min(1.));
+        features.bass = smooth(features.bass, bass);
+        features.mid = smooth(features.mid, mid);
+        features.treble = smooth(features.treble, treble);
+
+        features.beat = if bass > 0.05 && bass > average * BEAT_THRESHOLD {
+            1.
+        } else {
+            features.beat * BEAT_DECAY
+        };
+    }
+
+    pub(crate) fn decay(&mut self) {
+        self.spectrum
+            .iter_mut()
+            .chain(&mut self.bins)
+            .for_each(|magnitude| *magnitude *= RELEASE);
+        self.bass_history.clear();
+
+        let features = &mut self.features;
+        features.level *= RELEASE;
+        features.bass *= RELEASE;
+        features.mid *= RELEASE;
+        features.treble *= RELEASE;
+        features.beat *= BEAT_DECAY;
+    }
+
+    fn band(&self, low: f32, high: f32, sample_rate: u32) -> f32 {
+        let bin_width = sample_rate as f32 / FFT_SIZE as f32;
+        let low = ((low / bin_width) as usize).max(1);
+        let high = ((high / bin_width) as usize).clamp(low + 1, self.spectrum.len());
+
+        let power = self.spectrum[low..high].iter().map(|m| m * m).sum::<f32>();
+
+        power.sqrt().min(1.)
+    }
+}
+
+fn log_bin_range(bin: usize, bin_width: f32, len: usize) -> (usize, usize) {
+    let frequency = |i: usize| SPECTRUM_LOW * SPECTRUM_RANGE.powf(i as f32 / SPECTRUM_BINS as f32);
+
+    let low = ((frequency(bin) / bin_width) as usize).clamp(1, len - 1);
+    let high = ((frequency(bin + 1) / bin_width) as usize).clamp(low + 1, len);
+
+    (low, high)
+}
+
+fn smooth(previous: f32, next: f32) -> f32 {
+    if next > previous {
+        next
+    } else {
+        previous * RELEASE + next * (1. - RELEASE)
+    }
+}
+
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    let bits = n.trailing_zeros();
+
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut size = 2;
+    while size <= n {
+        let angle = -2. * PI / size as f32;
+        for start in (0..n).step_by(size) {
+            for k in 0..size / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let even = start + k;
+                let odd = even + size / 2;
+
+                let t_re = re[odd] * cos - im[odd] * sin;
+                let t_im = re[odd] * sin + im[odd] * cos;
+
+                re[odd] = re[even] - t_re;
+                im[odd] = im[even] - t_im;
+                re[even] += t_re;
+                im[even] += t_im;
+            }
+        }
+        size *= 2;
+    }
+}
@@ -0,0 +1,419 @@
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+    },
+    thread::{self, JoinHandle},
+};
+
+use anyhow::{Result, anyhow};
+use cpal::{
+    FromSample, OutputCallbackInfo, SampleFormat, SizedSample, Stream, StreamConfig,
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+};
+use symphonia::core::{
+    audio::SampleBuffer, codecs::DecoderOptions, errors::Error as SymphoniaError,
+    formats::FormatOptions, io::MediaSourceStream, meta::MetadataOptions, probe::Hint,
+};
+
+const NO_SEEK: u64 = u64::MAX;
+
+pub(crate) struct Track {
+    samples: Vec<f32>,
+    channels: usize,
+    sample_rate: u32,
+}
+
+impl Track {
+    pub(crate) fn decode(path: &Path) -> Result<Self> {
+        let source = MediaSourceStream::new(Box::new(File::open(path)?), Default::default());
+
+        let mut hint = Hint::new();
+        if let Some(extension) = path.extension().and_then(|extension| extension.to_str()) {
+            hint.with_extension(extension);
+        }
+
+        let mut format = symphonia::default::get_probe()
+            .format(
+                &hint,
+                source,
+                &FormatOptions::default(),
+                &MetadataOptions::default(),
+            )?
+            .format;
+
+        let track = format
+            .default_track()
+            .ok_or_else(|| anyhow!("No audio track found"))?;
+        let track_id = track.id;
+
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())?;
+
+        let mut samples = Vec::new();
+        let mut channels = track.codec_params.channels.map(|c| c.count());
+        let mut sample_rate = track.codec_params.sample_rate;
+
+        loop {
+            let packet = match format.next_packet() {
+                Ok(packet) => packet,
+                Err(SymphoniaError::IoError(err))
+                    if err.kind() == std::io::ErrorKind::UnexpectedEof =>
+                {
+                    break;
+                }
+                Err(err) => return Err(err.into()),
+            };
+
+            if packet.track_id() != track_id {
+                continue;
+            }
+
+            let decoded = match decoder.decode(&packet) {
+                Ok(decoded) => decoded,
+                Err(SymphoniaError::DecodeError(_)) => continue,
+                Err(err) => return Err(err.into()),
+            };
+
+            let spec = *decoded.spec();
+            channels.get_or_insert(spec.channels.count());
+            sample_rate.get_or_insert(spec.rate);
+
+            let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+            buffer.copy_interleaved_ref(decoded);
+            samples.extend_from_slice(buffer.samples());
+        }
+
+        Ok(Self {
+            samples,
+            channels: channels.unwrap_or(1).max(1),
+            sample_rate: sample_rate.ok_or_else(|| anyhow!("Unknown sample rate"))?,
+        })
+    }
+
+    pub(crate) fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub(crate) fn mono_samples(&self) -> Vec<f32> {
+        (0..self.frames()).map(|frame| self.mono(frame)).collect()
+    }
+
+    fn frames(&self) -> usize {
+        self.samples.len() / self.channels
+    }
+
+    fn duration(&self) -> f64 {
+        self.frames() as f64 / self.sample_rate as f64
+    }
+
+    fn sample(&self, position: f64, channel: usize) -> f32 {
+        let frame = position as usize;
+        let fract = (position - frame as f64) as f32;
+        let channel = channel.min(self.channels - 1);
+
+        let at = |frame: usize| {
+            self.samples
+                .get(frame * self.channels + channel)
+                .copied()
+                .unwrap_or(0.)
+        };
+
+        at(frame) * (1. - fract) + at(frame + 1) * fract
+    }
+
+    fn mono(&self, frame: usize) -> f32 {
+        let start = frame * self.channels;
+        self.samples[start..start + self.channels]
+            .iter()
+            .sum::<f32>()
+            / self.channels as f32
+    }
+}
+
+struct Shared {
+    position: AtomicU64,
+    seek: AtomicU64,
+    playing: AtomicBool,
+    looping: AtomicBool,
+    volume: AtomicU32,
+}
+
+struct Playback {
+    track: Arc<Track>,
+    shared: Arc<Shared>,
+    _stream: Stream,
+}
+
+impl Playback {
+    fn new(track: Track, volume: f32, looping: bool) -> Result<Self> {
+        let track = Arc::new(track);
+        let shared = Arc::new(Shared {
+            position: AtomicU64::new(0f64.to_bits()),
+            seek: AtomicU64::new(NO_SEEK),
+            playing: AtomicBool::new(false),
+            looping: AtomicBool::new(looping),
+            volume: AtomicU32::new(volume.to_bits()),
+        });
+
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or_else(|| anyhow!("No audio output device available"))?;
+        let config = device.default_output_config()?;
+
+        let stream = match config.sample_format() {
+            SampleFormat::F32 => Self::build_stream::<f32>(&device, config.into(), &track, &shared),
+            SampleFormat::I16 => Self::build_stream::<i16>(&device, config.into(), &track, &shared),
+            SampleFormat::U16 => Self::build_stream::<u16>(&device, config.into(), &track, &shared),
+            SampleFormat::I32 => Self::build_stream::<i32>(&device, config.into(), &track, &shared),
+            sample_format => Err(anyhow!("Unsupported sample format {sample_format}")),
+        }?;
+
+        stream.play()?;
+
+        Ok(Self {
+            track,
+            shared,
+            _stream: stream,
+        })
+    }
+
+    fn build_stream<T>(
+        device: &cpal::Device,
+        config: StreamConfig,
+        track: &Arc<Track>,
+        shared: &Arc<Shared>,
+    ) -> Result<Stream>
+    where
+        T: SizedSample + FromSample<f32>,
+    {
+        let channels = config.channels as usize;
+        let step = track.sample_rate as f64 / config.sample_rate as f64;
+        let frames = track.frames() as f64;
+
+        let track = track.clone();
+        let shared = shared.clone();
+
+        let stream = device.build_output_stream(
+            config,
+            move |data: &mut [T], _: &OutputCallbackInfo| {
+                let mut position = f64::from_bits(shared.position.load(Ordering::Acquire));
+
+                let seek = shared.seek.swap(NO_SEEK, Ordering::AcqRel);
+                if seek != NO_SEEK {
+                    position = f64::from_bits(seek);
+                }
+
+                let mut playing = shared.playing.load(Ordering::Acquire);
+                let looping = shared.looping.load(Ordering::Relaxed);
+                let volume = f32::from_bits(shared.volume.load(Ordering::Relaxed));
+
+                for frame in data.chunks_mut(channels) {
+                    if !playing {
+                        frame.fill(T::from_sample(0.));
+                        continue;
+                    }
+
+                    for (channel, sample) in frame.iter_mut().enumerate() {
+                        *sample = T::from_sample(track.sample(position, channel) * volume);
+                    }
+
+                    position += step;
+                    if position >= frames {
+                        position = 0.;
+                        // The rest of the buffer is filled with silence rather than left stale.
+                        if !looping {
+                            shared.playing.store(false, Ordering::Release);
+                            playing = false;
+                        }
+                    }
+                }
+
+                shared.position.store(position.to_bits(), Ordering::Release);
+            },
+            |err| log::error!("Audio stream error: {err}"),
+            None,
+        )?;
+
+        Ok(stream)
+    }
+
+    fn position(&self) -> f64 {
+        let seek = self.shared.seek.load(Ordering::Acquire);
+        let position = if seek != NO_SEEK {
+            f64::from_bits(seek)
+        } else {
+            f64::from_bits(self.shared.position.load(Ordering::Acquire))
+        };
+
+        position / self.track.sample_rate as f64
+    }
+}
+
+enum PlayerState {
+    Empty,
+    Loading(PathBuf, JoinHandle<Result<Track>>),
+    Ready(PathBuf, Playback),
+    Failed(String),
+}
+
+pub(crate) enum PlayerStatus<'a> {
+    Empty,
+    Loading(&'a Path),
+    Ready(&'a Path),
+    Failed(&'a str),
+}
+
+pub(crate) struct Player {
+    state: PlayerState,
+    volume: f32,
+    looping: bool,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self {
+            state: PlayerState::Empty,
+            volume: 1.,
+            looping: false,
+        }
+    }
+}
+
+impl Player {
+    pub(crate) fn load(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        let decode_path = path.clone();
+
+        let handle = thread::spawn(move || Track::decode(&decode_path));
+
+        self.state = PlayerState::Loading(path, handle);
+    }
+
+    pub(crate) fn poll(&mut self) {
+        let PlayerState::Loading(_, handle) = &self.state else {
+            return;
+        };
+
+        if !handle.is_finished() {
+            return;
+        }
+
+        let PlayerState::Loading(path, handle) =
+            std::mem::replace(&mut self.state, PlayerState::Empty)
+        else {
+            unreachable!();
+        };
+
+        let playback = handle
+            .join()
+            .map_err(|_| anyhow!("Audio decoder thread panicked"))
+            .and_then(|track| track)
+            .and_then(|track| Playback::new(track, self.volume, self.looping));
+
+        self.state = match playback {
+            Ok(playback) => {
+                playback.shared.playing.store(true, Ordering::Release);
+                PlayerState::Ready(path, playback)
+            }
+            Err(err) => PlayerState::Failed(format!("{}: {err}", path.display())),
+        };
+    }
+
+    pub(crate) fn status(&self) -> PlayerStatus<'_> {
+        match &self.state {
+            PlayerState::Empty => PlayerStatus::Empty,
+            PlayerState::Loading(path, _) => PlayerStatus::Loading(path),
+            PlayerState::Ready(path, _) => PlayerStatus::Ready(path),
+            PlayerState::Failed(err) => PlayerStatus::Failed(err),
+        }
+    }
+
+    fn playback(&self) -> Option<&Playback> {
+        match &self.state {
+            PlayerState::Ready(_, playback) => Some(playback),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_playing(&self) -> bool {
+        self.playback()
+            .is_some_and(|playback| playback.shared.playing.load(Ordering::Acquire))
+    }
+
+    pub(crate) fn play(&self) {
+        if let Some(playback) = self.playback() {
+            playback.shared.playing.store(true, Ordering::Release);
+        }
+    }
+
+    pub(crate) fn pause(&self) {
+        if let Some(playback) = self.playback() {
+            playback.shared.playing.store(false, Ordering::Release);
+        }
+    }
+
+    pub(crate) fn stop(&self) {
+        self.pause();
+        self.seek(0.);
+    }
+
+    pub(crate) fn seek(&self, seconds: f64) {
+        if let Some(playback) = self.playback() {
+            let frame = (seconds * playback.track.sample_rate as f64)
+                .clamp(0., playback.track.frames() as f64);
+            playback
+                .shared
+                .seek
+                .store(frame.to_bits(), Ordering::Release);
+        }
+    }
+
+    pub(crate) fn position(&self) -> f64 {
+        self.playback().map_or(0., Playback::position)
+    }
+
+    pub(crate) fn duration(&self) -> f64 {
+        self.playback()
+            .map_or(0., |playback| playback.track.duration())
+    }
+
+    pub(crate) fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    pub(crate) fn set_volume(&mut self, volume: f32) {
+        self.volume = volume;
+        if let Some(playback) = self.playback() {
+            playback
+                .shared
+                .volume
+                .store(volume.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn looping(&self) -> bool {
+        self.looping
+    }
+
+    pub(crate) fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+        if let Some(playback) = self.playback() {
+            playback.shared.looping.store(looping, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn recent_samples(&self, len: usize) -> Option<(Vec<f32>, u32)> {
+        let playback = self.playback().filter(|_| self.is_playing())?;
+        let track = &playback.track;
+
+        let end = ((playback.position() * track.sample_rate as f64) as usize).min(track.frames());
+        let start = end.saturating_sub(len);
+
+        let samples = (start..end).map(|frame| track.mono(frame)).collect();
+
+        Some((samples, track.sample_rate))
+    }
+}
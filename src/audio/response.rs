@@ -0,0 +1,152 @@
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{audio::analysis::AudioFeatures, ui::UiSineWaveData};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub(crate) enum AudioBand {
+    Level,
+    #[default]
+    Bass,
+    Mid,
+    Treble,
+    Beat,
+}
+
+impl AudioBand {
+    pub(crate) const ALL: [AudioBand; 5] = [
+        AudioBand::Level,
+        AudioBand::Bass,
+        AudioBand::Mid,
+        AudioBand::Treble,
+        AudioBand::Beat,
+    ];
+
+    fn value(self, audio: &AudioFeatures) -> f32 {
+        match self {
+            AudioBand::Level => audio.level,
+            AudioBand::Bass => audio.bass,
+            AudioBand::Mid => audio.mid,
+            AudioBand::Treble => audio.treble,
+            AudioBand::Beat => audio.beat,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct AudioResponse {
+    pub(crate) enabled: bool,
+    pub(crate) band: AudioBand,
+    pub(crate) attack: f32,
+    pub(crate) release: f32,
+    pub(crate) gain: f32,
+    pub(crate) threshold: f32,
+}
+
+impl Default for AudioResponse {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            band: AudioBand::Bass,
+            attack: 0.01,
+            release: 0.25,
+            gain: 1.,
+            threshold: 0.,
+        }
+    }
+}
+
+impl AudioResponse {
+    fn gate(&self, value: f32) -> f32 {
+        if value <= self.threshold {
+            return 0.;
+        }
+
+        (value - self.threshold) / (1. - self.threshold).max(1e-3)
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct WaveDrive {
+    pub(crate) thickness: f32,
+    pub(crate) amplitude: f32,
+}
+
+impl WaveDrive {
+    fn shared(audio: &AudioFeatures) -> Self {
+        Self {
+            thickness: audio.beat,
+            amplitude: 2. * audio.bass,
+        }
+    }
+
+    fn follow(envelope: f32) -> Self {
+        Self {
+            thickness: envelope,
+            amplitude: 2. * envelope,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ResponseFollowers {
+    envelopes: Vec<f32>,
+    drives: Vec<WaveDrive>,
+    last_update: Option<Instant>,
+}
+
+impl ResponseFollowers {
+    pub(crate) fn update(
+        &mut self,
+        waves: &[UiSineWaveData],
+        audio: &AudioFeatures,
+    ) -> &[WaveDrive] {
+        let now = Instant::now();
+        let dt = self
+            .last_update
+            .map_or(0., |last| now.duration_since(last).as_secs_f32());
+        self.last_update = Some(now);
+
+        self.follow(waves, audio, dt)
+    }
+
+    // Steps the envelopes by a fixed `dt` in seconds, for exports that don't run in real time.
+    pub(crate) fn follow(
+        &mut self,
+        waves: &[UiSineWaveData],
+        audio: &AudioFeatures,
+        dt: f32,
+    ) -> &[WaveDrive] {
+        self.envelopes.resize(waves.len(), 0.);
+        self.drives.clear();
+
+        for (wave, envelope) in waves.iter().zip(&mut self.envelopes) {
+            let response = &wave.audio_response;
+            if !response.enabled {
+                *envelope = 0.;
+                self.drives.push(WaveDrive::shared(audio));
+                continue;
+            }
+
+            let target = response.gate(response.band.value(audio));
+            let time = if target > *envelope {
+                response.attack
+            } else {
+                response.release
+            };
+            let follow = if time > 0. {
+                1. - (-dt / time).exp()
+            } else {
+                1.
+            };
+            *envelope += (target - *envelope) * follow;
+
+            self.drives
+                .push(WaveDrive::follow(*envelope * response.gain));
+        }
+
+        &self.drives
+    }
+}
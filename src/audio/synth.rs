@@ -0,0 +1,227 @@
+#[cfg(feature = "audio")]
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "audio")]
+use anyhow::{Result, anyhow};
+#[cfg(feature = "audio")]
+use cpal::{
+    FromSample, OutputCallbackInfo, SampleFormat, SizedSample, Stream, StreamConfig,
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+};
+
+#[cfg(feature = "audio")]
+use crate::ui::UiSineWaveData;
+
+#[cfg(feature = "audio")]
+const BASE_FREQUENCY: f32 = 55.;
+#[cfg(feature = "audio")]
+const MAX_HARMONICS: u32 = 16;
+#[cfg(feature = "audio")]
+const SMOOTHING_SECS: f32 = 0.02;
+
+pub(crate) struct UiSynth {
+    pub(crate) enabled: bool,
+    pub(crate) muted: bool,
+    pub(crate) volume: f32,
+    pub(crate) octaves: f32,
+    pub(crate) quantize: bool,
+    pub(crate) status: Option<String>,
+}
+
+impl Default for UiSynth {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            muted: false,
+            volume: 0.3,
+            octaves: 5.,
+            quantize: true,
+            status: None,
+        }
+    }
+}
+
+#[cfg(feature = "audio")]
+#[derive(Clone, Copy, Default)]
+struct Voice {
+    frequency: f32,
+    gain: f32,
+    harmonics: u32,
+}
+
+#[cfg(feature = "audio")]
+impl Voice {
+    // Wider rings sit lower, louder rings play louder, and more cycles add brighter partials.
+    fn new(settings: &UiSynth, wave: &UiSineWaveData) -> Self {
+        let mut semitones = 12. * settings.octaves * (1. - wave.inner_radius.clamp(0., 1.));
+        if settings.quantize {
+            semitones = semitones.round();
+        }
+
+        Self {
+            frequency: BASE_FREQUENCY * (semitones / 12.).exp2(),
+            gain: (wave.amplitude / 0.1).clamp(0., 1.) * wave.fade,
+            harmonics: (wave.cycles.round() as u32).clamp(1, MAX_HARMONICS),
+        }
+    }
+}
+
+#[cfg(feature = "audio")]
+fn voices(settings: &UiSynth, waves: &[UiSineWaveData]) -> Vec<Voice> {
+    waves
+        .iter()
+        .map(|wave| {
+            if wave.init {
+                Voice::new(settings, wave)
+            } else {
+                Voice::default()
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "audio")]
+#[derive(Default)]
+struct Mix {
+    voices: Vec<Voice>,
+    volume: f32,
+}
+
+#[cfg(feature = "audio")]
+#[derive(Clone, Copy, Default)]
+struct Oscillator {
+    phase: f32,
+    frequency: f32,
+    gain: f32,
+}
+
+#[cfg(feature = "audio")]
+struct Output {
+    mix: Arc<Mutex<Mix>>,
+    _stream: Stream,
+}
+
+#[cfg(feature = "audio")]
+impl Output {
+    fn new() -> Result<Self> {
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or_else(|| anyhow!("No audio output device available"))?;
+        let config = device.default_output_config()?;
+        let mix = Arc::new(Mutex::new(Mix::default()));
+
+        let stream = match config.sample_format() {
+            SampleFormat::F32 => Self::build_stream::<f32>(&device, config.into(), &mix),
+            SampleFormat::I16 => Self::build_stream::<i16>(&device, config.into(), &mix),
+            SampleFormat::U16 => Self::build_stream::<u16>(&device, config.into(), &mix),
+            SampleFormat::I32 => Self::build_stream::<i32>(&device, config.into(), &mix),
+            sample_format => Err(anyhow!("Unsupported sample format {sample_format}")),
+        }?;
+
+        stream.play()?;
+
+        Ok(Self {
+            mix,
+            _stream: stream,
+        })
+    }
+
+    fn build_stream<T>(
+        device: &cpal::Device,
+        config: StreamConfig,
+        mix: &Arc<Mutex<Mix>>,
+    ) -> Result<Stream>
+    where
+        T: SizedSample + FromSample<f32>,
+    {
+        let channels = config.channels as usize;
+        let sample_rate = config.sample_rate as f32;
+        let smoothing = 1. - (-1. / (SMOOTHING_SECS * sample_rate)).exp();
+
+        let mix = mix.clone();
+        let mut voices = Vec::<Voice>::new();
+        let mut volume = 0.;
+        let mut oscillators = Vec::<Oscillator>::new();
+
+        let stream = device.build_output_stream(
+            config,
+            move |data: &mut [T], _: &OutputCallbackInfo| {
+                // Never block the audio thread; a busy lock just keeps last buffer's targets.
+                if let Ok(mix) = mix.try_lock() {
+                    voices.clone_from(&mix.voices);
+                    volume = mix.volume;
+                }
+                oscillators.resize(voices.len(), Oscillator::default());
+                // Silent voices stay in the list so oscillators keep their waves, but don't count
+                // towards the mix or every muted ring would quiet the rest.
+                let audible = voices.iter().filter(|voice| voice.gain > 0.).count();
+
+                for frame in data.chunks_mut(channels) {
+                    let mut sample = 0.;
+                    for (oscillator, voice) in oscillators.iter_mut().zip(&voices) {
+                        oscillator.frequency +=
+                            (voice.frequency - oscillator.frequency) * smoothing;
+                        oscillator.gain += (voice.gain - oscillator.gain) * smoothing;
+                        oscillator.phase =
+                            (oscillator.phase + oscillator.frequency / sample_rate).fract();
+
+                        // Partials above Nyquist are skipped so high rings don't alias.
+                        let nyquist = 0.5 * sample_rate / oscillator.frequency.max(1.);
+                        let mut partials = 0.;
+                        for n in 1..=voice.harmonics.min(nyquist as u32) {
+                            let n = n as f32;
+                            partials += (std::f32::consts::TAU * n * oscillator.phase).sin() / n;
+                        }
+                        sample += oscillator.gain * partials;
+                    }
+
+                    let sample = (volume * sample / audible.max(1) as f32).clamp(-1., 1.);
+                    frame.fill(T::from_sample(sample));
+                }
+            },
+            |err| log::error!("Synth stream error: {err}"),
+            None,
+        )?;
+
+        Ok(stream)
+    }
+}
+
+#[cfg(feature = "audio")]
+#[derive(Default)]
+pub(crate) struct Synth {
+    output: Option<Output>,
+}
+
+#[cfg(feature = "audio")]
+impl Synth {
+    pub(crate) fn update(&mut self, settings: &mut UiSynth, waves: &[UiSineWaveData]) {
+        if !settings.enabled {
+            self.output = None;
+            settings.status = None;
+            return;
+        }
+
+        if self.output.is_none() {
+            match Output::new() {
+                Ok(output) => {
+                    self.output = Some(output);
+                    settings.status = None;
+                }
+                Err(err) => {
+                    settings.enabled = false;
+                    settings.status = Some(format!("Synth: {err}"));
+                    return;
+                }
+            }
+        }
+
+        let Some(output) = &self.output else {
+            return;
+        };
+        if let Ok(mut mix) = output.mix.lock() {
+            mix.voices = voices(settings, waves);
+            mix.volume = if settings.muted { 0. } else { settings.volume };
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use image::RgbaImage;
+use png::{BitDepth, ColorType, Encoder};
+
+#[cfg(feature = "audio")]
+use crate::audio::player::Track;
+use crate::{
+    args::BatchArgs,
+    audio::analysis::OfflineAnalysis,
+    color,
+    dump::{Headless, HeadlessScene},
+    preset::Preset,
+    screenshot,
+};
+
+const ASPECT: f32 = 16. / 9.;
+const THUMBNAIL_FRAME: u32 = 120;
+const VIDEO_WIDTH: u32 = 512;
+const VIDEO_FPS: u16 = 30;
+const TICKS_PER_FRAME: u32 = 2;
+const TICK_RATE: f32 = VIDEO_FPS as f32 * TICKS_PER_FRAME as f32;
+
+pub(crate) fn run(args: &BatchArgs, safe_mode: bool) -> Result<()> {
+    let presets = preset_files(&args.presets)?;
+    if presets.is_empty() {
+        bail!("No presets found in {}", args.presets.display());
+    }
+
+    fs::create_dir_all(&args.out)
+        .with_context(|| format!("Failed to create {}", args.out.display()))?;
+
+    let audio = match (&args.audio, args.video) {
+        (Some(path), Some(seconds)) => Some(analyze(path, seconds)?),
+        _ => None,
+    };
+
+    let headless = Headless::new(safe_mode)?;
+    let mut failed = 0;
+
+    for (index, path) in presets.iter().enumerate() {
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        log::info!("Exporting `{name}` ({}/{})", index + 1, presets.len());
+
+        if let Err(err) = export(&headless, path, name, args, audio.as_ref()) {
+            log::error!("{}: {err:#}", path.display());
+            failed += 1;
+        }
+        headless.log_errors();
+    }
+
+    log::info!(
+        "Exported {} of {} presets to {}",
+        presets.len() - failed,
+        presets.len(),
+        args.out.display()
+    );
+    if failed > 0 {
+        bail!("{failed} of {} presets failed to export", presets.len());
+    }
+
+    Ok(())
+}
+
+fn preset_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+
+    let mut paths = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json" || extension == "png")
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    // A screenshot and its `.json` sidecar describe the same preset.
+    paths.dedup_by(|next, previous| next.with_extension("") == previous.with_extension(""));
+
+    Ok(paths)
+}
+
+fn load(path: &Path) -> Result<Preset> {
+    if path.extension().is_some_and(|extension| extension == "png") {
+        screenshot::read_preset(path)
+    } else {
+        Preset::load(path)
+    }
+}
+
+#[cfg(feature = "audio")]
+fn analyze(path: &Path, seconds: f32) -> Result<OfflineAnalysis> {
+    let track =
+        Track::decode(path).with_context(|| format!("Failed to decode {}", path.display()))?;
+    log::info!("Analyzing {}", path.display());
+
+    Ok(OfflineAnalysis::new(
+        track.mono_samples(),
+        track.sample_rate(),
+        TICK_RATE,
+        seconds,
+    ))
+}
+
+#[cfg(not(feature = "audio"))]
+fn analyze(_path: &Path, _seconds: f32) -> Result<OfflineAnalysis> {
+    bail!("`--audio` requires building with the `audio` feature")
+}
+
+// `elapsed` counts ticks from the start of the video. Frame `f` is shown at `f / VIDEO_FPS`, so
+// its last tick, at `(f + 1) * TICKS_PER_FRAME`, hears the audio at exactly that instant.
+fn listen(scene: &mut HeadlessScene, audio: Option<&OfflineAnalysis>, elapsed: f32, step: f32) {
+    if let Some(audio) = audio {
+        let seconds = (elapsed - TICKS_PER_FRAME as f32) / TICK_RATE;
+        scene.set_audio(audio.snapshot(seconds), step / TICK_RATE);
+    }
+}
+
+fn size(width: u32) -> [u32; 2] {
+    [width, ((width as f32 / ASPECT).round() as u32).max(1)]
+}
+
+fn export(
+    headless: &Headless,
+    path: &Path,
+    name: &str,
+    args: &BatchArgs,
+    audio: Option<&OfflineAnalysis>,
+) -> Result<()> {
+    let preset = load(path)?;
+
+    if let Some(width) = args.thumbnail {
+        let mut scene = HeadlessScene::new(headless, &preset, size(width), false)?;
+        for frame in 0..=THUMBNAIL_FRAME {
+            scene.advance(headless, frame == THUMBNAIL_FRAME)?;
+        }
+
+        let path = args.out.join(format!("{name}.png"));
+        let image = scene.capture(headless)?;
+        fs::write(&path, screenshot::encode_png(&image, Some(&preset))?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        log::info!("Wrote {}", path.display());
+    }
+
+    if let Some(seconds) = args.video {
+        let [width, height] = size(args.video_width.unwrap_or(VIDEO_WIDTH));
+        let frames = ((seconds * VIDEO_FPS as f32).round() as u32).max(1);
+        let mut scene = HeadlessScene::new(headless, &preset, [width, height], false)?;
+
+        let path = args.out.join(format!("{name}.apng"));
+        let file =
+            File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+
+        let mut encoder = Encoder::new(BufWriter::new(file), width, height);
+        encoder.set_color(ColorType::Rgba);
+        encoder.set_depth(BitDepth::Eight);
+        encoder.set_animated(frames, 0)?;
+        encoder.set_frame_delay(1, VIDEO_FPS)?;
+        let mut writer = encoder.write_header()?;
+
+        for frame in 0..frames {
+            let start = (frame * TICKS_PER_FRAME) as f32;
+            let image = if args.motion_blur > 1 {
+                accumulate(
+                    headless,
+                    &mut scene,
+                    args.motion_blur,
+                    args.shutter,
+                    audio,
+                    start,
+                )?
+            } else {
+                for tick in 1..=TICKS_PER_FRAME {
+                    listen(&mut scene, audio, start + tick as f32, 1.);
+                    scene.advance(headless, tick == TICKS_PER_FRAME)?;
+                }
+                scene.capture(headless)?
+            };
+            writer.write_image_data(image.as_raw())?;
+        }
+        writer.finish()?;
+        log::info!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+// Samples are spread over the open part of the shutter and averaged in linear light.
+fn accumulate(
+    headless: &Headless,
+    scene: &mut HeadlessScene,
+    samples: u32,
+    shutter: f32,
+    audio: Option<&OfflineAnalysis>,
+    start: f32,
+) -> Result<RgbaImage> {
+    let open = TICKS_PER_FRAME as f32 * shutter / 360.;
+    let step = open / samples as f32;
+    let mut sum = Vec::new();
+    let mut size = (0, 0);
+
+    scene.set_time_step(step);
+    for sample in 1..=samples {
+        listen(scene, audio, start + sample as f32 * step, step);
+        scene.advance(headless, true)?;
+        let image = scene.capture(headless)?;
+        size = image.dimensions();
+        sum.resize(image.as_raw().len(), 0.);
+
+        for (i, (total, &byte)) in sum.iter_mut().zip(image.as_raw()).enumerate() {
+            let value = byte as f32 / 255.;
+            *total += if i % 4 == 3 {
+                value
+            } else {
+                color::srgb_to_linear(value)
+            };
+        }
+    }
+
+    let closed = TICKS_PER_FRAME as f32 - open;
+    if closed > 0. {
+        scene.set_time_step(closed);
+        listen(scene, audio, start + TICKS_PER_FRAME as f32, closed);
+        scene.advance(headless, false)?;
+    }
+
+    let pixels = sum
+        .iter()
+        .enumerate()
+        .map(|(i, total)| {
+            let value = total / samples as f32;
+            let encoded = if i % 4 == 3 {
+                value
+            } else {
+                color::linear_to_srgb(value)
+            };
+            (encoded.clamp(0., 1.) * 255.).round() as u8
+        })
+        .collect();
+
+    RgbaImage::from_raw(size.0, size.1, pixels).context("Accumulated frame has the wrong size")
+}
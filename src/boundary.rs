@@ -1,6 +1,5 @@
 use wgpu::{
-    Buffer, BufferUsages, Device, VertexAttribute, VertexBufferLayout, VertexFormat,
-    VertexStepMode,
+    BufferUsages, Device, VertexAttribute, VertexBufferLayout, VertexFormat, VertexStepMode,
     util::{BufferInitDescriptor, DeviceExt},
 };
 
@@ -0,0 +1,47 @@
+use winit::window::{Icon, Window};
+
+use crate::ui::Ui;
+
+pub(crate) const TITLE: &str = "sigil";
+
+const ICON: &[u8] = include_bytes!("../assets/icon.png");
+
+pub(crate) fn icon() -> Option<Icon> {
+    let image = image::load_from_memory(ICON)
+        .inspect_err(|err| log::warn!("Failed to decode window icon: {err}"))
+        .ok()?
+        .into_rgba8();
+    let (width, height) = image.dimensions();
+
+    Icon::from_rgba(image.into_raw(), width, height)
+        .inspect_err(|err| log::warn!("Failed to create window icon: {err}"))
+        .ok()
+}
+
+#[derive(Default)]
+pub(crate) struct WindowTitle {
+    current: String,
+}
+
+impl WindowTitle {
+    pub(crate) fn update(&mut self, window: &Window, ui: &Ui) {
+        let title = Self::format(ui);
+        if title != self.current {
+            window.set_title(&title);
+            self.current = title;
+        }
+    }
+
+    fn format(ui: &Ui) -> String {
+        let mut title = match &ui.presets.current {
+            Some(name) => format!("{name} - {TITLE}"),
+            None => TITLE.to_owned(),
+        };
+
+        if ui.waves.0.iter().any(|wave| wave.motion.recording) {
+            title.push_str(" [Recording Path]");
+        }
+
+        title
+    }
+}
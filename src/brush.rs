@@ -0,0 +1,142 @@
+use egui::{Color32, Context, Id, LayerId, Order, Pos2, Rect, Stroke};
+use winit::event::{Touch, TouchPhase};
+
+use crate::{
+    canvas::{WaveSelection, to_uv},
+    pipelines::sine::MAX_WAVES,
+    ui::{UiSineWaveData, UiWaves},
+};
+
+// Drag speed, in points per second, that paints the largest rings.
+const FAST_STROKE: f32 = 2000.;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum BrushSize {
+    #[default]
+    Speed,
+    Pressure,
+}
+
+impl BrushSize {
+    pub(crate) const ALL: [Self; 2] = [Self::Speed, Self::Pressure];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Speed => "Drag Speed",
+            Self::Pressure => "Pen Pressure",
+        }
+    }
+}
+
+pub(crate) struct Brush {
+    pub(crate) enabled: bool,
+    pub(crate) size: BrushSize,
+    pub(crate) spacing: f32,
+    pub(crate) min_radius: f32,
+    pub(crate) max_radius: f32,
+    pub(crate) min_thickness: f32,
+    pub(crate) max_thickness: f32,
+    stroking: bool,
+    last: Option<Pos2>,
+    pressure: Option<f32>,
+}
+
+impl Default for Brush {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            size: BrushSize::Speed,
+            spacing: 24.,
+            min_radius: 0.02,
+            max_radius: 0.2,
+            min_thickness: 0.005,
+            max_thickness: 0.04,
+            stroking: false,
+            last: None,
+            pressure: None,
+        }
+    }
+}
+
+impl Brush {
+    // egui already turns the first touch into pointer motion; only the pressure is taken here.
+    pub(crate) fn handle_touch(&mut self, touch: &Touch) {
+        self.pressure = match touch.phase {
+            TouchPhase::Started | TouchPhase::Moved => {
+                touch.force.map(|force| force.normalized() as f32)
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => None,
+        };
+    }
+
+    pub(crate) fn update(
+        &mut self,
+        ctx: &Context,
+        rect: Rect,
+        waves: &mut UiWaves,
+        selection: &mut WaveSelection,
+    ) {
+        let (pointer, pressed, down, speed) = ctx.input(|input| {
+            (
+                input.pointer.interact_pos(),
+                input.pointer.primary_pressed(),
+                input.pointer.primary_down(),
+                input.pointer.velocity().length(),
+            )
+        });
+
+        if !down {
+            self.stroking = false;
+            self.last = None;
+        }
+        let Some(pointer) = pointer else {
+            return;
+        };
+
+        if pressed && !ctx.is_pointer_over_area() {
+            self.stroking = true;
+        }
+
+        let amount = self.amount(speed);
+        if !ctx.is_pointer_over_area() {
+            let painter = ctx.layer_painter(LayerId::new(Order::Background, Id::new("brush")));
+            painter.circle_stroke(
+                pointer,
+                self.lerp_radius(amount) * rect.height(),
+                Stroke::new(1., Color32::from_white_alpha(120)),
+            );
+        }
+
+        if !self.stroking
+            || self
+                .last
+                .is_some_and(|last| last.distance(pointer) < self.spacing)
+        {
+            return;
+        }
+        // A full scene ends the stroke here; freeing slots lets the next stroke carry on.
+        if waves.0.len() >= MAX_WAVES {
+            return;
+        }
+
+        waves.0.push(UiSineWaveData {
+            center: to_uv(rect, pointer),
+            inner_radius: self.lerp_radius(amount),
+            thickness: self.min_thickness + (self.max_thickness - self.min_thickness) * amount,
+            ..UiSineWaveData::active()
+        });
+        selection.selected = Some(waves.0.len() - 1);
+        self.last = Some(pointer);
+    }
+
+    fn amount(&self, speed: f32) -> f32 {
+        match self.size {
+            BrushSize::Speed => (speed / FAST_STROKE).clamp(0., 1.),
+            BrushSize::Pressure => self.pressure.unwrap_or(0.5).clamp(0., 1.),
+        }
+    }
+
+    fn lerp_radius(&self, amount: f32) -> f32 {
+        self.min_radius + (self.max_radius - self.min_radius) * amount
+    }
+}
@@ -0,0 +1,192 @@
+use std::{collections::BTreeMap, time::Instant};
+
+use winit::{
+    event::{ElementState, WindowEvent},
+    keyboard::Key,
+};
+
+use crate::{macros::MacroParam, ui::UiSineWaveData};
+
+const MAX_BURSTS: usize = 64;
+const STREAM_THRESHOLD: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum BurstKind {
+    #[default]
+    Amplitude,
+    Radius,
+    Thickness,
+}
+
+impl BurstKind {
+    pub(crate) const ALL: [Self; 3] = [Self::Amplitude, Self::Radius, Self::Thickness];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Amplitude => "Amplitude Spike",
+            Self::Radius => "Radius Kick",
+            Self::Thickness => "Thickness Swell",
+        }
+    }
+
+    fn apply(self, wave: &mut UiSineWaveData, envelope: f32) {
+        let param = match self {
+            Self::Amplitude => MacroParam::Amplitude,
+            Self::Radius => MacroParam::InnerRadius,
+            Self::Thickness => MacroParam::Thickness,
+        };
+        if wave.locks.contains(param) {
+            return;
+        }
+
+        match self {
+            Self::Amplitude => wave.amplitude *= 1. + envelope,
+            Self::Radius => wave.inner_radius += 0.1 * envelope,
+            Self::Thickness => wave.thickness *= 1. + envelope,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct TriggerPad {
+    pub(crate) name: String,
+    pub(crate) wave: Option<usize>,
+    pub(crate) kind: BurstKind,
+    pub(crate) strength: f32,
+    pub(crate) decay_secs: f32,
+    pub(crate) key: String,
+    pub(crate) note: Option<u8>,
+    pub(crate) source: String,
+}
+
+impl TriggerPad {
+    pub(crate) fn new(index: usize) -> Self {
+        Self {
+            name: format!("Pad {}", index + 1),
+            wave: None,
+            kind: BurstKind::Amplitude,
+            strength: 2.,
+            decay_secs: 0.4,
+            key: ((index + 1) % 10).to_string(),
+            note: None,
+            source: String::new(),
+        }
+    }
+}
+
+pub(crate) struct UiBursts {
+    pub(crate) enabled: bool,
+    pub(crate) pads: Vec<TriggerPad>,
+    pub(crate) fired: Vec<usize>,
+}
+
+impl UiBursts {
+    pub(crate) fn handle_input(&mut self, event: &WindowEvent) {
+        let WindowEvent::KeyboardInput { event, .. } = event else {
+            return;
+        };
+        if !self.enabled || event.state != ElementState::Pressed || event.repeat {
+            return;
+        }
+        let Key::Character(text) = &event.logical_key else {
+            return;
+        };
+
+        for (i, pad) in self.pads.iter().enumerate() {
+            if !pad.key.is_empty() && pad.key.eq_ignore_ascii_case(text) {
+                self.fired.push(i);
+            }
+        }
+    }
+
+    pub(crate) fn handle_note(&mut self, note: u8, on: bool) {
+        if !self.enabled || !on {
+            return;
+        }
+
+        for (i, pad) in self.pads.iter().enumerate() {
+            if pad.note == Some(note) {
+                self.fired.push(i);
+            }
+        }
+    }
+}
+
+impl Default for UiBursts {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pads: (0..4).map(TriggerPad::new).collect(),
+            fired: Vec::new(),
+        }
+    }
+}
+
+struct Burst {
+    wave: Option<usize>,
+    kind: BurstKind,
+    strength: f32,
+    decay_secs: f32,
+    fired: Instant,
+}
+
+#[derive(Default)]
+pub(crate) struct Bursts {
+    active: Vec<Burst>,
+    held: Vec<bool>,
+}
+
+impl Bursts {
+    pub(crate) fn update(
+        &mut self,
+        settings: &mut UiBursts,
+        stream_values: &BTreeMap<String, f32>,
+    ) {
+        // Stream sources fire on the rising edge so a held note or button only triggers once.
+        self.held.resize(settings.pads.len(), false);
+        for (i, pad) in settings.pads.iter().enumerate() {
+            let held = stream_values
+                .get(&pad.source)
+                .is_some_and(|value| *value >= STREAM_THRESHOLD);
+            if held && !self.held[i] {
+                settings.fired.push(i);
+            }
+            self.held[i] = held;
+        }
+
+        let now = Instant::now();
+        for i in settings.fired.drain(..) {
+            let Some(pad) = settings.pads.get(i).filter(|_| settings.enabled) else {
+                continue;
+            };
+
+            if self.active.len() >= MAX_BURSTS {
+                self.active.remove(0);
+            }
+            self.active.push(Burst {
+                wave: pad.wave,
+                kind: pad.kind,
+                strength: pad.strength,
+                decay_secs: pad.decay_secs.max(0.01),
+                fired: now,
+            });
+        }
+
+        self.active
+            .retain(|burst| (now - burst.fired).as_secs_f32() < burst.decay_secs * 8.);
+    }
+
+    pub(crate) fn apply(&self, waves: &mut [UiSineWaveData]) {
+        let now = Instant::now();
+        for burst in &self.active {
+            let age = (now - burst.fired).as_secs_f32();
+            let envelope = burst.strength * (-age / burst.decay_secs).exp();
+
+            for (i, wave) in waves.iter_mut().enumerate() {
+                if burst.wave.is_none_or(|target| target == i) {
+                    burst.kind.apply(wave, envelope);
+                }
+            }
+        }
+    }
+}
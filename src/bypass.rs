@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use winit::{
+    event::{ElementState, WindowEvent},
+    keyboard::Key,
+};
+
+const STREAM_THRESHOLD: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum BypassTarget {
+    #[default]
+    Strobe,
+    Grade,
+    PixelSort,
+    FieldWarp,
+    ReactionDiffusion,
+    Shadertoy,
+    Layer(usize),
+}
+
+impl BypassTarget {
+    pub(crate) const EFFECTS: [Self; 6] = [
+        Self::Strobe,
+        Self::Grade,
+        Self::PixelSort,
+        Self::FieldWarp,
+        Self::ReactionDiffusion,
+        Self::Shadertoy,
+    ];
+
+    pub(crate) fn label(self) -> String {
+        match self {
+            Self::Strobe => "Strobe".to_owned(),
+            Self::Grade => "Color Grade".to_owned(),
+            Self::PixelSort => "Pixel Sort".to_owned(),
+            Self::FieldWarp => "Field Warp".to_owned(),
+            Self::ReactionDiffusion => "Reaction Diffusion".to_owned(),
+            Self::Shadertoy => "Shadertoy Backdrop".to_owned(),
+            Self::Layer(wave) => format!("Wave {}", wave + 1),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum BypassMode {
+    #[default]
+    Momentary,
+    Toggle,
+}
+
+impl BypassMode {
+    pub(crate) const ALL: [Self; 2] = [Self::Momentary, Self::Toggle];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Momentary => "Momentary",
+            Self::Toggle => "Toggle",
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct BypassBinding {
+    pub(crate) target: BypassTarget,
+    pub(crate) mode: BypassMode,
+    pub(crate) key: String,
+    pub(crate) note: Option<u8>,
+    pub(crate) source: String,
+    pub(crate) latched: bool,
+    key_held: bool,
+    note_held: bool,
+    source_held: bool,
+}
+
+impl BypassBinding {
+    pub(crate) fn active(&self) -> bool {
+        match self.mode {
+            BypassMode::Momentary => self.key_held || self.note_held || self.source_held,
+            BypassMode::Toggle => self.latched,
+        }
+    }
+
+    fn press(&mut self) {
+        if self.mode == BypassMode::Toggle {
+            self.latched = !self.latched;
+        }
+    }
+}
+
+pub(crate) struct UiBypass {
+    pub(crate) enabled: bool,
+    pub(crate) bindings: Vec<BypassBinding>,
+}
+
+impl Default for UiBypass {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            bindings: Vec::new(),
+        }
+    }
+}
+
+impl UiBypass {
+    pub(crate) fn handle_input(&mut self, event: &WindowEvent) {
+        let WindowEvent::KeyboardInput { event, .. } = event else {
+            return;
+        };
+        if event.repeat {
+            return;
+        }
+        let Key::Character(text) = &event.logical_key else {
+            return;
+        };
+
+        let pressed = event.state == ElementState::Pressed;
+        for binding in &mut self.bindings {
+            if binding.key.is_empty() || !binding.key.eq_ignore_ascii_case(text) {
+                continue;
+            }
+
+            binding.key_held = pressed;
+            if pressed {
+                binding.press();
+            }
+        }
+    }
+
+    pub(crate) fn handle_note(&mut self, note: u8, on: bool) {
+        for binding in &mut self.bindings {
+            if binding.note != Some(note) || binding.note_held == on {
+                continue;
+            }
+
+            binding.note_held = on;
+            if on {
+                binding.press();
+            }
+        }
+    }
+
+    // Stream sources latch on the rising edge, the same way trigger pads fire.
+    pub(crate) fn update(&mut self, stream_values: &BTreeMap<String, f32>) {
+        for binding in &mut self.bindings {
+            let held = stream_values
+                .get(&binding.source)
+                .is_some_and(|value| *value >= STREAM_THRESHOLD);
+            if held && !binding.source_held {
+                binding.press();
+            }
+            binding.source_held = held;
+        }
+    }
+
+    pub(crate) fn bypassed(&self, target: BypassTarget) -> bool {
+        self.enabled
+            && self
+                .bindings
+                .iter()
+                .any(|binding| binding.target == target && binding.active())
+    }
+
+    pub(crate) fn active(&self) -> Vec<BypassTarget> {
+        let mut active = Vec::new();
+        if !self.enabled {
+            return active;
+        }
+
+        for binding in &self.bindings {
+            if binding.active() && !active.contains(&binding.target) {
+                active.push(binding.target);
+            }
+        }
+        active
+    }
+}
@@ -0,0 +1,76 @@
+use egui::{Context, PointerButton, Pos2, Rect, Vec2};
+
+const MIN_ZOOM: f32 = 0.1;
+const MAX_ZOOM: f32 = 20.;
+const ZOOM_PER_POINT: f32 = 0.002;
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct Camera {
+    pub(crate) zoom: f32,
+    pub(crate) pan: [f32; 2],
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            zoom: 1.,
+            pan: [0.; 2],
+        }
+    }
+}
+
+impl Camera {
+    pub(crate) fn view_rect(&self, rect: Rect) -> Rect {
+        let [x, y] = self.pan;
+        let min = Vec2::new(0.5 - (0.5 + x) * self.zoom, 0.5 - (0.5 + y) * self.zoom);
+
+        Rect::from_min_size(rect.min + min * rect.size(), rect.size() * self.zoom)
+    }
+
+    fn scene_position(&self, rect: Rect, position: Pos2) -> [f32; 2] {
+        let screen = (position - rect.min) / rect.size();
+        [
+            0.5 + self.pan[0] + (screen.x - 0.5) / self.zoom,
+            0.5 + self.pan[1] + (screen.y - 0.5) / self.zoom,
+        ]
+    }
+
+    pub(crate) fn update(&mut self, ctx: &Context, rect: Rect) {
+        let (pointer, scroll, panning, delta, reset) = ctx.input(|input| {
+            (
+                input.pointer.hover_pos(),
+                input.raw_scroll_delta.y,
+                input.pointer.button_down(PointerButton::Middle),
+                input.pointer.delta(),
+                input.pointer.button_double_clicked(PointerButton::Primary),
+            )
+        });
+
+        let Some(pointer) = pointer.filter(|pointer| rect.contains(*pointer)) else {
+            return;
+        };
+        if ctx.is_pointer_over_area() {
+            return;
+        }
+
+        if reset {
+            *self = Self::default();
+            return;
+        }
+
+        if panning {
+            let size = rect.size() * self.zoom;
+            self.pan[0] -= delta.x / size.x;
+            self.pan[1] -= delta.y / size.y;
+        }
+
+        if scroll != 0. {
+            let anchor = self.scene_position(rect, pointer);
+            self.zoom = (self.zoom * (scroll * ZOOM_PER_POINT).exp()).clamp(MIN_ZOOM, MAX_ZOOM);
+
+            let moved = self.scene_position(rect, pointer);
+            self.pan[0] += anchor[0] - moved[0];
+            self.pan[1] += anchor[1] - moved[1];
+        }
+    }
+}
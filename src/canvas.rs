@@ -0,0 +1,294 @@
+use egui::{Color32, Context, Id, LayerId, Order, Painter, Pos2, Rect, Stroke, Vec2, pos2};
+
+use crate::{
+    hud::{HudHandle, UiHud},
+    ui::UiWaves,
+};
+
+const HIT_RADIUS: f32 = 20.;
+const SNAP_DISTANCE: f32 = 12.;
+const HANDLE_RADIUS: f32 = 8.;
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum SnapMode {
+    #[default]
+    Center,
+    Thirds,
+    Grid,
+}
+
+pub(crate) struct Snap {
+    pub(crate) enabled: bool,
+    pub(crate) mode: SnapMode,
+    pub(crate) divisions: u32,
+    pub(crate) guides: bool,
+}
+
+impl Default for Snap {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: SnapMode::default(),
+            divisions: 8,
+            guides: true,
+        }
+    }
+}
+
+impl Snap {
+    fn lines(&self) -> Vec<f32> {
+        if !self.enabled {
+            return Vec::new();
+        }
+
+        match self.mode {
+            SnapMode::Center => vec![0.5],
+            SnapMode::Thirds => vec![1. / 3., 0.5, 2. / 3.],
+            SnapMode::Grid => {
+                let divisions = self.divisions.max(1);
+                (0..=divisions)
+                    .map(|i| i as f32 / divisions as f32)
+                    .collect()
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct WaveSelection {
+    pub(crate) selected: Option<usize>,
+    pub(crate) solo: Option<usize>,
+    pub(crate) reveal: bool,
+}
+
+impl WaveSelection {
+    pub(crate) fn removed(&mut self, wave: usize) {
+        let shift = |index: &mut Option<usize>| {
+            *index = match *index {
+                Some(i) if i == wave => None,
+                Some(i) if i > wave => Some(i - 1),
+                index => index,
+            };
+        };
+        shift(&mut self.selected);
+        shift(&mut self.solo);
+    }
+}
+
+struct Drag {
+    wave: usize,
+    offset: Vec2,
+    handle: Option<HudHandle>,
+}
+
+#[derive(Default)]
+pub(crate) struct Canvas {
+    drag: Option<Drag>,
+    pub(crate) snap: Snap,
+}
+
+impl Canvas {
+    pub(crate) fn update(
+        &mut self,
+        ctx: &Context,
+        rect: Rect,
+        waves: &mut UiWaves,
+        selection: &mut WaveSelection,
+        hud: &mut UiHud,
+        stretch: bool,
+    ) {
+        let (pointer, pressed, down, alt) = ctx.input(|input| {
+            (
+                input.pointer.interact_pos(),
+                input.pointer.primary_pressed(),
+                input.pointer.primary_down(),
+                input.modifiers.alt,
+            )
+        });
+
+        if !down {
+            self.drag = None;
+        }
+
+        let Some(pointer) = pointer else {
+            hud.active = None;
+            return;
+        };
+
+        let aspect = if stretch {
+            1.
+        } else {
+            rect.width() / rect.height()
+        };
+        let handle = hud
+            .enabled
+            .then(|| Self::hit_handle(rect, pointer, waves, selection.selected?, aspect))
+            .flatten();
+        hud.active = self.drag.as_ref().map_or(handle, |drag| drag.handle);
+
+        if pressed
+            && !ctx.is_pointer_over_area()
+            && !alt
+            && let Some((wave, handle)) = selection.selected.zip(handle)
+        {
+            self.drag = Some(Drag {
+                wave,
+                offset: Vec2::ZERO,
+                handle: Some(handle),
+            });
+            hud.active = Some(handle);
+            return;
+        }
+
+        if pressed && !ctx.is_pointer_over_area() {
+            let hit = Self::hit_test(rect, pointer, waves, selection.solo, aspect);
+            let wave = hit.as_ref().map(|drag| drag.wave);
+
+            if alt {
+                selection.solo = if selection.solo == wave { None } else { wave };
+            } else {
+                self.drag = hit;
+            }
+            selection.selected = wave;
+            selection.reveal = wave.is_some();
+        }
+
+        let Some(drag) = &self.drag else {
+            return;
+        };
+
+        if let Some(handle) = drag.handle {
+            let wave = &mut waves.0[drag.wave];
+            let uv = to_uv(rect, pointer);
+            let offset = Vec2::new((uv[0] - wave.center[0]) * aspect, uv[1] - wave.center[1]);
+            handle.drag(wave, offset.length());
+            return;
+        }
+
+        let target = pointer + drag.offset;
+        let others = waves
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(i, wave)| wave.init && *i != drag.wave)
+            .map(|(_, wave)| to_screen(rect, wave.center))
+            .collect::<Vec<_>>();
+
+        let grid = self.snap.lines();
+        let guides = self.snap.guides;
+
+        let snap_axis = |value: f32, min: f32, size: f32, others: &mut dyn Iterator<Item = f32>| {
+            let grid_lines = grid.iter().map(|line| min + line * size);
+            let candidates = grid_lines.chain(others.filter(|_| guides));
+
+            candidates
+                .map(|candidate| (candidate, (candidate - value).abs()))
+                .filter(|(_, distance)| *distance < SNAP_DISTANCE)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(candidate, _)| candidate)
+        };
+
+        let snapped_x = snap_axis(
+            target.x,
+            rect.left(),
+            rect.width(),
+            &mut others.iter().map(|other| other.x),
+        );
+        let snapped_y = snap_axis(
+            target.y,
+            rect.top(),
+            rect.height(),
+            &mut others.iter().map(|other| other.y),
+        );
+
+        let position = pos2(snapped_x.unwrap_or(target.x), snapped_y.unwrap_or(target.y));
+        waves.0[drag.wave].center = to_uv(rect, position);
+
+        let painter = ctx.layer_painter(LayerId::new(Order::Background, Id::new("canvas")));
+        self.paint_grid(&painter, rect, &grid);
+
+        let guide = Stroke::new(1., Color32::from_rgb(255, 200, 0));
+        if let Some(x) = snapped_x {
+            painter.vline(x, rect.y_range(), guide);
+        }
+        if let Some(y) = snapped_y {
+            painter.hline(rect.x_range(), y, guide);
+        }
+        painter.circle_stroke(position, 6., guide);
+    }
+
+    fn hit_test(
+        rect: Rect,
+        pointer: Pos2,
+        waves: &UiWaves,
+        solo: Option<usize>,
+        aspect: f32,
+    ) -> Option<Drag> {
+        let uv = to_uv(rect, pointer);
+
+        waves
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(i, wave)| wave.init && solo.is_none_or(|solo| solo == *i))
+            .filter_map(|(i, wave)| {
+                let center = to_screen(rect, wave.center);
+                let center_distance = center.distance(pointer);
+
+                let ring = Vec2::new((uv[0] - wave.center[0]) * aspect, uv[1] - wave.center[1]);
+                let ring_distance = (ring.length() - wave.inner_radius).abs() * rect.height();
+
+                let distance = center_distance.min(ring_distance);
+                (distance < HIT_RADIUS).then_some((i, center, distance))
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(wave, center, _)| Drag {
+                wave,
+                offset: center - pointer,
+                handle: None,
+            })
+    }
+
+    fn hit_handle(
+        rect: Rect,
+        pointer: Pos2,
+        waves: &UiWaves,
+        selected: usize,
+        aspect: f32,
+    ) -> Option<HudHandle> {
+        let wave = waves.0.get(selected).filter(|wave| wave.init)?;
+
+        HudHandle::ALL
+            .into_iter()
+            .map(|handle| {
+                let position = to_screen(rect, handle.position(wave, aspect));
+                (handle, position.distance(pointer))
+            })
+            .filter(|(_, distance)| *distance < HANDLE_RADIUS)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(handle, _)| handle)
+    }
+
+    fn paint_grid(&self, painter: &Painter, rect: Rect, grid: &[f32]) {
+        let stroke = Stroke::new(1., Color32::from_white_alpha(40));
+
+        for line in grid {
+            painter.vline(rect.left() + line * rect.width(), rect.y_range(), stroke);
+            painter.hline(rect.x_range(), rect.top() + line * rect.height(), stroke);
+        }
+    }
+}
+
+pub(crate) fn to_screen(rect: Rect, uv: [f32; 2]) -> Pos2 {
+    pos2(
+        rect.left() + uv[0] * rect.width(),
+        rect.top() + uv[1] * rect.height(),
+    )
+}
+
+pub(crate) fn to_uv(rect: Rect, position: Pos2) -> [f32; 2] {
+    [
+        ((position.x - rect.left()) / rect.width()).clamp(0., 1.),
+        ((position.y - rect.top()) / rect.height()).clamp(0., 1.),
+    ]
+}
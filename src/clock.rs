@@ -0,0 +1,38 @@
+use std::time::{Duration, Instant};
+
+// Seconds since launch that a show-control STOP can hold, so everything keyed off the shared
+// clock freezes together and carries on from the same moment on RESUME.
+pub(crate) struct Clock {
+    banked: Duration,
+    running_since: Option<Instant>,
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self {
+            banked: Duration::ZERO,
+            running_since: Some(Instant::now()),
+        }
+    }
+}
+
+impl Clock {
+    pub(crate) fn elapsed(&self) -> f32 {
+        let running = self
+            .running_since
+            .map_or(Duration::ZERO, |since| since.elapsed());
+
+        (self.banked + running).as_secs_f32()
+    }
+
+    pub(crate) fn set_paused(&mut self, paused: bool) {
+        match (paused, self.running_since) {
+            (true, Some(since)) => {
+                self.banked += since.elapsed();
+                self.running_since = None;
+            }
+            (false, None) => self.running_since = Some(Instant::now()),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,261 @@
+#[cfg(feature = "network")]
+use std::{
+    io::ErrorKind,
+    net::{Ipv4Addr, SocketAddr, UdpSocket},
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "network")]
+use anyhow::Result;
+
+#[cfg(feature = "network")]
+const MAGIC: &[u8; 4] = b"SGCK";
+#[cfg(feature = "network")]
+const PACKET_LEN: usize = 20;
+#[cfg(feature = "network")]
+const STALE_AFTER: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum SyncRole {
+    #[default]
+    Off,
+    Master,
+    Slave,
+}
+
+impl SyncRole {
+    pub(crate) const ALL: [Self; 3] = [Self::Off, Self::Master, Self::Slave];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Master => "Master",
+            Self::Slave => "Slave",
+        }
+    }
+}
+
+pub(crate) struct UiClockSync {
+    pub(crate) role: SyncRole,
+    pub(crate) address: String,
+    pub(crate) latency_ms: f32,
+    pub(crate) locked: bool,
+    pub(crate) status: Option<String>,
+}
+
+impl Default for UiClockSync {
+    fn default() -> Self {
+        Self {
+            role: SyncRole::Off,
+            address: "255.255.255.255:7470".to_owned(),
+            latency_ms: 0.,
+            locked: false,
+            status: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct ClockFrame {
+    pub(crate) phase: f32,
+    pub(crate) elapsed: f32,
+    pub(crate) beat: f32,
+}
+
+#[cfg(feature = "network")]
+impl ClockFrame {
+    fn encode(self, sequence: u32) -> [u8; PACKET_LEN] {
+        let mut packet = [0; PACKET_LEN];
+        packet[..4].copy_from_slice(MAGIC);
+        packet[4..8].copy_from_slice(&sequence.to_le_bytes());
+        packet[8..12].copy_from_slice(&self.phase.to_le_bytes());
+        packet[12..16].copy_from_slice(&self.elapsed.to_le_bytes());
+        packet[16..20].copy_from_slice(&self.beat.to_le_bytes());
+        packet
+    }
+
+    fn decode(packet: &[u8]) -> Option<(u32, Self)> {
+        if packet.len() != PACKET_LEN || &packet[..4] != MAGIC {
+            return None;
+        }
+
+        let word = |i: usize| [packet[i], packet[i + 1], packet[i + 2], packet[i + 3]];
+        Some((
+            u32::from_le_bytes(word(4)),
+            Self {
+                phase: f32::from_le_bytes(word(8)),
+                elapsed: f32::from_le_bytes(word(12)),
+                beat: f32::from_le_bytes(word(16)),
+            },
+        ))
+    }
+}
+
+#[cfg(feature = "network")]
+struct Received {
+    sequence: u32,
+    frame: ClockFrame,
+    at: Instant,
+    phase_rate: f32,
+}
+
+#[cfg(feature = "network")]
+struct Link {
+    role: SyncRole,
+    address: String,
+    socket: UdpSocket,
+}
+
+#[cfg(feature = "network")]
+#[derive(Default)]
+pub(crate) struct ClockSync {
+    link: Option<Link>,
+    sequence: u32,
+    received: Option<Received>,
+}
+
+#[cfg(not(feature = "network"))]
+#[derive(Default)]
+pub(crate) struct ClockSync {}
+
+#[cfg(feature = "network")]
+impl ClockSync {
+    // Both roles share one address: the master sends to it, slaves listen on its port.
+    fn connect(role: SyncRole, address: &str) -> Result<UdpSocket> {
+        let address: SocketAddr = address.parse()?;
+        let socket = match role {
+            SyncRole::Master => {
+                let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+                socket.set_broadcast(true)?;
+                socket.connect(address)?;
+                socket
+            }
+            _ => UdpSocket::bind((Ipv4Addr::UNSPECIFIED, address.port()))?,
+        };
+        socket.set_nonblocking(true)?;
+
+        Ok(socket)
+    }
+
+    fn link(&mut self, settings: &mut UiClockSync) {
+        let address = settings.address.trim();
+        let stale = self
+            .link
+            .as_ref()
+            .is_none_or(|link| link.role != settings.role || link.address != address);
+
+        if settings.role == SyncRole::Off {
+            self.link = None;
+            self.received = None;
+            settings.status = None;
+            return;
+        }
+
+        if stale {
+            self.received = None;
+            self.link = match Self::connect(settings.role, address) {
+                Ok(socket) => {
+                    settings.status = None;
+                    Some(Link {
+                        role: settings.role,
+                        address: address.to_owned(),
+                        socket,
+                    })
+                }
+                Err(err) => {
+                    settings.status = Some(format!("Clock sync: `{address}`: {err}"));
+                    None
+                }
+            };
+        }
+    }
+
+    pub(crate) fn receive(&mut self, settings: &mut UiClockSync) -> Option<ClockFrame> {
+        self.link(settings);
+        settings.locked = false;
+        if settings.role != SyncRole::Slave {
+            return None;
+        }
+        let link = self.link.as_ref()?;
+
+        let mut buffer = [0; 64];
+        loop {
+            match link.socket.recv(&mut buffer) {
+                Ok(len) => {
+                    if let Some((sequence, frame)) = ClockFrame::decode(&buffer[..len]) {
+                        Self::accept(&mut self.received, sequence, frame);
+                    }
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) => {
+                    settings.status = Some(format!("Clock sync: {err}"));
+                    break;
+                }
+            }
+        }
+
+        let received = self.received.as_ref()?;
+        let age = received.at.elapsed();
+        settings.locked = age < STALE_AFTER;
+        if !settings.locked {
+            return None;
+        }
+
+        let ahead = age.as_secs_f32() + settings.latency_ms / 1000.;
+        Some(ClockFrame {
+            phase: received.frame.phase + received.phase_rate * ahead,
+            elapsed: received.frame.elapsed + ahead,
+            beat: received.frame.beat,
+        })
+    }
+
+    fn accept(received: &mut Option<Received>, sequence: u32, frame: ClockFrame) {
+        let at = Instant::now();
+        let phase_rate = match received {
+            // Drop reordered packets, but a large step back means the master restarted.
+            Some(last) if sequence <= last.sequence && last.sequence - sequence < 64 => return,
+            Some(last) if frame.elapsed > last.frame.elapsed => {
+                (frame.phase - last.frame.phase) / (frame.elapsed - last.frame.elapsed)
+            }
+            Some(last) => last.phase_rate,
+            None => 0.,
+        };
+
+        *received = Some(Received {
+            sequence,
+            frame,
+            at,
+            phase_rate,
+        });
+    }
+
+    pub(crate) fn broadcast(&mut self, settings: &mut UiClockSync, frame: ClockFrame) {
+        if settings.role != SyncRole::Master {
+            return;
+        }
+
+        let Some(link) = &self.link else {
+            return;
+        };
+
+        self.sequence = self.sequence.wrapping_add(1);
+        if let Err(err) = link.socket.send(&frame.encode(self.sequence))
+            && err.kind() != ErrorKind::WouldBlock
+        {
+            settings.status = Some(format!("Clock sync: {err}"));
+        }
+    }
+}
+
+#[cfg(not(feature = "network"))]
+impl ClockSync {
+    pub(crate) fn receive(&mut self, settings: &mut UiClockSync) -> Option<ClockFrame> {
+        settings.locked = false;
+        settings.status = (settings.role != SyncRole::Off)
+            .then(|| "Clock sync requires building with the `network` feature".to_owned());
+
+        None
+    }
+
+    pub(crate) fn broadcast(&mut self, _settings: &mut UiClockSync, _frame: ClockFrame) {}
+}
@@ -0,0 +1,78 @@
+use std::f32::consts::PI;
+
+use crate::ui::UiSineWaveData;
+
+pub(crate) const MAX_HIGHLIGHTS: usize = 4;
+const MAX_HALF_WIDTH: f32 = 0.6;
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Highlights {
+    pub(crate) angles: [f32; MAX_HIGHLIGHTS],
+    pub(crate) widths: [f32; MAX_HIGHLIGHTS],
+    count: usize,
+}
+
+impl Highlights {
+    fn push(&mut self, angle: f32, width: f32) {
+        if self.count < MAX_HIGHLIGHTS {
+            self.angles[self.count] = angle;
+            self.widths[self.count] = width;
+            self.count += 1;
+        }
+    }
+}
+
+pub(crate) fn detect(waves: &[UiSineWaveData], aspect: f32) -> Vec<Highlights> {
+    let mut highlights = vec![Highlights::default(); waves.len()];
+
+    for (i, a) in waves.iter().enumerate().filter(|(_, wave)| wave.init) {
+        for (j, b) in waves
+            .iter()
+            .enumerate()
+            .skip(i + 1)
+            .filter(|(_, wave)| wave.init)
+        {
+            let dx = (b.center[0] - a.center[0]) * aspect;
+            let dy = b.center[1] - a.center[1];
+            let distance = dx.hypot(dy);
+            if distance < 1e-4 {
+                continue;
+            }
+
+            let (ra, ha) = band(a);
+            let (rb, hb) = band(b);
+            let tolerance = ha + hb;
+            if distance > ra + rb + tolerance || distance < (ra - rb).abs() - tolerance {
+                continue;
+            }
+
+            let cos_crossing =
+                ((ra * ra + rb * rb - distance * distance) / (2. * ra * rb)).clamp(-1., 1.);
+            let sin_crossing = (1. - cos_crossing * cos_crossing).sqrt().max(0.2);
+
+            let direction = dy.atan2(dx);
+            let spread_a = ((distance * distance + ra * ra - rb * rb) / (2. * distance * ra))
+                .clamp(-1., 1.)
+                .acos();
+            let spread_b = ((distance * distance + rb * rb - ra * ra) / (2. * distance * rb))
+                .clamp(-1., 1.)
+                .acos();
+
+            let width_a = (tolerance / (ra * sin_crossing)).min(MAX_HALF_WIDTH);
+            let width_b = (tolerance / (rb * sin_crossing)).min(MAX_HALF_WIDTH);
+
+            for sign in [1., -1.] {
+                highlights[i].push(direction + sign * spread_a, width_a);
+                highlights[j].push(direction + PI - sign * spread_b, width_b);
+            }
+        }
+    }
+
+    highlights
+}
+
+fn band(wave: &UiSineWaveData) -> (f32, f32) {
+    let half = wave.thickness / 2. + wave.amplitude;
+
+    ((wave.inner_radius + wave.thickness / 2.).max(1e-3), half)
+}
@@ -0,0 +1,80 @@
+use std::f32::consts::{PI, TAU};
+
+const ACHROMATIC: f32 = 1e-4;
+
+pub(crate) fn linear_to_oklab([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let l = (0.412_221_5 * r + 0.536_332_5 * g + 0.051_446 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_397 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_8 * g + 0.629_978_7 * b).cbrt();
+
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+pub(crate) fn oklab_to_linear([l, a, b]: [f32; 3]) -> [f32; 3] {
+    let l_ = (l + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m_ = (l - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s_ = (l - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+
+    [
+        4.076_741_7 * l_ - 3.307_711_6 * m_ + 0.230_969_94 * s_,
+        -1.268_438 * l_ + 2.609_757_4 * m_ - 0.341_319_38 * s_,
+        -0.004_196_086_3 * l_ - 0.703_418_6 * m_ + 1.707_614_7 * s_,
+    ]
+}
+
+pub(crate) fn srgb_to_linear(channel: f32) -> f32 {
+    if channel <= 0.040_45 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub(crate) fn linear_to_srgb(channel: f32) -> f32 {
+    if channel <= 0.003_130_8 {
+        channel * 12.92
+    } else {
+        1.055 * channel.powf(1. / 2.4) - 0.055
+    }
+}
+
+pub(crate) fn oklab_to_oklch([l, a, b]: [f32; 3]) -> [f32; 3] {
+    [l, a.hypot(b), b.atan2(a).rem_euclid(TAU)]
+}
+
+pub(crate) fn oklch_to_oklab([l, c, h]: [f32; 3]) -> [f32; 3] {
+    [l, c * h.cos(), c * h.sin()]
+}
+
+pub(crate) fn linear_to_oklch(color: [f32; 3]) -> [f32; 3] {
+    oklab_to_oklch(linear_to_oklab(color))
+}
+
+pub(crate) fn oklch_to_linear(color: [f32; 3]) -> [f32; 3] {
+    oklab_to_linear(oklch_to_oklab(color)).map(|channel| channel.clamp(0., 1.))
+}
+
+pub(crate) fn mix_oklch(from: [f32; 3], to: [f32; 3], t: f32) -> [f32; 3] {
+    let [l0, c0, mut h0] = linear_to_oklch(from);
+    let [l1, c1, mut h1] = linear_to_oklch(to);
+
+    if c0 < ACHROMATIC {
+        h0 = h1;
+    }
+    if c1 < ACHROMATIC {
+        h1 = h0;
+    }
+
+    let mut delta = h1 - h0;
+    if delta > PI {
+        delta -= TAU;
+    } else if delta < -PI {
+        delta += TAU;
+    }
+
+    oklch_to_linear([l0 + (l1 - l0) * t, c0 + (c1 - c0) * t, h0 + delta * t])
+}
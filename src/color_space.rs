@@ -0,0 +1,334 @@
+use anyhow::{Result, anyhow, bail};
+use bytemuck::{Pod, Zeroable};
+use wgpu::{Backend, TextureFormat};
+
+use crate::{
+    strobe::StrobeLevel,
+    ui::{UiColorSpace, UiGrade},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum OffScreenFormat {
+    Rgba8,
+    #[default]
+    Rgba8Srgb,
+    Rgba16Float,
+}
+
+impl OffScreenFormat {
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        match name {
+            "rgba8" => Some(Self::Rgba8),
+            "rgba8-srgb" => Some(Self::Rgba8Srgb),
+            "rgba16f" => Some(Self::Rgba16Float),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn texture_format(self) -> TextureFormat {
+        match self {
+            Self::Rgba8 => TextureFormat::Rgba8Unorm,
+            Self::Rgba8Srgb => TextureFormat::Rgba8UnormSrgb,
+            Self::Rgba16Float => TextureFormat::Rgba16Float,
+        }
+    }
+}
+
+const SURFACE_FORMATS: [(&str, TextureFormat); 6] = [
+    ("bgra8", TextureFormat::Bgra8Unorm),
+    ("bgra8-srgb", TextureFormat::Bgra8UnormSrgb),
+    ("rgba8", TextureFormat::Rgba8Unorm),
+    ("rgba8-srgb", TextureFormat::Rgba8UnormSrgb),
+    ("rgba16f", TextureFormat::Rgba16Float),
+    ("rgb10a2", TextureFormat::Rgb10a2Unorm),
+];
+
+pub(crate) fn parse_surface_format(name: &str) -> Option<TextureFormat> {
+    SURFACE_FORMATS
+        .iter()
+        .find(|(format_name, _)| *format_name == name)
+        .map(|(_, format)| *format)
+}
+
+pub(crate) fn surface_format_names() -> String {
+    SURFACE_FORMATS
+        .iter()
+        .map(|(name, _)| format!("`{name}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub(crate) fn select_surface_format(
+    supported: &[TextureFormat],
+    requested: Option<TextureFormat>,
+    unorm: bool,
+) -> Result<TextureFormat> {
+    if let Some(requested) = requested {
+        if !supported.contains(&requested) {
+            bail!(
+                "Surface format {requested:?} is not supported, available formats are {supported:?}"
+            );
+        }
+        return Ok(requested);
+    }
+
+    match supported
+        .iter()
+        .find(|format| format.is_srgb() != unorm)
+        .or(supported.first())
+    {
+        Some(format) => Ok(*format),
+        None => bail!("Surface is incompatible with the adapter"),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HdrRequest {
+    Auto,
+    Only(HdrOutput),
+}
+
+impl HdrRequest {
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        match name {
+            "auto" => Some(Self::Auto),
+            "scrgb" => Some(Self::Only(HdrOutput::ScRgb)),
+            "pq" => Some(Self::Only(HdrOutput::Pq)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HdrOutput {
+    ScRgb,
+    Pq,
+}
+
+impl HdrOutput {
+    pub(crate) const ALL: [Self; 2] = [Self::ScRgb, Self::Pq];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::ScRgb => "scRGB",
+            Self::Pq => "PQ (HDR10)",
+        }
+    }
+
+    pub(crate) fn surface_format(self) -> TextureFormat {
+        match self {
+            Self::ScRgb => TextureFormat::Rgba16Float,
+            Self::Pq => TextureFormat::Rgb10a2Unorm,
+        }
+    }
+
+    // wgpu picks the swapchain color space from the format alone: Rgba16Float is tagged as
+    // extended linear sRGB on Vulkan and Metal and DXGI composes it as scRGB, while no backend
+    // tags a 10-bit swapchain as HDR10, so PQ codes would be shown as sRGB.
+    fn unsupported(self, backend: Backend) -> Option<&'static str> {
+        match self {
+            Self::ScRgb if matches!(backend, Backend::Vulkan | Backend::Metal | Backend::Dx12) => {
+                None
+            }
+            Self::ScRgb => Some("this backend cannot tag the surface as extended linear"),
+            Self::Pq => Some("the surface cannot be tagged as HDR10"),
+        }
+    }
+}
+
+// A format in the surface capabilities only means it can be presented; whether the display is
+// actually in HDR mode isn't reported, so HDR stays behind `--hdr`.
+pub(crate) fn hdr_outputs(supported: &[TextureFormat], backend: Backend) -> Vec<HdrOutput> {
+    HdrOutput::ALL
+        .into_iter()
+        .filter(|output| {
+            supported.contains(&output.surface_format()) && output.unsupported(backend).is_none()
+        })
+        .collect()
+}
+
+pub(crate) fn select_hdr_output(
+    supported: &[TextureFormat],
+    backend: Backend,
+    request: HdrRequest,
+) -> Result<HdrOutput> {
+    let output = match request {
+        HdrRequest::Auto => hdr_outputs(supported, backend)
+            .first()
+            .copied()
+            .ok_or_else(|| anyhow!("the surface offers no HDR format usable on {backend:?}"))?,
+        HdrRequest::Only(output) => output,
+    };
+
+    if let Some(reason) = output.unsupported(backend) {
+        bail!("{} output is unavailable, {reason}", output.label());
+    }
+    if !supported.contains(&output.surface_format()) {
+        bail!(
+            "{} output needs a {:?} surface, available formats are {supported:?}",
+            output.label(),
+            output.surface_format()
+        );
+    }
+
+    Ok(output)
+}
+
+pub(crate) fn validate_view_format(
+    surface: TextureFormat,
+    view: TextureFormat,
+    view_formats_supported: bool,
+) -> Result<()> {
+    if view == surface {
+        return Ok(());
+    }
+
+    if view.remove_srgb_suffix() != surface.remove_srgb_suffix() {
+        bail!("View format {view:?} is not compatible with surface format {surface:?}");
+    }
+    if !view_formats_supported {
+        bail!("The adapter cannot view the {surface:?} surface as {view:?}");
+    }
+
+    Ok(())
+}
+
+pub(crate) fn surface_view_format(format: TextureFormat, srgb: bool) -> TextureFormat {
+    if srgb {
+        format.add_srgb_suffix()
+    } else {
+        format.remove_srgb_suffix()
+    }
+}
+
+const TRANSFER_NONE: u32 = 0;
+const TRANSFER_ENCODE: u32 = 1;
+const TRANSFER_DECODE: u32 = 2;
+const TRANSFER_SCRGB: u32 = 3;
+const TRANSFER_PQ: u32 = 4;
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Pod, Zeroable)]
+pub(crate) struct PostSettings {
+    transfer: u32,
+    flash: f32,
+    gain: f32,
+    grade: u32,
+    hue_shift: f32,
+    saturation: f32,
+    contrast: f32,
+    lut_strength: f32,
+    lift: [f32; 4],
+    gamma: [f32; 4],
+    grade_gain: [f32; 4],
+    viewport: [f32; 4],
+    opacity: f32,
+    premultiply: u32,
+    master: f32,
+    hdr_white: f32,
+    hdr_peak: f32,
+    _padding: [f32; 3],
+}
+
+impl PostSettings {
+    pub(crate) fn new(target_srgb: bool, linear_output: bool) -> Self {
+        let transfer = match (target_srgb, linear_output) {
+            (false, true) => TRANSFER_ENCODE,
+            (true, false) => TRANSFER_DECODE,
+            _ => TRANSFER_NONE,
+        };
+
+        Self {
+            transfer,
+            flash: 0.,
+            gain: 1.,
+            grade: 0,
+            hue_shift: 0.,
+            saturation: 1.,
+            contrast: 1.,
+            lut_strength: 0.,
+            lift: [0.; 4],
+            gamma: [1.; 4],
+            grade_gain: [1.; 4],
+            viewport: [0.; 4],
+            opacity: 1.,
+            premultiply: 0,
+            master: 1.,
+            hdr_white: 0.,
+            hdr_peak: 0.,
+            _padding: [0.; 3],
+        }
+    }
+
+    pub(crate) fn with_viewport(self, viewport: [u32; 4]) -> Self {
+        Self {
+            viewport: viewport.map(|value| value as f32),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_opacity(self, opacity: f32, premultiplied: bool) -> Self {
+        Self {
+            opacity,
+            premultiply: premultiplied.into(),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_hdr(self, color_space: &UiColorSpace) -> Self {
+        let Some(output) = color_space.hdr else {
+            return self;
+        };
+
+        Self {
+            transfer: match output {
+                HdrOutput::ScRgb => TRANSFER_SCRGB,
+                HdrOutput::Pq => TRANSFER_PQ,
+            },
+            hdr_white: color_space.sdr_white,
+            hdr_peak: color_space.hdr_peak.max(color_space.sdr_white),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_master(self, master: f32) -> Self {
+        Self {
+            master: master.clamp(0., 1.),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_strobe(self, strobe: StrobeLevel) -> Self {
+        Self {
+            flash: strobe.flash,
+            gain: strobe.gain,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_grade(self, grade: &UiGrade) -> Self {
+        if !grade.enabled {
+            return self;
+        }
+
+        let [lift_r, lift_g, lift_b] = grade.lift;
+        let [gamma_r, gamma_g, gamma_b] = grade.gamma.map(|gamma| gamma.max(0.01));
+        let [gain_r, gain_g, gain_b] = grade.gain;
+
+        Self {
+            grade: 1,
+            hue_shift: grade.hue_shift.to_radians(),
+            saturation: grade.saturation,
+            contrast: grade.contrast,
+            lut_strength: if grade.lut_loaded {
+                grade.lut_strength
+            } else {
+                0.
+            },
+            lift: [lift_r, lift_g, lift_b, 0.],
+            gamma: [gamma_r, gamma_g, gamma_b, 1.],
+            grade_gain: [gain_r, gain_g, gain_b, 1.],
+            ..self
+        }
+    }
+}
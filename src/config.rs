@@ -0,0 +1,82 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    locale::{DecimalSeparator, Language},
+    lock::RandomConstraints,
+};
+
+pub(crate) fn config_dir() -> PathBuf {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    base.join("sigil")
+}
+
+pub(crate) fn presets_dir() -> PathBuf {
+    config_dir().join("presets")
+}
+
+pub(crate) fn ui_state_path() -> PathBuf {
+    config_dir().join("ui_state.json")
+}
+
+pub(crate) fn theme_path() -> PathBuf {
+    config_dir().join("theme.json")
+}
+
+pub(crate) fn playlist_path() -> PathBuf {
+    config_dir().join("playlist.json")
+}
+
+pub(crate) fn settings_path() -> PathBuf {
+    config_dir().join("settings.json")
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Settings {
+    pub(crate) show_startup: bool,
+    pub(crate) language: Language,
+    pub(crate) decimal: DecimalSeparator,
+    pub(crate) constraints: RandomConstraints,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            show_startup: true,
+            language: Language::English,
+            decimal: DecimalSeparator::System,
+            constraints: RandomConstraints::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub(crate) fn load() -> Self {
+        let path = settings_path();
+        let Ok(contents) = fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        serde_json::from_str(&contents).unwrap_or_else(|err| {
+            log::warn!("Ignoring settings in {}: {err}", path.display());
+            Self::default()
+        })
+    }
+
+    pub(crate) fn save(&self) -> Result<()> {
+        let path = settings_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+use std::{f32::consts::TAU, fs, path::Path};
+
+use anyhow::{Context, Result};
+use image::{Rgba, RgbaImage};
+
+use crate::{
+    color,
+    pipelines::sine::{
+        FLAG_FRACTAL, FLAG_MIRROR_X, FLAG_MIRROR_Y, FLAG_OUTLINE, FLAG_SHADOW, MAX_WAVES,
+        SineWaveData,
+    },
+    preset::Preset,
+    screenshot,
+};
+
+pub(crate) const DEFAULT_SIZE: [u32; 2] = [320, 180];
+const MAX_OCTAVES: u32 = 8;
+
+// Ports of the helpers in noise.wgsl, so fractal rings match the GPU path.
+fn fract(x: f32) -> f32 {
+    x - x.floor()
+}
+
+fn hash2([x, y]: [f32; 2]) -> f32 {
+    fract((x * 127.1 + y * 311.7).sin() * 43_758.547)
+}
+
+fn value_noise2([x, y]: [f32; 2]) -> f32 {
+    let [ix, iy] = [x.floor(), y.floor()];
+    let [fx, fy] = [x - ix, y - iy];
+    let [ux, uy] = [fx * fx * (3. - 2. * fx), fy * fy * (3. - 2. * fy)];
+    let mix = |a: f32, b: f32, t: f32| a + (b - a) * t;
+
+    let a = hash2([ix, iy]);
+    let b = hash2([ix + 1., iy]);
+    let c = hash2([ix, iy + 1.]);
+    let d = hash2([ix + 1., iy + 1.]);
+
+    mix(mix(a, b, ux), mix(c, d, ux), uy) * 2. - 1.
+}
+
+fn fbm_ring(angle: f32, radius: f32, seed: f32, fractal: [f32; 4]) -> f32 {
+    let octaves = (fractal[0] as u32).min(MAX_OCTAVES);
+    let base = [angle.cos() * radius, angle.sin() * radius];
+
+    let mut sum = 0.;
+    let mut weight = 0.;
+    let mut amplitude = 1.;
+    let mut frequency = 1.;
+    for octave in 0..octaves {
+        sum += amplitude
+            * value_noise2([
+                base[0] * frequency + seed,
+                base[1] * frequency + octave as f32 * 7.31,
+            ]);
+        weight += amplitude;
+        amplitude *= fractal[1];
+        frequency *= fractal[2];
+    }
+
+    sum / weight.max(0.0001)
+}
+
+// Scope and spectrum rings have no audio to follow here, so they draw flat like a silent input.
+fn wave_radius(theta: f32, wave: &SineWaveData) -> f32 {
+    let phase = wave.cycles * theta + wave.phase_offset;
+    let mut displacement = phase.sin();
+    if wave.flags & FLAG_FRACTAL != 0 {
+        let octave_cycles = wave.cycles.max(1.);
+        displacement = fbm_ring(
+            phase / octave_cycles,
+            0.5 * octave_cycles,
+            wave.layer as f32 * 17.,
+            wave.fractal,
+        );
+    }
+
+    wave.inner_radius + wave.amplitude * displacement
+}
+
+fn ring_edge(dist: f32, inner: f32, thickness: f32) -> f32 {
+    (dist - inner).min(inner + thickness - dist)
+}
+
+fn smoothstep(from: f32, to: f32, x: f32) -> f32 {
+    let t = ((x - from) / (to - from)).clamp(0., 1.);
+    t * t * (3. - 2. * t)
+}
+
+// The fragment shader's stroke, outline and shadow for one ring, without textures or modulation.
+fn shade(wave: &SineWaveData, uv: [f32; 2], aspect: f32, pixel: f32) -> Option<[f32; 4]> {
+    let sign = |flag: u32| if wave.flags & flag != 0 { -1. } else { 1. };
+    let mirror = [sign(FLAG_MIRROR_X), sign(FLAG_MIRROR_Y)];
+    let scene_pos = [(uv[0] - wave.center[0]) * aspect, uv[1] - wave.center[1]];
+    let pos = [scene_pos[0] * mirror[0], scene_pos[1] * mirror[1]];
+    let dist = pos[0].hypot(pos[1]);
+    if dist > wave.reach() + 2. * pixel {
+        return None;
+    }
+
+    let coverage = |edge: f32| (edge / pixel + 0.5).clamp(0., 1.);
+    let theta = pos[1].atan2(pos[0]);
+    let inner_wave = wave_radius(theta, wave);
+    let stroke_alpha = coverage(ring_edge(dist, inner_wave, wave.thickness));
+
+    let mut outline_coverage = 0.;
+    if wave.flags & FLAG_OUTLINE != 0 {
+        let outline_inner = inner_wave + wave.thickness + wave.outline[1];
+        outline_coverage = coverage(ring_edge(dist, outline_inner, wave.outline[0]));
+    }
+
+    let mut shadow_coverage = 0.;
+    if wave.flags & FLAG_SHADOW != 0 {
+        let shadow_pos = [
+            (scene_pos[0] - wave.shadow[0]) * mirror[0],
+            (scene_pos[1] - wave.shadow[1]) * mirror[1],
+        ];
+        let shadow_inner = wave_radius(shadow_pos[1].atan2(shadow_pos[0]), wave);
+        let shadow_edge = ring_edge(
+            shadow_pos[0].hypot(shadow_pos[1]),
+            shadow_inner,
+            wave.thickness,
+        );
+        let blur = wave.shadow[2].max(0.0001);
+        shadow_coverage = wave.shadow[3] * smoothstep(-blur, blur, shadow_edge);
+    }
+
+    if stroke_alpha.max(outline_coverage).max(shadow_coverage) <= 0. {
+        return None;
+    }
+
+    let hue = fract(theta / TAU + 0.5);
+    let stroke = color::oklch_to_linear([0.75, 0.13, TAU * hue]);
+    let stroke_alpha = wave.opacity * stroke_alpha;
+
+    let outline_alpha = wave.opacity * outline_coverage * (1. - stroke_alpha);
+    let ring_alpha = stroke_alpha + outline_alpha;
+    let shadow_alpha = wave.opacity * shadow_coverage * (1. - ring_alpha);
+    let alpha = ring_alpha + shadow_alpha;
+    let [r, g, b] = std::array::from_fn(|i| {
+        (stroke[i] * stroke_alpha
+            + wave.outline_color[i] * outline_alpha
+            + wave.shadow_color[i] * shadow_alpha)
+            / alpha.max(0.0001)
+    });
+
+    Some([r, g, b, alpha])
+}
+
+// Layers are numbered before inactive waves are dropped, as on the GPU, so per-layer fractal
+// seeds agree between the two paths.
+fn rings(preset: &Preset, aspect: f32) -> Vec<SineWaveData> {
+    let mut rings = Vec::new();
+    for (layer, new_data) in preset
+        .waves
+        .iter()
+        .take(MAX_WAVES)
+        .enumerate()
+        .filter(|(_, wave)| wave.init)
+    {
+        // The GPU path's phase offset at time zero, before any jitter has accumulated.
+        let phase_offset =
+            -new_data.cycles * (new_data.speed * new_data.easing.warp(0.) + new_data.rotation);
+        SineWaveData::new(new_data, new_data.outline, layer, phase_offset).instances(
+            new_data,
+            aspect,
+            |ring| rings.push(ring),
+        );
+    }
+
+    rings
+}
+
+pub(crate) fn render(preset: &Preset, [width, height]: [u32; 2]) -> RgbaImage {
+    let aspect = width as f32 / height.max(1) as f32;
+    let rings = rings(preset, aspect);
+
+    let pixel = 1. / height.max(1) as f32;
+    let mut image = RgbaImage::new(width, height);
+    for (x, y, output) in image.enumerate_pixels_mut() {
+        let uv = [
+            (x as f32 + 0.5) / width as f32,
+            (y as f32 + 0.5) / height as f32,
+        ];
+
+        // Alpha blending onto a black clear, the same as the scene pass.
+        let mut color = [0.; 3];
+        for ring in &rings {
+            if let Some([r, g, b, alpha]) = shade(ring, uv, aspect, pixel) {
+                for (channel, value) in color.iter_mut().zip([r, g, b]) {
+                    *channel = value * alpha + *channel * (1. - alpha);
+                }
+            }
+        }
+
+        let [r, g, b] = color
+            .map(|channel| (color::linear_to_srgb(channel.clamp(0., 1.)) * 255.).round() as u8);
+        *output = Rgba([r, g, b, 255]);
+    }
+
+    image
+}
+
+pub(crate) fn run(preset: Option<&str>, path: &Path, size: [u32; 2]) -> Result<()> {
+    let preset = match preset {
+        Some(name) => Preset::resolve(name)?,
+        None => Preset::default(),
+    };
+
+    let image = render(&preset, size);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, screenshot::encode_png(&image, Some(&preset))?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    log::info!("Wrote {}", path.display());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::ui::UiSineWaveData;
+
+    const GOLDEN_SIZE: [u32; 2] = [96, 54];
+    // Headroom for float differences between platforms, in 8-bit sRGB steps.
+    const GOLDEN_TOLERANCE: u8 = 2;
+
+    fn reference_preset() -> Preset {
+        let mut fractal = UiSineWaveData {
+            center: [0.3, 0.5],
+            inner_radius: 0.25,
+            thickness: 0.02,
+            ..UiSineWaveData::active()
+        };
+        fractal.fractal.enabled = true;
+        fractal.outline.enabled = true;
+
+        Preset {
+            waves: vec![
+                UiSineWaveData::default(),
+                fractal,
+                UiSineWaveData {
+                    center: [0.7, 0.5],
+                    inner_radius: 0.2,
+                    cycles: 5.,
+                    ..UiSineWaveData::active()
+                },
+            ],
+            ..Preset::default()
+        }
+    }
+
+    #[test]
+    fn layers_match_gpu_numbering() {
+        let layers = rings(&reference_preset(), 16. / 9.)
+            .iter()
+            .map(|ring| ring.layer)
+            .collect::<Vec<_>>();
+
+        assert_eq!(layers, [1, 2]);
+    }
+
+    #[test]
+    fn render_is_deterministic() {
+        let preset = reference_preset();
+
+        assert_eq!(render(&preset, GOLDEN_SIZE), render(&preset, GOLDEN_SIZE));
+    }
+
+    // Run with `SIGIL_BLESS=1` to rewrite the reference image after an intended change.
+    #[test]
+    fn render_matches_golden() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden/cpu_render.png");
+        let image = render(&reference_preset(), GOLDEN_SIZE);
+
+        if std::env::var_os("SIGIL_BLESS").is_some() {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            image.save(&path).unwrap();
+            return;
+        }
+
+        let golden = image::open(&path)
+            .unwrap_or_else(|err| panic!("Failed to open {}: {err}", path.display()))
+            .to_rgba8();
+        assert_eq!(golden.dimensions(), image.dimensions());
+
+        let worst = golden
+            .pixels()
+            .zip(image.pixels())
+            .flat_map(|(expected, actual)| {
+                expected
+                    .0
+                    .iter()
+                    .zip(actual.0)
+                    .map(|(expected, actual)| expected.abs_diff(actual))
+            })
+            .max()
+            .unwrap_or(0);
+        assert!(
+            worst <= GOLDEN_TOLERANCE,
+            "Render differs from {} by up to {worst}",
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use egui::{Context, TextureId};
+use egui_wgpu::Renderer;
+use wgpu::{Device, FilterMode, TextureView};
+
+struct DebugTarget {
+    name: String,
+    texture_id: TextureId,
+    size: [u32; 2],
+}
+
+#[derive(Default)]
+pub(crate) struct DebugView {
+    pub(crate) open: bool,
+    targets: Vec<DebugTarget>,
+    selected: usize,
+}
+
+impl DebugView {
+    pub(crate) fn set_target(
+        &mut self,
+        name: &str,
+        view: &TextureView,
+        size: [u32; 2],
+        renderer: &mut Renderer,
+        device: &Device,
+    ) {
+        match self.targets.iter_mut().find(|target| target.name == name) {
+            Some(target) => {
+                renderer.update_egui_texture_from_wgpu_texture(
+                    device,
+                    view,
+                    FilterMode::Linear,
+                    target.texture_id,
+                );
+                target.size = size;
+            }
+            None => self.targets.push(DebugTarget {
+                name: name.to_owned(),
+                texture_id: renderer.register_native_texture(device, view, FilterMode::Linear),
+                size,
+            }),
+        }
+    }
+
+    pub(crate) fn show(&mut self, ctx: &Context) {
+        if !self.open {
+            return;
+        }
+
+        egui::Window::new("Debug View")
+            .open(&mut self.open)
+            .resizable(true)
+            .default_width(320.)
+            .show(ctx, |ui| {
+                let Some(current) = self.targets.get(self.selected) else {
+                    ui.label("No render targets registered");
+                    return;
+                };
+
+                egui::ComboBox::from_label("Target")
+                    .selected_text(&current.name)
+                    .show_ui(ui, |ui| {
+                        for (i, target) in self.targets.iter().enumerate() {
+                            ui.selectable_value(&mut self.selected, i, &target.name);
+                        }
+                    });
+
+                let Some(target) = self.targets.get(self.selected) else {
+                    return;
+                };
+
+                let [width, height] = target.size.map(|size| size.max(1) as f32);
+                ui.label(format!("{} x {}", target.size[0], target.size[1]));
+
+                let display_width = ui.available_width();
+                ui.image((
+                    target.texture_id,
+                    egui::vec2(display_width, display_width * height / width),
+                ));
+            });
+    }
+}
@@ -0,0 +1,60 @@
+use std::f32::consts::PI;
+
+use crate::pipelines::sine::SineWaveData;
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct DrawStats {
+    pub(crate) draw_calls: u32,
+    pub(crate) instances: u32,
+    pub(crate) ring_coverage: f32,
+    pub(crate) shaded_screens: f32,
+}
+
+impl DrawStats {
+    pub(crate) fn fullscreen() -> Self {
+        Self {
+            draw_calls: 1,
+            instances: 1,
+            ring_coverage: 0.,
+            shaded_screens: 1.,
+        }
+    }
+
+    pub(crate) fn rings(instances: &[SineWaveData], aspect: f32) -> Self {
+        let mut stats = Self {
+            draw_calls: 1,
+            instances: instances.len() as u32,
+            ..Default::default()
+        };
+
+        let screen_area = aspect.max(f32::EPSILON);
+        for wave in instances {
+            let outer = wave.inner_radius + wave.amplitude.abs() + wave.thickness;
+            let inner = (wave.inner_radius - wave.amplitude.abs()).max(0.);
+            stats.ring_coverage += PI * (outer * outer - inner * inner) / screen_area;
+
+            // Mirrors the bounding quad the sine vertex shader expands each ring into.
+            let reach = wave.reach();
+            if 2. * reach >= 1. || 2. * reach >= aspect {
+                stats.shaded_screens += 1.;
+            } else {
+                let [x, y] = wave.center;
+                let (half_x, half_y) = (reach / aspect, reach);
+                let width = (x + half_x).min(1.) - (x - half_x).max(0.);
+                let height = (y + half_y).min(1.) - (y - half_y).max(0.);
+                stats.shaded_screens += width.max(0.) * height.max(0.);
+            }
+        }
+
+        stats
+    }
+
+    pub(crate) fn add(self, other: Self) -> Self {
+        Self {
+            draw_calls: self.draw_calls + other.draw_calls,
+            instances: self.instances + other.instances,
+            ring_coverage: self.ring_coverage + other.ring_coverage,
+            shaded_screens: self.shaded_screens + other.shaded_screens,
+        }
+    }
+}
@@ -0,0 +1,589 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result, bail};
+use image::RgbaImage;
+use wgpu::{
+    Backends, Color, CommandEncoder, Device, Instance, InstanceDescriptor, LoadOp, Operations,
+    PowerPreference, Queue, RenderPass, RenderPassColorAttachment, RenderPassDescriptor, StoreOp,
+    Texture, TextureFormat, TextureView, wgt::CommandEncoderDescriptor,
+};
+
+use crate::{
+    audio::{analysis::AudioSnapshot, response::ResponseFollowers},
+    color_space::PostSettings,
+    global::Global,
+    gpu::{self, Capabilities, ErrorLog},
+    macros::Macro,
+    motion,
+    physics::Physics,
+    pipelines::{
+        background::BackgroundPipeline, field_warp::FieldWarpPipeline,
+        pixel_sort::PixelSortPipeline, post::PostPipeline,
+        reaction_diffusion::ReactionDiffusionPipeline, scene::ScenePipeline, sine::MAX_WAVES,
+    },
+    preset::Preset,
+    render::Render,
+    render_graph::RenderGraph,
+    scope, screenshot,
+    strobe::StrobeLevel,
+    stroke,
+    ui::{
+        UiFieldWarp, UiGrade, UiPhysics, UiPixelSort, UiReactionDiffusion, UiScene, UiSineWaveData,
+    },
+    upload::Upload,
+    upload::Uploads,
+};
+
+const DUMP_WIDTH: u32 = 1280;
+const DUMP_HEIGHT: u32 = 720;
+const DUMP_FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+// The scene advances one tick per frame, and the live window draws about 60 a second.
+const TICK_SECONDS: f32 = 1. / 60.;
+
+pub(crate) struct Headless {
+    pub(crate) device: Device,
+    pub(crate) queue: Queue,
+    capabilities: Capabilities,
+    errors: ErrorLog,
+}
+
+impl Headless {
+    pub(crate) fn new(safe_mode: bool) -> Result<Self> {
+        let instance = Instance::new(&InstanceDescriptor {
+            backends: Backends::default(),
+            ..Default::default()
+        });
+        let adapter = pollster::block_on(gpu::request_adapter(
+            &instance,
+            None,
+            PowerPreference::default(),
+        ))?;
+        let (device, queue, capabilities) =
+            pollster::block_on(gpu::request_device(&adapter, safe_mode))?;
+
+        let errors = ErrorLog::default();
+        errors.install(&device);
+
+        Ok(Self {
+            device,
+            queue,
+            capabilities,
+            errors,
+        })
+    }
+
+    pub(crate) fn log_errors(&self) {
+        for message in self.errors.take() {
+            log::error!("{message}");
+        }
+    }
+}
+
+// Effects a preset doesn't carry, run only so a dump can write each stage. They chain in the live
+// window's order, while the final frame leaves them out as the window does by default.
+struct Stages {
+    background: Option<(Texture, TextureView)>,
+    reaction_diffusion: Option<(ReactionDiffusionPipeline, UiReactionDiffusion)>,
+    pixel_sort: Option<(PixelSortPipeline, UiPixelSort)>,
+    field_warp: Option<(FieldWarpPipeline, UiFieldWarp)>,
+    post_texture: Texture,
+    post_view: TextureView,
+}
+
+impl Stages {
+    fn new(
+        headless: &Headless,
+        scene_view: &TextureView,
+        background: bool,
+        [width, height]: [u32; 2],
+    ) -> Result<Self> {
+        let Headless {
+            device,
+            capabilities,
+            ..
+        } = headless;
+        let compute = capabilities.compute;
+
+        let background = background
+            .then(|| Render::create_off_screen_texture(width, height, DUMP_FORMAT, device));
+
+        let reaction_diffusion = compute
+            .then(|| {
+                gpu::validated(device, || {
+                    ReactionDiffusionPipeline::new(DUMP_FORMAT, device)
+                })
+            })
+            .transpose()?
+            .map(|pipeline| (pipeline, UiReactionDiffusion::default()));
+
+        let pixel_sort = compute
+            .then(|| {
+                gpu::validated(device, || {
+                    PixelSortPipeline::new(scene_view, width, height, device)
+                })
+            })
+            .transpose()?
+            .map(|pipeline| {
+                let settings = UiPixelSort {
+                    enabled: true,
+                    ..Default::default()
+                };
+                (pipeline, settings)
+            });
+
+        let warp_source = pixel_sort
+            .as_ref()
+            .map_or(scene_view, |(pipeline, _)| pipeline.output_view());
+        let field_warp = compute
+            .then(|| {
+                gpu::validated(device, || {
+                    FieldWarpPipeline::new(warp_source, width, height, device)
+                })
+            })
+            .transpose()?
+            .map(|pipeline| {
+                let settings = UiFieldWarp {
+                    enabled: true,
+                    ..Default::default()
+                };
+                (pipeline, settings)
+            });
+
+        let (post_texture, post_view) =
+            Render::create_off_screen_texture(width, height, DUMP_FORMAT, device);
+
+        Ok(Self {
+            background,
+            reaction_diffusion,
+            pixel_sort,
+            field_warp,
+            post_texture,
+            post_view,
+        })
+    }
+
+    fn update(&mut self, upload: &mut Upload) {
+        if let Some((pipeline, settings)) = &mut self.reaction_diffusion {
+            pipeline.update(settings, upload);
+        }
+        if let Some((pipeline, settings)) = &mut self.pixel_sort {
+            pipeline.update(settings, upload);
+        }
+        if let Some((pipeline, settings)) = &mut self.field_warp {
+            pipeline.update(settings, upload);
+        }
+    }
+}
+
+pub(crate) struct HeadlessScene {
+    scene: UiScene,
+    waves: Vec<UiSineWaveData>,
+    macros: Vec<Macro>,
+    physics_settings: UiPhysics,
+    physics: Physics,
+    time_step: f32,
+    elapsed: f32,
+    background_pipeline: Option<BackgroundPipeline>,
+    scene_pipeline: ScenePipeline,
+    scene_texture: Texture,
+    scene_view: TextureView,
+    size: [u32; 2],
+    final_texture: Texture,
+    final_view: TextureView,
+    drawn: bool,
+    stages: Option<Stages>,
+    post_pipeline: PostPipeline,
+    uploads: Uploads,
+    audio_response: ResponseFollowers,
+    audio: Option<(AudioSnapshot, f32)>,
+}
+
+impl HeadlessScene {
+    pub(crate) fn new(
+        headless: &Headless,
+        preset: &Preset,
+        [width, height]: [u32; 2],
+        stages: bool,
+    ) -> Result<Self> {
+        let Headless {
+            device,
+            queue,
+            capabilities,
+            errors,
+        } = headless;
+
+        let scene = UiScene {
+            mode: preset.mode,
+            ramp: preset.ramp.clone(),
+            background: preset.background.clone(),
+            ..Default::default()
+        };
+
+        let mut waves = preset
+            .waves
+            .iter()
+            .filter(|wave| wave.init)
+            .take(MAX_WAVES)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let global = Global::new(width, height);
+        let scene_pipeline = ScenePipeline::new(
+            Render::create_sine(),
+            global,
+            &scene.ramp,
+            DUMP_FORMAT,
+            capabilities,
+            errors,
+            device,
+        )?;
+
+        for (i, wave) in waves.iter_mut().enumerate() {
+            wave.textured = false;
+            if wave.texture_path.trim().is_empty() {
+                continue;
+            }
+
+            match stroke::load_image(Path::new(wave.texture_path.trim())) {
+                Ok(image) => {
+                    scene_pipeline.set_wave_texture(i, &image, queue);
+                    wave.textured = true;
+                }
+                Err(err) => log::warn!("Wave {}: failed to load texture: {err}", i + 1),
+            }
+        }
+
+        let (scene_texture, scene_view) =
+            Render::create_off_screen_texture(width, height, DUMP_FORMAT, device);
+        let (final_texture, final_view) =
+            Render::create_off_screen_texture(width, height, DUMP_FORMAT, device);
+
+        let background_pipeline = scene
+            .background
+            .enabled
+            .then(|| {
+                gpu::validated(device, || {
+                    BackgroundPipeline::new(&scene.background, DUMP_FORMAT, device)
+                })
+            })
+            .transpose()?;
+
+        let stages = stages
+            .then(|| {
+                Stages::new(
+                    headless,
+                    &scene_view,
+                    scene.background.enabled,
+                    [width, height],
+                )
+            })
+            .transpose()?;
+
+        let post_pipeline = PostPipeline::new(
+            &scene_view,
+            DUMP_FORMAT,
+            global,
+            PostSettings::new(DUMP_FORMAT.is_srgb(), true),
+            device,
+            queue,
+        );
+
+        Ok(Self {
+            scene,
+            waves,
+            macros: preset.macros.clone(),
+            physics_settings: preset.physics,
+            physics: Physics::default(),
+            time_step: 1.,
+            elapsed: 0.,
+            background_pipeline,
+            scene_pipeline,
+            scene_texture,
+            scene_view,
+            size: [width, height],
+            final_texture,
+            final_view,
+            drawn: false,
+            stages,
+            post_pipeline,
+            uploads: Uploads::default(),
+            audio_response: ResponseFollowers::default(),
+            audio: None,
+        })
+    }
+
+    pub(crate) fn advance(&mut self, headless: &Headless, draw: bool) -> Result<()> {
+        let Headless { device, queue, .. } = headless;
+
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("Dump Encoder"),
+        });
+
+        // The same per-frame wave pipeline as the live window, minus what only a performer drives.
+        let [width, height] = self.size;
+        let mut waves = self.waves.clone();
+        for macro_control in &self.macros {
+            macro_control.apply(&mut waves);
+        }
+        motion::apply(
+            &mut waves,
+            self.elapsed,
+            width as f32 / height.max(1) as f32,
+        );
+        let dt = self.time_step * TICK_SECONDS;
+        self.elapsed += dt;
+        let features = self
+            .audio
+            .as_ref()
+            .map(|(audio, _)| audio.features)
+            .unwrap_or_default();
+        let waves = self.physics.step(
+            &waves,
+            self.physics_settings.enabled,
+            self.physics_settings.spring,
+            &features,
+            dt,
+        );
+
+        {
+            let mut upload = self.uploads.begin(&mut encoder, device);
+            if let Some(background_pipeline) = &self.background_pipeline {
+                background_pipeline.update(
+                    &self.scene.background,
+                    width as f32 / height.max(1) as f32,
+                    &mut upload,
+                );
+            }
+            let drives = match &self.audio {
+                Some((audio, dt)) => self.audio_response.follow(waves, &audio.features, *dt),
+                None => &[],
+            };
+            self.scene_pipeline.update(
+                waves,
+                &[],
+                &self.scene.ramp,
+                &self.scene,
+                drives,
+                &mut upload,
+            );
+            if let Some((audio, _)) = &self.audio {
+                self.scene_pipeline
+                    .update_scope(&scope::capture(&audio.waveform, false), &mut upload);
+                self.scene_pipeline
+                    .update_spectrum(&audio.spectrum, &mut upload);
+            }
+
+            if let Some(stages) = &mut self.stages {
+                stages.update(&mut upload);
+            }
+        }
+        self.uploads.finish();
+
+        let stages = self.stages.as_ref();
+        let mut graph = RenderGraph::<Self>::new();
+        let pattern = graph.import("Reaction Diffusion");
+        let background = graph.import("Background");
+        let draw_args = graph.import("Draw Arguments");
+        let scene = graph.import("Scene");
+        let sorted = graph.import("Sorted Scene");
+        let warped = graph.import("Warped Scene");
+        let final_target = graph.import("Final");
+
+        // The pattern is a simulation, so it steps on every advance rather than only when drawn.
+        if stages.is_some_and(|stages| stages.reaction_diffusion.is_some()) {
+            graph.add_pass("Reaction Diffusion", &[], &[pattern], |dump, encoder| {
+                let stages = dump.stages.as_mut();
+                if let Some((pipeline, settings)) =
+                    stages.and_then(|stages| stages.reaction_diffusion.as_mut())
+                {
+                    pipeline.dispatch(settings.steps, false, encoder);
+                }
+            });
+        }
+
+        if draw {
+            if stages.is_some_and(|stages| stages.background.is_some()) {
+                graph.add_pass("Background", &[], &[background], |dump, encoder| {
+                    let stages = dump.stages.as_ref();
+                    if let Some((_, view)) = stages.and_then(|stages| stages.background.as_ref())
+                        && let Some(background_pipeline) = &dump.background_pipeline
+                    {
+                        clear_pass(view, encoder, |render_pass| {
+                            background_pipeline.set_render_pass(render_pass)
+                        });
+                    }
+                });
+            }
+            graph.add_pass("Cull", &[], &[draw_args], |dump, encoder| {
+                dump.scene_pipeline.dispatch_cull(dump.scene.mode, encoder)
+            });
+            graph.add_pass("Scene", &[draw_args], &[scene], |dump, encoder| {
+                clear_pass(&dump.scene_view, encoder, |render_pass| {
+                    if let Some(background_pipeline) = &dump.background_pipeline {
+                        background_pipeline.set_render_pass(render_pass);
+                    }
+                    dump.scene_pipeline
+                        .set_render_pass(dump.scene.mode, render_pass)
+                })
+            });
+            if stages.is_some_and(|stages| stages.pixel_sort.is_some()) {
+                graph.add_pass("Pixel Sort", &[scene], &[sorted], |dump, encoder| {
+                    let stages = dump.stages.as_ref();
+                    if let Some((pipeline, _)) =
+                        stages.and_then(|stages| stages.pixel_sort.as_ref())
+                    {
+                        pipeline.dispatch(encoder);
+                    }
+                });
+            }
+            if stages.is_some_and(|stages| stages.field_warp.is_some()) {
+                graph.add_pass(
+                    "Field Warp",
+                    &[scene, sorted],
+                    &[warped],
+                    |dump, encoder| {
+                        let stages = dump.stages.as_ref();
+                        if let Some((pipeline, _)) =
+                            stages.and_then(|stages| stages.field_warp.as_ref())
+                        {
+                            pipeline.dispatch(encoder);
+                        }
+                    },
+                );
+            }
+            graph.add_pass("Post", &[scene], &[final_target], |dump, encoder| {
+                clear_pass(&dump.final_view, encoder, |render_pass| {
+                    dump.post_pipeline.set_render_pass(render_pass)
+                })
+            });
+        }
+
+        graph.execute(self, &mut encoder)?;
+        self.drawn |= draw;
+
+        queue.submit(std::iter::once(encoder.finish()));
+        self.uploads.recall();
+
+        Ok(())
+    }
+
+    pub(crate) fn set_time_step(&mut self, step: f32) {
+        self.time_step = step;
+        self.scene_pipeline.set_time_step(step);
+    }
+
+    // Audio heard by the next `advance`, which lasts `dt` seconds of audio time.
+    pub(crate) fn set_audio(&mut self, audio: AudioSnapshot, dt: f32) {
+        self.audio = Some((audio, dt));
+    }
+
+    pub(crate) fn capture(&self, headless: &Headless) -> Result<RgbaImage> {
+        if !self.drawn {
+            bail!("No frame has been drawn");
+        }
+        screenshot::capture(&headless.device, &headless.queue, &self.final_texture)
+    }
+
+    // Every target the last drawn frame wrote, in pass order.
+    fn stage_images(&mut self, headless: &Headless) -> Result<Vec<(&'static str, RgbaImage)>> {
+        let Headless { device, queue, .. } = headless;
+        let capture = |texture: &Texture| screenshot::capture(device, queue, texture);
+
+        let mut images = Vec::new();
+        let stages = self.stages.as_ref();
+        if let Some((texture, _)) = stages.and_then(|stages| stages.background.as_ref()) {
+            images.push(("background", capture(texture)?));
+        }
+        if let Some((pipeline, _)) = stages.and_then(|stages| stages.reaction_diffusion.as_ref()) {
+            images.push(("reaction-diffusion", capture(pipeline.pattern_texture())?));
+        }
+        images.push(("scene", capture(&self.scene_texture)?));
+        if let Some((pipeline, _)) = stages.and_then(|stages| stages.pixel_sort.as_ref()) {
+            images.push(("pixel-sort-polar", capture(pipeline.polar_texture())?));
+            images.push(("pixel-sort", capture(pipeline.output_texture())?));
+        }
+        if let Some((pipeline, _)) = stages.and_then(|stages| stages.field_warp.as_ref()) {
+            images.push(("field-warp", capture(pipeline.output_texture())?));
+        }
+
+        // Strobe and grade are steps of the one post shader, so each gets a post render of its
+        // own, adding steps in shader order. A dump carries no performer settings, so they run
+        // neutral.
+        if let Some(stages) = stages {
+            let settings = PostSettings::new(DUMP_FORMAT.is_srgb(), true);
+            let strobe = settings.with_strobe(StrobeLevel::default());
+            let grade = strobe.with_grade(&UiGrade {
+                enabled: true,
+                ..Default::default()
+            });
+
+            for (name, settings) in [("strobe", strobe), ("grade", grade)] {
+                self.post_pipeline.update_settings(settings, queue);
+                let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+                    label: Some("Dump Post Stage Encoder"),
+                });
+                clear_pass(&stages.post_view, &mut encoder, |render_pass| {
+                    self.post_pipeline.set_render_pass(render_pass)
+                });
+                queue.submit(std::iter::once(encoder.finish()));
+                images.push((name, capture(&stages.post_texture)?));
+            }
+            self.post_pipeline.update_settings(settings, queue);
+        }
+
+        if self.drawn {
+            images.push(("final", capture(&self.final_texture)?));
+        }
+
+        Ok(images)
+    }
+}
+
+pub(crate) fn run(frame: u32, preset: Option<&str>, dir: &Path, safe_mode: bool) -> Result<()> {
+    let preset = match preset {
+        Some(name) => Preset::resolve(name)?,
+        None => Preset::default(),
+    };
+
+    let headless = Headless::new(safe_mode)?;
+    let mut scene = HeadlessScene::new(&headless, &preset, [DUMP_WIDTH, DUMP_HEIGHT], true)?;
+
+    for current in 0..=frame {
+        scene.advance(&headless, current == frame)?;
+    }
+
+    headless.log_errors();
+
+    fs::create_dir_all(dir)?;
+
+    for (i, (name, image)) in scene.stage_images(&headless)?.into_iter().enumerate() {
+        let path = dir.join(format!("{:02}-{name}.png", i + 1));
+        fs::write(&path, screenshot::encode_png(&image, Some(&preset))?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        log::info!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn clear_pass(
+    view: &TextureView,
+    encoder: &mut CommandEncoder,
+    draw: impl FnOnce(&mut RenderPass<'_>),
+) {
+    let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view,
+            ops: Operations {
+                load: LoadOp::Clear(Color::BLACK),
+                store: StoreOp::Store,
+            },
+            resolve_target: None,
+            depth_slice: None,
+        })],
+        label: Some("Dump Render Pass"),
+        ..Default::default()
+    });
+
+    draw(&mut render_pass);
+}
@@ -0,0 +1,114 @@
+use std::f32::consts::TAU;
+
+use serde::{Deserialize, Serialize};
+
+const BEZIER_ITERATIONS: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub(crate) enum Curve {
+    #[default]
+    Linear,
+    EaseInOut,
+    Bounce,
+    Elastic,
+    Bezier,
+}
+
+impl Curve {
+    pub(crate) const ALL: [Curve; 5] = [
+        Curve::Linear,
+        Curve::EaseInOut,
+        Curve::Bounce,
+        Curve::Elastic,
+        Curve::Bezier,
+    ];
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Easing {
+    pub(crate) curve: Curve,
+    pub(crate) period: f32,
+    pub(crate) control: [[f32; 2]; 2],
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Self {
+            curve: Curve::Linear,
+            period: 120.,
+            control: [[0.42, 0.], [0.58, 1.]],
+        }
+    }
+}
+
+impl Easing {
+    pub(crate) fn sample(&self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+
+        match self.curve {
+            Curve::Linear => t,
+            Curve::EaseInOut => t * t * (3. - 2. * t),
+            Curve::Bounce => bounce(t),
+            Curve::Elastic => elastic(t),
+            Curve::Bezier => bezier(self.control, t),
+        }
+    }
+
+    pub(crate) fn warp(&self, time: f32) -> f32 {
+        if self.curve == Curve::Linear || self.period <= 0. {
+            return time;
+        }
+
+        let cycle = (time / self.period).floor();
+        let t = time / self.period - cycle;
+
+        (cycle + self.sample(t)) * self.period
+    }
+}
+
+fn bounce(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if t < 1. / D {
+        N * t * t
+    } else if t < 2. / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+fn elastic(t: f32) -> f32 {
+    if t <= 0. || t >= 1. {
+        return t;
+    }
+
+    2f32.powf(-10. * t) * ((10. * t - 0.75) * TAU / 3.).sin() + 1.
+}
+
+fn bezier([[x1, y1], [x2, y2]]: [[f32; 2]; 2], x: f32) -> f32 {
+    let axis = |a: f32, b: f32, s: f32| {
+        let inv = 1. - s;
+        3. * inv * inv * s * a + 3. * inv * s * s * b + s * s * s
+    };
+
+    let (mut low, mut high) = (0., 1.);
+    let mut s = x;
+    for _ in 0..BEZIER_ITERATIONS {
+        if axis(x1, x2, s) < x {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / 2.;
+    }
+
+    axis(y1, y2, s)
+}
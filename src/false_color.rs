@@ -0,0 +1,69 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum FalseColorMap {
+    #[default]
+    Off,
+    Thermal,
+    Contours,
+    Bands,
+}
+
+impl FalseColorMap {
+    pub(crate) const ALL: [Self; 4] = [Self::Off, Self::Thermal, Self::Contours, Self::Bands];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Thermal => "Thermal",
+            Self::Contours => "Contours",
+            Self::Bands => "Bands",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum FalseColorSource {
+    #[default]
+    Distance,
+    Field,
+}
+
+impl FalseColorSource {
+    pub(crate) const ALL: [Self; 2] = [Self::Distance, Self::Field];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Distance => "SDF Distance",
+            Self::Field => "Wave Field",
+        }
+    }
+}
+
+pub(crate) struct UiFalseColor {
+    pub(crate) map: FalseColorMap,
+    pub(crate) source: FalseColorSource,
+    pub(crate) range: f32,
+    pub(crate) bands: u32,
+}
+
+impl Default for UiFalseColor {
+    fn default() -> Self {
+        Self {
+            map: FalseColorMap::Off,
+            source: FalseColorSource::Distance,
+            range: 0.05,
+            bands: 8,
+        }
+    }
+}
+
+impl UiFalseColor {
+    // Packed as floats so the global uniform keeps a single vec4 for it.
+    pub(crate) fn params(&self) -> [f32; 4] {
+        [
+            self.map as u32 as f32,
+            self.source as u32 as f32,
+            self.range,
+            self.bands as f32,
+        ]
+    }
+}
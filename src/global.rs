@@ -7,14 +7,21 @@ use wgpu::{
     util::{BufferInitDescriptor, DeviceExt},
 };
 
-use crate::utils::BindGroupData;
+use crate::{camera::Camera, utils::BindGroupData};
 
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable)]
 pub(crate) struct Global {
     resolution: [f32; 2],
     phase: f32,
-    _padding: f32,
+    aspect: f32,
+    highlight: [f32; 4],
+    antialias: f32,
+    zoom: f32,
+    pan: [f32; 2],
+    false_color: [f32; 4],
+    hairline: f32,
+    _padding: [f32; 3],
 }
 
 impl Global {
@@ -22,16 +29,62 @@ impl Global {
         Self {
             resolution: [width as f32, height as f32],
             phase: 0.,
-            _padding: 0.,
+            aspect: width as f32 / height.max(1) as f32,
+            highlight: [0.; 4],
+            antialias: 0.,
+            zoom: 1.,
+            pan: [0.; 2],
+            false_color: [0.; 4],
+            hairline: 0.,
+            _padding: [0.; 3],
         }
     }
 
-    pub(crate) fn increment_frame(&mut self) {
-        self.phase = self.phase + 1.;
+    pub(crate) fn advance_frame(&mut self, step: f32) {
+        self.phase += step;
     }
 
     pub(crate) fn set_resolution(&mut self, width: u32, height: u32) {
         self.resolution = [width as f32, height as f32];
+        self.aspect = width as f32 / height.max(1) as f32;
+    }
+
+    pub(crate) fn set_phase(&mut self, phase: f32) {
+        self.phase = phase;
+    }
+
+    pub(crate) fn phase(&self) -> f32 {
+        self.phase
+    }
+
+    pub(crate) fn aspect(&self) -> f32 {
+        self.aspect
+    }
+
+    pub(crate) fn set_highlight(&mut self, [r, g, b]: [f32; 3], strength: f32) {
+        self.highlight = [r, g, b, strength];
+    }
+
+    pub(crate) fn set_antialias(&mut self, width: f32) {
+        self.antialias = width;
+    }
+
+    pub(crate) fn set_hairline(&mut self, hairline: bool) {
+        self.hairline = hairline as u32 as f32;
+    }
+
+    pub(crate) fn set_false_color(&mut self, params: [f32; 4]) {
+        self.false_color = params;
+    }
+
+    pub(crate) fn set_camera(&mut self, camera: &Camera) {
+        self.zoom = camera.zoom;
+        self.pan = camera.pan;
+    }
+
+    pub(crate) fn set_stretch(&mut self, stretch: bool) {
+        let [width, height] = self.resolution;
+        self.aspect = if stretch { 1. } else { width / height.max(1.) };
     }
 
     pub(crate) fn create_bind_group_data(&self, device: &Device) -> BindGroupData {
@@ -45,7 +98,7 @@ impl Global {
             label: Some("Global Bind Group Layout"),
             entries: &[BindGroupLayoutEntry {
                 binding: 0,
-                visibility: ShaderStages::FRAGMENT,
+                visibility: ShaderStages::VERTEX_FRAGMENT,
                 ty: BindingType::Buffer {
                     ty: BufferBindingType::Uniform,
                     has_dynamic_offset: false,
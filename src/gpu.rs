@@ -0,0 +1,183 @@
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use anyhow::{Result, anyhow};
+use wgpu::{
+    Adapter, Device, DeviceDescriptor, DeviceLostReason, DownlevelFlags, ErrorFilter, Instance,
+    Limits, PowerPreference, Queue, RequestAdapterOptions, Surface,
+};
+
+const MAX_PENDING_ERRORS: usize = 64;
+
+// Safe mode runs on WebGL2 limits. Without storage buffers the rings take the vertex path and
+// interference mode is off; without compute there is no pixel sort, field warp, reaction
+// diffusion, GPU audio analysis or indirect culling; HDR output is refused. The post, HUD,
+// background, downscale and Shadertoy passes are plain fragment shaders well inside those limits,
+// so they keep their full shaders. Nothing in sigil multisamples, so there is no MSAA to drop.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Capabilities {
+    pub(crate) safe_mode: bool,
+    pub(crate) storage_buffers: bool,
+    pub(crate) compute: bool,
+    pub(crate) indirect: bool,
+}
+
+pub(crate) async fn request_adapter(
+    instance: &Instance,
+    surface: Option<&Surface<'static>>,
+    power_preference: PowerPreference,
+) -> Result<Adapter> {
+    let adapter = instance
+        .request_adapter(&RequestAdapterOptions {
+            power_preference,
+            compatible_surface: surface,
+            ..Default::default()
+        })
+        .await;
+
+    match adapter {
+        Ok(adapter) => Ok(adapter),
+        Err(err) => {
+            log::warn!("No hardware adapter available ({err}), trying fallback adapter");
+
+            Ok(instance
+                .request_adapter(&RequestAdapterOptions {
+                    power_preference,
+                    compatible_surface: surface,
+                    force_fallback_adapter: true,
+                })
+                .await?)
+        }
+    }
+}
+
+pub(crate) async fn request_device(
+    adapter: &Adapter,
+    safe_mode: bool,
+) -> Result<(Device, Queue, Capabilities)> {
+    let full = if safe_mode {
+        None
+    } else {
+        match adapter
+            .request_device(&DeviceDescriptor {
+                label: Some("Device Descriptor"),
+                ..Default::default()
+            })
+            .await
+        {
+            Ok(device) => Some(device),
+            Err(err) => {
+                log::warn!("Device creation failed ({err}), retrying with downlevel limits");
+                None
+            }
+        }
+    };
+
+    let downlevel = full.is_none();
+
+    let (device, queue) = match full {
+        Some(device) => device,
+        None => {
+            adapter
+                .request_device(&DeviceDescriptor {
+                    label: Some("Downlevel Device Descriptor"),
+                    required_limits: Limits::downlevel_webgl2_defaults()
+                        .using_resolution(adapter.limits()),
+                    ..Default::default()
+                })
+                .await?
+        }
+    };
+
+    let storage_buffers = !downlevel
+        && adapter
+            .get_downlevel_capabilities()
+            .flags
+            .contains(DownlevelFlags::FRAGMENT_STORAGE)
+        && device.limits().max_storage_buffers_per_shader_stage > 0;
+
+    let limits = device.limits();
+    let compute = !downlevel
+        && adapter
+            .get_downlevel_capabilities()
+            .flags
+            .contains(DownlevelFlags::COMPUTE_SHADERS)
+        && limits.max_compute_invocations_per_workgroup >= 256
+        && limits.max_storage_textures_per_shader_stage > 0;
+
+    let indirect = compute
+        && storage_buffers
+        && adapter
+            .get_downlevel_capabilities()
+            .flags
+            .contains(DownlevelFlags::INDIRECT_EXECUTION)
+        && limits.max_storage_buffers_per_shader_stage >= 3;
+
+    let capabilities = Capabilities {
+        safe_mode: downlevel,
+        storage_buffers,
+        compute,
+        indirect,
+    };
+
+    Ok((device, queue, capabilities))
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct ErrorLog(Arc<Mutex<Vec<String>>>);
+
+impl ErrorLog {
+    pub(crate) fn push(&self, message: String) {
+        log::error!("{message}");
+
+        let Ok(mut pending) = self.0.lock() else {
+            return;
+        };
+
+        if pending.len() < MAX_PENDING_ERRORS {
+            pending.push(message);
+        }
+    }
+
+    pub(crate) fn take(&self) -> Vec<String> {
+        self.0
+            .lock()
+            .map(|mut pending| std::mem::take(&mut *pending))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn install(&self, device: &Device) {
+        let errors = self.clone();
+        device.on_uncaptured_error(Arc::new(move |err| {
+            errors.push(format!("Uncaptured GPU error: {err}"));
+        }));
+    }
+}
+
+pub(crate) fn watch_device_lost(device: &Device) -> Arc<AtomicBool> {
+    let lost = Arc::new(AtomicBool::new(false));
+    let flag = lost.clone();
+
+    device.set_device_lost_callback(move |reason, message| {
+        if reason == DeviceLostReason::Destroyed {
+            return;
+        }
+
+        log::error!("GPU device lost: {message}");
+        flag.store(true, Ordering::Relaxed);
+    });
+
+    lost
+}
+
+pub(crate) fn validated<T>(device: &Device, create: impl FnOnce() -> T) -> Result<T> {
+    device.push_error_scope(ErrorFilter::Validation);
+    let value = create();
+
+    match pollster::block_on(device.pop_error_scope()) {
+        Some(err) => Err(anyhow!("{err}")),
+        None => Ok(value),
+    }
+}
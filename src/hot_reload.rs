@@ -0,0 +1,212 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    time::{Instant, SystemTime},
+};
+
+use anyhow::{Context, Result};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{
+    preset::Preset,
+    ui::{Ui, UiSineWaveData},
+};
+
+struct Watched {
+    name: String,
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    // Watches the presets directory rather than the file: editors that save by rename replace
+    // the inode, which would silently end a watch on the file itself.
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+}
+
+impl Watched {
+    fn new(name: &str) -> Result<Self> {
+        let path = Preset::named_path(name)?;
+        let dir = path.parent().context("Preset path has no directory")?;
+
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .with_context(|| format!("Failed to watch {}", dir.display()))?;
+
+        Ok(Self {
+            name: name.to_owned(),
+            modified: PresetWatcher::modified(&path),
+            path,
+            _watcher: watcher,
+            events,
+        })
+    }
+
+    fn changed(&self) -> bool {
+        // Drains the queue either way so stale events don't trigger a later reload.
+        let mut changed = false;
+        for event in self.events.try_iter().filter_map(Result::ok) {
+            changed |= event.paths.contains(&self.path);
+        }
+        changed
+    }
+}
+
+pub(crate) struct Morph {
+    from: Vec<UiSineWaveData>,
+    to: Vec<UiSineWaveData>,
+    started: Instant,
+    duration: f32,
+    // What the last step wrote to each wave; a wave edited since is left where the user put it.
+    written: Vec<Option<[f32; 7]>>,
+    released: Vec<bool>,
+}
+
+impl Morph {
+    pub(crate) fn new(from: Vec<UiSineWaveData>, to: Vec<UiSineWaveData>, duration: f32) -> Self {
+        let count = to.len();
+        Self {
+            from,
+            to,
+            started: Instant::now(),
+            duration,
+            written: vec![None; count],
+            released: vec![false; count],
+        }
+    }
+
+    fn morphed(wave: &UiSineWaveData) -> [f32; 7] {
+        [
+            wave.center[0],
+            wave.center[1],
+            wave.inner_radius,
+            wave.thickness,
+            wave.amplitude,
+            wave.cycles,
+            wave.speed,
+        ]
+    }
+
+    // Returns whether the morph has reached its target.
+    pub(crate) fn advance(&mut self, waves: &mut [UiSineWaveData]) -> bool {
+        let t = if self.duration > 0. {
+            (self.started.elapsed().as_secs_f32() / self.duration).min(1.)
+        } else {
+            1.
+        };
+        let eased = t * t * (3. - 2. * t);
+
+        let pairs = self.from.iter().zip(&self.to);
+        for (i, (wave, (from, to))) in waves.iter_mut().zip(pairs).enumerate() {
+            if self.written[i].is_some_and(|written| written != Self::morphed(wave)) {
+                self.released[i] = true;
+            }
+            if self.released[i] {
+                continue;
+            }
+
+            let lerp = |a: f32, b: f32| a + (b - a) * eased;
+
+            wave.center = [
+                lerp(from.center[0], to.center[0]),
+                lerp(from.center[1], to.center[1]),
+            ];
+            wave.inner_radius = lerp(from.inner_radius, to.inner_radius);
+            wave.thickness = lerp(from.thickness, to.thickness);
+            wave.amplitude = lerp(from.amplitude, to.amplitude);
+            wave.cycles = lerp(from.cycles, to.cycles);
+            wave.speed = lerp(from.speed, to.speed);
+            from.locks.keep(from, wave);
+            self.written[i] = Some(Self::morphed(wave));
+        }
+
+        t >= 1.
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct PresetWatcher {
+    watched: Option<Watched>,
+    // The preset the watch was last armed for, so a failed watch isn't retried every frame.
+    armed: Option<String>,
+    transition: Option<Morph>,
+}
+
+impl PresetWatcher {
+    pub(crate) fn update(&mut self, ui: &mut Ui) {
+        self.advance(ui);
+
+        // The loaded preset, not the name field, which changes on every keystroke.
+        let current = ui
+            .presets
+            .current
+            .as_deref()
+            .filter(|_| ui.presets.hot_reload);
+        let Some(name) = current else {
+            self.watched = None;
+            self.armed = None;
+            return;
+        };
+        if self.armed.as_deref() != Some(name) {
+            self.armed = Some(name.to_owned());
+            self.watched = Watched::new(name)
+                .inspect_err(|err| log::warn!("Hot reload of `{name}` is off: {err:#}"))
+                .ok();
+            return;
+        }
+
+        let Some(watched) = &mut self.watched else {
+            return;
+        };
+        if !watched.changed() {
+            return;
+        }
+
+        let modified = Self::modified(&watched.path);
+        if modified.is_none() || modified == watched.modified {
+            return;
+        }
+        watched.modified = modified;
+        // sigil's own save already matches the scene.
+        if modified == ui.presets.saved {
+            return;
+        }
+
+        match Preset::load(&watched.path) {
+            Ok(preset) => {
+                let from = ui.waves.0.clone();
+                preset.apply(ui);
+                // The morph below already animates the change, so skip the load reveal.
+                ui.reveal.replaced = None;
+
+                self.transition = Some(Morph::new(
+                    from,
+                    ui.waves.0.clone(),
+                    ui.presets.transition_secs,
+                ));
+                ui.presets.status = Some(format!("Reloaded `{}`", watched.name));
+                self.advance(ui);
+            }
+            Err(err) => {
+                ui.presets.status = Some(format!("Failed to reload `{}`: {err}", watched.name));
+            }
+        }
+    }
+
+    pub(crate) fn modified(path: &Path) -> Option<SystemTime> {
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    fn advance(&mut self, ui: &mut Ui) {
+        if self
+            .transition
+            .as_mut()
+            .is_some_and(|transition| transition.advance(&mut ui.waves.0))
+        {
+            self.transition = None;
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::ui::UiSineWaveData;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HudHandle {
+    Radius,
+    Thickness,
+    Amplitude,
+}
+
+impl HudHandle {
+    pub(crate) const ALL: [Self; 3] = [Self::Radius, Self::Thickness, Self::Amplitude];
+
+    // Each handle sits on its own side of the ring so they stay apart for thin strokes.
+    fn direction(self) -> [f32; 2] {
+        match self {
+            Self::Radius => [1., 0.],
+            Self::Thickness => [0., -1.],
+            Self::Amplitude => [-1., 0.],
+        }
+    }
+
+    fn distance(self, wave: &UiSineWaveData) -> f32 {
+        match self {
+            Self::Radius => wave.inner_radius,
+            Self::Thickness => wave.inner_radius + wave.thickness,
+            Self::Amplitude => wave.inner_radius + wave.amplitude,
+        }
+    }
+
+    pub(crate) fn position(self, wave: &UiSineWaveData, aspect: f32) -> [f32; 2] {
+        let [x, y] = self.direction();
+        let distance = self.distance(wave);
+
+        [
+            wave.center[0] + x * distance / aspect,
+            wave.center[1] + y * distance,
+        ]
+    }
+
+    pub(crate) fn drag(self, wave: &mut UiSineWaveData, distance: f32) {
+        match self {
+            Self::Radius => wave.inner_radius = distance.clamp(0., 1.),
+            Self::Thickness => wave.thickness = (distance - wave.inner_radius).clamp(0.001, 0.1),
+            Self::Amplitude => wave.amplitude = (distance - wave.inner_radius).clamp(0., 0.1),
+        }
+    }
+
+    pub(crate) fn index(self) -> u32 {
+        match self {
+            Self::Radius => 0,
+            Self::Thickness => 1,
+            Self::Amplitude => 2,
+        }
+    }
+}
+
+pub(crate) struct UiHud {
+    pub(crate) enabled: bool,
+    pub(crate) opacity: f32,
+    pub(crate) color: [f32; 3],
+    pub(crate) active: Option<HudHandle>,
+}
+
+impl Default for UiHud {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            opacity: 0.7,
+            color: [1., 0.6, 0.],
+            active: None,
+        }
+    }
+}
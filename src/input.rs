@@ -0,0 +1,292 @@
+use std::{
+    collections::BTreeMap,
+    f32::consts::PI,
+    time::{Duration, Instant},
+};
+
+use egui::{Color32, Context, Id, LayerId, Order, Rect, Stroke, pos2};
+use winit::event::{Touch, TouchPhase};
+
+use crate::{
+    canvas::{to_screen, to_uv},
+    pipelines::sine::MAX_WAVES,
+    ui::{UiSineWaveData, UiWaves},
+};
+
+// Distances are in egui points, the same space as the mouse canvas.
+const HIT_RADIUS: f32 = 32.;
+const TAP_DURATION: Duration = Duration::from_millis(250);
+const TAP_DISTANCE: f32 = 12.;
+const MIN_PEN_RADIUS: f32 = 0.02;
+
+pub(crate) struct UiTouch {
+    pub(crate) enabled: bool,
+    pub(crate) pen_draws: bool,
+    pub(crate) min_thickness: f32,
+    pub(crate) max_thickness: f32,
+}
+
+impl Default for UiTouch {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pen_draws: true,
+            min_thickness: 0.005,
+            max_thickness: 0.06,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Contact {
+    start: [f32; 2],
+    position: [f32; 2],
+    started: Instant,
+}
+
+struct Gesture {
+    wave: usize,
+    distance: f32,
+    angle: f32,
+    inner_radius: f32,
+    rotation: f32,
+}
+
+struct PenStroke {
+    id: u64,
+    wave: usize,
+}
+
+pub(crate) struct TouchInput {
+    pub(crate) settings: UiTouch,
+    contacts: BTreeMap<u64, Contact>,
+    gesture: Option<Gesture>,
+    pen: Option<PenStroke>,
+    selected: Option<usize>,
+    rect: Rect,
+    aspect: f32,
+}
+
+impl Default for TouchInput {
+    fn default() -> Self {
+        Self {
+            settings: UiTouch::default(),
+            contacts: BTreeMap::new(),
+            gesture: None,
+            pen: None,
+            selected: None,
+            rect: Rect::ZERO,
+            aspect: 1.,
+        }
+    }
+}
+
+impl TouchInput {
+    pub(crate) fn gesturing(&self) -> bool {
+        self.gesture.is_some() || self.pen.is_some()
+    }
+
+    // `rect` is the camera view of the scene in points, the rect the canvas maps the mouse into.
+    pub(crate) fn handle(
+        &mut self,
+        touch: &Touch,
+        pixels_per_point: f32,
+        rect: Rect,
+        stretch: bool,
+        over_ui: bool,
+        waves: &mut UiWaves,
+    ) {
+        if !self.settings.enabled {
+            return;
+        }
+
+        self.rect = rect;
+        self.aspect = if stretch {
+            1.
+        } else {
+            rect.width() / rect.height().max(1.)
+        };
+        let position = [
+            touch.location.x as f32 / pixels_per_point,
+            touch.location.y as f32 / pixels_per_point,
+        ];
+        let pressure = touch.force.map(|force| force.normalized() as f32);
+
+        match touch.phase {
+            TouchPhase::Started => {
+                if over_ui {
+                    return;
+                }
+
+                self.contacts.insert(
+                    touch.id,
+                    Contact {
+                        start: position,
+                        position,
+                        started: Instant::now(),
+                    },
+                );
+
+                match self.contacts.len() {
+                    1 if pressure.is_some() && self.settings.pen_draws => {
+                        self.start_pen(touch.id, position, waves)
+                    }
+                    2 => self.start_gesture(waves),
+                    _ => {}
+                }
+            }
+            TouchPhase::Moved => {
+                let Some(contact) = self.contacts.get_mut(&touch.id) else {
+                    return;
+                };
+                contact.position = position;
+                let contact = *contact;
+
+                if let Some(pen) = &self.pen
+                    && pen.id == touch.id
+                {
+                    self.draw_pen(contact, pressure.unwrap_or(0.5), waves);
+                }
+                self.update_gesture(waves);
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                let Some(contact) = self.contacts.remove(&touch.id) else {
+                    return;
+                };
+
+                if self.pen.as_ref().is_some_and(|pen| pen.id == touch.id) {
+                    self.pen = None;
+                } else if touch.phase == TouchPhase::Ended
+                    && self.gesture.is_none()
+                    && self.contacts.is_empty()
+                    && self.is_tap(contact)
+                {
+                    self.selected = self.hit_test(position, waves);
+                }
+
+                if self.contacts.len() < 2 {
+                    self.gesture = None;
+                }
+            }
+        }
+    }
+
+    fn is_tap(&self, contact: Contact) -> bool {
+        let [dx, dy] = [
+            contact.position[0] - contact.start[0],
+            contact.position[1] - contact.start[1],
+        ];
+
+        contact.started.elapsed() < TAP_DURATION && dx.hypot(dy) < TAP_DISTANCE
+    }
+
+    fn to_uv(&self, [x, y]: [f32; 2]) -> [f32; 2] {
+        to_uv(self.rect, pos2(x, y))
+    }
+
+    fn hit_test(&self, position: [f32; 2], waves: &UiWaves) -> Option<usize> {
+        let [u, v] = self.to_uv(position);
+        let (aspect, height) = (self.aspect, self.rect.height());
+
+        waves
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, wave)| wave.init)
+            .map(|(i, wave)| {
+                let offset = [(u - wave.center[0]) * aspect, v - wave.center[1]];
+                let center_distance = offset[0].hypot(offset[1]);
+                let ring_distance = (center_distance - wave.inner_radius).abs();
+
+                (i, center_distance.min(ring_distance) * height)
+            })
+            .filter(|(_, distance)| *distance < HIT_RADIUS)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    fn start_gesture(&mut self, waves: &UiWaves) {
+        let Some(wave) = self.selected.filter(|i| waves.0.get(*i).is_some()) else {
+            return;
+        };
+        let Some((distance, angle)) = self.span() else {
+            return;
+        };
+
+        self.gesture = Some(Gesture {
+            wave,
+            distance,
+            angle,
+            inner_radius: waves.0[wave].inner_radius,
+            rotation: waves.0[wave].rotation,
+        });
+    }
+
+    fn update_gesture(&mut self, waves: &mut UiWaves) {
+        let (Some(gesture), Some((distance, angle))) = (&self.gesture, self.span()) else {
+            return;
+        };
+        let Some(wave) = waves.0.get_mut(gesture.wave) else {
+            return;
+        };
+
+        let scale = distance / gesture.distance.max(1.);
+        wave.inner_radius = (gesture.inner_radius * scale).clamp(0.01, 1.5);
+
+        let delta = (angle - gesture.angle + PI).rem_euclid(2. * PI) - PI;
+        wave.rotation = gesture.rotation + delta;
+    }
+
+    fn span(&self) -> Option<(f32, f32)> {
+        let mut contacts = self.contacts.values();
+        let (a, b) = (contacts.next()?, contacts.next()?);
+        let [dx, dy] = [b.position[0] - a.position[0], b.position[1] - a.position[1]];
+
+        Some((dx.hypot(dy), dy.atan2(dx)))
+    }
+
+    fn start_pen(&mut self, id: u64, position: [f32; 2], waves: &mut UiWaves) {
+        if waves.0.len() >= MAX_WAVES {
+            return;
+        }
+
+        waves.0.push(UiSineWaveData {
+            center: self.to_uv(position),
+            inner_radius: MIN_PEN_RADIUS,
+            thickness: self.settings.min_thickness,
+            ..UiSineWaveData::active()
+        });
+
+        let wave = waves.0.len() - 1;
+        self.pen = Some(PenStroke { id, wave });
+        self.selected = Some(wave);
+    }
+
+    fn draw_pen(&self, contact: Contact, pressure: f32, waves: &mut UiWaves) {
+        let Some(wave) = self.pen.as_ref().and_then(|pen| waves.0.get_mut(pen.wave)) else {
+            return;
+        };
+
+        let [u, v] = self.to_uv(contact.position);
+        let offset = [(u - wave.center[0]) * self.aspect, v - wave.center[1]];
+        wave.inner_radius = offset[0].hypot(offset[1]).max(MIN_PEN_RADIUS);
+
+        let pressure = pressure.clamp(0., 1.);
+        wave.thickness = self.settings.min_thickness
+            + (self.settings.max_thickness - self.settings.min_thickness) * pressure;
+    }
+
+    pub(crate) fn paint(&mut self, ctx: &Context, rect: Rect, waves: &UiWaves) {
+        let Some(wave) = self.selected.and_then(|i| waves.0.get(i)) else {
+            self.selected = None;
+            return;
+        };
+
+        let painter = ctx.layer_painter(LayerId::new(Order::Background, Id::new("touch")));
+        painter.circle_stroke(
+            to_screen(rect, wave.center),
+            wave.inner_radius * rect.height(),
+            Stroke::new(1.5, Color32::from_rgb(255, 200, 0)),
+        );
+    }
+}
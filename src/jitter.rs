@@ -0,0 +1,55 @@
+use crate::{random::hash, ui::UiSineWaveData};
+
+const STEP_SIZE: f32 = 0.08;
+const PULL: f32 = 0.02;
+
+#[derive(Clone, Copy, Default)]
+struct Walk {
+    seed: u32,
+    steps: u32,
+    offset: f32,
+    drift: f32,
+}
+
+impl Walk {
+    fn new(seed: u32) -> Self {
+        Self {
+            seed,
+            ..Default::default()
+        }
+    }
+
+    // A mean-reverting walk in [-1, 1]; its integral is what the phase sees, so speed changes never jump.
+    fn advance(&mut self, layer: u32, wave: &UiSineWaveData, step: f32) {
+        let kick = hash(self.seed, layer, self.steps as i32);
+        self.steps = self.steps.wrapping_add(1);
+        self.offset = ((self.offset + STEP_SIZE * kick) * (1. - PULL)).clamp(-1., 1.);
+        self.drift += wave.speed * wave.speed_jitter * self.offset * step;
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct SpeedJitter {
+    walks: Vec<Walk>,
+}
+
+impl SpeedJitter {
+    pub(crate) fn advance(&mut self, waves: &[UiSineWaveData], step: f32) {
+        self.walks.truncate(waves.len());
+        for (layer, wave) in waves.iter().enumerate() {
+            match self.walks.get_mut(layer) {
+                Some(walk) if walk.seed == wave.jitter_seed => {}
+                Some(walk) => *walk = Walk::new(wave.jitter_seed),
+                None => self.walks.push(Walk::new(wave.jitter_seed)),
+            }
+
+            if wave.speed_jitter > 0. {
+                self.walks[layer].advance(layer as u32, wave, step);
+            }
+        }
+    }
+
+    pub(crate) fn drift(&self, layer: usize) -> f32 {
+        self.walks.get(layer).map_or(0., |walk| walk.drift)
+    }
+}
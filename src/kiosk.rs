@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use anyhow::{Result, anyhow, bail};
+use winit::{
+    event::{ElementState, KeyEvent, WindowEvent},
+    keyboard::{ModifiersState, PhysicalKey},
+};
+
+pub(crate) const DEFAULT_CHORD: &str = "ctrl+alt+shift+q";
+pub(crate) const RESTART_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Chord {
+    modifiers: ModifiersState,
+    key: String,
+}
+
+impl Chord {
+    pub(crate) fn parse(chord: &str) -> Result<Self> {
+        let mut modifiers = ModifiersState::empty();
+        let mut key = None;
+
+        for part in chord.split('+').map(|part| part.trim().to_lowercase()) {
+            match part.as_str() {
+                "ctrl" | "control" => modifiers |= ModifiersState::CONTROL,
+                "alt" => modifiers |= ModifiersState::ALT,
+                "shift" => modifiers |= ModifiersState::SHIFT,
+                "super" | "cmd" | "meta" => modifiers |= ModifiersState::SUPER,
+                _ if key.is_some() => bail!("Chord `{chord}` names more than one key"),
+                _ => key = Some(Self::key_name(&part)?),
+            }
+        }
+
+        let key = key.ok_or_else(|| anyhow!("Chord `{chord}` has no key"))?;
+        if modifiers.is_empty() {
+            bail!("Chord `{chord}` needs at least one modifier");
+        }
+
+        Ok(Self { modifiers, key })
+    }
+
+    // Matches the `Debug` names of winit's `KeyCode`, so `q` becomes `KeyQ` and `f5` becomes `F5`.
+    fn key_name(part: &str) -> Result<String> {
+        let mut chars = part.chars();
+        let name = match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_alphabetic() => format!("Key{}", c.to_ascii_uppercase()),
+            (Some(c), None) if c.is_ascii_digit() => format!("Digit{c}"),
+            _ if part.starts_with('f')
+                && part[1..].parse::<u8>().is_ok_and(|n| (1..=24).contains(&n)) =>
+            {
+                part.to_uppercase()
+            }
+            _ => match part {
+                "escape" | "esc" => "Escape".to_owned(),
+                "space" => "Space".to_owned(),
+                "enter" | "return" => "Enter".to_owned(),
+                "backspace" => "Backspace".to_owned(),
+                "delete" => "Delete".to_owned(),
+                _ => bail!("Unknown key `{part}`"),
+            },
+        };
+
+        Ok(name)
+    }
+
+    pub(crate) fn matches(&self, modifiers: ModifiersState, event: &KeyEvent) -> bool {
+        let PhysicalKey::Code(code) = event.physical_key else {
+            return false;
+        };
+
+        event.state == ElementState::Pressed
+            && !event.repeat
+            && modifiers == self.modifiers
+            && format!("{code:?}") == self.key
+    }
+}
+
+pub(crate) struct Kiosk {
+    chord: Chord,
+    modifiers: ModifiersState,
+}
+
+impl Kiosk {
+    pub(crate) fn new(chord: Chord) -> Self {
+        Self {
+            chord,
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    pub(crate) fn exit_requested(&mut self, event: &WindowEvent) -> bool {
+        match event {
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers.state();
+                false
+            }
+            WindowEvent::KeyboardInput { event, .. } => self.chord.matches(self.modifiers, event),
+            _ => false,
+        }
+    }
+}
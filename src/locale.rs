@@ -0,0 +1,315 @@
+use std::{ops::RangeInclusive, sync::OnceLock};
+
+use egui::{Context, Id};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Languages whose conventions write `0,5` rather than `0.5`.
+const COMMA_LANGUAGES: [&str; 16] = [
+    "de", "fr", "es", "it", "nl", "pt", "ru", "pl", "tr", "sv", "da", "fi", "nb", "cs", "hu", "id",
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum Language {
+    #[default]
+    English,
+    German,
+    French,
+    Spanish,
+}
+
+impl Language {
+    pub(crate) const ALL: [Self; 4] = [Self::English, Self::German, Self::French, Self::Spanish];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+            Self::French => "Français",
+            Self::Spanish => "Español",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum DecimalSeparator {
+    #[default]
+    System,
+    Point,
+    Comma,
+}
+
+impl DecimalSeparator {
+    pub(crate) const ALL: [Self; 3] = [Self::System, Self::Point, Self::Comma];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::System => "System",
+            Self::Point => "0.5",
+            Self::Comma => "0,5",
+        }
+    }
+
+    fn comma(self) -> bool {
+        match self {
+            Self::System => system_uses_comma(),
+            Self::Point => false,
+            Self::Comma => true,
+        }
+    }
+}
+
+// The environment doesn't change under a running process, so it's read once.
+fn system_uses_comma() -> bool {
+    static COMMA: OnceLock<bool> = OnceLock::new();
+
+    *COMMA.get_or_init(|| {
+        let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        let language = locale.split(['_', '.', '-']).next().unwrap_or_default();
+
+        COMMA_LANGUAGES.contains(&language)
+    })
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Locale {
+    pub(crate) language: Language,
+    pub(crate) comma: bool,
+}
+
+impl Locale {
+    pub(crate) fn new(language: Language, decimal: DecimalSeparator) -> Self {
+        Self {
+            language,
+            comma: decimal.comma(),
+        }
+    }
+
+    fn id() -> Id {
+        Id::new("locale")
+    }
+
+    pub(crate) fn store(self, ctx: &Context) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(), self));
+    }
+
+    pub(crate) fn load(ctx: &Context) -> Self {
+        ctx.data(|data| data.get_temp(Self::id()).unwrap_or_default())
+    }
+}
+
+// Typed values accept either separator whatever the locale, so pasted numbers always parse.
+pub(crate) fn parse_number(text: &str) -> Option<f64> {
+    text.trim().replace(',', ".").parse().ok()
+}
+
+pub(crate) fn format_number(value: f64, decimals: RangeInclusive<usize>, comma: bool) -> String {
+    let text = egui::emath::format_with_decimals_in_range(value, decimals);
+    if comma { text.replace('.', ",") } else { text }
+}
+
+// Hand-edited presets sometimes carry `"0,5"` strings; this turns them back into numbers. Only
+// fields that hold a number in `schema` are touched, so a wave named `1,5` keeps its name.
+pub(crate) fn normalize_decimals(value: &mut Value, schema: &Value) {
+    match schema {
+        Value::Number(_) => {
+            if let Value::String(text) = value
+                && is_decimal(text)
+                && let Some(number) = parse_number(text).and_then(serde_json::Number::from_f64)
+            {
+                *value = Value::Number(number);
+            }
+        }
+        // List elements all share the shape of the schema's first one.
+        Value::Array(schema) => {
+            if let (Value::Array(items), Some(schema)) = (value, schema.first()) {
+                items
+                    .iter_mut()
+                    .for_each(|item| normalize_decimals(item, schema));
+            }
+        }
+        Value::Object(schema) => {
+            if let Value::Object(fields) = value {
+                for (name, field) in fields {
+                    if let Some(schema) = schema.get(name) {
+                        normalize_decimals(field, schema);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_decimal(text: &str) -> bool {
+    let digits = text.trim().strip_prefix('-').unwrap_or(text.trim());
+    let mut parts = digits.split([',', '.']);
+    let whole = parts.next().unwrap_or_default();
+    let fraction = parts.next();
+
+    parts.next().is_none()
+        && !whole.is_empty()
+        && whole.chars().all(|c| c.is_ascii_digit())
+        && fraction.is_none_or(|fraction| {
+            !fraction.is_empty() && fraction.chars().all(|c| c.is_ascii_digit())
+        })
+}
+
+// German, French and Spanish, in that order.
+const TRANSLATIONS: &[(&str, [&str; 3])] = &[
+    (
+        "Control Panel",
+        ["Bedienfeld", "Panneau de contrôle", "Panel de control"],
+    ),
+    ("Design", ["Entwurf", "Conception", "Diseño"]),
+    ("Performance", ["Auftritt", "Performance", "Actuación"]),
+    ("Console", ["Konsole", "Console", "Consola"]),
+    (
+        "Add Wave",
+        ["Welle hinzufügen", "Ajouter une onde", "Añadir onda"],
+    ),
+    ("Spawn…", ["Erzeugen…", "Générer…", "Generar…"]),
+    (
+        "Debug View",
+        ["Debug-Ansicht", "Vue de débogage", "Vista de depuración"],
+    ),
+    ("Logs", ["Protokoll", "Journaux", "Registros"]),
+    (
+        "Filter Waves:",
+        ["Wellen filtern:", "Filtrer les ondes :", "Filtrar ondas:"],
+    ),
+    ("Clear", ["Leeren", "Effacer", "Borrar"]),
+    ("Stats", ["Statistik", "Statistiques", "Estadísticas"]),
+    ("Power", ["Energie", "Énergie", "Energía"]),
+    ("Presets", ["Voreinstellungen", "Préréglages", "Preajustes"]),
+    ("Project", ["Projekt", "Projet", "Proyecto"]),
+    (
+        "Playlist",
+        [
+            "Wiedergabeliste",
+            "Liste de lecture",
+            "Lista de reproducción",
+        ],
+    ),
+    (
+        "Show Control",
+        [
+            "Showsteuerung",
+            "Contrôle de spectacle",
+            "Control de espectáculo",
+        ],
+    ),
+    (
+        "Cursor Trail",
+        ["Cursorspur", "Traînée du curseur", "Estela del cursor"],
+    ),
+    ("Brush", ["Pinsel", "Pinceau", "Pincel"]),
+    ("Macros", ["Makros", "Macros", "Macros"]),
+    (
+        "Trigger Pads",
+        ["Trigger-Pads", "Pads de déclenchement", "Pads de disparo"],
+    ),
+    ("Bypass", ["Umgehen", "Contournement", "Anular"]),
+    ("Scene", ["Szene", "Scène", "Escena"]),
+    ("Strobe", ["Stroboskop", "Stroboscope", "Estroboscopio"]),
+    ("Color Grade", ["Farbkorrektur", "Étalonnage", "Etalonaje"]),
+    ("Display", ["Anzeige", "Affichage", "Pantalla"]),
+    ("Theme", ["Design-Thema", "Thème", "Tema"]),
+    (
+        "Export Preview",
+        [
+            "Exportvorschau",
+            "Aperçu d'export",
+            "Vista previa de exportación",
+        ],
+    ),
+    (
+        "Replay Buffer",
+        [
+            "Wiederholungspuffer",
+            "Tampon de relecture",
+            "Búfer de repetición",
+        ],
+    ),
+    (
+        "Metrics Log",
+        [
+            "Messprotokoll",
+            "Journal des mesures",
+            "Registro de métricas",
+        ],
+    ),
+    ("Routing", ["Signalweg", "Routage", "Enrutamiento"]),
+    (
+        "Color Space",
+        ["Farbraum", "Espace colorimétrique", "Espacio de color"],
+    ),
+    (
+        "Pixel Sort",
+        ["Pixelsortierung", "Tri de pixels", "Ordenación de píxeles"],
+    ),
+    (
+        "Field Warp",
+        [
+            "Feldverzerrung",
+            "Déformation de champ",
+            "Deformación de campo",
+        ],
+    ),
+    (
+        "Reaction Diffusion",
+        [
+            "Reaktion-Diffusion",
+            "Réaction-diffusion",
+            "Reacción-difusión",
+        ],
+    ),
+    (
+        "Shadertoy Backdrop",
+        ["Shadertoy-Hintergrund", "Fond Shadertoy", "Fondo Shadertoy"],
+    ),
+    ("Compare", ["Vergleichen", "Comparer", "Comparar"]),
+    ("Physics", ["Physik", "Physique", "Física"]),
+    ("Ambient", ["Ambient", "Ambiance", "Ambiente"]),
+    ("Art-Net", ["Art-Net", "Art-Net", "Art-Net"]),
+    (
+        "Clock Sync",
+        [
+            "Taktsynchronisation",
+            "Synchro d'horloge",
+            "Sincronización de reloj",
+        ],
+    ),
+    ("Snapping", ["Einrasten", "Magnétisme", "Ajuste"]),
+    ("Wave HUD", ["Wellen-HUD", "HUD d'onde", "HUD de onda"]),
+    (
+        "Touch & Pen",
+        ["Touch & Stift", "Tactile et stylet", "Táctil y lápiz"],
+    ),
+    ("Audio", ["Audio", "Audio", "Audio"]),
+    ("Synth", ["Synthesizer", "Synthé", "Sintetizador"]),
+    (
+        "Random Constraints",
+        ["Zufallsgrenzen", "Limites aléatoires", "Límites aleatorios"],
+    ),
+    ("Language", ["Sprache", "Langue", "Idioma"]),
+    ("Decimals", ["Dezimalzeichen", "Décimales", "Decimales"]),
+];
+
+pub(crate) fn tr(ctx: &Context, text: &'static str) -> &'static str {
+    let slot = match Locale::load(ctx).language {
+        Language::English => return text,
+        Language::German => 0,
+        Language::French => 1,
+        Language::Spanish => 2,
+    };
+
+    TRANSLATIONS
+        .iter()
+        .find(|(english, _)| *english == text)
+        .map_or(text, |(_, translated)| translated[slot])
+}
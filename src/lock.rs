@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{macros::MacroParam, random::hash, ui::UiSineWaveData};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct ParamLocks(Vec<MacroParam>);
+
+impl ParamLocks {
+    pub(crate) fn contains(&self, param: MacroParam) -> bool {
+        self.0.contains(&param)
+    }
+
+    pub(crate) fn set(&mut self, param: MacroParam, locked: bool) {
+        self.0.retain(|locked| *locked != param);
+        if locked {
+            self.0.push(param);
+        }
+    }
+
+    // Copies every locked value back from `from`, undoing whatever a randomize or morph wrote.
+    pub(crate) fn keep(&self, from: &UiSineWaveData, wave: &mut UiSineWaveData) {
+        for &param in &self.0 {
+            *param.field(wave) = param.value(from);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RandomRange {
+    pub(crate) param: MacroParam,
+    pub(crate) min: f32,
+    pub(crate) max: f32,
+}
+
+// Only the narrowed params are stored; the rest roll across their full slider range.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct RandomConstraints(Vec<RandomRange>);
+
+impl RandomConstraints {
+    pub(crate) fn range(&self, param: MacroParam) -> (f32, f32) {
+        self.0
+            .iter()
+            .find(|range| range.param == param)
+            .map_or(param.range(), |range| {
+                (range.min.min(range.max), range.min.max(range.max))
+            })
+    }
+
+    pub(crate) fn set(&mut self, param: MacroParam, min: f32, max: f32) {
+        self.0.retain(|range| range.param != param);
+        if (min, max) != param.range() {
+            self.0.push(RandomRange { param, min, max });
+        }
+    }
+
+    pub(crate) fn is_constrained(&self, param: MacroParam) -> bool {
+        self.0.iter().any(|range| range.param == param)
+    }
+}
+
+pub(crate) fn randomize(wave: &mut UiSineWaveData, seed: u32, constraints: &RandomConstraints) {
+    for (channel, param) in MacroParam::ALL.into_iter().enumerate() {
+        if wave.locks.contains(param) {
+            continue;
+        }
+
+        let (min, max) = constraints.range(param);
+        let unit = (hash(seed, channel as u32, 0) + 1.) / 2.;
+        let value = min + (max - min) * unit;
+        *param.field(wave) = match param {
+            MacroParam::Cycles => value.round(),
+            _ => value,
+        };
+    }
+}
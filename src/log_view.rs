@@ -0,0 +1,185 @@
+use std::{
+    collections::VecDeque,
+    sync::{Mutex, OnceLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use egui::Context;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+const MAX_ENTRIES: usize = 2000;
+const CAPTURE_LEVEL: LevelFilter = LevelFilter::Info;
+
+struct LogEntry {
+    level: Level,
+    target: String,
+    message: String,
+    seconds: u64,
+}
+
+impl LogEntry {
+    fn line(&self) -> String {
+        let seconds = self.seconds % 86400;
+        format!(
+            "{:02}:{:02}:{:02} {:<5} {}: {}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60,
+            self.level,
+            self.target,
+            self.message
+        )
+    }
+}
+
+static ENTRIES: OnceLock<Mutex<VecDeque<LogEntry>>> = OnceLock::new();
+
+fn entries() -> &'static Mutex<VecDeque<LogEntry>> {
+    ENTRIES.get_or_init(|| Mutex::new(VecDeque::with_capacity(MAX_ENTRIES)))
+}
+
+struct RingLogger {
+    inner: env_logger::Logger,
+}
+
+impl Log for RingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= CAPTURE_LEVEL || self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.inner.matches(record) {
+            self.inner.log(record);
+        }
+
+        if record.level() > CAPTURE_LEVEL {
+            return;
+        }
+
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let entry = LogEntry {
+            level: record.level(),
+            target: record.target().to_owned(),
+            message: record.args().to_string(),
+            seconds,
+        };
+
+        let Ok(mut entries) = entries().lock() else {
+            return;
+        };
+        if entries.len() >= MAX_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+pub(crate) fn init() {
+    let inner = env_logger::Builder::from_default_env().build();
+    let max_level = inner.filter().max(CAPTURE_LEVEL);
+
+    if log::set_boxed_logger(Box::new(RingLogger { inner })).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+pub(crate) struct LogView {
+    pub(crate) open: bool,
+    level: LevelFilter,
+    search: String,
+}
+
+impl Default for LogView {
+    fn default() -> Self {
+        Self {
+            open: false,
+            level: LevelFilter::Warn,
+            search: String::new(),
+        }
+    }
+}
+
+impl LogView {
+    pub(crate) fn show(&mut self, ctx: &Context) {
+        if !self.open {
+            return;
+        }
+
+        egui::Window::new("Logs")
+            .open(&mut self.open)
+            .resizable(true)
+            .default_width(520.)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_label("Level")
+                        .selected_text(self.level.as_str())
+                        .show_ui(ui, |ui| {
+                            for level in [LevelFilter::Error, LevelFilter::Warn, LevelFilter::Info]
+                            {
+                                ui.selectable_value(&mut self.level, level, level.as_str());
+                            }
+                        });
+
+                    ui.label("Search:");
+                    ui.text_edit_singleline(&mut self.search);
+                });
+
+                let search = self.search.to_lowercase();
+                let Ok((total, lines)) = entries().lock().map(|entries| {
+                    let lines = entries
+                        .iter()
+                        .filter(|entry| entry.level <= self.level)
+                        .filter(|entry| {
+                            search.is_empty()
+                                || entry.message.to_lowercase().contains(&search)
+                                || entry.target.to_lowercase().contains(&search)
+                        })
+                        .map(|entry| (entry.level, entry.line()))
+                        .collect::<Vec<_>>();
+                    (entries.len(), lines)
+                }) else {
+                    return;
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} of {total} entries", lines.len()));
+                    if ui.button("Copy").clicked() {
+                        let text = lines
+                            .iter()
+                            .map(|(_, line)| line.as_str())
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        ui.ctx().copy_text(text);
+                    }
+                    if ui.button("Clear").clicked()
+                        && let Ok(mut entries) = entries().lock()
+                    {
+                        entries.clear();
+                    }
+                });
+
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(360.)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for (level, line) in &lines {
+                            let color = match level {
+                                Level::Error => egui::Color32::RED,
+                                Level::Warn => egui::Color32::YELLOW,
+                                _ => ui.visuals().text_color(),
+                            };
+                            ui.label(egui::RichText::new(line).monospace().color(color));
+                        }
+                    });
+            });
+    }
+}
@@ -0,0 +1,142 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result, anyhow, bail};
+use wgpu::{
+    Device, Extent3d, Queue, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    TextureView, TextureViewDescriptor,
+    util::{DeviceExt, TextureDataOrder},
+};
+
+const MAX_SIZE: u32 = 128;
+
+pub(crate) struct Lut {
+    size: u32,
+    entries: Vec<[f32; 3]>,
+}
+
+impl Lut {
+    pub(crate) fn identity() -> Self {
+        Self {
+            size: 2,
+            entries: (0..8)
+                .map(|i| [(i & 1) as f32, ((i >> 1) & 1) as f32, ((i >> 2) & 1) as f32])
+                .collect(),
+        }
+    }
+
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&text)
+    }
+
+    pub(crate) fn parse(text: &str) -> Result<Self> {
+        let mut size = None;
+        let mut domain_min = [0.; 3];
+        let mut domain_max = [1.; 3];
+        let mut entries = Vec::new();
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            let Some(keyword) = fields.next() else {
+                continue;
+            };
+
+            match keyword {
+                "TITLE" => {}
+                "LUT_1D_SIZE" => bail!("1D LUTs are not supported"),
+                "LUT_3D_SIZE" => {
+                    let value = fields
+                        .next()
+                        .and_then(|value| value.parse::<u32>().ok())
+                        .filter(|value| (2..=MAX_SIZE).contains(value))
+                        .ok_or_else(|| anyhow!("Invalid LUT_3D_SIZE `{line}`"))?;
+                    size = Some(value);
+                }
+                "DOMAIN_MIN" => domain_min = Self::triple(fields, line)?,
+                "DOMAIN_MAX" => domain_max = Self::triple(fields, line)?,
+                _ => entries.push(Self::triple(line.split_whitespace(), line)?),
+            }
+        }
+
+        let size = size.ok_or_else(|| anyhow!("Missing LUT_3D_SIZE"))?;
+        let expected = (size * size * size) as usize;
+        if entries.len() != expected {
+            bail!(
+                "Expected {expected} LUT entries but found {}",
+                entries.len()
+            );
+        }
+
+        for entry in &mut entries {
+            for channel in 0..3 {
+                let range = (domain_max[channel] - domain_min[channel]).max(f32::EPSILON);
+                entry[channel] = ((entry[channel] - domain_min[channel]) / range).clamp(0., 1.);
+            }
+        }
+
+        Ok(Self { size, entries })
+    }
+
+    fn triple<'a>(mut fields: impl Iterator<Item = &'a str>, line: &str) -> Result<[f32; 3]> {
+        let mut value = [0.; 3];
+        for channel in &mut value {
+            *channel = fields
+                .next()
+                .and_then(|field| field.parse::<f32>().ok())
+                .ok_or_else(|| anyhow!("Invalid LUT line `{line}`"))?;
+        }
+
+        Ok(value)
+    }
+
+    pub(crate) fn create_view(&self, device: &Device, queue: &Queue) -> TextureView {
+        let data = self
+            .entries
+            .iter()
+            .flat_map(|&[r, g, b]| [r, g, b, 1.])
+            .flat_map(|channel| f32_to_half(channel).to_le_bytes())
+            .collect::<Vec<_>>();
+
+        let texture = device.create_texture_with_data(
+            queue,
+            &TextureDescriptor {
+                label: Some("Color Grade LUT"),
+                size: Extent3d {
+                    width: self.size,
+                    height: self.size,
+                    depth_or_array_layers: self.size,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D3,
+                format: TextureFormat::Rgba16Float,
+                usage: TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            TextureDataOrder::LayerMajor,
+            &data,
+        );
+
+        texture.create_view(&TextureViewDescriptor::default())
+    }
+}
+
+fn f32_to_half(value: f32) -> u16 {
+    let value = value.clamp(0., 1.);
+    if value < 6.103_515_6e-5 {
+        return (value * 16_777_216.).round() as u16;
+    }
+
+    let bits = value.to_bits();
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127 + 15;
+    let mantissa = (bits >> 13) & 0x3ff;
+    let round = (bits >> 12) & 1;
+
+    (((exponent as u32) << 10 | mantissa) + round) as u16
+}
@@ -0,0 +1,346 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ui::{Ui, UiSineWaveData};
+
+pub(crate) const MACRO_COUNT: usize = 8;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MacroCurve {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    Smooth,
+    Step,
+}
+
+impl MacroCurve {
+    pub(crate) const ALL: [Self; 5] = [
+        Self::Linear,
+        Self::EaseIn,
+        Self::EaseOut,
+        Self::Smooth,
+        Self::Step,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Linear => "Linear",
+            Self::EaseIn => "Ease In",
+            Self::EaseOut => "Ease Out",
+            Self::Smooth => "Smooth",
+            Self::Step => "Step",
+        }
+    }
+
+    fn evaluate(self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => 1. - (1. - t) * (1. - t),
+            Self::Smooth => t * t * (3. - 2. * t),
+            Self::Step => (t >= 0.5) as u32 as f32,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MacroParam {
+    Amplitude,
+    InnerRadius,
+    Thickness,
+    Cycles,
+    Speed,
+    CenterX,
+    CenterY,
+    TextureScroll,
+}
+
+impl MacroParam {
+    pub(crate) const ALL: [Self; 8] = [
+        Self::Amplitude,
+        Self::InnerRadius,
+        Self::Thickness,
+        Self::Cycles,
+        Self::Speed,
+        Self::CenterX,
+        Self::CenterY,
+        Self::TextureScroll,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Amplitude => "Amplitude",
+            Self::InnerRadius => "Inner Radius",
+            Self::Thickness => "Thickness",
+            Self::Cycles => "Cycles",
+            Self::Speed => "Speed",
+            Self::CenterX => "Center X",
+            Self::CenterY => "Center Y",
+            Self::TextureScroll => "Texture Scroll",
+        }
+    }
+
+    pub(crate) fn range(self) -> (f32, f32) {
+        match self {
+            Self::Amplitude => (0., 0.1),
+            Self::InnerRadius => (0., 1.),
+            Self::Thickness => (0.01, 0.1),
+            Self::Cycles => (1., 16.),
+            Self::Speed => (-0.1, 0.1),
+            Self::CenterX | Self::CenterY => (0., 1.),
+            Self::TextureScroll => (-0.05, 0.05),
+        }
+    }
+
+    pub(crate) fn value(self, wave: &UiSineWaveData) -> f32 {
+        match self {
+            Self::Amplitude => wave.amplitude,
+            Self::InnerRadius => wave.inner_radius,
+            Self::Thickness => wave.thickness,
+            Self::Cycles => wave.cycles,
+            Self::Speed => wave.speed,
+            Self::CenterX => wave.center[0],
+            Self::CenterY => wave.center[1],
+            Self::TextureScroll => wave.texture_scroll,
+        }
+    }
+
+    pub(crate) fn field(self, wave: &mut UiSineWaveData) -> &mut f32 {
+        match self {
+            Self::Amplitude => &mut wave.amplitude,
+            Self::InnerRadius => &mut wave.inner_radius,
+            Self::Thickness => &mut wave.thickness,
+            Self::Cycles => &mut wave.cycles,
+            Self::Speed => &mut wave.speed,
+            Self::CenterX => &mut wave.center[0],
+            Self::CenterY => &mut wave.center[1],
+            Self::TextureScroll => &mut wave.texture_scroll,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum GlobalParam {
+    MasterOpacity,
+    HueShift,
+    Saturation,
+    Contrast,
+    LutStrength,
+    StrobeIntensity,
+    PixelSortMix,
+    FieldWarpStrength,
+    ShadertoyOpacity,
+}
+
+impl GlobalParam {
+    pub(crate) const ALL: [Self; 9] = [
+        Self::MasterOpacity,
+        Self::HueShift,
+        Self::Saturation,
+        Self::Contrast,
+        Self::LutStrength,
+        Self::StrobeIntensity,
+        Self::PixelSortMix,
+        Self::FieldWarpStrength,
+        Self::ShadertoyOpacity,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::MasterOpacity => "Master Opacity",
+            Self::HueShift => "Hue Shift",
+            Self::Saturation => "Saturation",
+            Self::Contrast => "Contrast",
+            Self::LutStrength => "LUT Strength",
+            Self::StrobeIntensity => "Strobe Intensity",
+            Self::PixelSortMix => "Pixel Sort Mix",
+            Self::FieldWarpStrength => "Field Warp Strength",
+            Self::ShadertoyOpacity => "Backdrop Opacity",
+        }
+    }
+
+    pub(crate) fn range(self) -> (f32, f32) {
+        match self {
+            Self::HueShift => (-180., 180.),
+            Self::Saturation | Self::Contrast => (0., 2.),
+            Self::FieldWarpStrength => (0., 0.2),
+            Self::MasterOpacity
+            | Self::LutStrength
+            | Self::StrobeIntensity
+            | Self::PixelSortMix
+            | Self::ShadertoyOpacity => (0., 1.),
+        }
+    }
+
+    fn field(self, ui: &mut Ui) -> &mut f32 {
+        match self {
+            Self::MasterOpacity => &mut ui.scene.master_opacity,
+            Self::HueShift => &mut ui.grade.hue_shift,
+            Self::Saturation => &mut ui.grade.saturation,
+            Self::Contrast => &mut ui.grade.contrast,
+            Self::LutStrength => &mut ui.grade.lut_strength,
+            Self::StrobeIntensity => &mut ui.strobe.intensity,
+            Self::PixelSortMix => &mut ui.pixel_sort.mix,
+            Self::FieldWarpStrength => &mut ui.field_warp.strength,
+            Self::ShadertoyOpacity => &mut ui.shadertoy.opacity,
+        }
+    }
+}
+
+// Wave and global names don't overlap, so either kind reads back from its bare name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum TargetParam {
+    Wave(MacroParam),
+    Global(GlobalParam),
+}
+
+impl TargetParam {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Wave(param) => param.label(),
+            Self::Global(param) => param.label(),
+        }
+    }
+
+    pub(crate) fn range(self) -> (f32, f32) {
+        match self {
+            Self::Wave(param) => param.range(),
+            Self::Global(param) => param.range(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct MacroTarget {
+    pub(crate) param: TargetParam,
+    pub(crate) wave: Option<usize>,
+    pub(crate) min: f32,
+    pub(crate) max: f32,
+    #[serde(default)]
+    pub(crate) curve: MacroCurve,
+}
+
+impl MacroTarget {
+    pub(crate) fn new(param: TargetParam) -> Self {
+        let (min, max) = param.range();
+
+        Self {
+            param,
+            wave: None,
+            min,
+            max,
+            curve: MacroCurve::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct MacroSource {
+    pub(crate) name: String,
+    pub(crate) min: f32,
+    pub(crate) max: f32,
+}
+
+impl MacroSource {
+    pub(crate) fn new(name: String) -> Self {
+        Self {
+            name,
+            min: 0.,
+            max: 1.,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Macro {
+    pub(crate) name: String,
+    pub(crate) value: f32,
+    pub(crate) targets: Vec<MacroTarget>,
+    pub(crate) source: Option<MacroSource>,
+}
+
+impl Default for Macro {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            value: 0.5,
+            targets: Vec::new(),
+            source: None,
+        }
+    }
+}
+
+impl Macro {
+    pub(crate) fn defaults() -> Vec<Self> {
+        (1..=MACRO_COUNT)
+            .map(|i| Self {
+                name: format!("Macro {i}"),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    pub(crate) fn follow_source(&mut self, values: &BTreeMap<String, f32>) {
+        let Some(source) = &self.source else {
+            return;
+        };
+        let Some(value) = values.get(&source.name) else {
+            return;
+        };
+
+        let span = source.max - source.min;
+        if span.abs() > f32::EPSILON {
+            self.value = ((value - source.min) / span).clamp(0., 1.);
+        }
+    }
+
+    fn target_value(&self, target: &MacroTarget) -> f32 {
+        target.min + (target.max - target.min) * target.curve.evaluate(self.value)
+    }
+
+    pub(crate) fn apply(&self, waves: &mut [UiSineWaveData]) {
+        for target in &self.targets {
+            let TargetParam::Wave(param) = target.param else {
+                continue;
+            };
+            let value = self.target_value(target);
+
+            for (i, wave) in waves.iter_mut().enumerate() {
+                if target.wave.is_none_or(|index| index == i) && !wave.locks.contains(param) {
+                    *param.field(wave) = value;
+                }
+            }
+        }
+    }
+}
+
+// Global targets write straight into the settings, so their sliders follow the macro.
+pub(crate) fn apply_globals(ui: &mut Ui) {
+    let values = ui
+        .macros
+        .iter()
+        .flat_map(|macro_control| {
+            macro_control
+                .targets
+                .iter()
+                .filter_map(|target| match target.param {
+                    TargetParam::Global(param) => Some((param, macro_control.target_value(target))),
+                    TargetParam::Wave(_) => None,
+                })
+        })
+        .collect::<Vec<_>>();
+
+    for (param, value) in values {
+        *param.field(ui) = value;
+    }
+}
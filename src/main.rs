@@ -1,24 +1,118 @@
 use anyhow::Result;
 use winit::event_loop::EventLoop;
 
-use crate::app::App;
+use crate::{app::App, args::Args};
 
+mod ambient;
 mod app;
+mod args;
+mod artnet;
+mod audio;
+mod batch;
 mod boundary;
+mod branding;
+mod brush;
+mod burst;
+mod bypass;
+mod camera;
+mod canvas;
+mod clock;
+mod clock_sync;
+mod collision;
+mod color;
+mod color_space;
+mod config;
+mod cpu_render;
+mod debug_view;
+mod draw_stats;
+mod dump;
+mod easing;
+mod false_color;
 mod global;
+mod gpu;
+mod hot_reload;
+mod hud;
+mod input;
+mod jitter;
+mod kiosk;
+mod locale;
+mod lock;
+mod log_view;
+mod lut;
+mod macros;
+mod metrics;
+mod modulation;
+mod motion;
+mod param;
+mod physics;
 mod pipelines;
+mod playlist;
+mod power;
+mod preset;
+mod preset_diff;
+mod project;
+mod quality;
+mod quantize;
+mod ramp;
+mod random;
 mod render;
+mod render_graph;
+mod repaint;
+mod replay;
+mod reveal;
+mod routing;
+mod scope;
+mod screenshot;
+#[cfg(feature = "http")]
+mod server;
+mod shadertoy;
+mod show_control;
+mod spawn;
+mod startup;
+mod stream;
+mod strobe;
+mod stroke;
+mod theme;
+mod trail;
+mod transient;
 mod ui;
+mod upload;
 mod utils;
 mod vertex;
+#[cfg(target_os = "linux")]
+mod wallpaper;
+mod widget;
 
 fn main() -> Result<()> {
-    env_logger::init();
+    log_view::init();
+    let args = Args::parse()?;
+
+    if let Some(batch) = &args.batch {
+        return batch::run(batch, args.safe_mode);
+    }
+
+    if let Some(path) = &args.cpu_render {
+        return cpu_render::run(
+            args.preset.as_deref(),
+            path,
+            args.cpu_size.unwrap_or(cpu_render::DEFAULT_SIZE),
+        );
+    }
+
+    if let Some(frame) = args.dump_frame {
+        return dump::run(
+            frame,
+            args.preset.as_deref(),
+            &args.dump_dir(),
+            args.safe_mode,
+        );
+    }
+
     let event_loop = EventLoop::new()?;
 
     event_loop.set_control_flow(winit::event_loop::ControlFlow::Poll);
 
-    let mut app = App::default();
+    let mut app = App::new(args);
 
     event_loop.run_app(&mut app)?;
 
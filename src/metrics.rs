@@ -0,0 +1,255 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{audio::analysis::AudioFeatures, ui::UiSineWaveData};
+
+const CSV_HEADER: &str = "time,frame,frame_ms,waves,level,bass,mid,treble,beat,\
+                          wave,center_x,center_y,inner_radius,thickness,amplitude,cycles,speed";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum MetricsFormat {
+    #[default]
+    Csv,
+    Jsonl,
+}
+
+impl MetricsFormat {
+    pub(crate) const ALL: [Self; 2] = [Self::Csv, Self::Jsonl];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Csv => "CSV",
+            Self::Jsonl => "JSON Lines",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Jsonl => "jsonl",
+        }
+    }
+}
+
+pub(crate) struct UiMetrics {
+    pub(crate) recording: bool,
+    pub(crate) format: MetricsFormat,
+    pub(crate) directory: String,
+    pub(crate) rotate_mb: f32,
+    pub(crate) frames: u64,
+    pub(crate) status: Option<String>,
+}
+
+impl Default for UiMetrics {
+    fn default() -> Self {
+        Self {
+            recording: false,
+            format: MetricsFormat::Csv,
+            directory: "metrics".to_owned(),
+            rotate_mb: 16.,
+            frames: 0,
+            status: None,
+        }
+    }
+}
+
+pub(crate) struct MetricsFrame<'a> {
+    pub(crate) elapsed: f32,
+    pub(crate) frame_ms: f32,
+    pub(crate) audio: &'a AudioFeatures,
+    pub(crate) waves: &'a [UiSineWaveData],
+}
+
+impl MetricsFrame<'_> {
+    // One row per active wave keeps the columns fixed while waves come and go mid-session.
+    fn csv(&self, frame: u64) -> String {
+        let audio = self.audio;
+        let active = self.waves.iter().filter(|wave| wave.init).count();
+        let prefix = format!(
+            "{:.4},{frame},{:.3},{},{:.4},{:.4},{:.4},{:.4},{:.4}",
+            self.elapsed,
+            self.frame_ms,
+            active,
+            audio.level,
+            audio.bass,
+            audio.mid,
+            audio.treble,
+            audio.beat,
+        );
+
+        let mut rows = String::new();
+        for (i, wave) in self.waves.iter().enumerate().filter(|(_, wave)| wave.init) {
+            let [x, y] = wave.center;
+            rows.push_str(&format!(
+                "{prefix},{i},{x:.5},{y:.5},{:.5},{:.5},{:.5},{:.3},{:.5}\n",
+                wave.inner_radius, wave.thickness, wave.amplitude, wave.cycles, wave.speed,
+            ));
+        }
+        if rows.is_empty() {
+            rows = format!("{prefix},,,,,,,,\n");
+        }
+
+        rows
+    }
+
+    fn jsonl(&self, frame: u64) -> Result<String> {
+        let audio = self.audio;
+        let waves = self
+            .waves
+            .iter()
+            .enumerate()
+            .filter(|(_, wave)| wave.init)
+            .map(|(i, wave)| {
+                serde_json::json!({
+                    "wave": i,
+                    "center": wave.center,
+                    "inner_radius": wave.inner_radius,
+                    "thickness": wave.thickness,
+                    "amplitude": wave.amplitude,
+                    "cycles": wave.cycles,
+                    "speed": wave.speed,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let line = serde_json::json!({
+            "time": self.elapsed,
+            "frame": frame,
+            "frame_ms": self.frame_ms,
+            "audio": {
+                "level": audio.level,
+                "bass": audio.bass,
+                "mid": audio.mid,
+                "treble": audio.treble,
+                "beat": audio.beat,
+            },
+            "waves": waves,
+        });
+
+        Ok(format!("{}\n", serde_json::to_string(&line)?))
+    }
+}
+
+struct Writer {
+    file: BufWriter<File>,
+    path: PathBuf,
+    format: MetricsFormat,
+    directory: PathBuf,
+    session: u64,
+    part: u32,
+    bytes: u64,
+}
+
+impl Writer {
+    fn open(format: MetricsFormat, directory: &Path, session: u64, part: u32) -> Result<Self> {
+        fs::create_dir_all(directory)?;
+        let path = directory.join(format!(
+            "metrics-{session}-{part:03}.{}",
+            format.extension()
+        ));
+        let file =
+            File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+
+        let mut writer = Self {
+            file: BufWriter::new(file),
+            path,
+            format,
+            directory: directory.to_owned(),
+            session,
+            part,
+            bytes: 0,
+        };
+        if format == MetricsFormat::Csv {
+            writer.write(&format!("{CSV_HEADER}\n"))?;
+        }
+
+        Ok(writer)
+    }
+
+    fn write(&mut self, text: &str) -> Result<()> {
+        self.file.write_all(text.as_bytes())?;
+        self.bytes += text.len() as u64;
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct MetricsLog {
+    writer: Option<Writer>,
+    frame: u64,
+}
+
+impl MetricsLog {
+    pub(crate) fn record(&mut self, settings: &mut UiMetrics, frame: &MetricsFrame) {
+        if !settings.recording {
+            self.stop(settings);
+            return;
+        }
+
+        if let Err(err) = self.write(settings, frame) {
+            settings.status = Some(format!("Metrics log stopped: {err:#}"));
+            settings.recording = false;
+            self.writer = None;
+        }
+    }
+
+    fn stop(&mut self, settings: &mut UiMetrics) {
+        let Some(mut writer) = self.writer.take() else {
+            return;
+        };
+
+        settings.status = Some(match writer.file.flush() {
+            Ok(()) => format!(
+                "Logged {} frames to {}",
+                settings.frames,
+                writer.path.display()
+            ),
+            Err(err) => format!("Failed to finish the metrics log: {err}"),
+        });
+    }
+
+    fn write(&mut self, settings: &mut UiMetrics, frame: &MetricsFrame) -> Result<()> {
+        let writer = match &mut self.writer {
+            Some(writer) => writer,
+            None => {
+                let session = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |elapsed| elapsed.as_secs());
+                let directory = PathBuf::from(settings.directory.trim());
+                let writer = Writer::open(settings.format, &directory, session, 1)?;
+                self.frame = 0;
+                settings.frames = 0;
+                settings.status = Some(format!("Logging to {}", writer.path.display()));
+                self.writer.insert(writer)
+            }
+        };
+
+        let text = match writer.format {
+            MetricsFormat::Csv => frame.csv(self.frame),
+            MetricsFormat::Jsonl => frame.jsonl(self.frame)?,
+        };
+        writer.write(&text)?;
+        self.frame += 1;
+        settings.frames = self.frame;
+
+        if writer.bytes as f32 >= settings.rotate_mb * 1e6 {
+            writer.file.flush()?;
+            let next = Writer::open(
+                writer.format,
+                &writer.directory,
+                writer.session,
+                writer.part + 1,
+            )?;
+            settings.status = Some(format!("Logging to {}", next.path.display()));
+            *writer = next;
+        }
+
+        Ok(())
+    }
+}
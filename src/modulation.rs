@@ -0,0 +1,153 @@
+use std::num::NonZero;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, Buffer,
+    BufferBindingType, BufferUsages, Device, Extent3d, FilterMode, Sampler, SamplerBindingType,
+    SamplerDescriptor, ShaderStages, TextureDescriptor, TextureDimension, TextureFormat,
+    TextureSampleType, TextureUsages, TextureView, TextureViewDescriptor, TextureViewDimension,
+    util::{BufferInitDescriptor, DeviceExt},
+};
+
+use crate::{ui::UiReactionDiffusion, upload::Upload};
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct ModulationParams {
+    thickness: f32,
+    color: f32,
+    _padding: [f32; 2],
+}
+
+pub(crate) struct Modulation {
+    pub(crate) layout: BindGroupLayout,
+    pub(crate) bind_group: BindGroup,
+    buffer: Buffer,
+    sampler: Sampler,
+}
+
+impl Modulation {
+    pub(crate) fn new(device: &Device) -> Self {
+        let buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Modulation Buffer"),
+            contents: bytemuck::bytes_of(&ModulationParams::zeroed()),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Modulation Sampler"),
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::Repeat,
+            min_filter: FilterMode::Linear,
+            mag_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Modulation Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::VERTEX_FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: NonZero::new(
+                            std::mem::size_of::<ModulationParams>() as u64
+                        ),
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let fallback = device
+            .create_texture(&TextureDescriptor {
+                label: Some("Modulation Fallback Texture"),
+                size: Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba16Float,
+                usage: TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+            .create_view(&TextureViewDescriptor::default());
+
+        let bind_group = Self::create_bind_group(&layout, &buffer, &sampler, &fallback, device);
+
+        Self {
+            layout,
+            bind_group,
+            buffer,
+            sampler,
+        }
+    }
+
+    fn create_bind_group(
+        layout: &BindGroupLayout,
+        buffer: &Buffer,
+        sampler: &Sampler,
+        view: &TextureView,
+        device: &Device,
+    ) -> BindGroup {
+        device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Modulation Bind Group"),
+            layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(sampler),
+                },
+            ],
+        })
+    }
+
+    pub(crate) fn set_texture(&mut self, view: &TextureView, device: &Device) {
+        self.bind_group =
+            Self::create_bind_group(&self.layout, &self.buffer, &self.sampler, view, device);
+    }
+
+    pub(crate) fn update(&self, settings: &UiReactionDiffusion, upload: &mut Upload) {
+        let params = if settings.enabled {
+            ModulationParams {
+                thickness: settings.thickness_mod,
+                color: settings.color_mod,
+                _padding: [0.; 2],
+            }
+        } else {
+            ModulationParams::zeroed()
+        };
+
+        upload.write(&self.buffer, 0, bytemuck::bytes_of(&params));
+    }
+}
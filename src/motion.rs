@@ -0,0 +1,164 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ui::UiSineWaveData;
+
+const RECORD_SPACING: f32 = 0.01;
+pub(crate) const MAX_PATH_POINTS: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub(crate) enum PathShape {
+    #[default]
+    Fixed,
+    Circle,
+    Lissajous,
+    Polyline,
+}
+
+impl PathShape {
+    pub(crate) const ALL: [PathShape; 4] = [
+        PathShape::Fixed,
+        PathShape::Circle,
+        PathShape::Lissajous,
+        PathShape::Polyline,
+    ];
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub(crate) enum PathLoop {
+    #[default]
+    Repeat,
+    PingPong,
+    Once,
+}
+
+impl PathLoop {
+    pub(crate) const ALL: [PathLoop; 3] = [PathLoop::Repeat, PathLoop::PingPong, PathLoop::Once];
+
+    fn wrap(self, progress: f32) -> f32 {
+        match self {
+            PathLoop::Repeat => progress.rem_euclid(1.),
+            PathLoop::PingPong => 1. - ((progress * 0.5).rem_euclid(1.) * 2. - 1.).abs(),
+            PathLoop::Once => progress.clamp(0., 1.),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct MotionPath {
+    pub(crate) shape: PathShape,
+    pub(crate) looping: PathLoop,
+    pub(crate) speed: f32,
+    pub(crate) phase: f32,
+    pub(crate) radius: [f32; 2],
+    pub(crate) frequency: [f32; 2],
+    pub(crate) points: Vec<[f32; 2]>,
+    #[serde(skip)]
+    pub(crate) recording: bool,
+}
+
+impl Default for MotionPath {
+    fn default() -> Self {
+        Self {
+            shape: PathShape::Fixed,
+            looping: PathLoop::Repeat,
+            speed: 0.1,
+            phase: 0.,
+            radius: [0.2, 0.2],
+            frequency: [3., 2.],
+            points: Vec::new(),
+            recording: false,
+        }
+    }
+}
+
+impl MotionPath {
+    pub(crate) fn active(&self) -> bool {
+        !self.recording
+            && match self.shape {
+                PathShape::Fixed => false,
+                PathShape::Polyline => !self.points.is_empty(),
+                PathShape::Circle | PathShape::Lissajous => true,
+            }
+    }
+
+    pub(crate) fn record(&mut self, center: [f32; 2]) {
+        if !self.recording || self.points.len() >= MAX_PATH_POINTS {
+            return;
+        }
+
+        let far_enough = self
+            .points
+            .last()
+            .is_none_or(|&[x, y]| (center[0] - x).hypot(center[1] - y) >= RECORD_SPACING);
+        if far_enough {
+            self.points.push(center);
+        }
+    }
+
+    pub(crate) fn position(&self, center: [f32; 2], time: f32, aspect: f32) -> [f32; 2] {
+        let t = self.looping.wrap(self.speed * time + self.phase);
+        let aspect = aspect.max(f32::EPSILON);
+
+        match self.shape {
+            PathShape::Fixed => center,
+            PathShape::Circle => {
+                let angle = TAU * t;
+                [
+                    center[0] + self.radius[0] * angle.cos() / aspect,
+                    center[1] + self.radius[1] * angle.sin(),
+                ]
+            }
+            PathShape::Lissajous => {
+                let angle = TAU * t;
+                [
+                    center[0]
+                        + self.radius[0] * (self.frequency[0] * angle + FRAC_PI_2).sin() / aspect,
+                    center[1] + self.radius[1] * (self.frequency[1] * angle).sin(),
+                ]
+            }
+            PathShape::Polyline => self.along_polyline(t).unwrap_or(center),
+        }
+    }
+
+    fn along_polyline(&self, t: f32) -> Option<[f32; 2]> {
+        let first = *self.points.first()?;
+        let closed = self.looping == PathLoop::Repeat;
+
+        let mut segments = self
+            .points
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect::<Vec<_>>();
+        if closed && let Some(&last) = self.points.last() {
+            segments.push((last, first));
+        }
+
+        let length = |(a, b): ([f32; 2], [f32; 2])| (b[0] - a[0]).hypot(b[1] - a[1]);
+        let total = segments.iter().copied().map(length).sum::<f32>();
+        if total <= f32::EPSILON {
+            return Some(first);
+        }
+
+        let mut remaining = t * total;
+        for segment in segments {
+            let segment_length = length(segment);
+            if remaining <= segment_length {
+                let s = remaining / segment_length.max(f32::EPSILON);
+                let (a, b) = segment;
+                return Some([a[0] + (b[0] - a[0]) * s, a[1] + (b[1] - a[1]) * s]);
+            }
+            remaining -= segment_length;
+        }
+
+        self.points.last().copied()
+    }
+}
+
+pub(crate) fn apply(waves: &mut [UiSineWaveData], time: f32, aspect: f32) {
+    for wave in waves.iter_mut().filter(|wave| wave.motion.active()) {
+        wave.center = wave.motion.position(wave.center, time, aspect);
+    }
+}
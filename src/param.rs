@@ -0,0 +1,178 @@
+use std::ops::RangeInclusive;
+
+use egui::{DragValue, Id, Response, Slider, Ui, Widget, WidgetText, emath::Numeric};
+
+use crate::locale::{self, Locale};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Unit {
+    Px,
+    Cycles,
+    Hz,
+    Secs,
+    Ms,
+    Degrees,
+    Radians,
+    Nits,
+}
+
+impl Unit {
+    fn suffix(self) -> &'static str {
+        match self {
+            Self::Px => " px",
+            Self::Cycles => " cycles",
+            Self::Hz => " Hz",
+            Self::Secs => " s",
+            Self::Ms => " ms",
+            Self::Degrees => "°",
+            Self::Radians => " rad",
+            Self::Nits => " nits",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum ParamStyle {
+    #[default]
+    Slider,
+    Drag,
+}
+
+impl ParamStyle {
+    pub(crate) const ALL: [Self; 2] = [Self::Slider, Self::Drag];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Slider => "Sliders",
+            Self::Drag => "Drag Values",
+        }
+    }
+
+    fn id() -> Id {
+        Id::new("param_style")
+    }
+
+    pub(crate) fn store(self, ctx: &egui::Context) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(), self));
+    }
+
+    fn load(ctx: &egui::Context) -> Self {
+        ctx.data(|data| data.get_temp(Self::id()).unwrap_or_default())
+    }
+}
+
+#[must_use = "You should put this widget in a ui with `ui.add(widget);`"]
+pub(crate) struct Param<'a, T: Numeric> {
+    value: &'a mut T,
+    range: RangeInclusive<T>,
+    text: WidgetText,
+    unit: Option<Unit>,
+    default: Option<T>,
+    logarithmic: bool,
+    step: Option<f64>,
+    decimals: Option<usize>,
+}
+
+impl<'a, T: Numeric> Param<'a, T> {
+    pub(crate) fn new(value: &'a mut T, range: RangeInclusive<T>) -> Self {
+        Self {
+            value,
+            range,
+            text: WidgetText::default(),
+            unit: None,
+            default: None,
+            logarithmic: false,
+            step: None,
+            decimals: None,
+        }
+    }
+
+    pub(crate) fn text(mut self, text: impl Into<WidgetText>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    pub(crate) fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    pub(crate) fn default(mut self, default: T) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    pub(crate) fn logarithmic(mut self, logarithmic: bool) -> Self {
+        self.logarithmic = logarithmic;
+        self
+    }
+
+    pub(crate) fn step_by(mut self, step: f64) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    pub(crate) fn fixed_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+}
+
+impl<T: Numeric> Widget for Param<'_, T> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let suffix = self.unit.map_or("", Unit::suffix);
+        let comma = Locale::load(ui.ctx()).comma;
+        let formatter = move |value: f64, decimals: RangeInclusive<usize>| {
+            locale::format_number(value, decimals, comma)
+        };
+        let mut response = match ParamStyle::load(ui.ctx()) {
+            ParamStyle::Slider => {
+                let mut slider = Slider::new(&mut *self.value, self.range)
+                    .text(self.text)
+                    .suffix(suffix)
+                    .logarithmic(self.logarithmic)
+                    .custom_parser(locale::parse_number);
+                if let Some(step) = self.step {
+                    slider = slider.step_by(step);
+                }
+                if let Some(decimals) = self.decimals {
+                    slider = slider.fixed_decimals(decimals);
+                }
+                if comma {
+                    slider = slider.custom_formatter(formatter);
+                }
+                ui.add(slider)
+            }
+            ParamStyle::Drag => {
+                let span = self.range.end().to_f64() - self.range.start().to_f64();
+                let mut drag = DragValue::new(&mut *self.value)
+                    .range(self.range)
+                    .suffix(suffix)
+                    .speed(self.step.unwrap_or(span / 300.))
+                    .custom_parser(locale::parse_number);
+                if let Some(decimals) = self.decimals {
+                    drag = drag.fixed_decimals(decimals);
+                }
+                if comma {
+                    drag = drag.custom_formatter(formatter);
+                }
+                ui.horizontal(|ui| {
+                    let response = ui.add(drag);
+                    ui.label(self.text);
+                    response
+                })
+                .inner
+            }
+        };
+
+        if let Some(default) = self.default {
+            if response.secondary_clicked() && *self.value != default {
+                *self.value = default;
+                response.mark_changed();
+            }
+            response = response.on_hover_text("Right-click to reset");
+        }
+
+        response
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{audio::analysis::AudioFeatures, ui::UiSineWaveData};
+
+const MAX_STEP: f32 = 1. / 120.;
+const MAX_FRAME_TIME: f32 = 0.1;
+const BEAT_IMPULSE: f32 = 2.;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SpringSettings {
+    pub(crate) stiffness: f32,
+    pub(crate) damping: f32,
+}
+
+impl Default for SpringSettings {
+    fn default() -> Self {
+        Self {
+            stiffness: 120.,
+            damping: 8.,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Spring {
+    value: f32,
+    velocity: f32,
+}
+
+impl Spring {
+    fn step(&mut self, target: f32, settings: SpringSettings, dt: f32) {
+        let acceleration =
+            settings.stiffness * (target - self.value) - settings.damping * self.velocity;
+
+        self.velocity += acceleration * dt;
+        self.value += self.velocity * dt;
+    }
+
+    fn snap(&mut self, target: f32) {
+        self.value = target;
+        self.velocity = 0.;
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct WaveSprings {
+    active: bool,
+    inner_radius: Spring,
+    amplitude: Spring,
+}
+
+pub(crate) struct Physics {
+    springs: Vec<WaveSprings>,
+    last_update: Option<Instant>,
+    last_beat: f32,
+    waves: Vec<UiSineWaveData>,
+}
+
+impl Default for Physics {
+    fn default() -> Self {
+        Self {
+            springs: Vec::new(),
+            last_update: None,
+            last_beat: 0.,
+            waves: Vec::new(),
+        }
+    }
+}
+
+impl Physics {
+    pub(crate) fn update<'a>(
+        &'a mut self,
+        waves: &'a [UiSineWaveData],
+        enabled: bool,
+        global: SpringSettings,
+        audio: &AudioFeatures,
+    ) -> &'a [UiSineWaveData] {
+        let now = Instant::now();
+        let elapsed = self
+            .last_update
+            .replace(now)
+            .map_or(0., |last| (now - last).as_secs_f32().min(MAX_FRAME_TIME));
+
+        self.step(waves, enabled, global, audio, elapsed)
+    }
+
+    // Advances by a fixed `elapsed`, for headless renders that don't run on the wall clock.
+    pub(crate) fn step<'a>(
+        &'a mut self,
+        waves: &'a [UiSineWaveData],
+        enabled: bool,
+        global: SpringSettings,
+        audio: &AudioFeatures,
+        elapsed: f32,
+    ) -> &'a [UiSineWaveData] {
+        let onset = if audio.beat > self.last_beat {
+            audio.beat
+        } else {
+            0.
+        };
+        self.last_beat = audio.beat;
+
+        if !enabled {
+            self.springs
+                .iter_mut()
+                .for_each(|springs| springs.active = false);
+            return waves;
+        }
+
+        let steps = (elapsed / MAX_STEP).ceil().max(1.);
+        let dt = elapsed / steps;
+
+        self.waves.clear();
+        self.waves.extend_from_slice(waves);
+        self.springs.resize(waves.len(), WaveSprings::default());
+
+        for (springs, wave) in self.springs.iter_mut().zip(self.waves.iter_mut()) {
+            if !wave.init {
+                springs.active = false;
+                continue;
+            }
+
+            if !std::mem::replace(&mut springs.active, true) {
+                springs.inner_radius.snap(wave.inner_radius);
+                springs.amplitude.snap(wave.amplitude);
+            }
+
+            let settings = if wave.spring_override {
+                wave.spring
+            } else {
+                global
+            };
+
+            springs.amplitude.velocity += BEAT_IMPULSE * onset * wave.amplitude;
+
+            for _ in 0..steps as u32 {
+                springs.inner_radius.step(wave.inner_radius, settings, dt);
+                springs.amplitude.step(wave.amplitude, settings, dt);
+            }
+
+            wave.inner_radius = springs.inner_radius.value.max(0.);
+            wave.amplitude = springs.amplitude.value.max(0.);
+        }
+
+        &self.waves
+    }
+}
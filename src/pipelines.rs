@@ -1,2 +1,15 @@
+pub(crate) mod background;
+pub(crate) mod cull;
+pub(crate) mod downscale;
+pub(crate) mod field_warp;
+pub(crate) mod hud;
+pub(crate) mod interference;
+pub(crate) mod pixel_sort;
 pub(crate) mod post;
+pub(crate) mod reaction_diffusion;
+pub(crate) mod scene;
+pub(crate) mod shader;
+pub(crate) mod shadertoy;
 pub(crate) mod sine;
+pub(crate) mod spectrum;
+pub(crate) mod ui_layer;
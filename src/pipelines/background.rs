@@ -0,0 +1,155 @@
+use std::num::NonZero;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingType, Buffer, BufferBindingType, BufferUsages, ColorTargetState,
+    ColorWrites, Device, FragmentState, MultisampleState, PipelineCompilationOptions,
+    PipelineLayoutDescriptor, PrimitiveState, RenderPass, RenderPipeline, RenderPipelineDescriptor,
+    ShaderStages, TextureFormat, VertexState,
+    util::{BufferInitDescriptor, DeviceExt},
+};
+
+use crate::{
+    pipelines::shader,
+    ramp::RampUniform,
+    ui::{GradientStyle, UiBackground},
+    upload::Upload,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct BackgroundSettings {
+    style: u32,
+    aspect: f32,
+    _padding: [u32; 2],
+}
+
+impl BackgroundSettings {
+    fn new(style: GradientStyle, aspect: f32) -> Self {
+        Self {
+            style: style as u32,
+            aspect,
+            _padding: [0; 2],
+        }
+    }
+}
+
+// Fills the scene with a gradient sampled from the background ramp, under everything else.
+pub(crate) struct BackgroundPipeline {
+    pipeline: RenderPipeline,
+    bind_group: BindGroup,
+    ramp_buffer: Buffer,
+    settings_buffer: Buffer,
+}
+
+impl BackgroundPipeline {
+    pub(crate) fn new(
+        background: &UiBackground,
+        texture_format: TextureFormat,
+        device: &Device,
+    ) -> Self {
+        let ramp_buffer = RampUniform::from(&background.ramp).create_buffer(device);
+        let settings_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Background Settings Buffer"),
+            contents: bytemuck::bytes_of(&BackgroundSettings::new(background.style, 1.)),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Background Layout"),
+            entries: &[
+                RampUniform::layout_entry(0),
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: NonZero::new(
+                            std::mem::size_of::<BackgroundSettings>() as u64
+                        ),
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Background Bind Group"),
+            layout: &layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: ramp_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: settings_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Background Pipeline Layout"),
+            bind_group_layouts: &[&layout],
+            ..Default::default()
+        });
+
+        let shader_module =
+            shader::create_module("background.wgsl", include_str!("background.wgsl"), device);
+
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Background Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: VertexState {
+                module: &shader_module,
+                entry_point: Some("vs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(FragmentState {
+                module: &shader_module,
+                entry_point: Some("fs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                targets: &[Some(ColorTargetState {
+                    format: texture_format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multiview: None,
+            cache: None,
+            multisample: MultisampleState::default(),
+        });
+
+        Self {
+            pipeline,
+            bind_group,
+            ramp_buffer,
+            settings_buffer,
+        }
+    }
+
+    pub(crate) fn set_render_pass(&self, render_pass: &mut RenderPass<'_>) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+
+        render_pass.draw(0..3, 0..1);
+    }
+
+    pub(crate) fn update(&self, background: &UiBackground, aspect: f32, upload: &mut Upload) {
+        upload.write(
+            &self.ramp_buffer,
+            0,
+            bytemuck::bytes_of(&RampUniform::from(&background.ramp)),
+        );
+        upload.write(
+            &self.settings_buffer,
+            0,
+            bytemuck::bytes_of(&BackgroundSettings::new(background.style, aspect)),
+        );
+    }
+}
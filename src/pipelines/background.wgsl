@@ -0,0 +1,40 @@
+#include "ramp.wgsl"
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>
+}
+
+struct Settings {
+    style: u32,
+    aspect: f32
+}
+
+@group(0) @binding(0)
+var<uniform> ramp: Ramp;
+@group(0) @binding(1)
+var<uniform> settings: Settings;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+
+    var output: VertexOutput;
+    output.position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    output.uv = uv;
+    return output;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    var t = input.uv.y;
+    if settings.style == 1u {
+        t = input.uv.x;
+    } else if settings.style == 2u {
+        // Radial runs from the centre to the nearest edge, kept circular on wide targets.
+        let centered = (input.uv - 0.5) * vec2<f32>(settings.aspect, 1.0);
+        t = length(centered) * 2.0;
+    }
+
+    return vec4<f32>(sample_ramp(clamp(t, 0.0, 1.0)), 1.0);
+}
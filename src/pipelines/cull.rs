@@ -0,0 +1,175 @@
+use std::num::NonZero;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingType, Buffer, BufferBindingType, BufferDescriptor, BufferUsages,
+    CommandEncoder, ComputePassDescriptor, ComputePipeline, ComputePipelineDescriptor, Device,
+    PipelineCompilationOptions, PipelineLayoutDescriptor, ShaderStages,
+    util::{BufferInitDescriptor, DeviceExt, DrawIndexedIndirectArgs},
+};
+
+use crate::{camera::Camera, pipelines::shader, upload::Upload};
+
+pub(crate) const CULL_THRESHOLD: u32 = 64;
+const INDEX_COUNT: u32 = 6;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct CullParams {
+    count: u32,
+    aspect: f32,
+    zoom: f32,
+    _padding: u32,
+    pan: [f32; 2],
+    _padding2: [u32; 2],
+}
+
+pub(crate) struct CullPipeline {
+    pipeline: ComputePipeline,
+    bind_group: BindGroup,
+    params_buffer: Buffer,
+    visible_buffer: Buffer,
+    indirect_buffer: Buffer,
+}
+
+impl CullPipeline {
+    fn storage_entry(binding: u32, read_only: bool) -> BindGroupLayoutEntry {
+        BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }
+    }
+
+    pub(crate) fn new(waves: &Buffer, device: &Device) -> Self {
+        let params_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Cull Params Buffer"),
+            contents: bytemuck::bytes_of(&CullParams::zeroed()),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let visible_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Visible Wave Buffer"),
+            size: waves.size(),
+            usage: BufferUsages::VERTEX | BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        let indirect_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Wave Indirect Buffer"),
+            contents: DrawIndexedIndirectArgs {
+                index_count: INDEX_COUNT,
+                instance_count: 0,
+                first_index: 0,
+                base_vertex: 0,
+                first_instance: 0,
+            }
+            .as_bytes(),
+            usage: BufferUsages::INDIRECT | BufferUsages::STORAGE,
+        });
+
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Cull Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: NonZero::new(std::mem::size_of::<CullParams>() as u64),
+                    },
+                    count: None,
+                },
+                Self::storage_entry(1, true),
+                Self::storage_entry(2, false),
+                Self::storage_entry(3, false),
+            ],
+        });
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Cull Bind Group"),
+            layout: &layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: waves.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: visible_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: indirect_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Cull Pipeline Layout"),
+            bind_group_layouts: &[&layout],
+            ..Default::default()
+        });
+
+        let shader_module = shader::create_module("cull.wgsl", include_str!("cull.wgsl"), device);
+
+        let pipeline = device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: Some("Cull Pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader_module,
+            entry_point: Some("cs_main"),
+            compilation_options: PipelineCompilationOptions::default(),
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            bind_group,
+            params_buffer,
+            visible_buffer,
+            indirect_buffer,
+        }
+    }
+
+    pub(crate) fn update(&self, count: u32, aspect: f32, camera: &Camera, upload: &mut Upload) {
+        let params = CullParams {
+            count,
+            aspect,
+            zoom: camera.zoom,
+            _padding: 0,
+            pan: camera.pan,
+            _padding2: [0; 2],
+        };
+        upload.write(&self.params_buffer, 0, bytemuck::bytes_of(&params));
+    }
+
+    pub(crate) fn dispatch(&self, encoder: &mut CommandEncoder) {
+        let mut compute_pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("Cull Pass"),
+            timestamp_writes: None,
+        });
+
+        compute_pass.set_pipeline(&self.pipeline);
+        compute_pass.set_bind_group(0, &self.bind_group, &[]);
+        compute_pass.dispatch_workgroups(1, 1, 1);
+    }
+
+    pub(crate) fn visible_buffer(&self) -> &Buffer {
+        &self.visible_buffer
+    }
+
+    pub(crate) fn indirect_buffer(&self) -> &Buffer {
+        &self.indirect_buffer
+    }
+}
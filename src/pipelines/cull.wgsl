@@ -0,0 +1,97 @@
+#include "wave.wgsl"
+
+struct DrawArgs {
+    index_count: u32,
+    instance_count: u32,
+    first_index: u32,
+    base_vertex: i32,
+    first_instance: u32
+}
+
+struct CullParams {
+    count: u32,
+    aspect: f32,
+    zoom: f32,
+    pan: vec2<f32>
+}
+
+@group(0) @binding(0)
+var<uniform> params: CullParams;
+@group(0) @binding(1)
+var<storage, read> waves: array<Wave>;
+@group(0) @binding(2)
+var<storage, read_write> visible: array<Wave>;
+@group(0) @binding(3)
+var<storage, read_write> draw: DrawArgs;
+
+fn is_visible(wave: Wave) -> bool {
+    if wave.init == 0u || wave.opacity <= 0.0 {
+        return false;
+    }
+
+    let scene_center = vec2<f32>(wave.center_x, wave.center_y);
+    let center = 0.5 + (scene_center - 0.5 - params.pan) * params.zoom;
+    // Breathing is left to the vertex shader, so culling allows for its full swing.
+    let breath = abs(wave.breath_depth);
+    let wobble = (abs(wave.amplitude) + breath) * params.zoom;
+    let outer = params.zoom * ring_reach(
+        wave.inner_radius + breath,
+        wave.thickness,
+        wave.amplitude,
+        wave.flags,
+        vec2<f32>(wave.outline[0], wave.outline[1]),
+        vec4<f32>(wave.shadow[0], wave.shadow[1], wave.shadow[2], wave.shadow[3])
+    );
+    let extent = vec2<f32>(outer / params.aspect, outer);
+
+    if any(center + extent < vec2<f32>(0.0)) || any(center - extent > vec2<f32>(1.0)) {
+        return false;
+    }
+
+    let scale = vec2<f32>(params.aspect, 1.0);
+    let corner = max(abs(center), abs(vec2<f32>(1.0) - center)) * scale;
+
+    return wave.inner_radius * params.zoom - wobble < length(corner);
+}
+
+const WORKGROUP_SIZE: u32 = 64u;
+
+var<workgroup> offsets: array<u32, WORKGROUP_SIZE>;
+
+// A single workgroup compacts the list so the visible waves keep their blend order.
+@compute @workgroup_size(64)
+fn cs_main(@builtin(local_invocation_index) thread: u32) {
+    let chunk = (params.count + WORKGROUP_SIZE - 1u) / WORKGROUP_SIZE;
+    let first = min(thread * chunk, params.count);
+    let last = min(first + chunk, params.count);
+
+    var visible_count = 0u;
+    for (var i = first; i < last; i++) {
+        if is_visible(waves[i]) {
+            visible_count++;
+        }
+    }
+    offsets[thread] = visible_count;
+
+    workgroupBarrier();
+
+    if thread == 0u {
+        var total = 0u;
+        for (var i = 0u; i < WORKGROUP_SIZE; i++) {
+            let count = offsets[i];
+            offsets[i] = total;
+            total += count;
+        }
+        draw.instance_count = total;
+    }
+
+    workgroupBarrier();
+
+    var slot = offsets[thread];
+    for (var i = first; i < last; i++) {
+        if is_visible(waves[i]) {
+            visible[slot] = waves[i];
+            slot++;
+        }
+    }
+}
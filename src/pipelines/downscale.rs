@@ -0,0 +1,152 @@
+use wgpu::{
+    AddressMode, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingResource, BindingType, Color, ColorTargetState, ColorWrites,
+    CommandEncoder, Device, FilterMode, FragmentState, LoadOp, MultisampleState, Operations,
+    PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState,
+    RenderPassColorAttachment, RenderPassDescriptor, RenderPipeline, RenderPipelineDescriptor,
+    Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages, StoreOp, Texture, TextureFormat,
+    TextureSampleType, TextureView, TextureViewDimension, VertexState,
+};
+
+use crate::pipelines::shader;
+
+// Shrinks a frame into an 8-bit texture for readback. The target keeps the source's encoding:
+// sRGB and float sources land in an sRGB target, raw 8-bit sources stay raw.
+pub(crate) struct DownscalePipeline {
+    linear: RenderPipeline,
+    srgb: RenderPipeline,
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl DownscalePipeline {
+    pub(crate) fn new(device: &Device) -> Self {
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Downscale Bind Group Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            min_filter: FilterMode::Linear,
+            mag_filter: FilterMode::Linear,
+            label: Some("Downscale Sampler"),
+            ..Default::default()
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Downscale Pipeline Layout"),
+            bind_group_layouts: &[&layout],
+            ..Default::default()
+        });
+        let shader_module =
+            shader::create_module("downscale.wgsl", include_str!("downscale.wgsl"), device);
+
+        let create_pipeline = |format: TextureFormat| {
+            device.create_render_pipeline(&RenderPipelineDescriptor {
+                label: Some("Downscale Pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex: VertexState {
+                    module: &shader_module,
+                    entry_point: Some("vs_main"),
+                    compilation_options: PipelineCompilationOptions::default(),
+                    buffers: &[],
+                },
+                fragment: Some(FragmentState {
+                    module: &shader_module,
+                    entry_point: Some("fs_main"),
+                    compilation_options: PipelineCompilationOptions::default(),
+                    targets: &[Some(ColorTargetState {
+                        format,
+                        blend: None,
+                        write_mask: ColorWrites::ALL,
+                    })],
+                }),
+                primitive: PrimitiveState::default(),
+                depth_stencil: None,
+                multiview: None,
+                cache: None,
+                multisample: MultisampleState::default(),
+            })
+        };
+
+        Self {
+            linear: create_pipeline(TextureFormat::Rgba8Unorm),
+            srgb: create_pipeline(TextureFormat::Rgba8UnormSrgb),
+            layout,
+            sampler,
+        }
+    }
+
+    pub(crate) fn target_format(source: TextureFormat) -> TextureFormat {
+        if source.is_srgb() || source == TextureFormat::Rgba16Float {
+            TextureFormat::Rgba8UnormSrgb
+        } else {
+            TextureFormat::Rgba8Unorm
+        }
+    }
+
+    pub(crate) fn record(
+        &self,
+        source: &Texture,
+        target: &TextureView,
+        encoder: &mut CommandEncoder,
+        device: &Device,
+    ) {
+        let source_view = source.create_view(&Default::default());
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Downscale Bind Group"),
+            layout: &self.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&source_view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
+        let pipeline = match Self::target_format(source.format()) {
+            TextureFormat::Rgba8UnormSrgb => &self.srgb,
+            _ => &self.linear,
+        };
+
+        let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            label: Some("Downscale Render Pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                depth_slice: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::BLACK),
+                    store: StoreOp::Store,
+                },
+            })],
+            ..Default::default()
+        });
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
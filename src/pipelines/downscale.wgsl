@@ -0,0 +1,37 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>
+}
+
+// Bilinear taps per axis across one target pixel, enough to average an 8x reduction.
+const TAPS: i32 = 4;
+
+@group(0) @binding(0)
+var source_tex: texture_2d<f32>;
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+
+    var output: VertexOutput;
+    output.position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    output.uv = uv;
+    return output;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let footprint = fwidth(input.uv);
+
+    var color = vec4<f32>(0.0);
+    for (var y = 0; y < TAPS; y++) {
+        for (var x = 0; x < TAPS; x++) {
+            let offset = ((vec2<f32>(f32(x), f32(y)) + 0.5) / f32(TAPS) - 0.5) * footprint;
+            color += textureSampleLevel(source_tex, source_sampler, input.uv + offset, 0.0);
+        }
+    }
+
+    return color / f32(TAPS * TAPS);
+}
@@ -0,0 +1,251 @@
+use std::num::NonZero;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, Buffer,
+    BufferBindingType, BufferUsages, CommandEncoder, ComputePassDescriptor, ComputePipeline,
+    ComputePipelineDescriptor, Device, Extent3d, FilterMode, PipelineCompilationOptions,
+    PipelineLayoutDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages,
+    StorageTextureAccess, Texture, TextureDescriptor, TextureDimension, TextureFormat,
+    TextureSampleType, TextureUsages, TextureView, TextureViewDescriptor, TextureViewDimension,
+    util::{BufferInitDescriptor, DeviceExt},
+};
+
+use crate::{pipelines::shader, ui::UiFieldWarp, upload::Upload};
+
+const OUTPUT_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct FieldWarpParams {
+    strength: f32,
+    scale: f32,
+    time: f32,
+    steps: u32,
+}
+
+pub(crate) struct FieldWarpPipeline {
+    pipeline: ComputePipeline,
+    layout: BindGroupLayout,
+    bind_group: BindGroup,
+    params_buffer: Buffer,
+    sampler: Sampler,
+    params: FieldWarpParams,
+    output_texture: Texture,
+    output_view: TextureView,
+}
+
+impl FieldWarpPipeline {
+    fn create_texture(width: u32, height: u32, device: &Device) -> (Texture, TextureView) {
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("Field Warp Output Texture"),
+            size: Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: OUTPUT_FORMAT,
+            usage: TextureUsages::STORAGE_BINDING
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        let view = texture.create_view(&TextureViewDescriptor::default());
+
+        (texture, view)
+    }
+
+    fn create_bind_group(
+        layout: &BindGroupLayout,
+        params_buffer: &Buffer,
+        sampler: &Sampler,
+        source_view: &TextureView,
+        output_view: &TextureView,
+        device: &Device,
+    ) -> BindGroup {
+        device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Field Warp Bind Group"),
+            layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(source_view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(sampler),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: BindingResource::TextureView(output_view),
+                },
+            ],
+        })
+    }
+
+    pub(crate) fn new(source_view: &TextureView, width: u32, height: u32, device: &Device) -> Self {
+        let params = FieldWarpParams::zeroed();
+
+        let params_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Field Warp Params Buffer"),
+            contents: bytemuck::bytes_of(&params),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Field Warp Sampler"),
+            address_mode_u: AddressMode::MirrorRepeat,
+            address_mode_v: AddressMode::MirrorRepeat,
+            min_filter: FilterMode::Linear,
+            mag_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Field Warp Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: NonZero::new(
+                            std::mem::size_of::<FieldWarpParams>() as u64
+                        ),
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::StorageTexture {
+                        access: StorageTextureAccess::WriteOnly,
+                        format: OUTPUT_FORMAT,
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let shader_module =
+            shader::create_module("field_warp.wgsl", include_str!("field_warp.wgsl"), device);
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Field Warp Pipeline Layout"),
+            bind_group_layouts: &[&layout],
+            ..Default::default()
+        });
+
+        let pipeline = device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: Some("Field Warp Pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader_module,
+            entry_point: Some("warp_main"),
+            compilation_options: PipelineCompilationOptions::default(),
+            cache: None,
+        });
+
+        let (output_texture, output_view) = Self::create_texture(width, height, device);
+        let bind_group = Self::create_bind_group(
+            &layout,
+            &params_buffer,
+            &sampler,
+            source_view,
+            &output_view,
+            device,
+        );
+
+        Self {
+            pipeline,
+            layout,
+            bind_group,
+            params_buffer,
+            sampler,
+            params,
+            output_texture,
+            output_view,
+        }
+    }
+
+    pub(crate) fn resize(
+        &mut self,
+        source_view: &TextureView,
+        width: u32,
+        height: u32,
+        device: &Device,
+    ) {
+        let (output_texture, output_view) = Self::create_texture(width, height, device);
+        self.output_texture = output_texture;
+        self.output_view = output_view;
+        self.set_source(source_view, device);
+    }
+
+    pub(crate) fn set_source(&mut self, source_view: &TextureView, device: &Device) {
+        self.bind_group = Self::create_bind_group(
+            &self.layout,
+            &self.params_buffer,
+            &self.sampler,
+            source_view,
+            &self.output_view,
+            device,
+        );
+    }
+
+    pub(crate) fn update(&mut self, settings: &UiFieldWarp, upload: &mut Upload) {
+        self.params = FieldWarpParams {
+            strength: settings.strength,
+            scale: settings.scale,
+            time: self.params.time + settings.speed,
+            steps: settings.steps,
+        };
+
+        upload.write(&self.params_buffer, 0, bytemuck::bytes_of(&self.params));
+    }
+
+    pub(crate) fn dispatch(&self, encoder: &mut CommandEncoder) {
+        let mut compute_pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("Field Warp Pass"),
+            timestamp_writes: None,
+        });
+
+        let size = self.output_texture.size();
+        compute_pass.set_pipeline(&self.pipeline);
+        compute_pass.set_bind_group(0, &self.bind_group, &[]);
+        compute_pass.dispatch_workgroups(size.width.div_ceil(8), size.height.div_ceil(8), 1);
+    }
+
+    pub(crate) fn output_view(&self) -> &TextureView {
+        &self.output_view
+    }
+
+    pub(crate) fn output_texture(&self) -> &Texture {
+        &self.output_texture
+    }
+}
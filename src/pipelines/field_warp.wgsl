@@ -0,0 +1,53 @@
+#include "noise.wgsl"
+
+const MAX_STEPS: u32 = 32u;
+
+struct Params {
+    strength: f32,
+    scale: f32,
+    time: f32,
+    steps: u32
+}
+
+@group(0) @binding(0)
+var<uniform> params: Params;
+@group(0) @binding(1)
+var source_tex: texture_2d<f32>;
+@group(0) @binding(2)
+var source_sampler: sampler;
+@group(0) @binding(3)
+var output: texture_storage_2d<rgba16float, write>;
+
+// The curl of a scalar potential is divergence free, so the flow swirls instead of pinching.
+fn curl(p: vec2<f32>) -> vec2<f32> {
+    let e = 0.01;
+    let q = vec3<f32>(p * params.scale, params.time);
+    let dx = value_noise3(q + vec3<f32>(e, 0.0, 0.0)) - value_noise3(q - vec3<f32>(e, 0.0, 0.0));
+    let dy = value_noise3(q + vec3<f32>(0.0, e, 0.0)) - value_noise3(q - vec3<f32>(0.0, e, 0.0));
+
+    return vec2<f32>(dy, -dx) / (2.0 * e);
+}
+
+@compute @workgroup_size(8, 8)
+fn warp_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(output);
+    if id.x >= size.x || id.y >= size.y {
+        return;
+    }
+
+    let aspect = f32(size.x) / f32(size.y);
+    let steps = clamp(params.steps, 1u, MAX_STEPS);
+    let step = params.strength / f32(steps);
+
+    var uv = (vec2<f32>(id.xy) + 0.5) / vec2<f32>(size);
+    var color = vec4<f32>(0.0);
+
+    // Walk backwards along the flow and average what was picked up, smearing along the streamline.
+    for (var i = 0u; i < steps; i++) {
+        let flow = curl(vec2<f32>(uv.x * aspect, uv.y));
+        uv -= vec2<f32>(flow.x / aspect, flow.y) * step;
+        color += textureSampleLevel(source_tex, source_sampler, uv, 0.0);
+    }
+
+    textureStore(output, id.xy, color / f32(steps));
+}
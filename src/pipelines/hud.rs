@@ -0,0 +1,203 @@
+use std::num::NonZero;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor, BindGroupLayoutEntry,
+    BindingType, BlendState, BufferBindingType, BufferUsages, ColorTargetState, ColorWrites,
+    CommandEncoder, Device, FragmentState, LoadOp, MultisampleState, Operations,
+    PipelineCompilationOptions, PipelineLayout, PipelineLayoutDescriptor, PrimitiveState, Queue,
+    RenderPassColorAttachment, RenderPassDescriptor, RenderPipeline, RenderPipelineDescriptor,
+    ShaderModule, ShaderStages, StoreOp, TextureFormat, TextureView, VertexState,
+    util::{BufferInitDescriptor, DeviceExt},
+};
+
+use crate::{
+    camera::Camera, hud::UiHud, pipelines::shader, ui::UiSineWaveData, utils::BindGroupData,
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct HudParams {
+    viewport: [f32; 4],
+    center: [f32; 2],
+    pan: [f32; 2],
+    inner_radius: f32,
+    thickness: f32,
+    amplitude: f32,
+    aspect: f32,
+    color: [f32; 3],
+    zoom: f32,
+    opacity: f32,
+    active_handle: f32,
+    _padding: [f32; 2],
+}
+
+pub(crate) struct HudPipeline {
+    pipeline: RenderPipeline,
+    layout: PipelineLayout,
+    shader_module: ShaderModule,
+    target_format: TextureFormat,
+    params_bind_group_data: BindGroupData,
+}
+
+impl HudPipeline {
+    pub(crate) fn new(texture_format: TextureFormat, device: &Device) -> Self {
+        let buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("HUD Buffer"),
+            contents: bytemuck::bytes_of(&HudParams::zeroed()),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("HUD Bind Group Layout"),
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: NonZero::new(std::mem::size_of::<HudParams>() as u64),
+                },
+                count: None,
+            }],
+        });
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("HUD Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+
+        let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("HUD Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            ..Default::default()
+        });
+
+        let shader_module = shader::create_module("hud.wgsl", include_str!("hud.wgsl"), device);
+        let pipeline = Self::create_pipeline(&layout, &shader_module, texture_format, device);
+
+        Self {
+            pipeline,
+            layout,
+            shader_module,
+            target_format: texture_format,
+            params_bind_group_data: BindGroupData {
+                buffer,
+                layout: bind_group_layout,
+                bind_group,
+            },
+        }
+    }
+
+    fn create_pipeline(
+        layout: &PipelineLayout,
+        shader_module: &ShaderModule,
+        texture_format: TextureFormat,
+        device: &Device,
+    ) -> RenderPipeline {
+        device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("HUD Pipeline"),
+            layout: Some(layout),
+            vertex: VertexState {
+                module: shader_module,
+                entry_point: Some("vs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(FragmentState {
+                module: shader_module,
+                entry_point: Some("fs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                targets: &[Some(ColorTargetState {
+                    format: texture_format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multiview: None,
+            cache: None,
+            multisample: MultisampleState::default(),
+        })
+    }
+
+    pub(crate) fn set_target_format(&mut self, texture_format: TextureFormat, device: &Device) {
+        if texture_format == self.target_format {
+            return;
+        }
+
+        self.pipeline =
+            Self::create_pipeline(&self.layout, &self.shader_module, texture_format, device);
+        self.target_format = texture_format;
+    }
+
+    pub(crate) fn update(
+        &self,
+        settings: &UiHud,
+        wave: &UiSineWaveData,
+        camera: &Camera,
+        viewport: [u32; 4],
+        stretch: bool,
+        queue: &Queue,
+    ) {
+        let viewport = viewport.map(|value| value as f32);
+        let params = HudParams {
+            viewport,
+            center: wave.center,
+            pan: camera.pan,
+            inner_radius: wave.inner_radius,
+            thickness: wave.thickness,
+            amplitude: wave.amplitude,
+            aspect: if stretch {
+                1.
+            } else {
+                viewport[2] / viewport[3].max(1.)
+            },
+            color: settings.color,
+            zoom: camera.zoom,
+            opacity: settings.opacity,
+            active_handle: settings
+                .active
+                .map_or(0., |handle| handle.index() as f32 + 1.),
+            _padding: [0.; 2],
+        };
+
+        queue.write_buffer(
+            &self.params_bind_group_data.buffer,
+            0,
+            bytemuck::bytes_of(&params),
+        );
+    }
+
+    pub(crate) fn draw(
+        &self,
+        view: &TextureView,
+        viewport: [u32; 4],
+        encoder: &mut CommandEncoder,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                },
+                resolve_target: None,
+                depth_slice: None,
+            })],
+            label: Some("HUD Render Pass"),
+            ..Default::default()
+        });
+
+        let [x, y, width, height] = viewport.map(|value| value as f32);
+        render_pass.set_viewport(x, y, width, height, 0., 1.);
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.params_bind_group_data.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
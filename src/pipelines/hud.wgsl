@@ -0,0 +1,71 @@
+struct Hud {
+    viewport: vec4<f32>,
+    center: vec2<f32>,
+    pan: vec2<f32>,
+    inner_radius: f32,
+    thickness: f32,
+    amplitude: f32,
+    aspect: f32,
+    color: vec3<f32>,
+    zoom: f32,
+    opacity: f32,
+    // One plus the index of the hovered or dragged handle, zero for none.
+    active_handle: f32,
+}
+
+const TAU: f32 = 6.28318530718;
+// One dash plus its gap, in pixels.
+const DASH_LENGTH: f32 = 24.0;
+
+@group(0) @binding(0)
+var<uniform> hud: Hud;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+fn hairline(offset: f32, pixel: f32, width: f32) -> f32 {
+    return 1.0 - smoothstep(0.5 * width * pixel, (0.5 * width + 1.0) * pixel, abs(offset));
+}
+
+// Dashes are counted along the arc so they keep the same on-screen length on every radius.
+fn dash(turn: f32, radius: f32, pixel: f32) -> f32 {
+    let dashes = max(2.0, round(TAU * radius / (DASH_LENGTH * pixel)));
+    return step(0.5, fract(turn * dashes));
+}
+
+fn handle_dot(pos: vec2<f32>, direction: vec2<f32>, distance: f32, index: u32, pixel: f32) -> f32 {
+    let radius = select(5.0, 7.0, u32(hud.active_handle) == index + 1u) * pixel;
+    let offset = length(pos - direction * distance);
+    return 1.0 - smoothstep(radius - pixel, radius, offset);
+}
+
+@fragment
+fn fs_main(@builtin(position) frag_coord: vec4<f32>) -> @location(0) vec4<f32> {
+    let uv = (frag_coord.xy - hud.viewport.xy) / hud.viewport.zw;
+    let scene = 0.5 + hud.pan + (uv - 0.5) / hud.zoom;
+    let pos = vec2<f32>((scene.x - hud.center.x) * hud.aspect, scene.y - hud.center.y);
+    let pixel = 1.0 / (hud.viewport.w * hud.zoom);
+    let dist = length(pos);
+
+    var alpha = hairline(dist - hud.inner_radius, pixel, 1.5);
+    alpha = max(alpha, 0.8 * hairline(dist - hud.inner_radius - hud.thickness, pixel, 1.0));
+
+    // The amplitude envelope is dashed so it reads as a range rather than another stroke.
+    let turn = atan2(pos.y, pos.x) / TAU;
+    let outer = hud.inner_radius + hud.amplitude;
+    let inner = max(hud.inner_radius - hud.amplitude, 0.0);
+    let envelope = max(
+        dash(turn, outer, pixel) * hairline(dist - outer, pixel, 1.0),
+        dash(turn, inner, pixel) * hairline(dist - inner, pixel, 1.0)
+    );
+    alpha = max(alpha, 0.6 * envelope);
+
+    alpha = max(alpha, handle_dot(pos, vec2<f32>(1.0, 0.0), hud.inner_radius, 0u, pixel));
+    alpha = max(alpha, handle_dot(pos, vec2<f32>(0.0, -1.0), hud.inner_radius + hud.thickness, 1u, pixel));
+    alpha = max(alpha, handle_dot(pos, vec2<f32>(-1.0, 0.0), hud.inner_radius + hud.amplitude, 2u, pixel));
+
+    return vec4<f32>(hud.color, alpha * hud.opacity);
+}
@@ -0,0 +1,29 @@
+fn linear_to_srgb(color: vec3<f32>) -> vec3<f32> {
+    let c = clamp(color, vec3<f32>(0.0), vec3<f32>(1.0));
+    return select(1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055, c * 12.92, c <= vec3<f32>(0.0031308));
+}
+
+fn srgb_to_linear(color: vec3<f32>) -> vec3<f32> {
+    let c = clamp(color, vec3<f32>(0.0), vec3<f32>(1.0));
+    return select(pow((c + 0.055) / 1.055, vec3<f32>(2.4)), c / 12.92, c <= vec3<f32>(0.04045));
+}
+
+fn oklab_to_linear(lab: vec3<f32>) -> vec3<f32> {
+    let lms = vec3<f32>(
+        lab.x + 0.39633778 * lab.y + 0.21580376 * lab.z,
+        lab.x - 0.10556135 * lab.y - 0.06385417 * lab.z,
+        lab.x - 0.08948418 * lab.y - 1.2914855 * lab.z
+    );
+    let cubed = lms * lms * lms;
+
+    return vec3<f32>(
+        4.0767417 * cubed.x - 3.3077116 * cubed.y + 0.23096994 * cubed.z,
+        -1.268438 * cubed.x + 2.6097574 * cubed.y - 0.34131938 * cubed.z,
+        -0.0041960863 * cubed.x - 0.7034186 * cubed.y + 1.7076147 * cubed.z
+    );
+}
+
+fn oklch_to_linear(lch: vec3<f32>) -> vec3<f32> {
+    let lab = vec3<f32>(lch.x, lch.y * cos(lch.z), lch.y * sin(lch.z));
+    return clamp(oklab_to_linear(lab), vec3<f32>(0.0), vec3<f32>(1.0));
+}
@@ -0,0 +1,12 @@
+struct Global {
+    resolution: vec2<f32>,
+    phase: f32,
+    aspect: f32,
+    highlight: vec4<f32>,
+    antialias: f32,
+    zoom: f32,
+    pan: vec2<f32>,
+    // Map, source, range and band count; see `UiFalseColor::params`.
+    false_color: vec4<f32>,
+    hairline: f32
+}
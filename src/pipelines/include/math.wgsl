@@ -0,0 +1,2 @@
+const PI: f32 = 3.14159265;
+const TAU: f32 = 6.28318531;
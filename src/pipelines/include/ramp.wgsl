@@ -0,0 +1,60 @@
+#include "math.wgsl"
+#include "color.wgsl"
+
+// Expects the including shader to bind a `ramp: Ramp` uniform.
+struct Ramp {
+    colors: array<vec4<f32>, 8>,
+    positions: array<vec4<f32>, 2>,
+    count: u32,
+    interpolation: u32
+}
+
+fn ramp_position(i: u32) -> f32 {
+    return ramp.positions[i / 4u][i % 4u];
+}
+
+fn mix_oklch(start: vec3<f32>, end: vec3<f32>, t: f32) -> vec3<f32> {
+    var h0 = start.z;
+    var h1 = end.z;
+    if start.y < 1e-4 {
+        h0 = h1;
+    }
+    if end.y < 1e-4 {
+        h1 = h0;
+    }
+
+    var delta = h1 - h0;
+    if delta > PI {
+        delta -= TAU;
+    } else if delta < -PI {
+        delta += TAU;
+    }
+
+    return oklch_to_linear(vec3<f32>(mix(start.x, end.x, t), mix(start.y, end.y, t), h0 + delta * t));
+}
+
+fn sample_ramp(t: f32) -> vec3<f32> {
+    if ramp.count == 0u {
+        return vec3<f32>(0.0, 0.0, 0.0);
+    }
+
+    if t <= ramp_position(0u) {
+        return oklch_to_linear(ramp.colors[0].rgb);
+    }
+
+    for (var i = 1u; i < ramp.count; i++) {
+        let end = ramp_position(i);
+        if t <= end {
+            let start = ramp_position(i - 1u);
+            var f = (t - start) / max(end - start, 1e-5);
+            if ramp.interpolation == 1u {
+                f = smoothstep(0.0, 1.0, f);
+            } else if ramp.interpolation == 2u {
+                f = 0.0;
+            }
+            return mix_oklch(ramp.colors[i - 1u].rgb, ramp.colors[i].rgb, f);
+        }
+    }
+
+    return oklch_to_linear(ramp.colors[ramp.count - 1u].rgb);
+}
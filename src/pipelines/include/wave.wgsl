@@ -0,0 +1,77 @@
+const FLAG_TEXTURED: u32 = 1u;
+const FLAG_SCOPE: u32 = 2u;
+const FLAG_SPECTRUM: u32 = 4u;
+const FLAG_FRACTAL: u32 = 8u;
+const FLAG_OUTLINE: u32 = 16u;
+const FLAG_MIRROR_X: u32 = 32u;
+const FLAG_MIRROR_Y: u32 = 64u;
+const FLAG_SHADOW: u32 = 128u;
+const FLAG_ARC_LENGTH: u32 = 256u;
+
+// Matches the `SineWaveData` layout in sine.rs.
+struct Wave {
+    center_x: f32,
+    center_y: f32,
+    inner_radius: f32,
+    thickness: f32,
+    amplitude: f32,
+    cycles: f32,
+    speed: f32,
+    init: u32,
+    texture_tiling: f32,
+    texture_scroll: f32,
+    flags: u32,
+    opacity: f32,
+    phase_offset: f32,
+    breath_depth: f32,
+    breath_period: f32,
+    layer: u32,
+    highlight_angles: array<f32, 4>,
+    highlight_widths: array<f32, 4>,
+    fractal: array<f32, 4>,
+    outline_color: array<f32, 3>,
+    outline: array<f32, 2>,
+    shadow_color: array<f32, 3>,
+    shadow: array<f32, 4>
+}
+
+fn mirror_sign(flags: u32) -> vec2<f32> {
+    return vec2<f32>(
+        select(1.0, -1.0, (flags & FLAG_MIRROR_X) != 0u),
+        select(1.0, -1.0, (flags & FLAG_MIRROR_Y) != 0u)
+    );
+}
+
+// The slow radius swing added on top of `inner_radius`; `period` is in frames like `global.phase`.
+fn breathing(depth: f32, period: f32, phase: f32) -> f32 {
+    if depth == 0.0 || period <= 0.0 {
+        return 0.0;
+    }
+
+    return depth * sin(6.2831853 * fract(phase / period));
+}
+
+// Farthest a ring can reach from its center, in scene units before zoom.
+fn ring_reach(
+    inner_radius: f32,
+    thickness: f32,
+    amplitude: f32,
+    flags: u32,
+    outline: vec2<f32>,
+    shadow: vec4<f32>
+) -> f32 {
+    var reach = inner_radius + thickness + abs(amplitude);
+    if (flags & FLAG_OUTLINE) != 0u {
+        reach += max(outline.x + outline.y, 0.0);
+    }
+    if (flags & FLAG_SHADOW) != 0u {
+        reach += length(shadow.xy) + shadow.z;
+    }
+
+    return reach;
+}
+
+// Positive inside a band of `thickness` starting at `inner`, in scene units.
+fn ring_edge(dist: f32, inner: f32, thickness: f32) -> f32 {
+    return min(dist - inner, inner + thickness - dist);
+}
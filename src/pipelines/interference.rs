@@ -0,0 +1,182 @@
+use std::num::NonZero;
+
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingType, Buffer, BufferBindingType, ColorTargetState, ColorWrites,
+    Device, FragmentState, MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor,
+    PrimitiveState, Queue, RenderPass, RenderPipeline, RenderPipelineDescriptor, ShaderStages,
+    TextureFormat, VertexState,
+};
+
+use crate::{
+    camera::Camera,
+    global::Global,
+    pipelines::{shader, sine::SineWaveData},
+    ramp::{ColorRamp, RampUniform},
+    upload::Upload,
+    utils::BindGroupData,
+};
+
+pub(crate) struct InterferencePipeline {
+    pipeline: RenderPipeline,
+    field_bind_group: BindGroup,
+    ramp_buffer: Buffer,
+    global_bind_group_data: BindGroupData,
+    global: Global,
+}
+
+impl InterferencePipeline {
+    fn create_field_bindgroup(
+        wave_buffer: &Buffer,
+        ramp_buffer: &Buffer,
+        device: &Device,
+    ) -> (BindGroupLayout, BindGroup) {
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Interference Field Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: NonZero::new(std::mem::size_of::<SineWaveData>() as u64),
+                    },
+                    count: None,
+                },
+                RampUniform::layout_entry(1),
+            ],
+        });
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Interference Field Bind Group"),
+            layout: &layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: wave_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: ramp_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        (layout, bind_group)
+    }
+
+    pub(crate) fn new(
+        wave_buffer: &Buffer,
+        ramp: &ColorRamp,
+        global: Global,
+        texture_format: TextureFormat,
+        device: &Device,
+    ) -> Self {
+        let ramp_buffer = RampUniform::from(ramp).create_buffer(device);
+
+        let (field_bind_group_layout, field_bind_group) =
+            Self::create_field_bindgroup(wave_buffer, &ramp_buffer, device);
+
+        let global_bind_group_data = global.create_bind_group_data(device);
+
+        let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Interference Pipeline Layout"),
+            bind_group_layouts: &[&global_bind_group_data.layout, &field_bind_group_layout],
+            ..Default::default()
+        });
+
+        let shader_module = shader::create_module(
+            "interference.wgsl",
+            include_str!("interference.wgsl"),
+            device,
+        );
+
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Interference Pipeline"),
+            layout: Some(&layout),
+            vertex: VertexState {
+                module: &shader_module,
+                entry_point: Some("vs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(FragmentState {
+                module: &shader_module,
+                entry_point: Some("fs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                targets: &[Some(ColorTargetState {
+                    format: texture_format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multiview: None,
+            cache: None,
+            multisample: MultisampleState::default(),
+        });
+
+        Self {
+            pipeline,
+            field_bind_group,
+            ramp_buffer,
+            global_bind_group_data,
+            global,
+        }
+    }
+
+    pub(crate) fn set_render_pass(&self, render_pass: &mut RenderPass<'_>) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.global_bind_group_data.bind_group, &[]);
+        render_pass.set_bind_group(1, &self.field_bind_group, &[]);
+
+        render_pass.draw(0..6, 0..1);
+    }
+
+    pub(crate) fn set_phase(&mut self, phase: f32) {
+        self.global.set_phase(phase);
+    }
+
+    pub(crate) fn update_global_frame(
+        &mut self,
+        stretch: bool,
+        camera: &Camera,
+        step: f32,
+        upload: &mut Upload,
+    ) {
+        self.global.set_stretch(stretch);
+        self.global.set_camera(camera);
+        self.global.advance_frame(step);
+        upload.write(
+            &self.global_bind_group_data.buffer,
+            0,
+            bytemuck::bytes_of(&self.global),
+        );
+    }
+
+    pub(crate) fn update_global_resolution(
+        &mut self,
+        new_width: u32,
+        new_height: u32,
+
+        queue: &Queue,
+    ) {
+        self.global.set_resolution(new_width, new_height);
+
+        queue.write_buffer(
+            &self.global_bind_group_data.buffer,
+            0,
+            bytemuck::bytes_of(&self.global),
+        );
+    }
+
+    pub(crate) fn update_ramp(&mut self, ramp: &ColorRamp, upload: &mut Upload) {
+        upload.write(
+            &self.ramp_buffer,
+            0,
+            bytemuck::bytes_of(&RampUniform::from(ramp)),
+        );
+    }
+}
@@ -0,0 +1,67 @@
+#include "math.wgsl"
+#include "global.wgsl"
+#include "color.wgsl"
+#include "ramp.wgsl"
+#include "wave.wgsl"
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    var pos = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+    );
+
+    return vec4<f32>(pos[vertex_index], 0.0, 1.0);
+}
+
+@group(0) @binding(0)
+var<uniform> global: Global;
+
+fn to_scene(screen_uv: vec2<f32>) -> vec2<f32> {
+    return 0.5 + global.pan + (screen_uv - 0.5) / global.zoom;
+}
+
+@group(1) @binding(0)
+var<storage, read> waves: array<Wave>;
+@group(1) @binding(1)
+var<uniform> ramp: Ramp;
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let uv = to_scene(position.xy / global.resolution);
+
+    var field = 0.0;
+    var count = 0.0;
+
+    for (var i = 0u; i < arrayLength(&waves); i++) {
+        let wave = waves[i];
+        if wave.init == 0u {
+            continue;
+        }
+
+        let centered = uv - vec2<f32>(wave.center_x, wave.center_y);
+        let mirror = mirror_sign(wave.flags);
+        let pos = vec2<f32>(centered.x * global.aspect, centered.y) * mirror;
+
+        let theta = atan2(pos.y, pos.x);
+        let phase = wave.cycles * (theta - wave.speed * global.phase) + wave.phase_offset;
+        let breath = breathing(wave.breath_depth, wave.breath_period, global.phase);
+        let inner_wave = wave.inner_radius + breath + wave.amplitude * sin(phase);
+
+        let wavelength = max(wave.thickness * 8.0, 1e-3);
+        field += wave.opacity * cos(6.2831 * (length(pos) - inner_wave) / wavelength);
+        count += wave.opacity;
+    }
+
+    if count <= 0.0 {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+
+    let t = 0.5 + 0.5 * field / count;
+
+    return vec4<f32>(sample_ramp(t), 1.0);
+}
@@ -0,0 +1,386 @@
+use std::num::NonZero;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, Buffer,
+    BufferBindingType, BufferUsages, CommandEncoder, ComputePassDescriptor, ComputePipeline,
+    ComputePipelineDescriptor, Device, Extent3d, FilterMode, PipelineCompilationOptions,
+    PipelineLayoutDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages,
+    StorageTextureAccess, Texture, TextureDescriptor, TextureDimension, TextureFormat,
+    TextureSampleType, TextureUsages, TextureView, TextureViewDescriptor, TextureViewDimension,
+    util::{BufferInitDescriptor, DeviceExt},
+};
+
+use crate::{
+    pipelines::shader,
+    ui::{SortMode, UiPixelSort},
+    upload::Upload,
+};
+
+const POLAR_SIZE: u32 = 512;
+const INTERMEDIATE_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct PixelSortParams {
+    threshold: f32,
+    animate: f32,
+    speed: f32,
+    mix_amount: f32,
+    mode: u32,
+    descending: u32,
+    phase: f32,
+    _padding: f32,
+}
+
+struct Resources {
+    sort_layout: BindGroupLayout,
+    composite_layout: BindGroupLayout,
+    params_buffer: Buffer,
+    scene_sampler: Sampler,
+    polar_sampler: Sampler,
+    polar_texture: Texture,
+    polar_view: TextureView,
+}
+
+impl Resources {
+    fn create_bind_groups(
+        &self,
+        scene_view: &TextureView,
+        output_view: &TextureView,
+        device: &Device,
+    ) -> (BindGroup, BindGroup) {
+        let sort_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Pixel Sort Bind Group"),
+            layout: &self.sort_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: self.params_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(scene_view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(&self.scene_sampler),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: BindingResource::TextureView(&self.polar_view),
+                },
+            ],
+        });
+
+        let composite_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Pixel Sort Composite Bind Group"),
+            layout: &self.composite_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: self.params_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(scene_view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(&self.scene_sampler),
+                },
+                BindGroupEntry {
+                    binding: 4,
+                    resource: BindingResource::TextureView(&self.polar_view),
+                },
+                BindGroupEntry {
+                    binding: 5,
+                    resource: BindingResource::Sampler(&self.polar_sampler),
+                },
+                BindGroupEntry {
+                    binding: 6,
+                    resource: BindingResource::TextureView(output_view),
+                },
+            ],
+        });
+
+        (sort_bind_group, composite_bind_group)
+    }
+}
+
+pub(crate) struct PixelSortPipeline {
+    sort_pipeline: ComputePipeline,
+    composite_pipeline: ComputePipeline,
+    resources: Resources,
+    sort_bind_group: BindGroup,
+    composite_bind_group: BindGroup,
+    params: PixelSortParams,
+    output_texture: Texture,
+    output_view: TextureView,
+}
+
+impl PixelSortPipeline {
+    fn create_texture(
+        label: &str,
+        width: u32,
+        height: u32,
+        device: &Device,
+    ) -> (Texture, TextureView) {
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some(label),
+            size: Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: INTERMEDIATE_FORMAT,
+            usage: TextureUsages::STORAGE_BINDING
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        let view = texture.create_view(&TextureViewDescriptor::default());
+
+        (texture, view)
+    }
+
+    fn params_entry() -> BindGroupLayoutEntry {
+        BindGroupLayoutEntry {
+            binding: 0,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: NonZero::new(std::mem::size_of::<PixelSortParams>() as u64),
+            },
+            count: None,
+        }
+    }
+
+    fn texture_entry(binding: u32) -> BindGroupLayoutEntry {
+        BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Texture {
+                sample_type: TextureSampleType::Float { filterable: true },
+                view_dimension: TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        }
+    }
+
+    fn sampler_entry(binding: u32) -> BindGroupLayoutEntry {
+        BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Sampler(SamplerBindingType::Filtering),
+            count: None,
+        }
+    }
+
+    fn storage_entry(binding: u32) -> BindGroupLayoutEntry {
+        BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::StorageTexture {
+                access: StorageTextureAccess::WriteOnly,
+                format: INTERMEDIATE_FORMAT,
+                view_dimension: TextureViewDimension::D2,
+            },
+            count: None,
+        }
+    }
+
+    pub(crate) fn new(scene_view: &TextureView, width: u32, height: u32, device: &Device) -> Self {
+        let params = PixelSortParams::zeroed();
+
+        let params_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Pixel Sort Params Buffer"),
+            contents: bytemuck::bytes_of(&params),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let scene_sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Pixel Sort Scene Sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            min_filter: FilterMode::Linear,
+            mag_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let polar_sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Pixel Sort Polar Sampler"),
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::ClampToEdge,
+            min_filter: FilterMode::Linear,
+            mag_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let sort_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Pixel Sort Layout"),
+            entries: &[
+                Self::params_entry(),
+                Self::texture_entry(1),
+                Self::sampler_entry(2),
+                Self::storage_entry(3),
+            ],
+        });
+
+        let composite_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Pixel Sort Composite Layout"),
+            entries: &[
+                Self::params_entry(),
+                Self::texture_entry(1),
+                Self::sampler_entry(2),
+                Self::texture_entry(4),
+                Self::sampler_entry(5),
+                Self::storage_entry(6),
+            ],
+        });
+
+        let shader_module =
+            shader::create_module("pixel_sort.wgsl", include_str!("pixel_sort.wgsl"), device);
+
+        let create_pipeline = |label, layout: &BindGroupLayout, entry_point| {
+            let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some(label),
+                bind_group_layouts: &[layout],
+                ..Default::default()
+            });
+
+            device.create_compute_pipeline(&ComputePipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                module: &shader_module,
+                entry_point: Some(entry_point),
+                compilation_options: PipelineCompilationOptions::default(),
+                cache: None,
+            })
+        };
+
+        let sort_pipeline = create_pipeline("Pixel Sort Pipeline", &sort_layout, "sort_main");
+        let composite_pipeline = create_pipeline(
+            "Pixel Sort Composite Pipeline",
+            &composite_layout,
+            "composite_main",
+        );
+
+        let (polar_texture, polar_view) =
+            Self::create_texture("Pixel Sort Polar Texture", POLAR_SIZE, POLAR_SIZE, device);
+        let (output_texture, output_view) =
+            Self::create_texture("Pixel Sort Output Texture", width, height, device);
+
+        let resources = Resources {
+            sort_layout,
+            composite_layout,
+            params_buffer,
+            scene_sampler,
+            polar_sampler,
+            polar_texture,
+            polar_view,
+        };
+
+        let (sort_bind_group, composite_bind_group) =
+            resources.create_bind_groups(scene_view, &output_view, device);
+
+        Self {
+            sort_pipeline,
+            composite_pipeline,
+            resources,
+            sort_bind_group,
+            composite_bind_group,
+            params,
+            output_texture,
+            output_view,
+        }
+    }
+
+    pub(crate) fn resize(
+        &mut self,
+        scene_view: &TextureView,
+        width: u32,
+        height: u32,
+        device: &Device,
+    ) {
+        let (output_texture, output_view) =
+            Self::create_texture("Pixel Sort Output Texture", width, height, device);
+
+        let (sort_bind_group, composite_bind_group) =
+            self.resources
+                .create_bind_groups(scene_view, &output_view, device);
+
+        self.sort_bind_group = sort_bind_group;
+        self.composite_bind_group = composite_bind_group;
+        self.output_texture = output_texture;
+        self.output_view = output_view;
+    }
+
+    pub(crate) fn update(&mut self, settings: &UiPixelSort, upload: &mut Upload) {
+        self.params = PixelSortParams {
+            threshold: settings.threshold,
+            animate: settings.animate,
+            speed: settings.speed,
+            mix_amount: settings.mix,
+            mode: match settings.mode {
+                SortMode::Radius => 0,
+                SortMode::Angle => 1,
+            },
+            descending: settings.descending as u32,
+            phase: self.params.phase + 1.,
+            _padding: 0.,
+        };
+
+        upload.write(
+            &self.resources.params_buffer,
+            0,
+            bytemuck::bytes_of(&self.params),
+        );
+    }
+
+    pub(crate) fn dispatch(&self, encoder: &mut CommandEncoder) {
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+                label: Some("Pixel Sort Pass"),
+                timestamp_writes: None,
+            });
+
+            compute_pass.set_pipeline(&self.sort_pipeline);
+            compute_pass.set_bind_group(0, &self.sort_bind_group, &[]);
+            compute_pass.dispatch_workgroups(POLAR_SIZE, 1, 1);
+        }
+
+        let mut compute_pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("Pixel Sort Composite Pass"),
+            timestamp_writes: None,
+        });
+
+        let size = self.output_texture.size();
+        compute_pass.set_pipeline(&self.composite_pipeline);
+        compute_pass.set_bind_group(0, &self.composite_bind_group, &[]);
+        compute_pass.dispatch_workgroups(size.width.div_ceil(8), size.height.div_ceil(8), 1);
+    }
+
+    pub(crate) fn polar_view(&self) -> &TextureView {
+        &self.resources.polar_view
+    }
+
+    pub(crate) fn output_view(&self) -> &TextureView {
+        &self.output_view
+    }
+
+    pub(crate) fn polar_texture(&self) -> &Texture {
+        &self.resources.polar_texture
+    }
+
+    pub(crate) fn output_texture(&self) -> &Texture {
+        &self.output_texture
+    }
+}
@@ -0,0 +1,160 @@
+#include "math.wgsl"
+
+const LINE: u32 = 512u;
+
+struct Params {
+    threshold: f32,
+    animate: f32,
+    speed: f32,
+    mix_amount: f32,
+    mode: u32,
+    descending: u32,
+    phase: f32
+}
+
+@group(0) @binding(0)
+var<uniform> params: Params;
+@group(0) @binding(1)
+var scene_tex: texture_2d<f32>;
+@group(0) @binding(2)
+var scene_sampler: sampler;
+@group(0) @binding(3)
+var polar_out: texture_storage_2d<rgba16float, write>;
+@group(0) @binding(4)
+var polar_tex: texture_2d<f32>;
+@group(0) @binding(5)
+var polar_sampler: sampler;
+@group(0) @binding(6)
+var screen_out: texture_storage_2d<rgba16float, write>;
+
+var<workgroup> keys: array<f32, LINE>;
+var<workgroup> colors: array<vec4<f32>, LINE>;
+var<workgroup> breaks: array<u32, LINE>;
+
+fn aspect() -> f32 {
+    let size = vec2<f32>(textureDimensions(scene_tex));
+    return size.x / size.y;
+}
+
+fn max_radius() -> f32 {
+    return length(vec2<f32>(0.5 * aspect(), 0.5));
+}
+
+fn polar_to_uv(angle: f32, radius: f32) -> vec2<f32> {
+    let theta = angle * TAU;
+    let offset = vec2<f32>(cos(theta), sin(theta)) * radius * max_radius();
+    return vec2<f32>(0.5 + offset.x / aspect(), 0.5 + offset.y);
+}
+
+fn line_coord(line: u32, i: u32) -> vec2<u32> {
+    if params.mode == 0u {
+        return vec2<u32>(line, i);
+    }
+    return vec2<u32>(i, line);
+}
+
+@compute @workgroup_size(256)
+fn sort_main(
+    @builtin(workgroup_id) group: vec3<u32>,
+    @builtin(local_invocation_index) local: u32,
+) {
+    let threshold = clamp(params.threshold + params.animate * sin(params.phase * params.speed), 0.0, 1.0);
+
+    for (var e = 0u; e < 2u; e++) {
+        let i = local * 2u + e;
+        let coord = vec2<f32>(line_coord(group.x, i)) + 0.5;
+        let uv = polar_to_uv(coord.x / f32(LINE), coord.y / f32(LINE));
+
+        var color = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+        if all(uv >= vec2<f32>(0.0)) && all(uv <= vec2<f32>(1.0)) {
+            color = textureSampleLevel(scene_tex, scene_sampler, uv, 0.0);
+        }
+
+        let luminance = dot(color.rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+        let bright = luminance > threshold;
+
+        colors[i] = color;
+        breaks[i] = select(1u, 0u, bright);
+        keys[i] = select(-1.0, clamp(luminance, 0.0, 1.0), bright);
+    }
+    workgroupBarrier();
+
+    for (var offset = 1u; offset < LINE; offset *= 2u) {
+        let i0 = local * 2u;
+        let i1 = i0 + 1u;
+
+        var add0 = 0u;
+        var add1 = 0u;
+        if i0 >= offset {
+            add0 = breaks[i0 - offset];
+        }
+        if i1 >= offset {
+            add1 = breaks[i1 - offset];
+        }
+        workgroupBarrier();
+
+        breaks[i0] += add0;
+        breaks[i1] += add1;
+        workgroupBarrier();
+    }
+
+    for (var e = 0u; e < 2u; e++) {
+        let i = local * 2u + e;
+        let luminance = keys[i];
+
+        var key = f32(breaks[i]);
+        if luminance >= 0.0 {
+            var order = luminance;
+            if params.descending != 0u {
+                order = 1.0 - luminance;
+            }
+            key += 0.25 + 0.5 * order;
+        }
+        keys[i] = key;
+    }
+    workgroupBarrier();
+
+    for (var k = 2u; k <= LINE; k *= 2u) {
+        for (var j = k / 2u; j > 0u; j /= 2u) {
+            let i = 2u * j * (local / j) + local % j;
+            let l = i + j;
+            let ascending = (i & k) == 0u;
+
+            let a = keys[i];
+            let b = keys[l];
+            if (a > b) == ascending {
+                keys[i] = b;
+                keys[l] = a;
+
+                let color = colors[i];
+                colors[i] = colors[l];
+                colors[l] = color;
+            }
+            workgroupBarrier();
+        }
+    }
+
+    for (var e = 0u; e < 2u; e++) {
+        let i = local * 2u + e;
+        textureStore(polar_out, line_coord(group.x, i), colors[i]);
+    }
+}
+
+@compute @workgroup_size(8, 8)
+fn composite_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(screen_out);
+    if id.x >= size.x || id.y >= size.y {
+        return;
+    }
+
+    let uv = (vec2<f32>(id.xy) + 0.5) / vec2<f32>(size);
+    let original = textureSampleLevel(scene_tex, scene_sampler, uv, 0.0);
+
+    let offset = vec2<f32>((uv.x - 0.5) * aspect(), uv.y - 0.5);
+    let angle = fract(atan2(offset.y, offset.x) / TAU);
+    let radius = length(offset) / max_radius();
+
+    let sorted = textureSampleLevel(polar_tex, polar_sampler, vec2<f32>(angle, radius), 0.0);
+
+    textureStore(screen_out, id.xy, mix(original, sorted, params.mix_amount));
+}
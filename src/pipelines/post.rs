@@ -1,26 +1,31 @@
-use std::alloc::GlobalAlloc;
+use std::num::NonZero;
 
 use wgpu::{
     AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
-    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType,
-    ColorTargetState, ColorWrites, Device, FilterMode, FragmentState, IndexFormat,
-    MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue,
-    RenderPass, RenderPipeline, RenderPipelineDescriptor, SamplerBindingType, SamplerDescriptor,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, Buffer,
+    BufferBindingType, BufferUsages, ColorTargetState, ColorWrites, Device, FilterMode,
+    FragmentState, MultisampleState, PipelineCompilationOptions, PipelineLayout,
+    PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass, RenderPipeline,
+    RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, ShaderModule,
     ShaderStages, TextureFormat, TextureSampleType, TextureView, TextureViewDimension, VertexState,
-    include_wgsl,
+    util::{BufferInitDescriptor, DeviceExt},
 };
 
 use crate::{
-    boundary::Boundary,
-    global::Global,
-    utils::{BindGroupData, VertexBufferData},
+    color_space::PostSettings, global::Global, lut::Lut, pipelines::shader, utils::BindGroupData,
 };
 
 pub(crate) struct PostPipeline {
     pipeline: RenderPipeline,
+    layout: PipelineLayout,
+    shader_module: ShaderModule,
+    target_format: TextureFormat,
     off_screen_bind_group: BindGroup,
     global_bind_group_data: BindGroupData,
     global: Global,
+    settings_bind_group_data: BindGroupData,
+    settings: PostSettings,
+    lut_sampler: Sampler,
 }
 
 impl PostPipeline {
@@ -77,39 +82,160 @@ impl PostPipeline {
         (layout, bind_group)
     }
 
+    fn create_settings_bind_group_data(
+        settings: &PostSettings,
+        lut_view: &TextureView,
+        lut_sampler: &Sampler,
+        device: &Device,
+    ) -> BindGroupData {
+        let buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Post Settings Buffer"),
+            contents: bytemuck::bytes_of(settings),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Post Settings Bind Group Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: NonZero::new(std::mem::size_of::<PostSettings>() as u64),
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D3,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let bind_group =
+            Self::create_settings_bind_group(&layout, &buffer, lut_view, lut_sampler, device);
+
+        BindGroupData {
+            layout,
+            buffer,
+            bind_group,
+        }
+    }
+
+    fn create_settings_bind_group(
+        layout: &BindGroupLayout,
+        buffer: &Buffer,
+        lut_view: &TextureView,
+        lut_sampler: &Sampler,
+        device: &Device,
+    ) -> BindGroup {
+        device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Post Settings Bind Group"),
+            layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(lut_view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(lut_sampler),
+                },
+            ],
+        })
+    }
+
     pub(crate) fn new(
         texture_view: &TextureView,
         texture_format: TextureFormat,
         global: Global,
+        settings: PostSettings,
         device: &Device,
+        queue: &Queue,
     ) -> Self {
         let (off_screen_bind_group_layout, off_screen_bind_group) =
             Self::create_off_screen_bindgroup(texture_view, device);
 
         let global_bind_group_data = global.create_bind_group_data(device);
+        let lut_sampler = device.create_sampler(&SamplerDescriptor {
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            min_filter: FilterMode::Linear,
+            mag_filter: FilterMode::Linear,
+            label: Some("Color Grade LUT Sampler"),
+            ..Default::default()
+        });
+        let settings_bind_group_data = Self::create_settings_bind_group_data(
+            &settings,
+            &Lut::identity().create_view(device, queue),
+            &lut_sampler,
+            device,
+        );
 
         let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
             label: Some("Post Pipeline Layout"),
             bind_group_layouts: &[
                 &off_screen_bind_group_layout,
                 &global_bind_group_data.layout,
+                &settings_bind_group_data.layout,
             ],
             ..Default::default()
         });
 
-        let shader_module = device.create_shader_module(include_wgsl!("post.wgsl"));
+        let shader_module = shader::create_module("post.wgsl", include_str!("post.wgsl"), device);
+
+        let pipeline = Self::create_pipeline(&layout, &shader_module, texture_format, device);
+
+        Self {
+            pipeline,
+            layout,
+            shader_module,
+            target_format: texture_format,
+            off_screen_bind_group,
+            global_bind_group_data,
+            global,
+            settings_bind_group_data,
+            settings,
+            lut_sampler,
+        }
+    }
 
-        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+    fn create_pipeline(
+        layout: &PipelineLayout,
+        shader_module: &ShaderModule,
+        texture_format: TextureFormat,
+        device: &Device,
+    ) -> RenderPipeline {
+        device.create_render_pipeline(&RenderPipelineDescriptor {
             label: Some("Post Pipeline"),
-            layout: Some(&layout),
+            layout: Some(layout),
             vertex: VertexState {
-                module: &shader_module,
+                module: shader_module,
                 entry_point: Some("vs_main"),
                 compilation_options: PipelineCompilationOptions::default(),
                 buffers: &[],
             },
             fragment: Some(FragmentState {
-                module: &shader_module,
+                module: shader_module,
                 entry_point: Some("fs_main"),
                 compilation_options: PipelineCompilationOptions::default(),
                 targets: &[Some(ColorTargetState {
@@ -123,20 +249,47 @@ impl PostPipeline {
             multiview: None,
             cache: None,
             multisample: MultisampleState::default(),
-        });
+        })
+    }
 
-        Self {
-            pipeline,
-            off_screen_bind_group,
-            global_bind_group_data,
-            global,
+    pub(crate) fn set_target_format(&mut self, texture_format: TextureFormat, device: &Device) {
+        if texture_format == self.target_format {
+            return;
+        }
+
+        self.pipeline =
+            Self::create_pipeline(&self.layout, &self.shader_module, texture_format, device);
+        self.target_format = texture_format;
+    }
+
+    pub(crate) fn update_settings(&mut self, settings: PostSettings, queue: &Queue) {
+        if settings == self.settings {
+            return;
         }
+
+        self.settings = settings;
+        queue.write_buffer(
+            &self.settings_bind_group_data.buffer,
+            0,
+            bytemuck::bytes_of(&self.settings),
+        );
+    }
+
+    pub(crate) fn set_lut(&mut self, lut: &Lut, device: &Device, queue: &Queue) {
+        self.settings_bind_group_data.bind_group = Self::create_settings_bind_group(
+            &self.settings_bind_group_data.layout,
+            &self.settings_bind_group_data.buffer,
+            &lut.create_view(device, queue),
+            &self.lut_sampler,
+            device,
+        );
     }
 
     pub(crate) fn set_render_pass(&self, render_pass: &mut RenderPass<'_>) {
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &self.off_screen_bind_group, &[]);
         render_pass.set_bind_group(1, &self.global_bind_group_data.bind_group, &[]);
+        render_pass.set_bind_group(2, &self.settings_bind_group_data.bind_group, &[]);
 
         render_pass.draw(0..6, 0..1);
     }
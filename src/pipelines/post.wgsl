@@ -1,3 +1,6 @@
+#include "global.wgsl"
+#include "color.wgsl"
+
 @vertex
 fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
     var pos = array<vec2<f32>, 6>(
@@ -18,20 +21,124 @@ var scene_tex: texture_2d<f32>;
 @group(0) @binding(1)
 var scene_sampler: sampler;
 
-struct Global {
-    resolution: vec2<f32>,
-    phase: f32
-}
-
 @group(1) @binding(0)
 var<uniform> global: Global;
 
+struct PostSettings {
+    transfer: u32,
+    flash: f32,
+    gain: f32,
+    grade: u32,
+    hue_shift: f32,
+    saturation: f32,
+    contrast: f32,
+    lut_strength: f32,
+    lift: vec4<f32>,
+    gamma: vec4<f32>,
+    grade_gain: vec4<f32>,
+    viewport: vec4<f32>,
+    opacity: f32,
+    premultiply: u32,
+    master: f32,
+    hdr_white: f32,
+    hdr_peak: f32,
+    _padding_0: f32,
+    _padding_1: f32,
+    _padding_2: f32,
+}
+
+@group(2) @binding(0)
+var<uniform> settings: PostSettings;
+@group(2) @binding(1)
+var lut_tex: texture_3d<f32>;
+@group(2) @binding(2)
+var lut_sampler: sampler;
+
+fn hue_rotate(color: vec3<f32>, angle: f32) -> vec3<f32> {
+    let axis = vec3<f32>(0.57735026);
+    let c = cos(angle);
+    return color * c + cross(axis, color) * sin(angle) + axis * dot(axis, color) * (1.0 - c);
+}
+
+fn color_grade(linear: vec3<f32>) -> vec3<f32> {
+    var color = hue_rotate(linear_to_srgb(linear), settings.hue_shift);
+
+    let luma = dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+    color = mix(vec3<f32>(luma), color, settings.saturation);
+    color = (color - 0.5) * settings.contrast + 0.5;
+
+    color = settings.grade_gain.rgb * (color + settings.lift.rgb * (1.0 - color));
+    color = pow(clamp(color, vec3<f32>(0.0), vec3<f32>(1.0)), 1.0 / settings.gamma.rgb);
+
+    let size = f32(textureDimensions(lut_tex).x);
+    let lut_uv = color * ((size - 1.0) / size) + 0.5 / size;
+    let graded = textureSampleLevel(lut_tex, lut_sampler, lut_uv, 0.0).rgb;
+    color = mix(color, graded, settings.lut_strength);
+
+    return srgb_to_linear(color);
+}
+
+// scRGB puts 1.0 at 80 nits; PQ is absolute up to 10000 nits.
+const SCRGB_WHITE_NITS: f32 = 80.0;
+const PQ_MAX_NITS: f32 = 10000.0;
+
+const REC709_TO_REC2020 = mat3x3<f32>(
+    vec3<f32>(0.6274040, 0.0690970, 0.0163916),
+    vec3<f32>(0.3292820, 0.9195400, 0.0880132),
+    vec3<f32>(0.0433136, 0.0113612, 0.8955950),
+);
+
+// Scene-linear to display nits, with SDR white at `hdr_white` and highlights clipped at `hdr_peak`.
+fn hdr_nits(linear: vec3<f32>) -> vec3<f32> {
+    return clamp(linear * settings.hdr_white, vec3<f32>(0.0), vec3<f32>(settings.hdr_peak));
+}
+
+fn pq_encode(nits: vec3<f32>) -> vec3<f32> {
+    let m1 = 0.1593017578125;
+    let m2 = 78.84375;
+    let c1 = 0.8359375;
+    let c2 = 18.8515625;
+    let c3 = 18.6875;
+    let y = pow(nits / PQ_MAX_NITS, vec3<f32>(m1));
+    return pow((c1 + c2 * y) / (1.0 + c3 * y), vec3<f32>(m2));
+}
+
 @fragment
 fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
     let frag_coord = position;
 
-    let uv = frag_coord.xy / global.resolution;
+    var uv = frag_coord.xy / global.resolution;
+    if settings.viewport.z > 0.0 {
+        uv = (frag_coord.xy - settings.viewport.xy) / settings.viewport.zw;
+    }
+
+    let sampled = textureSample(scene_tex, scene_sampler, uv);
+    let strobed = mix(sampled.rgb * settings.gain, vec3<f32>(1.0), settings.flash);
+    var color = vec4<f32>(strobed, sampled.a);
+    if settings.grade != 0u {
+        color = vec4<f32>(color_grade(color.rgb), color.a);
+    }
+    color = vec4<f32>(color.rgb * settings.master, color.a);
+
+    switch settings.transfer {
+        case 1u: {
+            color = vec4<f32>(linear_to_srgb(color.rgb), color.a);
+        }
+        case 2u: {
+            color = vec4<f32>(srgb_to_linear(color.rgb), color.a);
+        }
+        case 3u: {
+            color = vec4<f32>(hdr_nits(color.rgb) / SCRGB_WHITE_NITS, color.a);
+        }
+        case 4u: {
+            color = vec4<f32>(pq_encode(hdr_nits(REC709_TO_REC2020 * color.rgb)), color.a);
+        }
+        default: {}
+    }
 
-    let color = textureSample(scene_tex, scene_sampler, uv);
+    if settings.opacity < 1.0 {
+        let rgb = select(color.rgb, color.rgb * settings.opacity, settings.premultiply != 0u);
+        color = vec4<f32>(rgb, settings.opacity);
+    }
     return color;
 }
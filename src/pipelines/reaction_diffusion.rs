@@ -0,0 +1,408 @@
+use std::num::NonZero;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, BlendState,
+    Buffer, BufferBindingType, BufferUsages, ColorTargetState, ColorWrites, CommandEncoder,
+    ComputePassDescriptor, ComputePipeline, ComputePipelineDescriptor, Device, Extent3d,
+    FilterMode, FragmentState, MultisampleState, PipelineCompilationOptions,
+    PipelineLayoutDescriptor, PrimitiveState, RenderPass, RenderPipeline, RenderPipelineDescriptor,
+    SamplerBindingType, SamplerDescriptor, ShaderStages, StorageTextureAccess, Texture,
+    TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+    TextureView, TextureViewDescriptor, TextureViewDimension, VertexState,
+    util::{BufferInitDescriptor, DeviceExt},
+};
+
+use crate::{pipelines::shader, ui::UiReactionDiffusion, upload::Upload};
+
+pub(crate) const RD_SIZE: u32 = 256;
+const STATE_FORMAT: TextureFormat = TextureFormat::Rgba32Float;
+const DISPLAY_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct ReactionDiffusionParams {
+    feed: f32,
+    kill: f32,
+    seed: u32,
+    _padding: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct BackgroundParams {
+    color: [f32; 4],
+}
+
+pub(crate) struct ReactionDiffusionPipeline {
+    step_pipeline: ComputePipeline,
+    seed_pipeline: ComputePipeline,
+    display_pipeline: ComputePipeline,
+    background_pipeline: RenderPipeline,
+    params_buffer: Buffer,
+    background_buffer: Buffer,
+    forward_bind_group: BindGroup,
+    backward_bind_group: BindGroup,
+    display_bind_group: BindGroup,
+    background_bind_group: BindGroup,
+    display_texture: Texture,
+    display_view: TextureView,
+    seed: u32,
+    pending_seed: bool,
+}
+
+impl ReactionDiffusionPipeline {
+    fn create_texture(label: &str, format: TextureFormat, device: &Device) -> Texture {
+        device.create_texture(&TextureDescriptor {
+            label: Some(label),
+            size: Extent3d {
+                width: RD_SIZE,
+                height: RD_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::STORAGE_BINDING
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        })
+    }
+
+    fn uniform_entry(visibility: ShaderStages, size: usize) -> BindGroupLayoutEntry {
+        BindGroupLayoutEntry {
+            binding: 0,
+            visibility,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: NonZero::new(size as u64),
+            },
+            count: None,
+        }
+    }
+
+    fn texture_entry(visibility: ShaderStages, filterable: bool) -> BindGroupLayoutEntry {
+        BindGroupLayoutEntry {
+            binding: 1,
+            visibility,
+            ty: BindingType::Texture {
+                sample_type: TextureSampleType::Float { filterable },
+                view_dimension: TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        }
+    }
+
+    fn storage_entry(format: TextureFormat) -> BindGroupLayoutEntry {
+        BindGroupLayoutEntry {
+            binding: 2,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::StorageTexture {
+                access: StorageTextureAccess::WriteOnly,
+                format,
+                view_dimension: TextureViewDimension::D2,
+            },
+            count: None,
+        }
+    }
+
+    fn create_step_bind_group(
+        layout: &BindGroupLayout,
+        params_buffer: &Buffer,
+        input: &TextureView,
+        output: &TextureView,
+        device: &Device,
+    ) -> BindGroup {
+        device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Reaction Diffusion Step Bind Group"),
+            layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(input),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::TextureView(output),
+                },
+            ],
+        })
+    }
+
+    pub(crate) fn new(texture_format: TextureFormat, device: &Device) -> Self {
+        let params_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Reaction Diffusion Params Buffer"),
+            contents: bytemuck::bytes_of(&ReactionDiffusionParams::zeroed()),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let background_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Reaction Diffusion Background Buffer"),
+            contents: bytemuck::bytes_of(&BackgroundParams::zeroed()),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let state_views =
+            ["Reaction Diffusion State A", "Reaction Diffusion State B"].map(|label| {
+                Self::create_texture(label, STATE_FORMAT, device)
+                    .create_view(&TextureViewDescriptor::default())
+            });
+        let display_texture =
+            Self::create_texture("Reaction Diffusion Display", DISPLAY_FORMAT, device);
+        let display_view = display_texture.create_view(&TextureViewDescriptor::default());
+
+        let step_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Reaction Diffusion Step Layout"),
+            entries: &[
+                Self::uniform_entry(
+                    ShaderStages::COMPUTE,
+                    std::mem::size_of::<ReactionDiffusionParams>(),
+                ),
+                Self::texture_entry(ShaderStages::COMPUTE, false),
+                Self::storage_entry(STATE_FORMAT),
+            ],
+        });
+
+        let display_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Reaction Diffusion Display Layout"),
+            entries: &[
+                Self::texture_entry(ShaderStages::COMPUTE, false),
+                Self::storage_entry(DISPLAY_FORMAT),
+            ],
+        });
+
+        let background_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Reaction Diffusion Background Layout"),
+            entries: &[
+                Self::uniform_entry(
+                    ShaderStages::FRAGMENT,
+                    std::mem::size_of::<BackgroundParams>(),
+                ),
+                Self::texture_entry(ShaderStages::FRAGMENT, true),
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Reaction Diffusion Sampler"),
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::Repeat,
+            min_filter: FilterMode::Linear,
+            mag_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let forward_bind_group = Self::create_step_bind_group(
+            &step_layout,
+            &params_buffer,
+            &state_views[0],
+            &state_views[1],
+            device,
+        );
+        let backward_bind_group = Self::create_step_bind_group(
+            &step_layout,
+            &params_buffer,
+            &state_views[1],
+            &state_views[0],
+            device,
+        );
+
+        let display_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Reaction Diffusion Display Bind Group"),
+            layout: &display_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&state_views[0]),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::TextureView(&display_view),
+                },
+            ],
+        });
+
+        let background_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Reaction Diffusion Background Bind Group"),
+            layout: &background_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: background_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&display_view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
+        let shader_module = shader::create_module(
+            "reaction_diffusion.wgsl",
+            include_str!("reaction_diffusion.wgsl"),
+            device,
+        );
+
+        let create_pipeline = |label, layout: &BindGroupLayout, entry_point| {
+            let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some(label),
+                bind_group_layouts: &[layout],
+                ..Default::default()
+            });
+
+            device.create_compute_pipeline(&ComputePipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                module: &shader_module,
+                entry_point: Some(entry_point),
+                compilation_options: PipelineCompilationOptions::default(),
+                cache: None,
+            })
+        };
+
+        let step_pipeline = create_pipeline(
+            "Reaction Diffusion Step Pipeline",
+            &step_layout,
+            "step_main",
+        );
+        let seed_pipeline = create_pipeline(
+            "Reaction Diffusion Seed Pipeline",
+            &step_layout,
+            "seed_main",
+        );
+        let display_pipeline = create_pipeline(
+            "Reaction Diffusion Display Pipeline",
+            &display_layout,
+            "display_main",
+        );
+
+        let background_pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Reaction Diffusion Background Pipeline Layout"),
+            bind_group_layouts: &[&background_layout],
+            ..Default::default()
+        });
+
+        let background_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Reaction Diffusion Background Pipeline"),
+            layout: Some(&background_pipeline_layout),
+            vertex: VertexState {
+                module: &shader_module,
+                entry_point: Some("vs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(FragmentState {
+                module: &shader_module,
+                entry_point: Some("fs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                targets: &[Some(ColorTargetState {
+                    format: texture_format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multiview: None,
+            cache: None,
+            multisample: MultisampleState::default(),
+        });
+
+        Self {
+            step_pipeline,
+            seed_pipeline,
+            display_pipeline,
+            background_pipeline,
+            params_buffer,
+            background_buffer,
+            forward_bind_group,
+            backward_bind_group,
+            display_bind_group,
+            background_bind_group,
+            display_texture,
+            display_view,
+            seed: 0,
+            pending_seed: true,
+        }
+    }
+
+    pub(crate) fn update(&mut self, settings: &UiReactionDiffusion, upload: &mut Upload) {
+        if settings.reseed {
+            self.seed = self.seed.wrapping_add(1);
+        }
+
+        let params = ReactionDiffusionParams {
+            feed: settings.feed,
+            kill: settings.kill,
+            seed: self.seed,
+            _padding: 0,
+        };
+        upload.write(&self.params_buffer, 0, bytemuck::bytes_of(&params));
+
+        let [r, g, b] = settings.color;
+        let background = BackgroundParams {
+            color: [r, g, b, settings.intensity],
+        };
+        upload.write(&self.background_buffer, 0, bytemuck::bytes_of(&background));
+    }
+
+    pub(crate) fn dispatch(&mut self, steps: u32, reseed: bool, encoder: &mut CommandEncoder) {
+        let mut compute_pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("Reaction Diffusion Pass"),
+            timestamp_writes: None,
+        });
+
+        let workgroups = RD_SIZE.div_ceil(8);
+
+        // Seeding writes state A, and every step pair reads A and ends back in A.
+        if std::mem::take(&mut self.pending_seed) || reseed {
+            compute_pass.set_pipeline(&self.seed_pipeline);
+            compute_pass.set_bind_group(0, &self.backward_bind_group, &[]);
+            compute_pass.dispatch_workgroups(workgroups, workgroups, 1);
+        }
+
+        compute_pass.set_pipeline(&self.step_pipeline);
+        for _ in 0..steps.div_ceil(2) {
+            compute_pass.set_bind_group(0, &self.forward_bind_group, &[]);
+            compute_pass.dispatch_workgroups(workgroups, workgroups, 1);
+            compute_pass.set_bind_group(0, &self.backward_bind_group, &[]);
+            compute_pass.dispatch_workgroups(workgroups, workgroups, 1);
+        }
+
+        compute_pass.set_pipeline(&self.display_pipeline);
+        compute_pass.set_bind_group(0, &self.display_bind_group, &[]);
+        compute_pass.dispatch_workgroups(workgroups, workgroups, 1);
+    }
+
+    pub(crate) fn set_render_pass(&self, render_pass: &mut RenderPass<'_>) {
+        render_pass.set_pipeline(&self.background_pipeline);
+        render_pass.set_bind_group(0, &self.background_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+
+    pub(crate) fn pattern_view(&self) -> &TextureView {
+        &self.display_view
+    }
+
+    pub(crate) fn pattern_texture(&self) -> &Texture {
+        &self.display_texture
+    }
+}
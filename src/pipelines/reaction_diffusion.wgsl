@@ -0,0 +1,124 @@
+struct Params {
+    feed: f32,
+    kill: f32,
+    seed: u32,
+    _padding: u32
+}
+
+@group(0) @binding(0)
+var<uniform> params: Params;
+@group(0) @binding(1)
+var state_in: texture_2d<f32>;
+@group(0) @binding(2)
+var state_out: texture_storage_2d<rgba32float, write>;
+
+const DIFFUSION_U: f32 = 1.0;
+const DIFFUSION_V: f32 = 0.5;
+
+fn hash(value: u32) -> u32 {
+    var x = value;
+    x ^= x >> 16u;
+    x *= 0x7feb352du;
+    x ^= x >> 15u;
+    x *= 0x846ca68bu;
+    x ^= x >> 16u;
+    return x;
+}
+
+fn load(coord: vec2<i32>, size: vec2<i32>) -> vec2<f32> {
+    let wrapped = (coord + size) % size;
+    return textureLoad(state_in, wrapped, 0).rg;
+}
+
+@compute @workgroup_size(8, 8)
+fn step_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = vec2<i32>(textureDimensions(state_in));
+    let coord = vec2<i32>(id.xy);
+    if coord.x >= size.x || coord.y >= size.y {
+        return;
+    }
+
+    let center = load(coord, size);
+    var laplacian = -center;
+    laplacian += 0.2 * (
+        load(coord + vec2<i32>(1, 0), size) + load(coord - vec2<i32>(1, 0), size)
+            + load(coord + vec2<i32>(0, 1), size) + load(coord - vec2<i32>(0, 1), size)
+    );
+    laplacian += 0.05 * (
+        load(coord + vec2<i32>(1, 1), size) + load(coord - vec2<i32>(1, 1), size)
+            + load(coord + vec2<i32>(1, -1), size) + load(coord - vec2<i32>(1, -1), size)
+    );
+
+    let u = center.x;
+    let v = center.y;
+    let reaction = u * v * v;
+
+    let next = vec2<f32>(
+        u + DIFFUSION_U * laplacian.x - reaction + params.feed * (1.0 - u),
+        v + DIFFUSION_V * laplacian.y + reaction - (params.feed + params.kill) * v
+    );
+
+    textureStore(state_out, coord, vec4<f32>(clamp(next, vec2<f32>(0.0), vec2<f32>(1.0)), 0.0, 1.0));
+}
+
+@compute @workgroup_size(8, 8)
+fn seed_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = vec2<u32>(textureDimensions(state_in));
+    if id.x >= size.x || id.y >= size.y {
+        return;
+    }
+
+    let cell = id.xy / 8u;
+    let noise = hash(cell.x * 7919u + cell.y * 104729u + params.seed * 15485863u);
+    let v = select(0.0, 1.0, noise % 23u == 0u);
+
+    textureStore(state_out, vec2<i32>(id.xy), vec4<f32>(1.0, v, 0.0, 1.0));
+}
+
+@group(0) @binding(2)
+var display_out: texture_storage_2d<rgba16float, write>;
+
+@compute @workgroup_size(8, 8)
+fn display_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = vec2<u32>(textureDimensions(state_in));
+    if id.x >= size.x || id.y >= size.y {
+        return;
+    }
+
+    let state = textureLoad(state_in, vec2<i32>(id.xy), 0);
+    textureStore(display_out, vec2<i32>(id.xy), vec4<f32>(state.rg, 0.0, 1.0));
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+
+    var output: VertexOutput;
+    output.position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    output.uv = uv;
+    return output;
+}
+
+struct Background {
+    color: vec4<f32>
+}
+
+@group(0) @binding(0)
+var<uniform> background: Background;
+@group(0) @binding(1)
+var pattern_tex: texture_2d<f32>;
+@group(0) @binding(2)
+var pattern_sampler: sampler;
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let v = textureSampleLevel(pattern_tex, pattern_sampler, input.uv, 0.0).g;
+    let level = smoothstep(0.1, 0.4, v) * background.color.a;
+
+    return vec4<f32>(background.color.rgb * level, 1.0);
+}
@@ -0,0 +1,198 @@
+use anyhow::Result;
+use image::RgbaImage;
+use wgpu::{Buffer, CommandEncoder, Device, Queue, RenderPass, TextureFormat, TextureView};
+
+use crate::{
+    audio::response::WaveDrive,
+    draw_stats::DrawStats,
+    global::Global,
+    gpu::{self, Capabilities, ErrorLog},
+    pipelines::{
+        cull::CullPipeline,
+        interference::InterferencePipeline,
+        sine::{Sine, SinePipeline},
+    },
+    ramp::ColorRamp,
+    transient::TransientInstance,
+    ui::{SceneMode, UiReactionDiffusion, UiScene, UiSineWaveData},
+    upload::Upload,
+};
+
+pub(crate) struct ScenePipeline {
+    sine_pipeline: SinePipeline,
+    interference_pipeline: Option<InterferencePipeline>,
+}
+
+impl ScenePipeline {
+    pub(crate) fn new(
+        sine: Sine,
+        global: Global,
+        ramp: &ColorRamp,
+        texture_format: TextureFormat,
+        capabilities: &Capabilities,
+        errors: &ErrorLog,
+        device: &Device,
+    ) -> Result<Self> {
+        let mut sine_pipeline = gpu::validated(device, || {
+            SinePipeline::new(sine, global, texture_format, capabilities, device)
+        })?;
+
+        if capabilities.indirect {
+            match gpu::validated(device, || {
+                CullPipeline::new(sine_pipeline.wave_buffer(), device)
+            }) {
+                Ok(cull) => sine_pipeline.set_cull_pipeline(cull),
+                Err(err) => errors.push(format!("Instance culling unavailable: {err}")),
+            }
+        }
+
+        let interference_pipeline = capabilities
+            .storage_buffers
+            .then(|| {
+                gpu::validated(device, || {
+                    InterferencePipeline::new(
+                        sine_pipeline.wave_buffer(),
+                        ramp,
+                        global,
+                        texture_format,
+                        device,
+                    )
+                })
+            })
+            .and_then(|pipeline| {
+                pipeline
+                    .inspect_err(|err| {
+                        errors.push(format!("Interference pipeline unavailable: {err}"))
+                    })
+                    .ok()
+            });
+
+        Ok(Self {
+            sine_pipeline,
+            interference_pipeline,
+        })
+    }
+
+    pub(crate) fn set_render_pass(&self, mode: SceneMode, render_pass: &mut RenderPass<'_>) {
+        match (mode, &self.interference_pipeline) {
+            (SceneMode::Interference, Some(interference_pipeline)) => {
+                interference_pipeline.set_render_pass(render_pass)
+            }
+            _ => self.sine_pipeline.set_render_pass(render_pass),
+        }
+    }
+
+    pub(crate) fn draw_stats(&self, mode: SceneMode) -> DrawStats {
+        match (mode, &self.interference_pipeline) {
+            (SceneMode::Interference, Some(_)) => DrawStats::fullscreen(),
+            _ => self.sine_pipeline.draw_stats(),
+        }
+    }
+
+    pub(crate) fn dispatch_cull(&self, mode: SceneMode, encoder: &mut CommandEncoder) {
+        if mode == SceneMode::Rings || self.interference_pipeline.is_none() {
+            self.sine_pipeline.dispatch_cull(encoder);
+        }
+    }
+
+    pub(crate) fn update(
+        &mut self,
+        sine_wave_data: &[UiSineWaveData],
+        transients: &[TransientInstance],
+        ramp: &ColorRamp,
+        scene: &UiScene,
+        drives: &[WaveDrive],
+        upload: &mut Upload,
+    ) {
+        self.sine_pipeline.update_sine_wave_data(
+            sine_wave_data,
+            transients,
+            &scene.collision,
+            drives,
+            scene.selection.selected,
+            upload,
+        );
+        self.sine_pipeline.update_global_frame(
+            scene.stretch,
+            &scene.antialias,
+            scene.false_color.params(),
+            &scene.camera,
+            upload,
+        );
+
+        if let Some(interference_pipeline) = &mut self.interference_pipeline {
+            interference_pipeline.update_global_frame(
+                scene.stretch,
+                &scene.camera,
+                self.sine_pipeline.time_step(),
+                upload,
+            );
+            interference_pipeline.update_ramp(ramp, upload);
+        }
+    }
+
+    pub(crate) fn set_time_step(&mut self, step: f32) {
+        self.sine_pipeline.set_time_step(step);
+    }
+
+    // Lands on `phase` once the next `update` has advanced the frame.
+    pub(crate) fn set_phase(&mut self, phase: f32) {
+        let phase = phase - self.sine_pipeline.time_step();
+        self.sine_pipeline.set_phase(phase);
+        if let Some(interference_pipeline) = &mut self.interference_pipeline {
+            interference_pipeline.set_phase(phase);
+        }
+    }
+
+    pub(crate) fn phase(&self) -> f32 {
+        self.sine_pipeline.phase()
+    }
+
+    pub(crate) fn update_scope(&self, samples: &[f32], upload: &mut Upload) {
+        self.sine_pipeline.update_scope(samples, upload);
+    }
+
+    pub(crate) fn update_spectrum(&self, bins: &[f32], upload: &mut Upload) {
+        self.sine_pipeline.update_spectrum(bins, upload);
+    }
+
+    pub(crate) fn copy_spectrum_from(&self, buffer: &Buffer, encoder: &mut CommandEncoder) {
+        self.sine_pipeline.copy_spectrum_from(buffer, encoder);
+    }
+
+    pub(crate) fn set_modulation_texture(&mut self, view: &TextureView, device: &Device) {
+        self.sine_pipeline.set_modulation_texture(view, device);
+    }
+
+    pub(crate) fn update_modulation(&self, settings: &UiReactionDiffusion, upload: &mut Upload) {
+        self.sine_pipeline.update_modulation(settings, upload);
+    }
+
+    pub(crate) fn update_global_resolution(
+        &mut self,
+        new_width: u32,
+        new_height: u32,
+
+        queue: &Queue,
+    ) {
+        self.sine_pipeline
+            .update_global_resolution(new_width, new_height, queue);
+
+        if let Some(interference_pipeline) = &mut self.interference_pipeline {
+            interference_pipeline.update_global_resolution(new_width, new_height, queue);
+        }
+    }
+
+    pub(crate) fn set_wave_texture(&self, index: usize, image: &RgbaImage, queue: &Queue) {
+        self.sine_pipeline.set_wave_texture(index, image, queue);
+    }
+
+    pub(crate) fn wave_texture_view(&self, index: usize) -> TextureView {
+        self.sine_pipeline.wave_texture_view(index)
+    }
+
+    pub(crate) fn copy_wave_textures_from(&self, other: &Self, encoder: &mut CommandEncoder) {
+        self.sine_pipeline
+            .copy_wave_textures_from(&other.sine_pipeline, encoder);
+    }
+}
@@ -0,0 +1,60 @@
+use std::borrow::Cow;
+
+use anyhow::{Context, Result};
+use wgpu::{Device, ShaderModule, ShaderModuleDescriptor, ShaderSource};
+
+const INCLUDES: [(&str, &str); 6] = [
+    ("color.wgsl", include_str!("include/color.wgsl")),
+    ("global.wgsl", include_str!("include/global.wgsl")),
+    ("math.wgsl", include_str!("include/math.wgsl")),
+    ("noise.wgsl", include_str!("include/noise.wgsl")),
+    ("ramp.wgsl", include_str!("include/ramp.wgsl")),
+    ("wave.wgsl", include_str!("include/wave.wgsl")),
+];
+
+pub(crate) fn create_module(name: &str, source: &str, device: &Device) -> ShaderModule {
+    // Every include is built in, so a failure here is a typo in a shader rather than bad input.
+    let source = preprocess(source)
+        .with_context(|| format!("Failed to preprocess `{name}`"))
+        .unwrap_or_else(|err| panic!("{err:#}"));
+
+    device.create_shader_module(ShaderModuleDescriptor {
+        label: Some(name),
+        source: ShaderSource::Wgsl(Cow::Owned(source)),
+    })
+}
+
+fn preprocess(source: &str) -> Result<String> {
+    let mut output = String::with_capacity(source.len());
+    expand(source, &mut Vec::new(), &mut output)?;
+
+    Ok(output)
+}
+
+fn expand<'a>(source: &'a str, included: &mut Vec<&'a str>, output: &mut String) -> Result<()> {
+    for line in source.lines() {
+        let Some(path) = line.trim().strip_prefix("#include") else {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        };
+
+        let name = path
+            .trim()
+            .strip_prefix('"')
+            .and_then(|path| path.strip_suffix('"'))
+            .with_context(|| format!("Malformed include `{}`", line.trim()))?;
+        if included.contains(&name) {
+            continue;
+        }
+
+        let (_, snippet) = INCLUDES
+            .iter()
+            .find(|(include, _)| *include == name)
+            .with_context(|| format!("Unknown include `{name}`"))?;
+        included.push(name);
+        expand(snippet, included, output)?;
+    }
+
+    Ok(())
+}
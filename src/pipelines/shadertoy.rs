@@ -0,0 +1,121 @@
+use std::borrow::Cow;
+
+use wgpu::{
+    BlendComponent, BlendFactor, BlendOperation, BlendState, Color, ColorTargetState, ColorWrites,
+    Device, FragmentState, MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor,
+    PrimitiveState, Queue, RenderPass, RenderPipeline, RenderPipelineDescriptor,
+    ShaderModuleDescriptor, ShaderSource, TextureFormat, VertexState,
+};
+
+use crate::{global::Global, pipelines::shader, upload::Upload, utils::BindGroupData};
+
+// Opacity comes in through the blend constant, so the imported shader needs no extra uniform.
+const CONSTANT_BLEND: BlendComponent = BlendComponent {
+    src_factor: BlendFactor::Constant,
+    dst_factor: BlendFactor::OneMinusConstant,
+    operation: BlendOperation::Add,
+};
+
+pub(crate) struct ShadertoyPipeline {
+    pipeline: RenderPipeline,
+    global_bind_group_data: BindGroupData,
+    global: Global,
+}
+
+impl ShadertoyPipeline {
+    pub(crate) fn new(
+        fragment_source: String,
+        global: Global,
+        texture_format: TextureFormat,
+        device: &Device,
+    ) -> Self {
+        let global_bind_group_data = global.create_bind_group_data(device);
+
+        let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Shadertoy Pipeline Layout"),
+            bind_group_layouts: &[&global_bind_group_data.layout],
+            ..Default::default()
+        });
+
+        let vertex_module =
+            shader::create_module("shadertoy.wgsl", include_str!("shadertoy.wgsl"), device);
+        let fragment_module = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("Shadertoy Import"),
+            source: ShaderSource::Wgsl(Cow::Owned(fragment_source)),
+        });
+
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("Shadertoy Pipeline"),
+            layout: Some(&layout),
+            vertex: VertexState {
+                module: &vertex_module,
+                entry_point: Some("vs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(FragmentState {
+                module: &fragment_module,
+                entry_point: Some("main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                targets: &[Some(ColorTargetState {
+                    format: texture_format,
+                    blend: Some(BlendState {
+                        color: CONSTANT_BLEND,
+                        alpha: CONSTANT_BLEND,
+                    }),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multiview: None,
+            cache: None,
+            multisample: MultisampleState::default(),
+        });
+
+        Self {
+            pipeline,
+            global_bind_group_data,
+            global,
+        }
+    }
+
+    pub(crate) fn set_render_pass(&self, opacity: f32, render_pass: &mut RenderPass<'_>) {
+        let opacity = opacity.clamp(0., 1.) as f64;
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_blend_constant(Color {
+            r: opacity,
+            g: opacity,
+            b: opacity,
+            a: opacity,
+        });
+        render_pass.set_bind_group(0, &self.global_bind_group_data.bind_group, &[]);
+
+        render_pass.draw(0..3, 0..1);
+    }
+
+    pub(crate) fn update_time(&mut self, time: f32, upload: &mut Upload) {
+        self.global.set_phase(time);
+        upload.write(
+            &self.global_bind_group_data.buffer,
+            0,
+            bytemuck::bytes_of(&self.global),
+        );
+    }
+
+    pub(crate) fn update_global_resolution(
+        &mut self,
+        new_width: u32,
+        new_height: u32,
+
+        queue: &Queue,
+    ) {
+        self.global.set_resolution(new_width, new_height);
+
+        queue.write_buffer(
+            &self.global_bind_group_data.buffer,
+            0,
+            bytemuck::bytes_of(&self.global),
+        );
+    }
+}
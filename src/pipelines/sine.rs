@@ -1,23 +1,37 @@
-use std::{array, num::NonZero, slice::Iter};
+use std::array;
 
 use bytemuck::{Pod, Zeroable};
+use image::RgbaImage;
 use wgpu::{
-    BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor, BindGroupLayoutEntry,
-    BindingType, BlendState, BufferBindingType, BufferUsages, ColorTargetState, ColorWrites,
-    Device, Face, FragmentState, FrontFace, IndexFormat, MultisampleState,
-    PipelineCompilationOptions, PipelineLayoutDescriptor, PolygonMode, PrimitiveState,
-    PrimitiveTopology, Queue, RenderPass, RenderPipeline, RenderPipelineDescriptor, ShaderStages,
-    TextureFormat, VertexAttribute, VertexBufferLayout, VertexFormat, VertexState, VertexStepMode,
-    include_wgsl,
+    BlendState, Buffer, BufferUsages, ColorTargetState, ColorWrites, CommandEncoder, Device, Face,
+    FragmentState, FrontFace, IndexFormat, MultisampleState, PipelineCompilationOptions,
+    PipelineLayoutDescriptor, PolygonMode, PrimitiveState, PrimitiveTopology, Queue, RenderPass,
+    RenderPipeline, RenderPipelineDescriptor, TextureFormat, TextureView, VertexAttribute,
+    VertexBufferLayout, VertexFormat, VertexState, VertexStepMode,
     util::{BufferInitDescriptor, DeviceExt},
 };
 
 use crate::{
+    audio::{analysis::SPECTRUM_BINS, response::WaveDrive},
     boundary::Boundary,
+    camera::Camera,
+    collision::{self, MAX_HIGHLIGHTS},
+    draw_stats::DrawStats,
     global::Global,
-    ui::UiSineWaveData,
+    gpu::Capabilities,
+    jitter::SpeedJitter,
+    macros::MacroParam,
+    modulation::Modulation,
+    pipelines::{
+        cull::{CULL_THRESHOLD, CullPipeline},
+        shader,
+    },
+    scope,
+    stroke::StrokeTextures,
+    transient::{MAX_TRANSIENTS, TransientInstance},
+    ui::{UiAntialias, UiCollision, UiOutline, UiReactionDiffusion, UiSineWaveData},
+    upload::Upload,
     utils::{BindGroupData, InstanceBufferData, VertexBufferData},
-    vertex::Vertex,
 };
 
 pub(crate) struct Sine {
@@ -25,8 +39,31 @@ pub(crate) struct Sine {
     pub(crate) wave_data: Waves,
 }
 
+pub(crate) const MAX_WAVES: usize = 32;
+pub(crate) const MAX_ECHOES: usize = 6;
+pub(crate) const MAX_REPEATS: usize = 24;
+pub(crate) const FLAG_TEXTURED: u32 = 1;
+pub(crate) const FLAG_SCOPE: u32 = 2;
+pub(crate) const FLAG_SPECTRUM: u32 = 4;
+pub(crate) const FLAG_FRACTAL: u32 = 8;
+pub(crate) const FLAG_OUTLINE: u32 = 16;
+pub(crate) const FLAG_MIRROR_X: u32 = 32;
+pub(crate) const FLAG_MIRROR_Y: u32 = 64;
+pub(crate) const FLAG_SHADOW: u32 = 128;
+pub(crate) const FLAG_ARC_LENGTH: u32 = 256;
+const MAX_TWINS: usize = 3;
+const SELECTION_OUTLINE: UiOutline = UiOutline {
+    enabled: true,
+    thickness: 0.003,
+    offset: 0.006,
+    color: [1., 0.78, 0.],
+};
+// Radial repeats share this budget instead of growing it, so the per-frame upload stays the same.
+const MAX_RING_INSTANCES: usize = MAX_WAVES * (1 + MAX_ECHOES) * (1 + MAX_TWINS);
+const MAX_INSTANCES: usize = MAX_RING_INSTANCES + MAX_TRANSIENTS;
+
 #[derive(Clone, Debug)]
-pub(crate) struct Waves(pub(crate) [SineWaveData; 8]);
+pub(crate) struct Waves(pub(crate) [SineWaveData; MAX_INSTANCES]);
 
 impl Default for Waves {
     fn default() -> Self {
@@ -39,11 +76,20 @@ impl Default for Waves {
 }
 
 impl Waves {
-    fn create_instance_buffer_data(&self, device: &Device) -> InstanceBufferData {
+    fn create_instance_buffer_data(
+        &self,
+        capabilities: &Capabilities,
+        device: &Device,
+    ) -> InstanceBufferData {
+        let mut usage = BufferUsages::VERTEX | BufferUsages::COPY_DST;
+        if capabilities.storage_buffers {
+            usage |= BufferUsages::STORAGE;
+        }
+
         let vertex_buffer = device.create_buffer_init(&BufferInitDescriptor {
             label: Some("Wave Vertex Buffer"),
             contents: bytemuck::cast_slice(&self.0),
-            usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+            usage,
         });
 
         const F32X2_SIZE: u64 = std::mem::size_of::<[f32; 2]>() as u64;
@@ -60,34 +106,74 @@ impl Waves {
                     offset: 0,
                 },
                 VertexAttribute {
-                    format: VertexFormat::Float32,
+                    format: VertexFormat::Float32x4,
                     shader_location: 2,
                     offset: F32X2_SIZE,
                 },
                 VertexAttribute {
-                    format: VertexFormat::Float32,
+                    format: VertexFormat::Float32x3,
                     shader_location: 3,
-                    offset: F32X2_SIZE + F32_SIZE,
+                    offset: F32X2_SIZE + 26 * F32_SIZE,
                 },
                 VertexAttribute {
-                    format: VertexFormat::Float32,
+                    format: VertexFormat::Float32x2,
                     shader_location: 4,
-                    offset: F32X2_SIZE + 2 * F32_SIZE,
+                    offset: F32X2_SIZE + 29 * F32_SIZE,
                 },
                 VertexAttribute {
                     format: VertexFormat::Float32,
                     shader_location: 5,
-                    offset: F32X2_SIZE + 3 * F32_SIZE,
+                    offset: F32X2_SIZE + 4 * F32_SIZE,
                 },
                 VertexAttribute {
-                    format: VertexFormat::Float32,
+                    format: VertexFormat::Uint8,
                     shader_location: 6,
-                    offset: F32X2_SIZE + 4 * F32_SIZE,
+                    offset: F32X2_SIZE + 5 * F32_SIZE,
                 },
                 VertexAttribute {
-                    format: VertexFormat::Uint8,
+                    format: VertexFormat::Float32x2,
                     shader_location: 7,
-                    offset: F32X2_SIZE + 5 * F32_SIZE,
+                    offset: F32X2_SIZE + 6 * F32_SIZE,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    shader_location: 8,
+                    offset: F32X2_SIZE + 22 * F32_SIZE,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Uint32,
+                    shader_location: 9,
+                    offset: F32X2_SIZE + 8 * F32_SIZE,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    shader_location: 10,
+                    offset: F32X2_SIZE + 9 * F32_SIZE,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Float32x3,
+                    shader_location: 11,
+                    offset: F32X2_SIZE + 31 * F32_SIZE,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Uint32,
+                    shader_location: 12,
+                    offset: F32X2_SIZE + 13 * F32_SIZE,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    shader_location: 13,
+                    offset: F32X2_SIZE + 14 * F32_SIZE,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    shader_location: 14,
+                    offset: F32X2_SIZE + 18 * F32_SIZE,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    shader_location: 15,
+                    offset: F32X2_SIZE + 34 * F32_SIZE,
                 },
             ],
         };
@@ -112,83 +198,160 @@ pub(crate) struct SineWaveData {
 
     pub(crate) speed: f32,
     pub(crate) init: u32,
-    _padding: f32,
-}
 
-impl Default for SineWaveData {
-    fn default() -> Self {
-        Self {
-            amplitude: 0.05,
-            center: [0.5, 0.5],
-            inner_radius: 0.50,
-            thickness: 0.01,
-            cycles: 8.,
-            speed: 0.005,
-            init: 0,
-            _padding: 0.,
-        }
-    }
+    pub(crate) texture_tiling: f32,
+    pub(crate) texture_scroll: f32,
+    pub(crate) flags: u32,
+    pub(crate) opacity: f32,
+    pub(crate) phase_offset: f32,
+    pub(crate) breath_depth: f32,
+    pub(crate) breath_period: f32,
+    pub(crate) layer: u32,
+    pub(crate) highlight_angles: [f32; MAX_HIGHLIGHTS],
+    pub(crate) highlight_widths: [f32; MAX_HIGHLIGHTS],
+    pub(crate) fractal: [f32; 4],
+    pub(crate) outline_color: [f32; 3],
+    pub(crate) outline: [f32; 2],
+    pub(crate) shadow_color: [f32; 3],
+    pub(crate) shadow: [f32; 4],
 }
 
 impl SineWaveData {
     pub(crate) fn new(
-        center: [f32; 2],
-
-        inner_radius: f32,
-        thickness: f32,
-
-        amplitude: f32,
-        cycles: f32,
-
-        speed: f32,
-        init: bool,
+        new_data: &UiSineWaveData,
+        outline: UiOutline,
+        layer: usize,
+        phase_offset: f32,
     ) -> Self {
         Self {
-            speed,
-            cycles,
-            center,
-            inner_radius,
-            thickness,
-            amplitude,
-            init: init as u32,
-            _padding: 0.,
+            center: new_data.center,
+            inner_radius: new_data.inner_radius,
+            thickness: new_data.thickness,
+            amplitude: new_data.amplitude,
+            cycles: new_data.cycles,
+            speed: new_data.speed,
+            init: new_data.init as u32,
+            texture_tiling: if new_data.texture_arc_length {
+                new_data.texture_tile_length
+            } else {
+                new_data.texture_tiling
+            },
+            texture_scroll: new_data.texture_scroll,
+            flags: (new_data.textured as u32 * FLAG_TEXTURED)
+                | (new_data.texture_arc_length as u32 * FLAG_ARC_LENGTH)
+                | (new_data.scope as u32 * FLAG_SCOPE)
+                | (new_data.spectrum as u32 * FLAG_SPECTRUM)
+                | (new_data.fractal.enabled as u32 * FLAG_FRACTAL)
+                | (outline.enabled as u32 * FLAG_OUTLINE)
+                | (new_data.shadow.enabled as u32 * FLAG_SHADOW),
+            opacity: new_data.fade * new_data.opacity,
+            phase_offset,
+            breath_depth: new_data.breathing.depth,
+            breath_period: new_data.breathing.period,
+            layer: layer as u32,
+            highlight_angles: [0.; MAX_HIGHLIGHTS],
+            highlight_widths: [0.; MAX_HIGHLIGHTS],
+            fractal: new_data.fractal.params(),
+            outline_color: outline.color,
+            outline: [outline.thickness, outline.offset],
+            shadow_color: new_data.shadow.color,
+            shadow: new_data.shadow.params(),
         }
     }
 
-    fn create_bind_group_data(&self, device: &Device) -> BindGroupData {
-        let buffer = device.create_buffer_init(&BufferInitDescriptor {
-            label: None,
-            contents: bytemuck::bytes_of(self),
-            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
-        });
+    // Every ring a wave draws: its radial repeats, their mirrored twins and the echo taps of each.
+    pub(crate) fn instances(
+        self,
+        new_data: &UiSineWaveData,
+        aspect: f32,
+        mut emit: impl FnMut(Self),
+    ) {
+        let echo = &new_data.echo;
+        let taps = if new_data.init {
+            echo.count.min(MAX_ECHOES)
+        } else {
+            0
+        };
 
-        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
-            label: None,
-            entries: &[BindGroupLayoutEntry {
-                binding: 0,
-                visibility: ShaderStages::FRAGMENT,
-                ty: BindingType::Buffer {
-                    ty: BufferBindingType::Uniform,
-                    has_dynamic_offset: false,
-                    min_binding_size: NonZero::new(std::mem::size_of::<Self>() as u64),
-                },
-                count: None,
-            }],
-        });
+        for (i, (center, angle)) in new_data.repeat.copies(self.center, aspect).enumerate() {
+            let repeat = Self {
+                center,
+                phase_offset: self.phase_offset - self.cycles * angle
+                    + new_data.repeat.phase * i as f32,
+                ..self
+            };
+
+            let twins = std::iter::once([false, false]).chain(new_data.symmetry.twins());
+            for [flip_x, flip_y] in twins {
+                let [x, y] = repeat.center;
+                let copy = Self {
+                    center: [
+                        if flip_x { 1. - x } else { x },
+                        if flip_y { 1. - y } else { y },
+                    ],
+                    flags: repeat.flags
+                        | (flip_x as u32 * FLAG_MIRROR_X)
+                        | (flip_y as u32 * FLAG_MIRROR_Y),
+                    ..repeat
+                };
+
+                for tap in 0..=taps {
+                    emit(Self {
+                        inner_radius: copy.inner_radius + echo.spread * tap as f32,
+                        phase_offset: copy.phase_offset + echo.phase * tap as f32,
+                        opacity: copy.opacity * echo.decay.powi(tap as i32),
+                        highlight_widths: if tap == 0 && i == 0 {
+                            copy.highlight_widths
+                        } else {
+                            [0.; MAX_HIGHLIGHTS]
+                        },
+                        ..copy
+                    });
+                }
+            }
+        }
+    }
 
-        let bind_group = device.create_bind_group(&BindGroupDescriptor {
-            label: None,
-            layout: &layout,
-            entries: &[BindGroupEntry {
-                binding: 0,
-                resource: buffer.as_entire_binding(),
-            }],
-        });
+    // Matches `ring_reach` in wave.wgsl.
+    pub(crate) fn reach(&self) -> f32 {
+        let mut reach =
+            self.inner_radius + self.breath_depth.abs() + self.thickness + self.amplitude.abs();
+        if self.flags & FLAG_OUTLINE != 0 {
+            reach += (self.outline[0] + self.outline[1]).max(0.);
+        }
+        if self.flags & FLAG_SHADOW != 0 {
+            reach += self.shadow[0].hypot(self.shadow[1]) + self.shadow[2];
+        }
 
-        BindGroupData {
-            layout,
-            buffer,
-            bind_group,
+        reach
+    }
+}
+
+impl Default for SineWaveData {
+    fn default() -> Self {
+        Self {
+            amplitude: 0.05,
+            center: [0.5, 0.5],
+            inner_radius: 0.50,
+            thickness: 0.01,
+            cycles: 8.,
+            speed: 0.005,
+            init: 0,
+            texture_tiling: 4.,
+            texture_scroll: 0.,
+            flags: 0,
+            opacity: 1.,
+            phase_offset: 0.,
+            breath_depth: 0.,
+            breath_period: 0.,
+            layer: 0,
+            highlight_angles: [0.; MAX_HIGHLIGHTS],
+            highlight_widths: [0.; MAX_HIGHLIGHTS],
+            fractal: [0.; 4],
+            outline_color: [0.; 3],
+            outline: [0.; 2],
+            shadow_color: [0.; 3],
+            shadow: [0.; 4],
         }
     }
 }
@@ -197,8 +360,17 @@ pub(crate) struct SinePipeline {
     boundary_buffer_data: VertexBufferData,
     sinewave_instance_buffer_data: InstanceBufferData,
     global_bind_group_data: BindGroupData,
+    scope_bind_group_data: BindGroupData,
+    stroke_textures: StrokeTextures,
+    modulation: Modulation,
     global: Global,
     sine: Sine,
+    instance_count: u32,
+    time_step: f32,
+    stats: DrawStats,
+    jitter: SpeedJitter,
+    staged: Vec<SineWaveData>,
+    cull: Option<CullPipeline>,
     pipeline: RenderPipeline,
 }
 
@@ -207,16 +379,28 @@ impl SinePipeline {
         sine: Sine,
         global: Global,
         texture_format: TextureFormat,
+        capabilities: &Capabilities,
         device: &Device,
     ) -> Self {
-        let shader_module = device.create_shader_module(include_wgsl!("sine.wgsl"));
+        let shader_module = shader::create_module("sine.wgsl", include_str!("sine.wgsl"), device);
 
         let global_bind_group_data = global.create_bind_group_data(device);
-        let sinewave_instance_buffer_data = sine.wave_data.create_instance_buffer_data(device);
+        let sinewave_instance_buffer_data = sine
+            .wave_data
+            .create_instance_buffer_data(capabilities, device);
+
+        let stroke_textures = StrokeTextures::new(MAX_WAVES as u32, device);
+        let scope_bind_group_data = scope::create_bind_group_data(device);
+        let modulation = Modulation::new(device);
 
         let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
             label: Some("Sine Pipeline Layout"),
-            bind_group_layouts: &[&global_bind_group_data.layout],
+            bind_group_layouts: &[
+                &global_bind_group_data.layout,
+                &stroke_textures.layout,
+                &scope_bind_group_data.layout,
+                &modulation.layout,
+            ],
             ..Default::default()
         });
 
@@ -259,64 +443,264 @@ impl SinePipeline {
 
         Self {
             global,
+            instance_count: sine.wave_data.0.len() as u32,
+            time_step: 1.,
+            stats: DrawStats::default(),
+            jitter: SpeedJitter::default(),
+            staged: Vec::with_capacity(MAX_INSTANCES),
+            cull: None,
             sine,
             boundary_buffer_data,
             global_bind_group_data,
+            scope_bind_group_data,
             sinewave_instance_buffer_data,
+            stroke_textures,
+            modulation,
             pipeline,
         }
     }
 
+    pub(crate) fn set_cull_pipeline(&mut self, cull: CullPipeline) {
+        self.cull = Some(cull);
+    }
+
+    fn active_cull(&self) -> Option<&CullPipeline> {
+        self.cull
+            .as_ref()
+            .filter(|_| self.instance_count > CULL_THRESHOLD)
+    }
+
+    pub(crate) fn dispatch_cull(&self, encoder: &mut CommandEncoder) {
+        if let Some(cull) = self.active_cull() {
+            cull.dispatch(encoder);
+        }
+    }
+
     pub(crate) fn set_render_pass(&self, render_pass: &mut RenderPass<'_>) {
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_vertex_buffer(0, self.boundary_buffer_data.vertex_buffer.slice(..));
-        render_pass.set_vertex_buffer(
-            1,
-            self.sinewave_instance_buffer_data.vertex_buffer.slice(..),
-        );
         render_pass.set_index_buffer(
             self.boundary_buffer_data.index_buffer.slice(..),
             IndexFormat::Uint16,
         );
         render_pass.set_bind_group(0, &self.global_bind_group_data.bind_group, &[]);
-        render_pass.draw_indexed(0..6, 0, 0..self.sine.wave_data.0.len() as u32);
+        render_pass.set_bind_group(1, &self.stroke_textures.bind_group, &[]);
+        render_pass.set_bind_group(2, &self.scope_bind_group_data.bind_group, &[]);
+        render_pass.set_bind_group(3, &self.modulation.bind_group, &[]);
+
+        match self.active_cull() {
+            Some(cull) => {
+                render_pass.set_vertex_buffer(1, cull.visible_buffer().slice(..));
+                render_pass.draw_indexed_indirect(cull.indirect_buffer(), 0);
+            }
+            None => {
+                render_pass.set_vertex_buffer(
+                    1,
+                    self.sinewave_instance_buffer_data.vertex_buffer.slice(..),
+                );
+                render_pass.draw_indexed(0..6, 0, 0..self.instance_count);
+            }
+        }
     }
 
-    pub(crate) fn update_global_frame(&mut self, queue: &Queue) {
-        self.global.increment_frame();
-        queue.write_buffer(
+    pub(crate) fn set_wave_texture(&self, index: usize, image: &RgbaImage, queue: &Queue) {
+        self.stroke_textures.write_layer(index as u32, image, queue);
+    }
+
+    pub(crate) fn wave_texture_view(&self, index: usize) -> TextureView {
+        self.stroke_textures.layer_view(index as u32)
+    }
+
+    pub(crate) fn copy_wave_textures_from(&self, other: &Self, encoder: &mut CommandEncoder) {
+        self.stroke_textures
+            .copy_from(&other.stroke_textures, encoder);
+    }
+
+    pub(crate) fn wave_buffer(&self) -> &Buffer {
+        &self.sinewave_instance_buffer_data.vertex_buffer
+    }
+
+    pub(crate) fn update_global_frame(
+        &mut self,
+        stretch: bool,
+        antialias: &UiAntialias,
+        false_color: [f32; 4],
+        camera: &Camera,
+        upload: &mut Upload,
+    ) {
+        self.global.set_stretch(stretch);
+        self.global.set_antialias(antialias.effective_width());
+        self.global.set_hairline(antialias.hairline);
+        self.global.set_false_color(false_color);
+        self.global.set_camera(camera);
+        self.global.advance_frame(self.time_step);
+        if let Some(cull) = &self.cull {
+            cull.update(self.instance_count, self.global.aspect(), camera, upload);
+        }
+        upload.write(
             &self.global_bind_group_data.buffer,
             0,
             bytemuck::bytes_of(&self.global),
         );
     }
 
-    pub(crate) fn update_sine_wave_data<'a>(
-        &'a mut self,
+    pub(crate) fn set_time_step(&mut self, step: f32) {
+        self.time_step = step;
+    }
+
+    pub(crate) fn time_step(&self) -> f32 {
+        self.time_step
+    }
+
+    pub(crate) fn set_phase(&mut self, phase: f32) {
+        self.global.set_phase(phase);
+    }
+
+    pub(crate) fn phase(&self) -> f32 {
+        self.global.phase()
+    }
+
+    pub(crate) fn update_scope(&self, samples: &[f32], upload: &mut Upload) {
+        upload.write(
+            &self.scope_bind_group_data.buffer,
+            0,
+            bytemuck::cast_slice(samples),
+        );
+    }
+
+    pub(crate) fn update_spectrum(&self, bins: &[f32], upload: &mut Upload) {
+        upload.write(
+            &self.scope_bind_group_data.buffer,
+            scope::SPECTRUM_OFFSET,
+            bytemuck::cast_slice(bins),
+        );
+    }
+
+    pub(crate) fn copy_spectrum_from(&self, buffer: &Buffer, encoder: &mut CommandEncoder) {
+        encoder.copy_buffer_to_buffer(
+            buffer,
+            0,
+            &self.scope_bind_group_data.buffer,
+            scope::SPECTRUM_OFFSET,
+            (SPECTRUM_BINS * std::mem::size_of::<f32>()) as u64,
+        );
+    }
+
+    pub(crate) fn set_modulation_texture(&mut self, view: &TextureView, device: &Device) {
+        self.modulation.set_texture(view, device);
+    }
+
+    pub(crate) fn update_modulation(&self, settings: &UiReactionDiffusion, upload: &mut Upload) {
+        self.modulation.update(settings, upload);
+    }
+
+    pub(crate) fn update_sine_wave_data(
+        &mut self,
         sine_wave_data: &[UiSineWaveData],
-        queue: &Queue,
+        transients: &[TransientInstance],
+        collision: &UiCollision,
+        drives: &[WaveDrive],
+        selected: Option<usize>,
+        upload: &mut Upload,
     ) {
-        self.sine
-            .wave_data
-            .0
-            .iter_mut()
-            .zip(sine_wave_data)
-            .for_each(|(old_data, new_data)| {
-                old_data.center = new_data.center;
-                old_data.amplitude = new_data.amplitude;
-                old_data.inner_radius = new_data.inner_radius;
-                old_data.thickness = new_data.thickness;
-                old_data.cycles = new_data.cycles;
-                old_data.speed = new_data.speed;
-                old_data.center = new_data.center;
-                old_data.init = new_data.init as u32;
+        let highlights = if collision.enabled {
+            collision::detect(sine_wave_data, self.global.aspect())
+        } else {
+            Vec::new()
+        };
+        self.global
+            .set_highlight(collision.color, collision.strength);
+
+        // The frame counter advances when the global uniform is uploaded right after this.
+        let time = self.global.phase() + self.time_step;
+        self.jitter.advance(sine_wave_data, self.time_step);
+
+        let aspect = self.global.aspect();
+        let staged = &mut self.staged;
+        staged.clear();
+
+        for (layer, new_data) in sine_wave_data.iter().take(MAX_WAVES).enumerate() {
+            let highlight = highlights.get(layer).copied().unwrap_or_default();
+            let drive = drives.get(layer).copied().unwrap_or_default();
+            let outline = if selected == Some(layer) {
+                SELECTION_OUTLINE
+            } else {
+                new_data.outline
+            };
+            let eased_offset = -new_data.cycles
+                * (new_data.speed * (new_data.easing.warp(time) - time)
+                    + self.jitter.drift(layer)
+                    + new_data.rotation);
+            // Locked params hold their value, so the audio drive leaves them alone too.
+            let driven = |param, drive: f32| {
+                if new_data.locks.contains(param) {
+                    1.
+                } else {
+                    1. + drive
+                }
+            };
+            let wave = SineWaveData {
+                thickness: new_data.thickness * driven(MacroParam::Thickness, drive.thickness),
+                amplitude: new_data.amplitude * driven(MacroParam::Amplitude, drive.amplitude),
+                highlight_angles: highlight.angles,
+                highlight_widths: highlight.widths,
+                ..SineWaveData::new(new_data, outline, layer, eased_offset)
+            };
+
+            wave.instances(new_data, aspect, |instance| {
+                if staged.len() < MAX_RING_INSTANCES {
+                    staged.push(instance);
+                }
             });
+        }
 
-        queue.write_buffer(
-            &self.sinewave_instance_buffer_data.vertex_buffer,
-            0,
-            bytemuck::cast_slice(&self.sine.wave_data.0),
-        );
+        for transient in transients.iter().take(MAX_TRANSIENTS) {
+            staged.push(SineWaveData {
+                center: transient.center,
+                inner_radius: transient.inner_radius,
+                thickness: transient.thickness,
+                amplitude: transient.amplitude,
+                cycles: transient.cycles,
+                speed: transient.speed,
+                init: 1,
+                flags: 0,
+                opacity: transient.opacity,
+                ..Default::default()
+            });
+        }
+
+        // The shader advances each ring's phase from `speed` and the global time, so steady rings
+        // come out identical frame to frame. Eased or jittered rings fold their offset in above and
+        // change every frame, as do audio-driven ones; the upload covers one span from the first to
+        // the last changed instance, so a single moving ring can pull steady neighbours along.
+        let instances = &mut self.sine.wave_data.0;
+        let mut dirty: Option<(usize, usize)> = None;
+        for (i, instance) in instances.iter_mut().enumerate() {
+            let next = staged.get(i).copied().unwrap_or(SineWaveData {
+                init: 0,
+                ..*instance
+            });
+            if bytemuck::bytes_of(instance) != bytemuck::bytes_of(&next) {
+                *instance = next;
+                dirty = Some(dirty.map_or((i, i), |(first, _)| (first, i)));
+            }
+        }
+
+        let count = staged.len();
+        self.instance_count = count as u32;
+        self.stats = DrawStats::rings(&instances[..count], aspect);
+
+        if let Some((first, last)) = dirty {
+            upload.write(
+                &self.sinewave_instance_buffer_data.vertex_buffer,
+                (first * std::mem::size_of::<SineWaveData>()) as u64,
+                bytemuck::cast_slice(&instances[first..=last]),
+            );
+        }
+    }
+
+    pub(crate) fn draw_stats(&self) -> DrawStats {
+        self.stats
     }
 
     pub(crate) fn update_global_resolution(
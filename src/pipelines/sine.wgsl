@@ -1,23 +1,66 @@
+#include "global.wgsl"
+#include "color.wgsl"
+#include "noise.wgsl"
+#include "wave.wgsl"
+
 struct VertexInput {
     @location(0) position: vec2<f32>,
     @location(1) center: vec2<f32>,
-    @location(2) inner_radius: f32,
-    @location(3) thickness: f32,
-    @location(4) amplitude: f32,
-    @location(5) cycles: f32,
-    @location(6) speed: f32,
-    @location(7) init: u32
+    @location(2) ring: vec4<f32>,
+    @location(3) outline_color: vec3<f32>,
+    @location(4) outline: vec2<f32>,
+    @location(5) speed: f32,
+    @location(6) init: u32,
+    @location(7) texture: vec2<f32>,
+    @location(8) fractal: vec4<f32>,
+    @location(9) flags: u32,
+    @location(10) tone: vec4<f32>,
+    @location(11) shadow_color: vec3<f32>,
+    @location(12) layer: u32,
+    @location(13) highlight_angles: vec4<f32>,
+    @location(14) highlight_widths: vec4<f32>,
+    @location(15) shadow: vec4<f32>
 }
 
+// Downlevel devices allow 31 inter-stage components, so colors, outline and highlight widths
+// travel as half floats and the init bit and texture layer ride along in the flags.
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(1) center: vec2<f32>,
-    @location(2) inner_radius: f32,
-    @location(3) thickness: f32,
-    @location(4) amplitude: f32,
-    @location(5) cycles: f32,
-    @location(6) speed: f32,
-    @location(7) init: u32
+    @location(2) ring: vec4<f32>,
+    @location(3) outline_color: vec2<u32>,
+    @location(4) outline: u32,
+    @location(5) speed: f32,
+    @location(7) texture: vec2<f32>,
+    @location(8) fractal: vec4<f32>,
+    @location(9) flags: u32,
+    @location(10) tone: vec2<f32>,
+    @location(11) shadow_color: vec2<u32>,
+    @location(13) highlight_angles: vec4<f32>,
+    @location(14) highlight_widths: vec2<u32>,
+    @location(15) shadow: vec4<f32>
+}
+
+const FLAG_INIT: u32 = 0x80000000u;
+const LAYER_SHIFT: u32 = 16u;
+
+fn pack_color(color: vec3<f32>) -> vec2<u32> {
+    return vec2<u32>(pack2x16float(color.rg), pack2x16float(vec2<f32>(color.b, 0.0)));
+}
+
+fn unpack_color(packed: vec2<u32>) -> vec3<f32> {
+    return vec3<f32>(unpack2x16float(packed.x), unpack2x16float(packed.y).x);
+}
+
+fn wave_layer(flags: u32) -> u32 {
+    return (flags >> LAYER_SHIFT) & 0xffu;
+}
+
+@group(0) @binding(0)
+var<uniform> global: Global;
+
+fn to_scene(screen_uv: vec2<f32>) -> vec2<f32> {
+    return 0.5 + global.pan + (screen_uv - 0.5) / global.zoom;
 }
 
 @vertex
@@ -26,64 +69,332 @@ fn vs_main(
 ) -> VertexOutput {
     var output: VertexOutput;
 
-    let clip_center = input.center * 2.0 - vec2<f32>(1.0, 1.0);
-    output.position = vec4<f32>(input.position + clip_center, 0.0, 1.0);
+    let screen_center = 0.5 + (input.center - 0.5 - global.pan) * global.zoom;
+    let clip_center = vec2<f32>(screen_center.x * 2.0 - 1.0, 1.0 - screen_center.y * 2.0);
+
+    let inner_radius = max(input.ring.x + breathing(input.tone.z, input.tone.w, global.phase), 0.0);
+
+    // Small rings only shade their bounding quad; anything that would cover the screen stays full-screen.
+    let thickness = input.ring.y * (1.0 + max(modulation.thickness, 0.0));
+    let pixel = 1.0 / (global.resolution.y * global.zoom);
+    let reach = ring_reach(inner_radius, thickness, input.ring.z, input.flags, input.outline, input.shadow)
+        + 4.0 * pixel * max(global.antialias, 1.0);
+    let extent = 2.0 * reach * global.zoom * vec2<f32>(1.0 / global.aspect, 1.0);
+    if max(extent.x, extent.y) >= 1.0 {
+        output.position = vec4<f32>(input.position, 0.0, 1.0);
+    } else {
+        output.position = vec4<f32>(input.position * extent + clip_center, 0.0, 1.0);
+    }
 
     output.center = input.center;
+    output.ring = vec4<f32>(inner_radius, input.ring.yzw);
+    output.outline_color = pack_color(input.outline_color);
+    output.outline = pack2x16float(input.outline);
     output.speed = input.speed;
-    output.cycles = input.cycles;
-    output.amplitude = input.amplitude;
-    output.inner_radius = input.inner_radius;
-    output.thickness = input.thickness;
-    output.init = input.init;
+    output.texture = input.texture;
+    output.fractal = input.fractal;
+    output.flags = input.flags | (input.layer << LAYER_SHIFT) | select(0u, FLAG_INIT, input.init != 0u);
+    output.tone = input.tone.xy;
+    output.shadow_color = pack_color(input.shadow_color);
+    output.highlight_angles = input.highlight_angles;
+    output.highlight_widths = vec2<u32>(
+        pack2x16float(input.highlight_widths.xy),
+        pack2x16float(input.highlight_widths.zw)
+    );
+    output.shadow = input.shadow;
 
     return output;
 }
 
-struct Global {
-    resolution: vec2<f32>,
-    phase: f32
+@group(1) @binding(0)
+var stroke_textures: texture_2d_array<f32>;
+@group(1) @binding(1)
+var stroke_sampler: sampler;
+
+const MAX_OCTAVES: u32 = 8u;
+const SCOPE_SAMPLES: u32 = 512u;
+const SPECTRUM_BINS: u32 = 512u;
+
+struct Scope {
+    samples: array<vec4<f32>, 128>,
+    spectrum: array<vec4<f32>, 128>
 }
 
-@group(0) @binding(0)
-var<uniform> global: Global;
+@group(2) @binding(0)
+var<uniform> scope: Scope;
+
+struct Modulation {
+    thickness: f32,
+    color: f32,
+    _padding: vec2<f32>
+}
+
+@group(3) @binding(0)
+var<uniform> modulation: Modulation;
+@group(3) @binding(1)
+var modulation_tex: texture_2d<f32>;
+@group(3) @binding(2)
+var modulation_sampler: sampler;
+
+fn scope_at(i: u32) -> f32 {
+    let wrapped = i % SCOPE_SAMPLES;
+    return scope.samples[wrapped / 4u][wrapped % 4u];
+}
+
+fn scope_sample(t: f32) -> f32 {
+    let x = t * f32(SCOPE_SAMPLES);
+    let i = u32(floor(x));
+    return mix(scope_at(i), scope_at(i + 1u), fract(x));
+}
+
+fn spectrum_at(i: u32) -> f32 {
+    let clamped = min(i, SPECTRUM_BINS - 1u);
+    return scope.spectrum[clamped / 4u][clamped % 4u];
+}
+
+fn spectrum_sample(t: f32) -> f32 {
+    let x = t * f32(SPECTRUM_BINS - 1u);
+    let i = u32(floor(x));
+    return mix(spectrum_at(i), spectrum_at(i + 1u), fract(x));
+}
+
+// Sampling the noise on a circle keeps the ring seamless at theta = +-pi.
+fn fbm_ring(angle: f32, radius: f32, seed: f32, fractal: vec4<f32>) -> f32 {
+    let octaves = min(u32(fractal.x), MAX_OCTAVES);
+    let base = vec2<f32>(cos(angle), sin(angle)) * radius;
+
+    var sum = 0.0;
+    var weight = 0.0;
+    var amplitude = 1.0;
+    var frequency = 1.0;
+    for (var octave = 0u; octave < octaves; octave++) {
+        sum += amplitude * value_noise2(base * frequency + vec2<f32>(seed, f32(octave) * 7.31));
+        weight += amplitude;
+        amplitude *= fractal.y;
+        frequency *= fractal.z;
+    }
+
+    return sum / max(weight, 0.0001);
+}
+
+fn highlight_weight(theta: f32, angles: vec4<f32>, widths: vec4<f32>) -> f32 {
+    var weight = 0.0;
+
+    for (var i = 0; i < 4; i++) {
+        if widths[i] <= 0.0 {
+            continue;
+        }
+
+        let delta = abs(atan2(sin(theta - angles[i]), cos(theta - angles[i])));
+        weight = max(weight, 1.0 - smoothstep(0.5 * widths[i], widths[i], delta));
+    }
+
+    return weight;
+}
+
+fn edge_coverage(edge: f32, coord: vec2<f32>) -> f32 {
+    if global.antialias <= 0.0 {
+        return step(0.0, edge);
+    }
+
+    let pixel = 1.0 / (global.resolution.y * global.zoom);
+    let width = clamp(fwidth(edge), 0.25 * pixel, 4.0 * pixel) * global.antialias;
+    let coverage = clamp(edge / width + 0.5, 0.0, 1.0);
+    if coverage <= 0.0 || coverage >= 1.0 {
+        return coverage;
+    }
+
+    return clamp(coverage + (interleaved_gradient_noise(coord) - 0.5) / 255.0, 0.0, 1.0);
+}
+
+fn wave_radius(theta: f32, vertex_output: VertexOutput) -> f32 {
+    let inner_radius = vertex_output.ring.x;
+    let amplitude = vertex_output.ring.z;
+    let cycles = vertex_output.ring.w;
+
+    let phase = cycles * (theta - vertex_output.speed * global.phase)
+        + vertex_output.tone.y;
+    var displacement = sin(phase);
+    if (vertex_output.flags & FLAG_SCOPE) != 0u {
+        displacement = scope_sample(fract(theta / (2.0 * 3.14159) + 0.5));
+    }
+    if (vertex_output.flags & FLAG_SPECTRUM) != 0u {
+        let t = fract(theta / (2.0 * 3.14159) + 0.5);
+        displacement = spectrum_sample(1.0 - abs(2.0 * t - 1.0));
+    }
+    if (vertex_output.flags & FLAG_FRACTAL) != 0u {
+        let octave_cycles = max(cycles, 1.0);
+        displacement = fbm_ring(
+            phase / octave_cycles,
+            0.5 * octave_cycles,
+            f32(wave_layer(vertex_output.flags)) * 17.0,
+            vertex_output.fractal
+        );
+    }
+
+    return inner_radius + amplitude * displacement;
+}
+
+const FALSE_COLOR_THERMAL: u32 = 1u;
+const FALSE_COLOR_CONTOURS: u32 = 2u;
+const FALSE_COLOR_BANDS: u32 = 3u;
+const FALSE_COLOR_FIELD: u32 = 1u;
+
+fn thermal(t: f32) -> vec3<f32> {
+    var stops = array<vec3<f32>, 5>(
+        vec3<f32>(0.0, 0.0, 0.0),
+        vec3<f32>(0.3, 0.0, 0.6),
+        vec3<f32>(0.9, 0.1, 0.2),
+        vec3<f32>(1.0, 0.7, 0.0),
+        vec3<f32>(1.0, 1.0, 1.0)
+    );
+    let x = clamp(t, 0.0, 1.0) * 4.0;
+    let i = min(u32(x), 3u);
+    return srgb_to_linear(mix(stops[i], stops[i + 1u], x - f32(i)));
+}
+
+// `edge` is the band's signed distance and `field` the raw displacement, both visualized around the ring.
+fn false_color(edge: f32, field: f32) -> vec4<f32> {
+    let map = u32(global.false_color.x);
+    let range = max(global.false_color.z, 0.0001);
+    let falloff = 1.0 - smoothstep(0.5 * range, range, -edge);
+
+    var t = clamp(0.5 + 0.5 * edge / range, 0.0, 1.0);
+    if u32(global.false_color.y) == FALSE_COLOR_FIELD {
+        t = clamp(0.5 + 0.5 * field, 0.0, 1.0);
+    }
+
+    if map == FALSE_COLOR_CONTOURS {
+        let pixel = 1.0 / (global.resolution.y * global.zoom);
+        let spacing = 0.125 * range;
+        let offset = abs(fract(edge / spacing + 0.5) - 0.5) * spacing;
+        let line = 1.0 - smoothstep(0.5 * pixel, 1.5 * pixel, offset);
+        let zero = 1.0 - smoothstep(pixel, 2.0 * pixel, abs(edge));
+        return vec4<f32>(mix(thermal(t), vec3<f32>(1.0), zero), max(line, zero) * falloff);
+    }
+
+    if map == FALSE_COLOR_BANDS {
+        let bands = max(global.false_color.w, 2.0);
+        t = min(floor(t * bands), bands - 1.0) / (bands - 1.0);
+    }
+
+    return vec4<f32>(thermal(t), falloff);
+}
 
 @fragment
 fn fs_main(
     vertex_output: VertexOutput
 ) -> @location(0) vec4<f32> {
-    if vertex_output.init == 0u {
-        return vec4<f32>(0.0, 0.0, 0.0, 0.0);
-    }
-
     let frag_coord = vertex_output.position;
 
     let uv = frag_coord.xy / global.resolution;
 
-    let centered = uv - vertex_output.center;
-    let aspect = global.resolution.x / global.resolution.y;
-    let pos = vec2<f32>(centered.x * aspect, centered.y);
+    let centered = to_scene(uv) - vertex_output.center;
+    let mirror = mirror_sign(vertex_output.flags);
+    let scene_pos = vec2<f32>(centered.x * global.aspect, centered.y);
+    let pos = scene_pos * mirror;
 
     let theta = atan2(pos.y, pos.x);
+    let inner_wave = wave_radius(theta, vertex_output);
 
-    let phase = vertex_output.cycles * (theta - vertex_output.speed * global.phase);
-    let inner_wave = vertex_output.inner_radius + vertex_output.amplitude * sin(phase);
+    let pattern = textureSampleLevel(modulation_tex, modulation_sampler, uv, 0.0).g;
+    let thickness = vertex_output.ring.y * (1.0 + modulation.thickness * pattern);
 
+    // Hairline mode widens sub-pixel strokes to one device pixel and fades them by the same ratio.
+    let pixel = 1.0 / (global.resolution.y * global.zoom);
+    var drawn = thickness;
+    var hairline_alpha = 1.0;
+    if global.hairline > 0.0 && thickness < pixel {
+        drawn = pixel;
+        hairline_alpha = thickness / pixel;
+    }
 
     let dist = length(pos);
+    let edge = ring_edge(dist, inner_wave - 0.5 * (drawn - thickness), drawn);
+    let coverage = edge_coverage(edge, frag_coord.xy) * hairline_alpha;
+
+    // Coverage is computed for every fragment because fwidth needs uniform control flow.
+    let outline = unpack2x16float(vertex_output.outline);
+    let outline_inner = inner_wave + thickness + outline.y;
+    let outline_edge = ring_edge(dist, outline_inner, outline.x);
+    var outline_coverage = 0.0;
+    if (vertex_output.flags & FLAG_OUTLINE) != 0u {
+        outline_coverage = 1.0;
+    }
+    outline_coverage *= edge_coverage(outline_edge, frag_coord.xy);
+
+    // The shadow offset is applied before mirroring so every twin casts it the same way.
+    var shadow_coverage = 0.0;
+    if (vertex_output.flags & FLAG_SHADOW) != 0u {
+        let shadow_pos = (scene_pos - vertex_output.shadow.xy) * mirror;
+        let shadow_inner = wave_radius(atan2(shadow_pos.y, shadow_pos.x), vertex_output);
+        let shadow_dist = length(shadow_pos);
+        let shadow_edge = ring_edge(shadow_dist, shadow_inner, thickness);
+        let blur = max(vertex_output.shadow.z, 0.0001);
+        shadow_coverage = vertex_output.shadow.w * smoothstep(-blur, blur, shadow_edge);
+    }
+
+    if u32(global.false_color.x) != 0u {
+        if (vertex_output.flags & FLAG_INIT) == 0u {
+            return vec4<f32>(0.0, 0.0, 0.0, 0.0);
+        }
+
+        let amplitude = vertex_output.ring.z;
+        let field = (inner_wave - vertex_output.ring.x) / select(amplitude, 1.0, abs(amplitude) < 0.0001);
+        return false_color(edge, field);
+    }
+
+    if (vertex_output.flags & FLAG_INIT) == 0u || max(max(coverage, outline_coverage), shadow_coverage) <= 0.0 {
+        return vec4<f32>(0.0, 0.0, 0.0, 0.0);
+    }
 
     let hue = fract((theta / (2.0 * 3.14159)) + 0.5);
 
-    let color = vec3<f32>(
-        0.5 + 0.5 * sin(6.2831 * hue + 0.0),
-        0.5 + 0.5 * sin(6.2831 * hue + 2.094),
-        0.5 + 0.5 * sin(6.2831 * hue + 4.188)
+    let base_color = oklch_to_linear(vec3<f32>(0.75, 0.13, 6.2831 * hue));
+    let color = mix(base_color, base_color * (0.25 + 1.5 * pattern), modulation.color);
+
+    let highlight = global.highlight.a * highlight_weight(
+        theta,
+        vertex_output.highlight_angles,
+        vec4<f32>(
+            unpack2x16float(vertex_output.highlight_widths.x),
+            unpack2x16float(vertex_output.highlight_widths.y)
+        )
     );
 
-    if dist >= inner_wave && dist < inner_wave + vertex_output.thickness {
-        return vec4<f32>(color, 1.0);
-    } else {
-        return vec4<f32>(0.0, 0.0, 0.0, 0.0);
+    var stroke = vec4<f32>(mix(color, global.highlight.rgb, highlight), vertex_output.tone.x * coverage);
+    if (vertex_output.flags & FLAG_TEXTURED) != 0u {
+        // In arc-length mode texture.x is a tile length in pixels; rounding to whole tiles keeps the seam hidden.
+        var tiles = vertex_output.texture.x;
+        if (vertex_output.flags & FLAG_ARC_LENGTH) != 0u {
+            let radius = (vertex_output.ring.x + 0.5 * thickness) * global.resolution.y * global.zoom;
+            tiles = max(1.0, round(2.0 * 3.14159 * radius / max(vertex_output.texture.x, 1.0)));
+        }
+
+        let stroke_uv = vec2<f32>(
+            (theta / (2.0 * 3.14159) + 0.5) * tiles
+                + vertex_output.texture.y * global.phase,
+            clamp((dist - inner_wave) / thickness, 0.0, 1.0)
+        );
+
+        let sampled = textureSampleLevel(
+            stroke_textures,
+            stroke_sampler,
+            stroke_uv,
+            wave_layer(vertex_output.flags),
+            0.0
+        );
+
+        stroke = vec4<f32>(mix(sampled.rgb, global.highlight.rgb, highlight), sampled.a * vertex_output.tone.x * coverage);
     }
-}
 
+    let outline_alpha = vertex_output.tone.x * outline_coverage * (1.0 - stroke.a);
+    let ring_alpha = stroke.a + outline_alpha;
+    let shadow_alpha = vertex_output.tone.x * shadow_coverage * (1.0 - ring_alpha);
+    let alpha = ring_alpha + shadow_alpha;
+    let rgb = (stroke.rgb * stroke.a
+        + unpack_color(vertex_output.outline_color) * outline_alpha
+        + unpack_color(vertex_output.shadow_color) * shadow_alpha) / max(alpha, 0.0001);
+
+    return vec4<f32>(rgb, alpha);
+}
@@ -0,0 +1,333 @@
+use std::{
+    num::NonZero,
+    sync::{
+        Arc,
+        atomic::{AtomicU8, Ordering},
+    },
+};
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingType, Buffer, BufferBindingType, BufferDescriptor, BufferUsages,
+    CommandEncoder, ComputePassDescriptor, ComputePipeline, ComputePipelineDescriptor, Device,
+    MapMode, PipelineCompilationOptions, PipelineLayoutDescriptor, PollType, ShaderStages,
+    util::{BufferInitDescriptor, DeviceExt},
+};
+
+use crate::{
+    audio::{AudioFrame, analysis::SPECTRUM_BINS},
+    pipelines::shader,
+    upload::Upload,
+};
+
+pub(crate) const GPU_FFT_SIZE: usize = 16384;
+const OUTPUT_LEN: usize = SPECTRUM_BINS + 4;
+const WORKGROUP_SIZE: u32 = 64;
+
+const MAP_PENDING: u8 = 0;
+const MAP_READY: u8 = 1;
+const MAP_FAILED: u8 = 2;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct SpectrumParams {
+    size: u32,
+    bins: u32,
+    sample_rate: f32,
+    _padding: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct StageParams {
+    span: u32,
+    _padding: [u32; 3],
+}
+
+enum ReadbackState {
+    Idle,
+    Recorded,
+    Mapping(Arc<AtomicU8>),
+}
+
+pub(crate) struct SpectrumPipeline {
+    window_pipeline: ComputePipeline,
+    butterfly_pipeline: ComputePipeline,
+    bins_pipeline: ComputePipeline,
+    bands_pipeline: ComputePipeline,
+    params_buffer: Buffer,
+    samples_buffer: Buffer,
+    output_buffer: Buffer,
+    readback_buffer: Buffer,
+    bind_group: BindGroup,
+    stage_bind_groups: Vec<BindGroup>,
+    readback: ReadbackState,
+    samples: Vec<f32>,
+}
+
+impl SpectrumPipeline {
+    fn storage_entry(binding: u32, read_only: bool) -> BindGroupLayoutEntry {
+        BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }
+    }
+
+    fn uniform_entry(size: usize) -> BindGroupLayoutEntry {
+        BindGroupLayoutEntry {
+            binding: 0,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: NonZero::new(size as u64),
+            },
+            count: None,
+        }
+    }
+
+    pub(crate) fn new(device: &Device) -> Self {
+        let params_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Spectrum Params Buffer"),
+            contents: bytemuck::bytes_of(&SpectrumParams::zeroed()),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let samples_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Spectrum Samples Buffer"),
+            size: (GPU_FFT_SIZE * std::mem::size_of::<f32>()) as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let spectrum_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Spectrum Buffer"),
+            size: (GPU_FFT_SIZE * std::mem::size_of::<[f32; 2]>()) as u64,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        let output_size = (OUTPUT_LEN * std::mem::size_of::<f32>()) as u64;
+        let output_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Spectrum Output Buffer"),
+            size: output_size,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let readback_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Spectrum Readback Buffer"),
+            size: output_size,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Spectrum Layout"),
+            entries: &[
+                Self::uniform_entry(std::mem::size_of::<SpectrumParams>()),
+                Self::storage_entry(1, true),
+                Self::storage_entry(2, false),
+                Self::storage_entry(3, false),
+            ],
+        });
+
+        let stage_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Spectrum Stage Layout"),
+            entries: &[Self::uniform_entry(std::mem::size_of::<StageParams>())],
+        });
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Spectrum Bind Group"),
+            layout: &layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: samples_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: spectrum_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: output_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let stage_bind_groups = (1..=GPU_FFT_SIZE.trailing_zeros())
+            .map(|stage| {
+                let buffer = device.create_buffer_init(&BufferInitDescriptor {
+                    label: Some("Spectrum Stage Buffer"),
+                    contents: bytemuck::bytes_of(&StageParams {
+                        span: 1 << stage,
+                        _padding: [0; 3],
+                    }),
+                    usage: BufferUsages::UNIFORM,
+                });
+
+                device.create_bind_group(&BindGroupDescriptor {
+                    label: Some("Spectrum Stage Bind Group"),
+                    layout: &stage_layout,
+                    entries: &[BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    }],
+                })
+            })
+            .collect();
+
+        let shader_module =
+            shader::create_module("spectrum.wgsl", include_str!("spectrum.wgsl"), device);
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Spectrum Pipeline Layout"),
+            bind_group_layouts: &[&layout, &stage_layout],
+            ..Default::default()
+        });
+
+        let create_pipeline = |label, entry_point| {
+            device.create_compute_pipeline(&ComputePipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                module: &shader_module,
+                entry_point: Some(entry_point),
+                compilation_options: PipelineCompilationOptions::default(),
+                cache: None,
+            })
+        };
+
+        Self {
+            window_pipeline: create_pipeline("Spectrum Window Pipeline", "window_main"),
+            butterfly_pipeline: create_pipeline("Spectrum Butterfly Pipeline", "butterfly_main"),
+            bins_pipeline: create_pipeline("Spectrum Bins Pipeline", "bins_main"),
+            bands_pipeline: create_pipeline("Spectrum Bands Pipeline", "bands_main"),
+            params_buffer,
+            samples_buffer,
+            output_buffer,
+            readback_buffer,
+            bind_group,
+            stage_bind_groups,
+            readback: ReadbackState::Idle,
+            samples: vec![0.; GPU_FFT_SIZE],
+        }
+    }
+
+    pub(crate) fn update(&mut self, frame: &AudioFrame, upload: &mut Upload) {
+        let recent = &frame.samples[frame.samples.len().saturating_sub(GPU_FFT_SIZE)..];
+        let offset = GPU_FFT_SIZE - recent.len();
+        self.samples[..offset].fill(0.);
+        self.samples[offset..].copy_from_slice(recent);
+
+        let params = SpectrumParams {
+            size: GPU_FFT_SIZE as u32,
+            bins: SPECTRUM_BINS as u32,
+            sample_rate: frame.sample_rate as f32,
+            _padding: 0,
+        };
+        upload.write(&self.params_buffer, 0, bytemuck::bytes_of(&params));
+        upload.write(&self.samples_buffer, 0, bytemuck::cast_slice(&self.samples));
+    }
+
+    pub(crate) fn dispatch(&mut self, encoder: &mut CommandEncoder) {
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+                label: Some("Spectrum Pass"),
+                timestamp_writes: None,
+            });
+
+            let size = GPU_FFT_SIZE as u32;
+            compute_pass.set_bind_group(0, &self.bind_group, &[]);
+            compute_pass.set_bind_group(1, &self.stage_bind_groups[0], &[]);
+
+            compute_pass.set_pipeline(&self.window_pipeline);
+            compute_pass.dispatch_workgroups(size.div_ceil(WORKGROUP_SIZE), 1, 1);
+
+            compute_pass.set_pipeline(&self.butterfly_pipeline);
+            for stage_bind_group in &self.stage_bind_groups {
+                compute_pass.set_bind_group(1, stage_bind_group, &[]);
+                compute_pass.dispatch_workgroups((size / 2).div_ceil(WORKGROUP_SIZE), 1, 1);
+            }
+
+            compute_pass.set_pipeline(&self.bins_pipeline);
+            compute_pass.dispatch_workgroups((SPECTRUM_BINS as u32).div_ceil(WORKGROUP_SIZE), 1, 1);
+
+            compute_pass.set_pipeline(&self.bands_pipeline);
+            compute_pass.dispatch_workgroups(1, 1, 1);
+        }
+
+        if matches!(self.readback, ReadbackState::Idle) {
+            encoder.copy_buffer_to_buffer(
+                &self.output_buffer,
+                0,
+                &self.readback_buffer,
+                0,
+                self.readback_buffer.size(),
+            );
+            self.readback = ReadbackState::Recorded;
+        }
+    }
+
+    pub(crate) fn submitted(&mut self) {
+        if !matches!(self.readback, ReadbackState::Recorded) {
+            return;
+        }
+
+        let map_state = Arc::new(AtomicU8::new(MAP_PENDING));
+        let callback_state = map_state.clone();
+        self.readback_buffer
+            .slice(..)
+            .map_async(MapMode::Read, move |result| {
+                let state = if result.is_ok() {
+                    MAP_READY
+                } else {
+                    MAP_FAILED
+                };
+                callback_state.store(state, Ordering::Release);
+            });
+
+        self.readback = ReadbackState::Mapping(map_state);
+    }
+
+    pub(crate) fn receive(&mut self, device: &Device) -> Option<(Vec<f32>, [f32; 4])> {
+        let ReadbackState::Mapping(map_state) = &self.readback else {
+            return None;
+        };
+
+        let _ = device.poll(PollType::Poll);
+        match map_state.load(Ordering::Acquire) {
+            MAP_PENDING => return None,
+            MAP_FAILED => {
+                self.readback = ReadbackState::Idle;
+                return None;
+            }
+            _ => {}
+        }
+
+        let output = {
+            let mapped = self.readback_buffer.slice(..).get_mapped_range();
+            bytemuck::cast_slice::<u8, f32>(&mapped).to_vec()
+        };
+        self.readback_buffer.unmap();
+        self.readback = ReadbackState::Idle;
+
+        let (bins, bands) = output.split_at(SPECTRUM_BINS);
+        Some((bins.to_vec(), [bands[0], bands[1], bands[2], bands[3]]))
+    }
+
+    pub(crate) fn output_buffer(&self) -> &Buffer {
+        &self.output_buffer
+    }
+}
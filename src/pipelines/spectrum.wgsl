@@ -0,0 +1,135 @@
+#include "math.wgsl"
+
+struct Params {
+    size: u32,
+    bins: u32,
+    sample_rate: f32,
+    _padding: u32
+}
+
+struct Stage {
+    span: u32
+}
+
+@group(0) @binding(0)
+var<uniform> params: Params;
+@group(0) @binding(1)
+var<storage, read> samples: array<f32>;
+@group(0) @binding(2)
+var<storage, read_write> spectrum: array<vec2<f32>>;
+@group(0) @binding(3)
+var<storage, read_write> output: array<f32>;
+
+@group(1) @binding(0)
+var<uniform> stage: Stage;
+
+const SPECTRUM_LOW: f32 = 20.0;
+const SPECTRUM_RANGE: f32 = 1000.0;
+const BAND_THREADS: u32 = 256u;
+
+var<workgroup> partial: array<vec4<f32>, BAND_THREADS>;
+
+@compute @workgroup_size(64)
+fn window_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= params.size {
+        return;
+    }
+
+    let window = 0.5 - 0.5 * cos(2.0 * PI * f32(i) / f32(params.size - 1u));
+    let bits = countTrailingZeros(params.size);
+    let j = reverseBits(i) >> (32u - bits);
+
+    spectrum[j] = vec2<f32>(samples[i] * window, 0.0);
+}
+
+@compute @workgroup_size(64)
+fn butterfly_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let t = id.x;
+    if t >= params.size / 2u {
+        return;
+    }
+
+    let half = stage.span / 2u;
+    let k = t % half;
+    let even = (t / half) * stage.span + k;
+    let odd = even + half;
+
+    let angle = -2.0 * PI * f32(k) / f32(stage.span);
+    let twiddle = vec2<f32>(cos(angle), sin(angle));
+    let o = spectrum[odd];
+    let rotated = vec2<f32>(o.x * twiddle.x - o.y * twiddle.y, o.x * twiddle.y + o.y * twiddle.x);
+    let e = spectrum[even];
+
+    spectrum[even] = e + rotated;
+    spectrum[odd] = e - rotated;
+}
+
+fn magnitude(bin: u32) -> f32 {
+    return length(spectrum[bin]) * 4.0 / f32(params.size);
+}
+
+fn frequency(i: u32) -> f32 {
+    return SPECTRUM_LOW * pow(SPECTRUM_RANGE, f32(i) / f32(params.bins));
+}
+
+@compute @workgroup_size(64)
+fn bins_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= params.bins {
+        return;
+    }
+
+    let len = params.size / 2u;
+    let bin_width = params.sample_rate / f32(params.size);
+    let low = clamp(u32(frequency(i) / bin_width), 1u, len - 1u);
+    let high = clamp(u32(frequency(i + 1u) / bin_width), low + 1u, len);
+
+    var peak = 0.0;
+    for (var bin = low; bin < high; bin++) {
+        peak = max(peak, magnitude(bin));
+    }
+
+    output[i] = sqrt(min(peak, 1.0));
+}
+
+@compute @workgroup_size(256)
+fn bands_main(@builtin(local_invocation_index) index: u32) {
+    let bin_width = params.sample_rate / f32(params.size);
+
+    var sums = vec4<f32>(0.0);
+    for (var bin = max(index, 1u); bin < params.size / 2u; bin += BAND_THREADS) {
+        let frequency = f32(bin) * bin_width;
+        let m = magnitude(bin);
+        let power = m * m;
+
+        if frequency >= 20.0 && frequency < 250.0 {
+            sums.y += power;
+        } else if frequency >= 250.0 && frequency < 4000.0 {
+            sums.z += power;
+        } else if frequency >= 4000.0 && frequency < 16000.0 {
+            sums.w += power;
+        }
+    }
+    for (var i = index; i < params.size; i += BAND_THREADS) {
+        sums.x += samples[i] * samples[i];
+    }
+
+    partial[index] = sums;
+    workgroupBarrier();
+
+    for (var stride = BAND_THREADS / 2u; stride > 0u; stride /= 2u) {
+        if index < stride {
+            partial[index] += partial[index + stride];
+        }
+        workgroupBarrier();
+    }
+
+    if index == 0u {
+        let total = partial[0];
+        output[params.bins] = sqrt(total.x / f32(params.size));
+        output[params.bins + 1u] = sqrt(total.y);
+        output[params.bins + 2u] = sqrt(total.z);
+        output[params.bins + 3u] = sqrt(total.w);
+    }
+}
@@ -0,0 +1,224 @@
+use std::num::NonZero;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingResource, BindingType, BlendState, Buffer, BufferBindingType,
+    BufferUsages, Color, ColorTargetState, ColorWrites, CommandEncoder, Device, Extent3d,
+    FragmentState, LoadOp, MultisampleState, Operations, PipelineCompilationOptions,
+    PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPassColorAttachment,
+    RenderPassDescriptor, RenderPipeline, RenderPipelineDescriptor, ShaderStages, StoreOp,
+    TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+    TextureView, TextureViewDimension, VertexState,
+    util::{BufferInitDescriptor, DeviceExt},
+};
+
+use crate::pipelines::shader;
+
+// egui draws into this layer, the format its renderer is built for under HDR output.
+pub(crate) const LAYER_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct UiLayerSettings {
+    white: f32,
+    _padding: [f32; 3],
+}
+
+// Under scRGB output egui would write sRGB-encoded values straight into a linear surface, so the
+// UI is drawn into an 8-bit layer and composited with the same SDR white the post pass uses.
+pub(crate) struct UiLayerPipeline {
+    pipeline: RenderPipeline,
+    layout: BindGroupLayout,
+    bind_group: BindGroup,
+    settings_buffer: Buffer,
+    view: TextureView,
+}
+
+impl UiLayerPipeline {
+    pub(crate) fn new(
+        width: u32,
+        height: u32,
+        white: f32,
+        target_format: TextureFormat,
+        device: &Device,
+    ) -> Self {
+        let settings_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("UI Layer Settings Buffer"),
+            contents: bytemuck::bytes_of(&UiLayerSettings {
+                white,
+                _padding: [0.; 3],
+            }),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("UI Layer Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: NonZero::new(
+                            std::mem::size_of::<UiLayerSettings>() as u64
+                        ),
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("UI Layer Pipeline Layout"),
+            bind_group_layouts: &[&layout],
+            ..Default::default()
+        });
+
+        let shader_module =
+            shader::create_module("ui_layer.wgsl", include_str!("ui_layer.wgsl"), device);
+
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("UI Layer Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: VertexState {
+                module: &shader_module,
+                entry_point: Some("vs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(FragmentState {
+                module: &shader_module,
+                entry_point: Some("fs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                targets: &[Some(ColorTargetState {
+                    format: target_format,
+                    blend: Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multiview: None,
+            cache: None,
+            multisample: MultisampleState::default(),
+        });
+
+        let (view, bind_group) =
+            Self::create_layer(width, height, &layout, &settings_buffer, device);
+
+        Self {
+            pipeline,
+            layout,
+            bind_group,
+            settings_buffer,
+            view,
+        }
+    }
+
+    fn create_layer(
+        width: u32,
+        height: u32,
+        layout: &BindGroupLayout,
+        settings_buffer: &Buffer,
+        device: &Device,
+    ) -> (TextureView, BindGroup) {
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("UI Layer Texture"),
+            size: Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: LAYER_FORMAT,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&Default::default());
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("UI Layer Bind Group"),
+            layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: settings_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        (view, bind_group)
+    }
+
+    pub(crate) fn resize(&mut self, width: u32, height: u32, device: &Device) {
+        (self.view, self.bind_group) =
+            Self::create_layer(width, height, &self.layout, &self.settings_buffer, device);
+    }
+
+    pub(crate) fn update(&self, white: f32, queue: &Queue) {
+        queue.write_buffer(
+            &self.settings_buffer,
+            0,
+            bytemuck::bytes_of(&UiLayerSettings {
+                white,
+                _padding: [0.; 3],
+            }),
+        );
+    }
+
+    // Clears the layer and hands it to egui for this frame.
+    pub(crate) fn begin(&self, encoder: &mut CommandEncoder) -> &TextureView {
+        encoder.begin_render_pass(&RenderPassDescriptor {
+            label: Some("UI Layer Clear Pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &self.view,
+                resolve_target: None,
+                depth_slice: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::TRANSPARENT),
+                    store: StoreOp::Store,
+                },
+            })],
+            ..Default::default()
+        });
+
+        &self.view
+    }
+
+    pub(crate) fn composite(&self, target: &TextureView, encoder: &mut CommandEncoder) {
+        let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            label: Some("UI Layer Composite Pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                depth_slice: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                },
+            })],
+            ..Default::default()
+        });
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
@@ -0,0 +1,32 @@
+#include "color.wgsl"
+
+// scRGB puts 1.0 at 80 nits.
+const SCRGB_WHITE_NITS: f32 = 80.0;
+
+struct Settings {
+    white: f32
+}
+
+@group(0) @binding(0)
+var ui_tex: texture_2d<f32>;
+@group(0) @binding(1)
+var<uniform> settings: Settings;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    return vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    // egui blends premultiplied sRGB values, so un-premultiply before decoding.
+    let texel = textureLoad(ui_tex, vec2<i32>(position.xy), 0);
+    if texel.a <= 0.0 {
+        discard;
+    }
+
+    let linear = srgb_to_linear(texel.rgb / texel.a);
+    let scrgb = linear * settings.white / SCRGB_WHITE_NITS;
+    return vec4<f32>(scrgb * texel.a, texel.a);
+}
@@ -0,0 +1,269 @@
+use std::{fs, path::Path, time::Instant};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config,
+    hot_reload::Morph,
+    pipelines::sine::MAX_WAVES,
+    random,
+    ui::{Ui, UiSineWaveData},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum PlaylistTransition {
+    Cut,
+    #[default]
+    Crossfade,
+    Morph,
+}
+
+impl PlaylistTransition {
+    pub(crate) const ALL: [Self; 3] = [Self::Cut, Self::Crossfade, Self::Morph];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Cut => "Cut",
+            Self::Crossfade => "Crossfade",
+            Self::Morph => "Morph",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct PlaylistItem {
+    pub(crate) preset: String,
+    pub(crate) hold_secs: f32,
+    pub(crate) transition: PlaylistTransition,
+    pub(crate) transition_secs: f32,
+}
+
+impl Default for PlaylistItem {
+    fn default() -> Self {
+        Self {
+            preset: String::new(),
+            hold_secs: 30.,
+            transition: PlaylistTransition::Crossfade,
+            transition_secs: 3.,
+        }
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Playlist {
+    pub(crate) shuffle: bool,
+    pub(crate) items: Vec<PlaylistItem>,
+}
+
+impl Playlist {
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+}
+
+pub(crate) struct UiPlaylist {
+    pub(crate) playlist: Playlist,
+    pub(crate) path: String,
+    pub(crate) playing: bool,
+    pub(crate) skip: bool,
+    pub(crate) current: Option<usize>,
+    pub(crate) status: Option<String>,
+}
+
+impl Default for UiPlaylist {
+    fn default() -> Self {
+        Self {
+            playlist: Playlist::default(),
+            path: config::playlist_path().display().to_string(),
+            playing: false,
+            skip: false,
+            current: None,
+            status: None,
+        }
+    }
+}
+
+struct Crossfade {
+    outgoing: Vec<UiSineWaveData>,
+    started: Instant,
+    duration: f32,
+}
+
+pub(crate) struct PlaylistPlayer {
+    order: Vec<usize>,
+    position: usize,
+    started: Option<Instant>,
+    seed: u32,
+    pass: u32,
+    morph: Option<Morph>,
+    crossfade: Option<Crossfade>,
+}
+
+impl Default for PlaylistPlayer {
+    fn default() -> Self {
+        Self {
+            order: Vec::new(),
+            position: 0,
+            started: None,
+            seed: random::time_seed(),
+            pass: 0,
+            morph: None,
+            crossfade: None,
+        }
+    }
+}
+
+impl PlaylistPlayer {
+    pub(crate) fn update(&mut self, ui: &mut Ui) {
+        if ui.playlist.playlist.items.is_empty() {
+            ui.playlist.playing = false;
+        }
+        if !ui.playlist.playing {
+            if self.started.take().is_some() {
+                ui.playlist.current = None;
+                self.morph = None;
+            }
+            return;
+        }
+
+        let due = match (self.started, ui.playlist.current) {
+            (Some(started), Some(current)) => {
+                let hold = ui
+                    .playlist
+                    .playlist
+                    .items
+                    .get(current)
+                    .map_or(0., |item| item.hold_secs);
+                std::mem::take(&mut ui.playlist.skip) || started.elapsed().as_secs_f32() >= hold
+            }
+            _ => true,
+        };
+        if due {
+            self.next(ui);
+        }
+
+        if self
+            .morph
+            .as_mut()
+            .is_some_and(|morph| morph.advance(&mut ui.waves.0))
+        {
+            self.morph = None;
+        }
+    }
+
+    // Fades the outgoing preset's rings out over the incoming ones on the render copy, leaving the edited waves alone.
+    pub(crate) fn blend(&mut self, waves: &mut Vec<UiSineWaveData>) {
+        let Some(crossfade) = &self.crossfade else {
+            return;
+        };
+
+        let t = if crossfade.duration > 0. {
+            (crossfade.started.elapsed().as_secs_f32() / crossfade.duration).min(1.)
+        } else {
+            1.
+        };
+        if t >= 1. {
+            self.crossfade = None;
+            return;
+        }
+        let eased = t * t * (3. - 2. * t);
+
+        for wave in waves.iter_mut() {
+            wave.fade *= eased;
+        }
+
+        let room = MAX_WAVES.saturating_sub(waves.len());
+        waves.extend(
+            crossfade
+                .outgoing
+                .iter()
+                .filter(|wave| wave.init)
+                .take(room)
+                .map(|wave| {
+                    let mut wave = wave.clone();
+                    wave.fade *= 1. - eased;
+                    // The incoming preset has already replaced the stroke texture layers.
+                    wave.textured = false;
+                    wave
+                }),
+        );
+    }
+
+    fn next(&mut self, ui: &mut Ui) {
+        let count = ui.playlist.playlist.items.len();
+        if self.started.is_some() {
+            self.position += 1;
+        } else {
+            self.order.clear();
+        }
+        if self.position >= self.order.len() || self.order.len() != count {
+            self.reorder(count, ui.playlist.playlist.shuffle, ui.playlist.current);
+        }
+
+        let index = self.order[self.position];
+        let item = ui.playlist.playlist.items[index].clone();
+        // A preset that fails to load still holds its slot, so a broken entry doesn't retry every frame.
+        self.started = Some(Instant::now());
+        ui.playlist.current = Some(index);
+
+        let from = ui.waves.0.clone();
+        if !ui.load_preset(&item.preset) {
+            ui.playlist.status = ui.presets.status.clone();
+            return;
+        }
+        ui.playlist.status = Some(format!("Playing `{}`", item.preset));
+        ui.reveal.replaced = None;
+        self.morph = None;
+        self.crossfade = None;
+
+        match item.transition {
+            PlaylistTransition::Cut => {}
+            PlaylistTransition::Crossfade => {
+                self.crossfade = Some(Crossfade {
+                    outgoing: from,
+                    started: Instant::now(),
+                    duration: item.transition_secs,
+                });
+            }
+            PlaylistTransition::Morph => {
+                let mut morph = Morph::new(from, ui.waves.0.clone(), item.transition_secs);
+                morph.advance(&mut ui.waves.0);
+                self.morph = Some(morph);
+            }
+        }
+    }
+
+    fn reorder(&mut self, count: usize, shuffle: bool, last: Option<usize>) {
+        self.position = 0;
+        self.order = (0..count).collect();
+        if !shuffle {
+            return;
+        }
+
+        self.pass = self.pass.wrapping_add(1);
+        for i in (1..count).rev() {
+            let roll = (random::hash(self.seed, self.pass, i as i32) + 1.) * 0.5;
+            let j = ((roll * (i + 1) as f32) as usize).min(i);
+            self.order.swap(i, j);
+        }
+
+        // Avoid playing the same preset twice in a row across a reshuffle.
+        if count > 1 && self.order.first() == last.as_ref() {
+            self.order.swap(0, 1);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::time::{Duration, Instant};
+
+use crate::ui::UiPower;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+pub(crate) const LOW_POWER_FPS: f32 = 30.;
+pub(crate) const LOW_POWER_SCALE: f32 = 0.5;
+
+#[derive(Default)]
+pub(crate) struct PowerMonitor {
+    last_poll: Option<Instant>,
+}
+
+impl PowerMonitor {
+    pub(crate) fn update(&mut self, settings: &mut UiPower) {
+        let due = self
+            .last_poll
+            .is_none_or(|last_poll| last_poll.elapsed() >= POLL_INTERVAL);
+        if settings.enabled && settings.battery_only && due {
+            settings.on_battery = on_battery();
+            self.last_poll = Some(Instant::now());
+        }
+
+        settings.active = settings.enabled && (!settings.battery_only || settings.on_battery);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn on_battery() -> bool {
+    use std::fs;
+
+    let Ok(entries) = fs::read_dir("/sys/class/power_supply") else {
+        return false;
+    };
+
+    let read = |path: &std::path::Path, name: &str| {
+        fs::read_to_string(path.join(name))
+            .map(|value| value.trim().to_owned())
+            .unwrap_or_default()
+    };
+
+    let mut discharging = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match read(&path, "type").as_str() {
+            "Mains" | "USB" if read(&path, "online") == "1" => return false,
+            "Battery" => discharging |= read(&path, "status") == "Discharging",
+            _ => {}
+        }
+    }
+
+    discharging
+}
+
+#[cfg(not(target_os = "linux"))]
+fn on_battery() -> bool {
+    false
+}
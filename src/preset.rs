@@ -0,0 +1,193 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    canvas::WaveSelection,
+    config, locale,
+    macros::{Macro, MacroParam, MacroSource, MacroTarget, TargetParam},
+    pipelines::sine::MAX_WAVES,
+    ramp::ColorRamp,
+    screenshot,
+    ui::{SceneMode, Ui, UiBackground, UiPhysics, UiSineWaveData},
+};
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Preset {
+    pub(crate) mode: SceneMode,
+    pub(crate) ramp: ColorRamp,
+    pub(crate) background: UiBackground,
+    pub(crate) physics: UiPhysics,
+    pub(crate) macros: Vec<Macro>,
+    pub(crate) waves: Vec<UiSineWaveData>,
+}
+
+impl Default for Preset {
+    fn default() -> Self {
+        Self {
+            mode: SceneMode::default(),
+            ramp: ColorRamp::default(),
+            background: UiBackground::default(),
+            physics: UiPhysics::default(),
+            macros: Vec::new(),
+            waves: vec![UiSineWaveData::active()],
+        }
+    }
+}
+
+impl Preset {
+    pub(crate) fn capture(ui: &Ui) -> Self {
+        Self {
+            mode: ui.scene.mode,
+            ramp: ui.scene.ramp.clone(),
+            background: ui.scene.background.clone(),
+            physics: ui.physics,
+            macros: ui.macros.clone(),
+            waves: ui.waves.0.to_vec(),
+        }
+    }
+
+    pub(crate) fn apply(&self, ui: &mut Ui) {
+        ui.scene.mode = self.mode;
+        ui.scene.ramp = self.ramp.clone();
+        ui.scene.background = self.background.clone();
+        ui.physics = self.physics;
+        if !self.macros.is_empty() {
+            ui.macros = Macro::defaults();
+            for (slot, macro_control) in ui.macros.iter_mut().zip(&self.macros) {
+                *slot = macro_control.clone();
+            }
+        }
+
+        ui.scene.selection = WaveSelection::default();
+        ui.reveal.replaced = Some(std::mem::take(&mut ui.waves.0));
+        ui.waves.0 = self
+            .waves
+            .iter()
+            .filter(|wave| wave.init)
+            .take(MAX_WAVES)
+            .cloned()
+            .collect();
+
+        for wave in ui.waves.0.iter_mut() {
+            wave.texture_requested = wave.textured && !wave.texture_path.is_empty();
+            wave.textured = false;
+        }
+    }
+
+    // Swaps values in without the load reveal or selection reset, keeping hidden waves too.
+    pub(crate) fn restore(&self, ui: &mut Ui) {
+        ui.scene.mode = self.mode;
+        ui.scene.ramp = self.ramp.clone();
+        ui.scene.background = self.background.clone();
+        ui.physics = self.physics;
+        ui.macros.clone_from(&self.macros);
+
+        let mut waves = self
+            .waves
+            .iter()
+            .take(MAX_WAVES)
+            .cloned()
+            .collect::<Vec<_>>();
+        for (i, wave) in waves.iter_mut().enumerate() {
+            let previous = ui.waves.0.get(i);
+            if wave.textured
+                && previous.is_none_or(|previous| previous.texture_path != wave.texture_path)
+            {
+                wave.texture_requested = true;
+                wave.textured = false;
+            }
+        }
+        ui.waves.0 = waves;
+
+        let selection = &mut ui.scene.selection;
+        selection.selected = selection.selected.filter(|i| *i < ui.waves.0.len());
+        selection.solo = selection.solo.filter(|i| *i < ui.waves.0.len());
+    }
+
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        Ok(Self::parse(&fs::read_to_string(path)?)?)
+    }
+
+    // Strict JSON first; hand-edited files with `"0,5"`-style numbers are retried leniently.
+    pub(crate) fn parse(text: &str) -> serde_json::Result<Self> {
+        let err = match serde_json::from_str(text) {
+            Ok(preset) => return Ok(preset),
+            Err(err) => err,
+        };
+
+        let mut value = serde_json::from_str::<serde_json::Value>(text).map_err(|_| err)?;
+        locale::normalize_decimals(&mut value, &serde_json::to_value(Self::schema())?);
+        serde_json::from_value(value)
+    }
+
+    // A preset whose lists each hold one entry, so the lenient parse knows every field's type.
+    fn schema() -> Self {
+        let mut wave = UiSineWaveData::active();
+        wave.motion.points.push([0.; 2]);
+
+        Self {
+            macros: vec![Macro {
+                targets: vec![MacroTarget::new(TargetParam::Wave(MacroParam::Amplitude))],
+                source: Some(MacroSource::new(String::new())),
+                ..Macro::default()
+            }],
+            waves: vec![wave],
+            ..Self::default()
+        }
+    }
+
+    // Accepts what `--preset` does: a screenshot with an embedded preset, a file, or a saved name.
+    pub(crate) fn resolve(name: &str) -> Result<Self> {
+        if name.ends_with(".png") {
+            screenshot::read_preset(Path::new(name))
+        } else if Path::new(name).is_file() {
+            Self::load(Path::new(name))
+        } else {
+            Self::load(&Self::named_path(name)?)
+                .with_context(|| format!("Failed to load preset `{name}`"))
+        }
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+
+    pub(crate) fn named_path(name: &str) -> Result<PathBuf> {
+        if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            bail!("Invalid preset name `{name}`");
+        }
+
+        Ok(config::presets_dir().join(format!("{name}.json")))
+    }
+
+    pub(crate) fn list() -> Vec<String> {
+        let Ok(entries) = fs::read_dir(config::presets_dir()) else {
+            return Vec::new();
+        };
+
+        let mut names = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "json")
+            })
+            .filter_map(|path| Some(path.file_stem()?.to_str()?.to_owned()))
+            .collect::<Vec<_>>();
+        names.sort();
+
+        names
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::preset::Preset;
+
+// Presets store `f32`s, so anything closer than this is the same value after a round trip.
+const TOLERANCE: f64 = 1e-6;
+
+pub(crate) struct Change {
+    pub(crate) pointer: String,
+    pub(crate) label: String,
+    pub(crate) current: String,
+    pub(crate) saved: String,
+}
+
+#[derive(Default)]
+pub(crate) struct UiPresetDiff {
+    pub(crate) name: String,
+    pub(crate) saved: Option<Value>,
+    pub(crate) status: Option<String>,
+}
+
+impl UiPresetDiff {
+    pub(crate) fn load(&mut self) {
+        let name = self.name.trim();
+        let saved = Preset::resolve(name).and_then(|preset| Ok(serde_json::to_value(preset)?));
+
+        match saved {
+            Ok(saved) => {
+                self.saved = Some(saved);
+                self.status = None;
+            }
+            Err(err) => {
+                self.saved = None;
+                self.status = Some(format!("Failed to load `{name}`: {err:#}"));
+            }
+        }
+    }
+
+    pub(crate) fn changes(&self, current: &Preset) -> Vec<Change> {
+        let mut changes = Vec::new();
+        if let (Some(saved), Ok(current)) = (&self.saved, serde_json::to_value(current)) {
+            walk(&current, saved, "", "", &mut changes);
+        }
+
+        changes
+    }
+
+    pub(crate) fn revert(&self, current: &Preset, pointer: &str) -> Result<Preset> {
+        let saved = self
+            .saved
+            .as_ref()
+            .and_then(|saved| saved.pointer(pointer))
+            .with_context(|| format!("`{pointer}` is not in the saved preset"))?;
+
+        let mut value = serde_json::to_value(current)?;
+        *value
+            .pointer_mut(pointer)
+            .with_context(|| format!("`{pointer}` is not in the current scene"))? = saved.clone();
+
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+fn walk(current: &Value, saved: &Value, pointer: &str, label: &str, changes: &mut Vec<Change>) {
+    match (current, saved) {
+        (Value::Object(current), Value::Object(saved)) => {
+            for (key, value) in current {
+                if let Some(saved) = saved.get(key) {
+                    let label = if label.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{label}.{key}")
+                    };
+                    walk(value, saved, &format!("{pointer}/{key}"), &label, changes);
+                }
+            }
+        }
+        // Entries both sides share are compared one by one; a length change reverts as a whole.
+        (Value::Array(entries), Value::Array(saved_entries)) => {
+            if entries.len() != saved_entries.len() {
+                changes.push(Change {
+                    pointer: pointer.to_owned(),
+                    label: label.to_owned(),
+                    current: summary(current),
+                    saved: summary(saved),
+                });
+            }
+            for (i, (value, saved)) in entries.iter().zip(saved_entries).enumerate() {
+                walk(
+                    value,
+                    saved,
+                    &format!("{pointer}/{i}"),
+                    &format!("{label}[{i}]"),
+                    changes,
+                );
+            }
+        }
+        _ if !equal(current, saved) => changes.push(Change {
+            pointer: pointer.to_owned(),
+            label: label.to_owned(),
+            current: summary(current),
+            saved: summary(saved),
+        }),
+        _ => {}
+    }
+}
+
+fn equal(current: &Value, saved: &Value) -> bool {
+    match (current.as_f64(), saved.as_f64()) {
+        (Some(current), Some(saved)) => (current - saved).abs() <= TOLERANCE,
+        _ => current == saved,
+    }
+}
+
+fn summary(value: &Value) -> String {
+    match value {
+        Value::Number(number) if number.is_f64() => {
+            format!("{:.4}", number.as_f64().unwrap_or_default())
+        }
+        Value::String(text) => format!("\"{text}\""),
+        Value::Array(entries) => format!("{} entries", entries.len()),
+        Value::Object(_) => "{…}".to_owned(),
+        _ => value.to_string(),
+    }
+}
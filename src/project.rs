@@ -0,0 +1,235 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::{preset::Preset, ramp::ColorRamp, ui::Ui};
+
+const MANIFEST: &str = "project.json";
+const PRESETS_DIR: &str = "presets";
+const SHADERS_DIR: &str = "shaders";
+const IMAGES_DIR: &str = "images";
+const CURRENT: &str = "current";
+// Project shaders are Shadertoy `mainImage` snippets, played through the Shadertoy backdrop.
+const SHADER_EXTENSION: &str = "glsl";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Cue {
+    pub(crate) label: String,
+    pub(crate) preset: String,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Manifest {
+    name: String,
+    palettes: BTreeMap<String, ColorRamp>,
+    cues: Vec<Cue>,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct Project {
+    pub(crate) name: String,
+    pub(crate) presets: BTreeMap<String, Preset>,
+    pub(crate) palettes: BTreeMap<String, ColorRamp>,
+    pub(crate) shaders: BTreeMap<String, String>,
+    pub(crate) cues: Vec<Cue>,
+}
+
+impl Project {
+    pub(crate) fn capture(ui: &Ui, base: Option<&Project>) -> Self {
+        let mut project = base.cloned().unwrap_or_default();
+
+        for name in Preset::list() {
+            if project.presets.contains_key(&name) {
+                continue;
+            }
+
+            match Preset::named_path(&name).and_then(|path| Preset::load(&path)) {
+                Ok(preset) => {
+                    project.presets.insert(name, preset);
+                }
+                Err(err) => log::warn!("Skipping preset `{name}`: {err}"),
+            }
+        }
+
+        project
+            .presets
+            .insert(CURRENT.to_owned(), Preset::capture(ui));
+        project
+            .palettes
+            .insert(CURRENT.to_owned(), ui.scene.ramp.clone());
+        if ui.shadertoy.enabled {
+            project
+                .shaders
+                .insert(CURRENT.to_owned(), ui.shadertoy.source.clone());
+        }
+
+        project
+    }
+
+    pub(crate) fn load(root: &Path) -> Result<Self> {
+        let manifest_path = root.join(MANIFEST);
+        if !manifest_path.is_file() {
+            bail!("`{}` has no {MANIFEST}", root.display());
+        }
+
+        let manifest: Manifest = serde_json::from_str(&fs::read_to_string(&manifest_path)?)
+            .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+        let mut presets = BTreeMap::new();
+        for (name, path) in Self::entries(&root.join(PRESETS_DIR), "json") {
+            let mut preset =
+                Preset::load(&path).with_context(|| format!("Failed to load preset `{name}`"))?;
+
+            for wave in &mut preset.waves {
+                let texture = Path::new(wave.texture_path.trim());
+                if !wave.texture_path.trim().is_empty() && texture.is_relative() {
+                    wave.texture_path = root.join(texture).display().to_string();
+                }
+            }
+
+            presets.insert(name, preset);
+        }
+
+        let mut shaders = BTreeMap::new();
+        for (name, path) in Self::entries(&root.join(SHADERS_DIR), SHADER_EXTENSION) {
+            shaders.insert(name, fs::read_to_string(&path)?);
+        }
+
+        for cue in &manifest.cues {
+            if !presets.contains_key(&cue.preset) {
+                log::warn!(
+                    "Cue `{}` refers to missing preset `{}`",
+                    cue.label,
+                    cue.preset
+                );
+            }
+        }
+
+        Ok(Self {
+            name: manifest.name,
+            presets,
+            palettes: manifest.palettes,
+            shaders,
+            cues: manifest.cues,
+        })
+    }
+
+    pub(crate) fn save(&self, root: &Path) -> Result<()> {
+        let presets_dir = root.join(PRESETS_DIR);
+        let images_dir = root.join(IMAGES_DIR);
+        fs::create_dir_all(&presets_dir)?;
+
+        // Textures already inside the images dir, as they are after loading this project, keep
+        // their names; copying them onto themselves would truncate them.
+        let mut images = BTreeMap::<PathBuf, String>::new();
+        for wave in self.presets.values().flat_map(|preset| &preset.waves) {
+            let source = PathBuf::from(wave.texture_path.trim());
+            if let Some(file_name) = Self::bundled_name(&source, &images_dir) {
+                images.insert(source, file_name);
+            }
+        }
+
+        for (name, preset) in &self.presets {
+            let mut preset = preset.clone();
+
+            for wave in &mut preset.waves {
+                let source = PathBuf::from(wave.texture_path.trim());
+                if !source.is_file() {
+                    continue;
+                }
+
+                let file_name = match images.get(&source) {
+                    Some(file_name) => file_name.clone(),
+                    None => {
+                        let file_name = Self::unique_name(&source, images.len(), &images);
+                        fs::create_dir_all(&images_dir)?;
+                        fs::copy(&source, images_dir.join(&file_name))?;
+                        images.insert(source, file_name.clone());
+                        file_name
+                    }
+                };
+                wave.texture_path = format!("{IMAGES_DIR}/{file_name}");
+            }
+
+            preset.save(&presets_dir.join(format!("{name}.json")))?;
+        }
+
+        if !self.shaders.is_empty() {
+            let shaders_dir = root.join(SHADERS_DIR);
+            fs::create_dir_all(&shaders_dir)?;
+            for (name, source) in &self.shaders {
+                fs::write(
+                    shaders_dir.join(format!("{name}.{SHADER_EXTENSION}")),
+                    source,
+                )?;
+            }
+        }
+
+        let manifest = Manifest {
+            name: self.name.clone(),
+            palettes: self.palettes.clone(),
+            cues: self.cues.clone(),
+        };
+        fs::write(
+            root.join(MANIFEST),
+            serde_json::to_string_pretty(&manifest)?,
+        )?;
+
+        Ok(())
+    }
+
+    pub(crate) fn start_preset(&self) -> Option<&str> {
+        self.cues
+            .first()
+            .map(|cue| cue.preset.as_str())
+            .filter(|name| self.presets.contains_key(*name))
+            .or_else(|| self.presets.contains_key(CURRENT).then_some(CURRENT))
+            .or_else(|| self.presets.keys().next().map(String::as_str))
+    }
+
+    fn entries(dir: &Path, extension: &str) -> Vec<(String, PathBuf)> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+
+        let mut entries = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|found| found == extension))
+            .filter_map(|path| Some((path.file_stem()?.to_str()?.to_owned(), path)))
+            .collect::<Vec<_>>();
+        entries.sort();
+
+        entries
+    }
+
+    fn bundled_name(source: &Path, images_dir: &Path) -> Option<String> {
+        let source = source.canonicalize().ok()?;
+        if source.parent()? != images_dir.canonicalize().ok()? {
+            return None;
+        }
+
+        source.file_name()?.to_str().map(str::to_owned)
+    }
+
+    fn unique_name(source: &Path, index: usize, taken: &BTreeMap<PathBuf, String>) -> String {
+        let file_name = source
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("image")
+            .to_owned();
+
+        if taken.values().any(|name| *name == file_name) {
+            format!("{index}-{file_name}")
+        } else {
+            file_name
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::time::{Duration, Instant};
+
+use crate::ui::UiQuality;
+
+const DOWNGRADE_FRAMES: u32 = 30;
+const UPGRADE_DELAY: Duration = Duration::from_secs(3);
+const MAX_UPGRADE_DELAY: Duration = Duration::from_secs(60);
+const SMOOTHING: f32 = 0.1;
+
+pub(crate) struct QualityLevel {
+    pub(crate) name: &'static str,
+    pub(crate) render_scale: f32,
+    pub(crate) post_effects: bool,
+}
+
+pub(crate) const LEVELS: [QualityLevel; 4] = [
+    QualityLevel {
+        name: "High",
+        render_scale: 1.,
+        post_effects: true,
+    },
+    QualityLevel {
+        name: "Medium",
+        render_scale: 0.75,
+        post_effects: true,
+    },
+    QualityLevel {
+        name: "Low",
+        render_scale: 0.5,
+        post_effects: false,
+    },
+    QualityLevel {
+        name: "Minimum",
+        render_scale: 0.35,
+        post_effects: false,
+    },
+];
+
+pub(crate) struct AdaptiveQuality {
+    last_frame: Instant,
+    frame_time: f32,
+    slow_frames: u32,
+    steady_since: Instant,
+    upgrade_delay: Duration,
+    upgraded: Option<Instant>,
+}
+
+impl Default for AdaptiveQuality {
+    fn default() -> Self {
+        let now = Instant::now();
+
+        Self {
+            last_frame: now,
+            frame_time: 0.,
+            slow_frames: 0,
+            steady_since: now,
+            upgrade_delay: UPGRADE_DELAY,
+            upgraded: None,
+        }
+    }
+}
+
+impl AdaptiveQuality {
+    pub(crate) fn update(&mut self, settings: &mut UiQuality) {
+        let now = Instant::now();
+        let frame_time = (now - self.last_frame).as_secs_f32() * 1000.;
+        self.last_frame = now;
+
+        self.frame_time += (frame_time - self.frame_time) * SMOOTHING;
+        settings.frame_ms = self.frame_time;
+
+        if !settings.adaptive {
+            settings.level = 0;
+            self.reset(now);
+            return;
+        }
+
+        let target = settings.target_ms.max(1.);
+        if frame_time > target * 1.2 {
+            self.slow_frames += 1;
+            self.steady_since = now;
+        } else {
+            self.slow_frames = 0;
+        }
+
+        if self.slow_frames >= DOWNGRADE_FRAMES && settings.level + 1 < LEVELS.len() {
+            settings.level += 1;
+            self.slow_frames = 0;
+
+            if self
+                .upgraded
+                .is_some_and(|upgraded| now - upgraded < self.upgrade_delay)
+            {
+                self.upgrade_delay = (self.upgrade_delay * 2).min(MAX_UPGRADE_DELAY);
+            }
+            self.upgraded = None;
+        }
+
+        if settings.level > 0
+            && self.frame_time <= target * 1.05
+            && now - self.steady_since >= self.upgrade_delay
+        {
+            settings.level -= 1;
+            self.steady_since = now;
+            self.upgraded = Some(now);
+        }
+    }
+
+    fn reset(&mut self, now: Instant) {
+        self.slow_frames = 0;
+        self.steady_since = now;
+        self.upgrade_delay = UPGRADE_DELAY;
+        self.upgraded = None;
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{
+    audio::analysis::AudioFeatures,
+    strobe::BEAT_ONSET,
+    ui::{QuantizeDivision, Ui, UiSineWaveData, UiWaves},
+};
+
+pub(crate) const BEATS_PER_BAR: u32 = 4;
+
+#[derive(Default)]
+pub(crate) struct Quantizer {
+    live: Option<UiWaves>,
+    last_beat: f32,
+    beat: u32,
+}
+
+impl Quantizer {
+    pub(crate) fn update(&mut self, ui: &mut Ui, audio: &AudioFeatures) {
+        let rising = audio.beat >= BEAT_ONSET && self.last_beat < BEAT_ONSET;
+        self.last_beat = audio.beat;
+
+        let settings = &mut ui.quantize;
+        if std::mem::take(&mut settings.downbeat_requested) {
+            self.beat = BEATS_PER_BAR - 1;
+        }
+        if rising {
+            self.beat = (self.beat + 1) % BEATS_PER_BAR;
+        }
+        settings.beat = self.beat;
+
+        if !settings.enabled {
+            self.live = None;
+            settings.pending = false;
+            return;
+        }
+
+        let boundary = rising
+            && match settings.division {
+                QuantizeDivision::Beat => true,
+                QuantizeDivision::Bar => self.beat == 0,
+            };
+        let apply = std::mem::take(&mut settings.apply_requested) || boundary;
+
+        match &mut self.live {
+            Some(live) if apply => live.clone_from(&ui.waves),
+            Some(_) => {}
+            None => self.live = Some(ui.waves.clone()),
+        }
+
+        settings.pending = self.live.as_ref().is_some_and(|live| *live != ui.waves);
+    }
+
+    pub(crate) fn waves<'a>(&'a self, ui: &'a Ui) -> &'a [UiSineWaveData] {
+        &self.live.as_ref().unwrap_or(&ui.waves).0
+    }
+}
@@ -0,0 +1,144 @@
+use std::num::NonZero;
+
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+use wgpu::{
+    BindGroupLayoutEntry, BindingType, BufferBindingType, BufferUsages, Device, ShaderStages,
+    util::{BufferInitDescriptor, DeviceExt},
+};
+
+use crate::color;
+
+pub(crate) const MAX_STOPS: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ColorStop {
+    pub(crate) position: f32,
+    pub(crate) color: [f32; 3],
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Interpolation {
+    #[default]
+    Linear,
+    Smooth,
+    Constant,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ColorRamp {
+    pub(crate) stops: Vec<ColorStop>,
+    #[serde(default)]
+    pub(crate) interpolation: Interpolation,
+}
+
+impl Default for ColorRamp {
+    fn default() -> Self {
+        Self {
+            stops: vec![
+                ColorStop {
+                    position: 0.,
+                    color: [0.02, 0.0, 0.1],
+                },
+                ColorStop {
+                    position: 0.5,
+                    color: [0.8, 0.1, 0.5],
+                },
+                ColorStop {
+                    position: 1.,
+                    color: [1.0, 0.9, 0.4],
+                },
+            ],
+            interpolation: Interpolation::default(),
+        }
+    }
+}
+
+impl ColorRamp {
+    pub(crate) fn sorted_stops(&self) -> Vec<ColorStop> {
+        let mut stops = self.stops.clone();
+        stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+        stops.truncate(MAX_STOPS);
+
+        stops
+    }
+
+    pub(crate) fn sample(&self, t: f32) -> [f32; 3] {
+        let stops = self.sorted_stops();
+
+        let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+            return [0.; 3];
+        };
+
+        if t <= first.position {
+            return first.color;
+        }
+
+        for pair in stops.windows(2) {
+            let [start, end] = [pair[0], pair[1]];
+            if t <= end.position {
+                let f = (t - start.position) / (end.position - start.position).max(1e-5);
+                let f = match self.interpolation {
+                    Interpolation::Linear => f,
+                    Interpolation::Smooth => f * f * (3. - 2. * f),
+                    Interpolation::Constant => 0.,
+                };
+
+                return color::mix_oklch(start.color, end.color, f);
+            }
+        }
+
+        last.color
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub(crate) struct RampUniform {
+    colors: [[f32; 4]; MAX_STOPS],
+    positions: [[f32; 4]; MAX_STOPS / 4],
+    count: u32,
+    interpolation: u32,
+    _padding: [u32; 2],
+}
+
+impl From<&ColorRamp> for RampUniform {
+    fn from(ramp: &ColorRamp) -> Self {
+        let stops = ramp.sorted_stops();
+
+        let mut uniform = Self::zeroed();
+        for (i, stop) in stops.iter().enumerate() {
+            let [l, c, h] = color::linear_to_oklch(stop.color);
+            uniform.colors[i] = [l, c, h, 1.];
+            uniform.positions[i / 4][i % 4] = stop.position;
+        }
+        uniform.count = stops.len() as u32;
+        uniform.interpolation = ramp.interpolation as u32;
+
+        uniform
+    }
+}
+
+impl RampUniform {
+    pub(crate) fn create_buffer(&self, device: &Device) -> wgpu::Buffer {
+        device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Ramp Buffer"),
+            contents: bytemuck::bytes_of(self),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        })
+    }
+
+    pub(crate) fn layout_entry(binding: u32) -> BindGroupLayoutEntry {
+        BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: NonZero::new(std::mem::size_of::<Self>() as u64),
+            },
+            count: None,
+        }
+    }
+}
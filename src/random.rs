@@ -0,0 +1,35 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) fn time_seed() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(1, |elapsed| {
+            elapsed.subsec_nanos() ^ elapsed.as_secs() as u32
+        })
+}
+
+pub(crate) fn hash(seed: u32, channel: u32, cell: i32) -> f32 {
+    let mut x = seed
+        .wrapping_mul(0x9E37_79B9)
+        .wrapping_add(channel.wrapping_mul(0x85EB_CA6B))
+        .wrapping_add((cell as u32).wrapping_mul(0xC2B2_AE35));
+
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7FEB_352D);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846C_A68B);
+    x ^= x >> 16;
+
+    x as f32 / u32::MAX as f32 * 2. - 1.
+}
+
+pub(crate) fn noise(seed: u32, channel: u32, time: f32) -> f32 {
+    let cell = time.floor();
+    let f = time - cell;
+    let f = f * f * (3. - 2. * f);
+
+    let a = hash(seed, channel, cell as i32);
+    let b = hash(seed, channel, cell as i32 + 1);
+
+    a + (b - a) * f
+}
@@ -1,41 +1,145 @@
-use std::{array, sync::Arc};
+use std::{
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
 
 use anyhow::{Result, anyhow};
 use wgpu::{
-    Backends, Color, Device, DeviceDescriptor, Extent3d, Instance, InstanceDescriptor, LoadOp,
-    Operations, Queue, RenderPassColorAttachment, RenderPassDescriptor, RequestAdapterOptions,
-    StoreOp, Surface, SurfaceConfiguration, Texture, TextureDimension, TextureFormat,
-    TextureUsages, TextureView,
+    Backends, Color, CommandEncoder, CompositeAlphaMode, Device, DownlevelFlags, ErrorFilter,
+    Extent3d, Instance, InstanceDescriptor, LoadOp, Operations, PowerPreference, Queue,
+    RenderPassColorAttachment, RenderPassDescriptor, StoreOp, Surface, SurfaceConfiguration,
+    Texture, TextureDimension, TextureFormat, TextureUsages, TextureView,
     wgt::{CommandEncoderDescriptor, TextureDescriptor, TextureViewDescriptor},
 };
 use winit::{dpi::PhysicalSize, event::WindowEvent, window::Window};
 
 use crate::{
+    ambient::Ambient,
+    args::Args,
+    artnet::ArtNet,
+    audio::{Audio, response::ResponseFollowers},
     boundary::Boundary,
+    branding::WindowTitle,
+    burst::Bursts,
+    bypass::BypassTarget,
+    clock::Clock,
+    clock_sync::{ClockFrame, ClockSync},
+    color_space::{self, OffScreenFormat, PostSettings},
+    draw_stats::DrawStats,
     global::Global,
+    gpu::{self, ErrorLog},
+    hot_reload::PresetWatcher,
+    lut::Lut,
+    macros,
+    metrics::{MetricsFrame, MetricsLog},
+    motion,
+    physics::Physics,
     pipelines::{
+        background::BackgroundPipeline,
+        field_warp::FieldWarpPipeline,
+        hud::HudPipeline,
+        pixel_sort::PixelSortPipeline,
         post::PostPipeline,
-        sine::{Sine, SinePipeline, SineWaveData, Waves},
+        reaction_diffusion::{RD_SIZE, ReactionDiffusionPipeline},
+        scene::ScenePipeline,
+        shadertoy::ShadertoyPipeline,
+        sine::{MAX_WAVES, Sine, Waves},
+        spectrum::SpectrumPipeline,
+        ui_layer::{self, UiLayerPipeline},
     },
-    ui::Ui,
+    playlist::PlaylistPlayer,
+    power::{self, PowerMonitor},
+    preset::Preset,
+    project::Project,
+    quality::{self, AdaptiveQuality},
+    quantize::Quantizer,
+    render_graph::RenderGraph,
+    replay::ReplayBuffer,
+    reveal::RevealAnimation,
+    routing::{RouteOutput, RouteStage, RouteTargets},
+    scope, screenshot, shadertoy,
+    show_control::ShowControl,
+    stream::DataStream,
+    strobe::Strobe,
+    stroke,
+    ui::{SceneMode, Ui},
+    upload::Uploads,
     vertex::Vertex,
+    widget,
+};
+#[cfg(feature = "http")]
+use crate::{
+    server::{Command, Reply, Server},
+    ui::UiSineWaveData,
 };
 
+const SCENE_DEBUG_TARGET: &str = "Scene (pre-post)";
+const PIXEL_SORT_DEBUG_TARGET: &str = "Pixel Sort Output";
+const FIELD_WARP_DEBUG_TARGET: &str = "Field Warp Output";
+
 pub(crate) struct Render {
     surface: Surface<'static>,
     device: Device,
     queue: Queue,
     window: Arc<Window>,
     config: SurfaceConfiguration,
-    sine_pipeline: SinePipeline,
+    scene_pipeline: ScenePipeline,
+    baseline_pipeline: ScenePipeline,
     post_pipeline: PostPipeline,
+    monitor_pipeline: Option<PostPipeline>,
+    hud_pipeline: HudPipeline,
+    ui_layer_pipeline: Option<UiLayerPipeline>,
+    background_pipeline: BackgroundPipeline,
+    routes: RouteTargets,
+    pixel_sort_pipeline: Option<PixelSortPipeline>,
+    field_warp_pipeline: Option<FieldWarpPipeline>,
+    reaction_diffusion_pipeline: Option<ReactionDiffusionPipeline>,
+    shadertoy_pipeline: Option<ShadertoyPipeline>,
+    spectrum_pipeline: Option<SpectrumPipeline>,
+    post_source_sorted: bool,
+    post_source_warped: bool,
     off_screen_texture: Texture,
     off_screen_texture_view: TextureView,
     ui: Ui,
+    audio: Audio,
+    physics: Physics,
+    ambient: Ambient,
+    preset_watcher: PresetWatcher,
+    playlist: PlaylistPlayer,
+    reveal: RevealAnimation,
+    bursts: Bursts,
+    replay: ReplayBuffer,
+    metrics: MetricsLog,
+    uploads: Uploads,
+    art_net: ArtNet,
+    clock_sync: ClockSync,
+    quality: AdaptiveQuality,
+    power: PowerMonitor,
+    quantizer: Quantizer,
+    audio_response: ResponseFollowers,
+    baseline_audio_response: ResponseFollowers,
+    strobe: Strobe,
+    title: WindowTitle,
+    render_scale: f32,
+    scene_viewport: [u32; 4],
+    errors: ErrorLog,
+    device_lost: Arc<AtomicBool>,
+    frame_interval: Option<Duration>,
+    next_frame: Instant,
+    clock: Clock,
+    rescale_from: Option<f32>,
+    stream: Option<DataStream>,
+    show_control: Option<ShowControl>,
+    #[cfg(feature = "http")]
+    server: Option<Server>,
 }
 
 impl Render {
-    pub(crate) async fn new(window: Window) -> Result<Self> {
+    pub(crate) async fn new(window: Window, args: &Args) -> Result<Self> {
         let window = Arc::new(window);
         let window_size = window.inner_size();
 
@@ -46,31 +150,68 @@ impl Render {
 
         let surface = instance.create_surface(window.clone())?;
 
-        let adapter = instance
-            .request_adapter(&RequestAdapterOptions {
-                compatible_surface: Some(&surface),
-                ..Default::default()
-            })
-            .await?;
+        let power_preference = if args.low_power {
+            PowerPreference::LowPower
+        } else {
+            PowerPreference::default()
+        };
+        let adapter = gpu::request_adapter(&instance, Some(&surface), power_preference).await?;
 
-        let (device, queue) = adapter
-            .request_device(&DeviceDescriptor {
-                label: Some("Device Descriptor"),
-                ..Default::default()
-            })
-            .await?;
+        let (device, queue, capabilities) = gpu::request_device(&adapter, args.safe_mode).await?;
+
+        if capabilities.safe_mode {
+            log::warn!("Running in safe mode with downlevel limits");
+        }
+
+        let errors = ErrorLog::default();
+        errors.install(&device);
+        let device_lost = gpu::watch_device_lost(&device);
 
         let surface_compatibilities = surface.get_capabilities(&adapter);
 
-        let surface_format = surface_compatibilities
-            .formats
-            .iter()
-            .find(|format| format.is_srgb())
-            .or(surface_compatibilities.formats.first())
-            .copied()
-            .ok_or_else(|| anyhow!("Surface is incompatible with the adapter"))?;
+        let backend = adapter.get_info().backend;
+        let hdr_available = if capabilities.safe_mode {
+            Vec::new()
+        } else {
+            color_space::hdr_outputs(&surface_compatibilities.formats, backend)
+        };
+        if capabilities.safe_mode && args.hdr.is_some() {
+            log::warn!("HDR output is disabled in safe mode, falling back to SDR output");
+        }
+        let hdr = args
+            .hdr
+            .filter(|_| !capabilities.safe_mode)
+            .and_then(|request| {
+                match color_space::select_hdr_output(
+                    &surface_compatibilities.formats,
+                    backend,
+                    request,
+                ) {
+                    Ok(output) => {
+                        log::warn!(
+                            "{} output enabled, the display must be in HDR mode or highlights \
+                             will clip and the scene will look washed out",
+                            output.label()
+                        );
+                        Some(output)
+                    }
+                    Err(err) => {
+                        log::warn!("{err}, falling back to SDR output");
+                        None
+                    }
+                }
+            });
+
+        let surface_format = match hdr {
+            Some(output) => output.surface_format(),
+            None => color_space::select_surface_format(
+                &surface_compatibilities.formats,
+                args.format,
+                args.unorm_surface,
+            )?,
+        };
 
-        let config = SurfaceConfiguration {
+        let mut config = SurfaceConfiguration {
             usage: TextureUsages::RENDER_ATTACHMENT,
             format: surface_format,
             width: window_size.width,
@@ -80,54 +221,333 @@ impl Render {
                 .first()
                 .copied()
                 .ok_or_else(|| anyhow!("Surface is incompatible with the adapter"))?,
-            alpha_mode: surface_compatibilities
-                .alpha_modes
-                .first()
-                .copied()
+            alpha_mode: args
+                .widget
+                .then(|| widget::alpha_mode(&surface_compatibilities.alpha_modes))
+                .flatten()
+                .or_else(|| surface_compatibilities.alpha_modes.first().copied())
                 .ok_or_else(|| anyhow!("No supported alpha modes found"))?,
             view_formats: Vec::new(),
             desired_maximum_frame_latency: 2,
         };
 
-        surface.configure(&device, &config);
+        let view_formats_supported = adapter
+            .get_downlevel_capabilities()
+            .flags
+            .contains(DownlevelFlags::SURFACE_VIEW_FORMATS);
+        let view_format = args.view_format.unwrap_or(surface_format);
+        color_space::validate_view_format(surface_format, view_format, view_formats_supported)?;
+
+        let alternate_format =
+            color_space::surface_view_format(surface_format, !surface_format.is_srgb());
+        let srgb_available = alternate_format != surface_format && view_formats_supported;
+        if srgb_available {
+            config.view_formats.push(alternate_format);
+        }
 
-        let (off_screen_texture, off_screen_texture_view) =
-            Self::create_off_screen_texture(config.width, config.height, config.format, &device);
+        surface.configure(&device, &config);
 
-        let ui = Ui::new(&device, config.format, &window);
+        // Highlights above SDR white only survive to the post pass in a float scene buffer.
+        let off_screen_format = args
+            .off_screen_format
+            .unwrap_or(if hdr.is_some() {
+                OffScreenFormat::Rgba16Float
+            } else {
+                OffScreenFormat::default()
+            })
+            .texture_format();
+        let (off_screen_texture, off_screen_texture_view) = Self::create_off_screen_texture(
+            config.width,
+            config.height,
+            off_screen_format,
+            &device,
+        );
 
-        let sine = Sine {
-            boundary: Boundary::new(
-                Vertex::new(-1., 1.),
-                Vertex::new(-1., -1.),
-                Vertex::new(1., -1.),
-                Vertex::new(1., 1.),
-            ),
-            wave_data: Waves::default(),
+        // HDR surfaces are linear, so egui draws into its own layer and is composited onto them.
+        let ui_format = if hdr.is_some() {
+            ui_layer::LAYER_FORMAT
+        } else {
+            config.format
         };
+        let mut ui = Ui::new(&device, ui_format, &window);
+        ui.color_space.srgb_available = srgb_available;
+        ui.color_space.srgb_surface = view_format.is_srgb();
+        ui.color_space.surface_format = Some(surface_format);
+        ui.color_space.off_screen_format = Some(off_screen_format);
+        ui.color_space.hdr_available = hdr_available;
+        ui.color_space.hdr = hdr;
+        ui.scene.interference_available = capabilities.storage_buffers;
+        ui.hidden = args.wallpaper || args.kiosk.is_some();
+        ui.power.enabled = args.low_power;
+        ui.widget.enabled = args.widget;
+        ui.widget.translucent = widget::alpha_mode(&[config.alpha_mode]).is_some();
+        if args.widget && !ui.widget.translucent {
+            log::warn!("The surface does not support transparency; the widget will be opaque");
+        }
+        if args.kiosk.is_some() {
+            window.set_cursor_visible(false);
+            ui.ambient.enabled = true;
+            ui.ambient.cycle_presets = true;
+            if let Some(first) = Preset::list().first() {
+                ui.load_preset(first);
+            }
+        }
+        if let Some(dir) = &args.project {
+            ui.open_project(Project::load(dir)?);
+            ui.project.path = dir.display().to_string();
+        }
+        match &args.preset {
+            Some(name) => Preset::resolve(name)?.apply(&mut ui),
+            None if args.project.is_none()
+                && !ui.hidden
+                && !args.widget
+                && ui.settings.show_startup =>
+            {
+                ui.startup.show()
+            }
+            None => {}
+        }
+        if let Some(fps) = args.frame_rate() {
+            ui.quality.target_ms = ui.quality.target_ms.max(1000. / fps);
+        }
+
+        let global = Global::new(config.width, config.height);
 
-        let global = Global::new(800, 600);
+        let mut scene_pipeline = ScenePipeline::new(
+            Self::create_sine(),
+            global,
+            &ui.scene.ramp,
+            off_screen_format,
+            &capabilities,
+            &errors,
+            &device,
+        )?;
 
-        let sine_pipeline = SinePipeline::new(sine, global, config.format, &device);
+        let mut baseline_pipeline = ScenePipeline::new(
+            Self::create_sine(),
+            global,
+            &ui.scene.ramp,
+            off_screen_format,
+            &capabilities,
+            &errors,
+            &device,
+        )?;
 
-        let post_pipeline =
-            PostPipeline::new(&off_screen_texture_view, config.format, global, &device);
+        let post_pipeline = gpu::validated(&device, || {
+            PostPipeline::new(
+                &off_screen_texture_view,
+                config.format,
+                global,
+                PostSettings::new(config.format.is_srgb(), ui.color_space.linear_output),
+                &device,
+                &queue,
+            )
+        })?;
+
+        let hud_pipeline = gpu::validated(&device, || HudPipeline::new(config.format, &device))?;
+        let ui_layer_pipeline = hdr
+            .map(|_| {
+                gpu::validated(&device, || {
+                    UiLayerPipeline::new(
+                        config.width,
+                        config.height,
+                        ui.color_space.sdr_white,
+                        config.format,
+                        &device,
+                    )
+                })
+            })
+            .transpose()?;
+        let background_pipeline = gpu::validated(&device, || {
+            BackgroundPipeline::new(&ui.scene.background, off_screen_format, &device)
+        })?;
+
+        let pixel_sort_pipeline = capabilities.compute.then(|| {
+            gpu::validated(&device, || {
+                PixelSortPipeline::new(
+                    &off_screen_texture_view,
+                    config.width,
+                    config.height,
+                    &device,
+                )
+            })
+        });
+        let pixel_sort_pipeline = pixel_sort_pipeline.and_then(|pipeline| {
+            pipeline
+                .inspect_err(|err| errors.push(format!("Pixel sort unavailable: {err}")))
+                .ok()
+        });
+        ui.pixel_sort.available = pixel_sort_pipeline.is_some();
+
+        let field_warp_pipeline = capabilities.compute.then(|| {
+            gpu::validated(&device, || {
+                FieldWarpPipeline::new(
+                    &off_screen_texture_view,
+                    config.width,
+                    config.height,
+                    &device,
+                )
+            })
+        });
+        let field_warp_pipeline = field_warp_pipeline.and_then(|pipeline| {
+            pipeline
+                .inspect_err(|err| errors.push(format!("Field warp unavailable: {err}")))
+                .ok()
+        });
+        ui.field_warp.available = field_warp_pipeline.is_some();
+
+        let reaction_diffusion_pipeline = capabilities.compute.then(|| {
+            gpu::validated(&device, || {
+                ReactionDiffusionPipeline::new(off_screen_format, &device)
+            })
+        });
+        let reaction_diffusion_pipeline = reaction_diffusion_pipeline.and_then(|pipeline| {
+            pipeline
+                .inspect_err(|err| errors.push(format!("Reaction diffusion unavailable: {err}")))
+                .ok()
+        });
+        ui.reaction_diffusion.available = reaction_diffusion_pipeline.is_some();
+        if let Some(reaction_diffusion_pipeline) = &reaction_diffusion_pipeline {
+            let view = reaction_diffusion_pipeline.pattern_view();
+            scene_pipeline.set_modulation_texture(view, &device);
+            baseline_pipeline.set_modulation_texture(view, &device);
+            ui.set_debug_target("Reaction Diffusion", view, [RD_SIZE; 2], &device);
+        }
+
+        let spectrum_pipeline = capabilities
+            .compute
+            .then(|| gpu::validated(&device, || SpectrumPipeline::new(&device)));
+        let spectrum_pipeline = spectrum_pipeline.and_then(|pipeline| {
+            pipeline
+                .inspect_err(|err| errors.push(format!("GPU audio analysis unavailable: {err}")))
+                .ok()
+        });
+        ui.audio.gpu_available = spectrum_pipeline.is_some();
+
+        ui.set_debug_target(
+            SCENE_DEBUG_TARGET,
+            &off_screen_texture_view,
+            [config.width, config.height],
+            &device,
+        );
+
+        if let Some(pixel_sort_pipeline) = &pixel_sort_pipeline {
+            ui.set_debug_target(
+                "Pixel Sort (polar)",
+                pixel_sort_pipeline.polar_view(),
+                [512; 2],
+                &device,
+            );
+            ui.set_debug_target(
+                PIXEL_SORT_DEBUG_TARGET,
+                pixel_sort_pipeline.output_view(),
+                [config.width, config.height],
+                &device,
+            );
+        }
+
+        if let Some(field_warp_pipeline) = &field_warp_pipeline {
+            ui.set_debug_target(
+                FIELD_WARP_DEBUG_TARGET,
+                field_warp_pipeline.output_view(),
+                [config.width, config.height],
+                &device,
+            );
+        }
+
+        for i in 0..MAX_WAVES {
+            ui.set_debug_target(
+                &format!("Stroke Texture {}", i + 1),
+                &scene_pipeline.wave_texture_view(i),
+                [stroke::STROKE_TEXTURE_SIZE; 2],
+                &device,
+            );
+        }
+
+        #[cfg(feature = "http")]
+        let server = args.http.as_deref().map(Server::start).transpose()?;
+
+        let stream = args.stream.as_deref().map(DataStream::open).transpose()?;
+        let show_control = args
+            .show_control
+            .as_deref()
+            .map(ShowControl::open)
+            .transpose()?;
+        ui.show_control.connected = show_control.is_some();
 
         Ok(Self {
+            #[cfg(feature = "http")]
+            server,
+            stream,
+            show_control,
             ui,
+            audio: Audio::default(),
+            physics: Physics::default(),
+            ambient: Ambient::default(),
+            preset_watcher: PresetWatcher::default(),
+            playlist: PlaylistPlayer::default(),
+            reveal: RevealAnimation::default(),
+            bursts: Bursts::default(),
+            replay: ReplayBuffer::default(),
+            metrics: MetricsLog::default(),
+            uploads: Uploads::default(),
+            art_net: ArtNet::default(),
+            clock_sync: ClockSync::default(),
+            quality: AdaptiveQuality::default(),
+            power: PowerMonitor::default(),
+            quantizer: Quantizer::default(),
+            audio_response: ResponseFollowers::default(),
+            baseline_audio_response: ResponseFollowers::default(),
+            strobe: Strobe::default(),
+            title: WindowTitle::default(),
+            render_scale: 1.,
+            scene_viewport: [0, 0, config.width, config.height],
+            errors,
+            device_lost,
+            frame_interval: args
+                .frame_rate()
+                .map(|fps| Duration::from_secs_f32(1. / fps)),
+            next_frame: Instant::now(),
+            clock: Clock::default(),
+            rescale_from: None,
             off_screen_texture,
             surface,
             device,
-            sine_pipeline,
+            scene_pipeline,
+            baseline_pipeline,
             queue,
             window,
             config,
             off_screen_texture_view,
             post_pipeline,
+            monitor_pipeline: None,
+            hud_pipeline,
+            ui_layer_pipeline,
+            background_pipeline,
+            routes: RouteTargets::default(),
+            pixel_sort_pipeline,
+            field_warp_pipeline,
+            reaction_diffusion_pipeline,
+            shadertoy_pipeline: None,
+            spectrum_pipeline,
+            post_source_sorted: false,
+            post_source_warped: false,
         })
     }
 
-    fn create_off_screen_texture(
+    pub(crate) fn create_sine() -> Sine {
+        Sine {
+            boundary: Boundary::new(
+                Vertex::new(-1., 1.),
+                Vertex::new(-1., -1.),
+                Vertex::new(1., -1.),
+                Vertex::new(1., 1.),
+            ),
+            wave_data: Waves::default(),
+        }
+    }
+
+    pub(crate) fn create_off_screen_texture(
         width: u32,
         height: u32,
         format: TextureFormat,
@@ -136,15 +556,17 @@ impl Render {
         let texture = device.create_texture(&TextureDescriptor {
             label: Some("Off Screen Texture"),
             size: Extent3d {
-                width: width,
-                height: height,
+                width,
+                height,
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
             sample_count: 1,
             dimension: TextureDimension::D2,
-            format: format,
-            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            format,
+            usage: TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC,
             view_formats: &[],
         });
 
@@ -161,104 +583,1029 @@ impl Render {
             self.config.width = new_size.width;
             self.config.height = new_size.height;
             self.surface.configure(&self.device, &self.config);
-            self.sine_pipeline.update_global_resolution(
-                new_size.width,
-                new_size.height,
-                &self.queue,
-            );
+            if let Some(ui_layer_pipeline) = &mut self.ui_layer_pipeline {
+                ui_layer_pipeline.resize(new_size.width, new_size.height, &self.device);
+            }
             self.post_pipeline.update_global_resolution(
                 new_size.width,
                 new_size.height,
                 &self.queue,
             );
 
-            let (off_screen_texture, off_screen_texture_view) = Self::create_off_screen_texture(
-                new_size.width,
-                new_size.height,
-                self.config.format,
+            self.resize_scene_targets();
+        }
+    }
+
+    pub(crate) fn scale_factor_changed(&mut self, scale_factor: f64) {
+        let display = &mut self.ui.display;
+
+        if display.scale_scene && self.rescale_from.is_none() {
+            self.rescale_from = Some(self.config.height as f32 / display.scale_factor);
+        }
+        display.scale_factor = scale_factor as f32;
+    }
+
+    fn apply_scene_rescale(&mut self) {
+        let Some(logical_height) = self.rescale_from.take() else {
+            return;
+        };
+
+        let new_logical_height = self.config.height as f32 / self.ui.display.scale_factor;
+        if new_logical_height > 0. {
+            self.ui.waves.scale(logical_height / new_logical_height);
+        }
+    }
+
+    fn viewport(&self) -> [u32; 4] {
+        self.ui
+            .preview
+            .letterbox(self.config.width, self.config.height)
+    }
+
+    fn scene_size(&self) -> (u32, u32) {
+        let scale = |size: u32| ((size as f32 * self.render_scale).round() as u32).max(1);
+        let [_, _, width, height] = self.viewport();
+
+        (scale(width), scale(height))
+    }
+
+    fn resize_scene_targets(&mut self) {
+        self.scene_viewport = self.viewport();
+        let (width, height) = self.scene_size();
+
+        self.scene_pipeline
+            .update_global_resolution(width, height, &self.queue);
+        self.baseline_pipeline
+            .update_global_resolution(width, height, &self.queue);
+        if let Some(shadertoy_pipeline) = &mut self.shadertoy_pipeline {
+            shadertoy_pipeline.update_global_resolution(width, height, &self.queue);
+        }
+
+        let (off_screen_texture, off_screen_texture_view) = Self::create_off_screen_texture(
+            width,
+            height,
+            self.off_screen_texture.format(),
+            &self.device,
+        );
+
+        self.off_screen_texture = off_screen_texture;
+        self.off_screen_texture_view = off_screen_texture_view;
+        self.monitor_pipeline = None;
+
+        self.ui.set_debug_target(
+            SCENE_DEBUG_TARGET,
+            &self.off_screen_texture_view,
+            [width, height],
+            &self.device,
+        );
+
+        if let Some(pixel_sort_pipeline) = &mut self.pixel_sort_pipeline {
+            pixel_sort_pipeline.resize(&self.off_screen_texture_view, width, height, &self.device);
+
+            self.ui.set_debug_target(
+                PIXEL_SORT_DEBUG_TARGET,
+                pixel_sort_pipeline.output_view(),
+                [width, height],
                 &self.device,
             );
+        }
 
-            self.off_screen_texture = off_screen_texture;
-            self.off_screen_texture_view = off_screen_texture_view;
+        if let Some(field_warp_pipeline) = &mut self.field_warp_pipeline {
+            field_warp_pipeline.resize(&self.off_screen_texture_view, width, height, &self.device);
 
-            self.post_pipeline
-                .update_off_screen_bindgroup(&self.off_screen_texture_view, &self.device);
+            self.ui.set_debug_target(
+                FIELD_WARP_DEBUG_TARGET,
+                field_warp_pipeline.output_view(),
+                [width, height],
+                &self.device,
+            );
+        }
+
+        self.update_post_source();
+    }
+
+    fn update_quality(&mut self) {
+        self.quality.update(&mut self.ui.quality);
+        self.power.update(&mut self.ui.power);
+
+        let mut render_scale = quality::LEVELS[self.ui.quality.level].render_scale;
+        if self.ui.power.active {
+            render_scale *= power::LOW_POWER_SCALE;
+        }
+        if render_scale != self.render_scale || self.viewport() != self.scene_viewport {
+            self.render_scale = render_scale;
+            self.resize_scene_targets();
+        }
+    }
+
+    fn update_post_source(&mut self) {
+        let mut source = match &self.pixel_sort_pipeline {
+            Some(pixel_sort_pipeline) if self.post_source_sorted => {
+                pixel_sort_pipeline.output_view()
+            }
+            _ => &self.off_screen_texture_view,
+        };
+
+        if let Some(field_warp_pipeline) = &mut self.field_warp_pipeline {
+            field_warp_pipeline.set_source(source, &self.device);
+            if self.post_source_warped {
+                source = field_warp_pipeline.output_view();
+            }
+        }
+
+        self.post_pipeline
+            .update_off_screen_bindgroup(source, &self.device);
+    }
+
+    pub(crate) fn save_ui_state(&self) {
+        if let Err(err) = self.ui.save_memory() {
+            log::warn!("Failed to save UI state: {err}");
         }
     }
 
     pub(crate) fn handle_ui_inputs(&mut self, event: &WindowEvent) {
+        if matches!(
+            event,
+            WindowEvent::KeyboardInput { .. }
+                | WindowEvent::CursorMoved { .. }
+                | WindowEvent::MouseInput { .. }
+                | WindowEvent::MouseWheel { .. }
+                | WindowEvent::Touch(_)
+        ) {
+            self.ambient.register_input();
+        }
+
         self.ui.handle_input(&self.window, event);
     }
 
-    pub(crate) fn render(&mut self) -> Result<()> {
+    #[cfg(feature = "http")]
+    fn handle_remote_requests(&mut self) {
+        let Some(server) = &self.server else {
+            return;
+        };
+
+        for mut incoming in server.poll() {
+            let reply = match incoming.take_command() {
+                Some(command) => self.execute_remote_command(command),
+                None => Reply::NotFound,
+            };
+
+            incoming.respond(reply);
+        }
+    }
+
+    #[cfg(feature = "http")]
+    fn execute_remote_command(&mut self, command: Command) -> Reply {
+        let result = match command {
+            Command::GetScene => {
+                serde_json::to_string_pretty(&Preset::capture(&self.ui)).map(Reply::Json)
+            }
+            Command::PutScene(body) => Preset::parse(&body).map(|preset| {
+                // Paths the scene already uses stay allowed, so a fetched scene can be put back.
+                let rejected = preset.waves.iter().find_map(|wave| {
+                    let known = self
+                        .ui
+                        .waves
+                        .0
+                        .iter()
+                        .any(|current| current.texture_path == wave.texture_path);
+                    (!known).then(|| remote_texture_error(&wave.texture_path))?
+                });
+                if let Some(err) = rejected {
+                    return Reply::BadRequest(err);
+                }
+
+                preset.apply(&mut self.ui);
+                Reply::Ok
+            }),
+            Command::GetWave(index) => match self.ui.waves.0.get(index) {
+                Some(wave) => serde_json::to_string_pretty(wave).map(Reply::Json),
+                None => Ok(Reply::NotFound),
+            },
+            Command::PutWave(index, body) => match self.ui.waves.0.get_mut(index) {
+                Some(wave) => Self::patch_wave(wave, &body),
+                None => Ok(Reply::NotFound),
+            },
+            Command::Screenshot => {
+                return screenshot::capture(
+                    &self.device,
+                    &self.queue,
+                    self.routed(RouteOutput::Remote),
+                )
+                .and_then(|image| screenshot::encode_png(&image, Some(&Preset::capture(&self.ui))))
+                .map_or_else(|err| Reply::BadRequest(err.to_string()), Reply::Png);
+            }
+            Command::ListPresets => {
+                self.ui.presets.refresh();
+                serde_json::to_string(&self.ui.presets.names).map(Reply::Json)
+            }
+            Command::GetPreset => {
+                serde_json::to_string(&serde_json::json!({ "name": self.ui.presets.name }))
+                    .map(Reply::Json)
+            }
+            Command::LoadPreset(name) => {
+                return if self.ui.load_preset(&name) {
+                    Reply::Ok
+                } else {
+                    Reply::BadRequest(self.ui.presets.status.clone().unwrap_or_default())
+                };
+            }
+        };
+
+        result.unwrap_or_else(|err| Reply::BadRequest(err.to_string()))
+    }
+
+    #[cfg(feature = "http")]
+    fn patch_wave(wave: &mut UiSineWaveData, body: &str) -> serde_json::Result<Reply> {
+        let patch = serde_json::from_str::<serde_json::Value>(body)?;
+        let mut value = serde_json::to_value(&*wave)?;
+
+        if let (Some(target), Some(patch)) = (value.as_object_mut(), patch.as_object()) {
+            target.extend(patch.clone());
+        }
+
+        let patched = UiSineWaveData {
+            texture_requested: wave.texture_requested,
+            ..serde_json::from_value(value)?
+        };
+        if patched.texture_path != wave.texture_path
+            && let Some(err) = remote_texture_error(&patched.texture_path)
+        {
+            return Ok(Reply::BadRequest(err));
+        }
+
+        let texture_path = std::mem::replace(wave, patched).texture_path;
+        if wave.textured && wave.texture_path != texture_path {
+            wave.textured = false;
+            wave.texture_requested = true;
+        }
+
+        Ok(Reply::Ok)
+    }
+
+    fn load_requested_textures(&mut self) {
+        for (i, wave) in self.ui.waves.0.iter_mut().enumerate() {
+            if !std::mem::take(&mut wave.texture_requested) {
+                continue;
+            }
+
+            match stroke::load_image(Path::new(wave.texture_path.trim())) {
+                Ok(image) => {
+                    self.scene_pipeline.set_wave_texture(i, &image, &self.queue);
+                    wave.textured = true;
+                    wave.texture_error = None;
+                }
+                Err(err) => {
+                    wave.texture_error = Some(format!("Failed to load texture: {err}"));
+                }
+            }
+        }
+    }
+
+    fn load_requested_lut(&mut self) {
+        let grade = &mut self.ui.grade;
+        if !std::mem::take(&mut grade.lut_requested) {
+            return;
+        }
+
+        let path = grade.lut_path.trim();
+        let lut = if path.is_empty() {
+            Ok(Lut::identity())
+        } else {
+            Lut::load(Path::new(path))
+        };
+
+        match lut {
+            Ok(lut) => {
+                self.post_pipeline.set_lut(&lut, &self.device, &self.queue);
+                grade.lut_loaded = !path.is_empty();
+                grade.lut_error = None;
+            }
+            Err(err) => {
+                grade.lut_error = Some(format!("Failed to load LUT: {err}"));
+            }
+        }
+    }
+
+    fn save_requested_screenshot(&mut self) {
+        if !std::mem::take(&mut self.ui.presets.screenshot_requested) {
+            return;
+        }
+
+        let path = self.ui.presets.image_path.trim().to_owned();
+        let result = screenshot::capture(
+            &self.device,
+            &self.queue,
+            self.routed(RouteOutput::Screenshot),
+        )
+        .and_then(|image| screenshot::save(&image, &Preset::capture(&self.ui), Path::new(&path)));
+
+        self.ui.presets.status = Some(match result {
+            Ok(()) => format!("Saved screenshot to {path}"),
+            Err(err) => format!("Failed to save screenshot: {err}"),
+        });
+    }
+
+    fn routed(&self, output: RouteOutput) -> &Texture {
+        self.routes
+            .texture(self.ui.routing.stage(output))
+            .unwrap_or(&self.off_screen_texture)
+    }
+
+    pub(crate) fn frame_deadline(&self) -> Option<Instant> {
+        self.effective_frame_interval().map(|_| self.next_frame)
+    }
+
+    fn effective_frame_interval(&self) -> Option<Duration> {
+        let low_power = self
+            .ui
+            .power
+            .active
+            .then(|| Duration::from_secs_f32(1. / power::LOW_POWER_FPS));
+
+        self.frame_interval.max(low_power)
+    }
+
+    pub(crate) fn device_lost(&self) -> bool {
+        self.device_lost.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn request_redraw(&self) {
         self.window.request_redraw();
+    }
+
+    fn spectrum_pass(&mut self, encoder: &mut CommandEncoder) {
+        let Some(spectrum_pipeline) = &mut self.spectrum_pipeline else {
+            return;
+        };
+
+        spectrum_pipeline.dispatch(encoder);
+        let output = spectrum_pipeline.output_buffer();
+        self.scene_pipeline.copy_spectrum_from(output, encoder);
+        self.baseline_pipeline.copy_spectrum_from(output, encoder);
+    }
+
+    fn reaction_diffusion_pass(&mut self, reseed: bool, encoder: &mut CommandEncoder) {
+        if let Some(reaction_diffusion_pipeline) = &mut self.reaction_diffusion_pipeline {
+            reaction_diffusion_pipeline.dispatch(self.ui.reaction_diffusion.steps, reseed, encoder);
+        }
+    }
+
+    fn compile_shadertoy(&mut self) {
+        let (width, height) = self.scene_size();
+        let settings = &mut self.ui.shadertoy;
+        if !settings.enabled || !std::mem::take(&mut settings.compile) {
+            return;
+        }
+
+        let format = self.off_screen_texture.format();
+        let pipeline = shadertoy::transpile(&settings.source).and_then(|source| {
+            gpu::validated(&self.device, || {
+                ShadertoyPipeline::new(source, Global::new(width, height), format, &self.device)
+            })
+        });
+
+        // A failed compile keeps the previous backdrop running so live edits don't flash black.
+        match pipeline {
+            Ok(pipeline) => {
+                self.shadertoy_pipeline = Some(pipeline);
+                settings.status = None;
+            }
+            Err(err) => settings.status = Some(format!("{err:#}")),
+        }
+    }
+
+    fn cull_pass(&mut self, baseline_mode: Option<SceneMode>, encoder: &mut CommandEncoder) {
+        self.scene_pipeline
+            .dispatch_cull(self.ui.scene.mode, encoder);
+        if let Some(mode) = baseline_mode {
+            self.baseline_pipeline.dispatch_cull(mode, encoder);
+        }
+    }
+
+    fn scene_pass(
+        &mut self,
+        baseline_mode: Option<SceneMode>,
+        backdrop: bool,
+        background: bool,
+        encoder: &mut CommandEncoder,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &self.off_screen_texture_view,
+
+                ops: Operations {
+                    load: LoadOp::Clear(Color::BLACK),
+                    store: StoreOp::Store,
+                },
+                resolve_target: None,
+                depth_slice: None,
+            })],
+            label: Some("Render Pass"),
+            ..Default::default()
+        });
+
+        if self.ui.scene.background.enabled {
+            self.background_pipeline.set_render_pass(&mut render_pass);
+        }
+
+        if let Some(shadertoy_pipeline) = &self.shadertoy_pipeline
+            && backdrop
+        {
+            shadertoy_pipeline.set_render_pass(self.ui.shadertoy.opacity, &mut render_pass);
+        }
+
+        if let Some(reaction_diffusion_pipeline) = &self.reaction_diffusion_pipeline
+            && background
+        {
+            reaction_diffusion_pipeline.set_render_pass(&mut render_pass);
+        }
+
+        match baseline_mode {
+            Some(mode) => {
+                let (width, height) = self.scene_size();
+                let split = ((self.ui.compare.split * width as f32) as u32).min(width);
+
+                render_pass.set_scissor_rect(0, 0, split, height);
+                self.scene_pipeline
+                    .set_render_pass(self.ui.scene.mode, &mut render_pass);
+
+                render_pass.set_scissor_rect(split, 0, width - split, height);
+                self.baseline_pipeline
+                    .set_render_pass(mode, &mut render_pass);
+            }
+            None => self
+                .scene_pipeline
+                .set_render_pass(self.ui.scene.mode, &mut render_pass),
+        }
+    }
+
+    fn update_monitor_pipeline(&mut self) {
+        if self.ui.routing.stage(RouteOutput::Window) != RouteStage::PrePost {
+            self.monitor_pipeline = None;
+            return;
+        }
+
+        let viewport = self.scene_viewport;
+        let post_format =
+            color_space::surface_view_format(self.config.format, self.ui.color_space.srgb_surface);
+
+        // Same shader as the post pass, but with grading and strobe left neutral and the raw scene
+        // as its source, so the window shows what the scene pass drew.
+        let settings = PostSettings::new(post_format.is_srgb(), self.ui.color_space.linear_output)
+            .with_viewport(viewport)
+            .with_hdr(&self.ui.color_space);
+        match &mut self.monitor_pipeline {
+            Some(monitor_pipeline) => {
+                monitor_pipeline.set_target_format(post_format, &self.device);
+                monitor_pipeline.update_settings(settings, &self.queue);
+            }
+            None => {
+                let pipeline = gpu::validated(&self.device, || {
+                    PostPipeline::new(
+                        &self.off_screen_texture_view,
+                        post_format,
+                        Global::new(self.config.width, self.config.height),
+                        settings,
+                        &self.device,
+                        &self.queue,
+                    )
+                });
+                match pipeline {
+                    Ok(pipeline) => self.monitor_pipeline = Some(pipeline),
+                    Err(err) => {
+                        self.errors
+                            .push(format!("Pre-post monitor unavailable: {err}"));
+                        *self.ui.routing.stage_mut(RouteOutput::Window) = RouteStage::Composite;
+                    }
+                }
+            }
+        }
+    }
+
+    fn post_pass(
+        &self,
+        post_view: &TextureView,
+        viewport: [u32; 4],
+        monitor: bool,
+        encoder: &mut CommandEncoder,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_view,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::BLACK),
+                    store: StoreOp::Store,
+                },
+                resolve_target: None,
+                depth_slice: None,
+            })],
+            label: Some("Post Render Pass"),
+            ..Default::default()
+        });
+
+        let [x, y, width, height] = viewport.map(|value| value as f32);
+        render_pass.set_viewport(x, y, width, height, 0., 1.);
+        match &self.monitor_pipeline {
+            Some(monitor_pipeline) if monitor => monitor_pipeline.set_render_pass(&mut render_pass),
+            _ => self.post_pipeline.set_render_pass(&mut render_pass),
+        }
+    }
+
+    pub(crate) fn render(&mut self) -> Result<()> {
+        match self.effective_frame_interval() {
+            Some(interval) => self.next_frame = Instant::now() + interval,
+            None => self.window.request_redraw(),
+        }
+
+        self.update_quality();
+        self.apply_scene_rescale();
+
+        for message in self.errors.take() {
+            self.ui.errors.record(message);
+        }
+
+        #[cfg(feature = "http")]
+        self.handle_remote_requests();
+
+        self.ambient.update(&mut self.ui);
+        self.preset_watcher.update(&mut self.ui);
+        self.playlist.update(&mut self.ui);
+        self.title.update(&self.window, &self.ui);
+
+        self.art_net.receive(&mut self.ui.art_net, &self.device);
+
+        self.load_requested_textures();
+        self.load_requested_lut();
+        self.save_requested_screenshot();
+        let replay_texture = self
+            .routes
+            .texture(self.ui.routing.stage(RouteOutput::Replay))
+            .unwrap_or(&self.off_screen_texture);
+        self.replay.update(
+            &mut self.ui.replay,
+            &self.device,
+            &self.queue,
+            replay_texture,
+        );
+
+        self.audio
+            .set_gpu_analysis(self.ui.audio.gpu_analysis && self.spectrum_pipeline.is_some());
+        if let Some(spectrum_pipeline) = &mut self.spectrum_pipeline
+            && let Some((bins, bands)) = spectrum_pipeline.receive(&self.device)
+        {
+            self.audio.apply_gpu_analysis(&bins, bands);
+        }
+        self.audio.update();
 
-        self.sine_pipeline.update_global_frame(&self.queue);
-        self.sine_pipeline
-            .update_sine_wave_data(&self.ui.waves.0, &self.queue);
+        let mut audio = self.audio.features().scaled(self.ui.audio.reactivity);
+
+        if let Some(stream) = &mut self.stream {
+            let values = stream.poll();
+            for macro_control in &mut self.ui.macros {
+                macro_control.follow_source(values);
+            }
+            self.ui.stream_values.clone_from(values);
+        }
+
+        if let Some(show_control) = &mut self.show_control {
+            show_control.update(&mut self.ui);
+            // STOP holds the scene where it is; RESUME carries on from the same frame.
+            let stopped = self.ui.show_control.stopped;
+            self.clock.set_paused(stopped);
+            let time_step = if stopped { 0. } else { 1. };
+            self.scene_pipeline.set_time_step(time_step);
+            self.baseline_pipeline.set_time_step(time_step);
+        }
+
+        let mut elapsed = self.clock.elapsed();
+        if let Some(frame) = self.clock_sync.receive(&mut self.ui.clock_sync) {
+            elapsed = frame.elapsed;
+            audio.beat = frame.beat;
+            self.scene_pipeline.set_phase(frame.phase);
+            self.baseline_pipeline.set_phase(frame.phase);
+        }
+
+        for wave in &mut self.ui.waves.0 {
+            wave.motion.record(wave.center);
+        }
+
+        self.quantizer.update(&mut self.ui, &audio);
+        let mut waves = self.quantizer.waves(&self.ui).to_vec();
+        for macro_control in &self.ui.macros {
+            macro_control.apply(&mut waves);
+        }
+        macros::apply_globals(&mut self.ui);
+        if let Some(solo) = self.ui.scene.selection.solo {
+            for (i, wave) in waves.iter_mut().enumerate() {
+                wave.init &= i == solo;
+            }
+        }
+        self.ui.bypass.update(&self.ui.stream_values);
+        for (i, wave) in waves.iter_mut().enumerate() {
+            wave.init &= !self.ui.bypass.bypassed(BypassTarget::Layer(i));
+        }
+
+        let (width, height) = self.scene_size();
+        motion::apply(&mut waves, elapsed, width as f32 / height.max(1) as f32);
+        self.reveal.update(&mut self.ui.reveal, &mut waves);
+        self.playlist.blend(&mut waves);
+        self.bursts
+            .update(&mut self.ui.bursts, &self.ui.stream_values);
+        self.bursts.apply(&mut waves);
+        self.compile_shadertoy();
+        self.update_monitor_pipeline();
+        let (scene_width, scene_height) = self.scene_size();
+
+        let waves = self.physics.update(
+            &waves,
+            self.ui.physics.enabled,
+            self.ui.physics.spring,
+            &audio,
+        );
+        self.audio.sonify(&mut self.ui.synth, waves);
+        self.metrics.record(
+            &mut self.ui.metrics,
+            &MetricsFrame {
+                elapsed,
+                frame_ms: self.ui.quality.frame_ms,
+                audio: &audio,
+                waves,
+            },
+        );
+
+        let baseline = self
+            .ui
+            .compare
+            .baseline
+            .as_ref()
+            .filter(|_| self.ui.compare.enabled);
+        let baseline_mode = baseline.map(|baseline| baseline.mode);
 
         let surface_texture = self.surface.get_current_texture()?;
 
+        self.device.push_error_scope(ErrorFilter::Validation);
+
         let texture_view = surface_texture.texture.create_view(&TextureViewDescriptor {
             label: Some("Texture View Descriptor"),
             ..Default::default()
         });
 
+        let viewport = self.scene_viewport;
+        let post_format =
+            color_space::surface_view_format(self.config.format, self.ui.color_space.srgb_surface);
+        let post_view = surface_texture.texture.create_view(&TextureViewDescriptor {
+            label: Some("Post Texture View"),
+            format: Some(post_format),
+            ..Default::default()
+        });
+        self.post_pipeline
+            .set_target_format(post_format, &self.device);
+        let hud = match self.ui.hud_wave() {
+            Some(wave) => {
+                self.hud_pipeline
+                    .set_target_format(post_format, &self.device);
+                self.hud_pipeline.update(
+                    &self.ui.hud,
+                    wave,
+                    &self.ui.scene.camera,
+                    viewport,
+                    self.ui.scene.stretch,
+                    &self.queue,
+                );
+                true
+            }
+            None => false,
+        };
+        let strobe = self.strobe.update(&self.ui.strobe, &audio);
+        let mut post_settings =
+            PostSettings::new(post_format.is_srgb(), self.ui.color_space.linear_output)
+                .with_viewport(viewport)
+                .with_master(self.ui.scene.master_opacity)
+                .with_hdr(&self.ui.color_space)
+                .with_opacity(
+                    self.ui.widget.opacity(),
+                    self.config.alpha_mode == CompositeAlphaMode::PreMultiplied,
+                );
+        if !self.ui.bypass.bypassed(BypassTarget::Strobe) {
+            post_settings = post_settings.with_strobe(strobe);
+        }
+        if !self.ui.bypass.bypassed(BypassTarget::Grade) {
+            post_settings = post_settings.with_grade(&self.ui.grade);
+        }
+        self.post_pipeline
+            .update_settings(post_settings, &self.queue);
+        self.routes.prepare(
+            &self.ui.routing,
+            [self.config.width, self.config.height],
+            self.config.format,
+            post_format,
+            &self.device,
+        );
+
         let mut encoder = self
             .device
             .create_command_encoder(&CommandEncoderDescriptor {
                 label: Some("Command Encoder"),
             });
 
+        let spectrum_frame = self.audio.take_pending();
         {
-            let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
-                color_attachments: &[Some(RenderPassColorAttachment {
-                    view: &self.off_screen_texture_view,
-
-                    ops: Operations {
-                        load: LoadOp::Clear(Color::BLACK),
-                        store: StoreOp::Store,
-                    },
-                    resolve_target: None,
-                    depth_slice: None,
-                })],
-                label: Some("Render Pass"),
-                ..Default::default()
-            });
+            let mut upload = self.uploads.begin(&mut encoder, &self.device);
+
+            if self.ui.scene.background.enabled {
+                self.background_pipeline.update(
+                    &self.ui.scene.background,
+                    scene_width as f32 / scene_height.max(1) as f32,
+                    &mut upload,
+                );
+            }
+
+            let transients = self.ui.transients.update();
+            self.scene_pipeline.update(
+                waves,
+                transients,
+                &self.ui.scene.ramp,
+                &self.ui.scene,
+                self.audio_response.update(waves, &audio),
+                &mut upload,
+            );
+            self.clock_sync.broadcast(
+                &mut self.ui.clock_sync,
+                ClockFrame {
+                    phase: self.scene_pipeline.phase(),
+                    elapsed,
+                    beat: audio.beat,
+                },
+            );
+            self.scene_pipeline.update_scope(
+                &scope::capture(self.audio.waveform(), self.ui.audio.scope_trigger),
+                &mut upload,
+            );
+
+            match (&spectrum_frame, &mut self.spectrum_pipeline) {
+                (Some(frame), Some(spectrum_pipeline)) => {
+                    spectrum_pipeline.update(frame, &mut upload);
+                }
+                _ => {
+                    self.scene_pipeline
+                        .update_spectrum(self.audio.spectrum(), &mut upload);
+                    self.baseline_pipeline
+                        .update_spectrum(self.audio.spectrum(), &mut upload);
+                }
+            }
+
+            if let Some(baseline) = baseline {
+                self.baseline_pipeline.update(
+                    &baseline.waves.0,
+                    &[],
+                    &baseline.ramp,
+                    &self.ui.scene,
+                    self.baseline_audio_response
+                        .update(&baseline.waves.0, &audio),
+                    &mut upload,
+                );
+            }
+
+            if let Some(shadertoy_pipeline) = &mut self.shadertoy_pipeline {
+                shadertoy_pipeline.update_time(
+                    self.ui.shadertoy.time(self.scene_pipeline.phase()),
+                    &mut upload,
+                );
+            }
 
-            self.sine_pipeline.set_render_pass(&mut render_pass);
+            if let Some(pixel_sort_pipeline) = &mut self.pixel_sort_pipeline {
+                pixel_sort_pipeline.update(&self.ui.pixel_sort, &mut upload);
+            }
+
+            if let Some(field_warp_pipeline) = &mut self.field_warp_pipeline {
+                field_warp_pipeline.update(&self.ui.field_warp, &mut upload);
+            }
+
+            let reaction_diffusion = &self.ui.reaction_diffusion;
+            self.scene_pipeline
+                .update_modulation(reaction_diffusion, &mut upload);
+            self.baseline_pipeline
+                .update_modulation(reaction_diffusion, &mut upload);
+            if let Some(reaction_diffusion_pipeline) = &mut self.reaction_diffusion_pipeline {
+                reaction_diffusion_pipeline.update(reaction_diffusion, &mut upload);
+            }
         }
+        self.uploads.finish();
 
-        {
-            let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
-                color_attachments: &[Some(RenderPassColorAttachment {
-                    view: &texture_view,
-                    ops: Operations {
-                        load: LoadOp::Clear(Color::BLACK),
-                        store: StoreOp::Store,
-                    },
-                    resolve_target: None,
-                    depth_slice: None,
-                })],
-                label: Some("Post Render Pass"),
-                ..Default::default()
-            });
+        let spectrum_ready = spectrum_frame.is_some() && self.spectrum_pipeline.is_some();
+        let reseed = std::mem::take(&mut self.ui.reaction_diffusion.reseed);
+        let bypass = &self.ui.bypass;
+        let reaction_diffusion = self.ui.reaction_diffusion.enabled
+            && self.reaction_diffusion_pipeline.is_some()
+            && !bypass.bypassed(BypassTarget::ReactionDiffusion);
+        let background = reaction_diffusion && self.ui.reaction_diffusion.background;
+        let backdrop = self.ui.shadertoy.enabled
+            && self.shadertoy_pipeline.is_some()
+            && !bypass.bypassed(BypassTarget::Shadertoy);
+        let baseline_saved = std::mem::take(&mut self.ui.compare.baseline_saved);
+
+        let mut stats = self.scene_pipeline.draw_stats(self.ui.scene.mode);
+        if let Some(mode) = baseline_mode {
+            stats = stats.add(self.baseline_pipeline.draw_stats(mode));
+        }
+        if background {
+            stats = stats.add(DrawStats::fullscreen());
+        }
+        if backdrop {
+            stats = stats.add(DrawStats::fullscreen());
+        }
+        if hud {
+            stats = stats.add(DrawStats::fullscreen());
+        }
+        self.ui.quality.draw = stats.add(DrawStats::fullscreen());
 
-            self.post_pipeline.set_render_pass(&mut render_pass);
+        let post_effects =
+            quality::LEVELS[self.ui.quality.level].post_effects && !self.ui.power.active;
+        let sorted = self.ui.pixel_sort.enabled
+            && self.pixel_sort_pipeline.is_some()
+            && post_effects
+            && !bypass.bypassed(BypassTarget::PixelSort);
+        let warped = self.ui.field_warp.enabled
+            && self.field_warp_pipeline.is_some()
+            && post_effects
+            && !bypass.bypassed(BypassTarget::FieldWarp);
+        if sorted != self.post_source_sorted || warped != self.post_source_warped {
+            self.post_source_sorted = sorted;
+            self.post_source_warped = warped;
+            self.update_post_source();
         }
 
-        self.ui.render(
-            &self.window,
-            &self.device,
-            &self.queue,
-            &texture_view,
-            &mut encoder,
+        let mut graph = RenderGraph::<Self>::new();
+        let spectrum = graph.import("Spectrum");
+        let pattern = graph.import("Reaction Diffusion");
+        let wave_textures = graph.import("Baseline Wave Textures");
+        let draw_args = graph.import("Draw Arguments");
+        let scene = graph.import(SCENE_DEBUG_TARGET);
+        let sorted_scene = graph.import(PIXEL_SORT_DEBUG_TARGET);
+        let warped_scene = graph.import(FIELD_WARP_DEBUG_TARGET);
+        let surface = graph.import("Surface");
+
+        if spectrum_ready {
+            graph.add_pass("Spectrum", &[], &[spectrum], |render, encoder| {
+                render.spectrum_pass(encoder)
+            });
+        }
+        if reaction_diffusion {
+            graph.add_pass(
+                "Reaction Diffusion",
+                &[],
+                &[pattern],
+                move |render, encoder| render.reaction_diffusion_pass(reseed, encoder),
+            );
+        }
+        if baseline_saved {
+            graph.add_pass(
+                "Baseline Wave Textures",
+                &[],
+                &[wave_textures],
+                |render, encoder| {
+                    render
+                        .baseline_pipeline
+                        .copy_wave_textures_from(&render.scene_pipeline, encoder)
+                },
+            );
+        }
+        graph.add_pass("Cull", &[], &[draw_args], move |render, encoder| {
+            render.cull_pass(baseline_mode, encoder)
+        });
+        graph.add_pass(
+            "Scene",
+            &[spectrum, pattern, wave_textures, draw_args],
+            &[scene],
+            move |render, encoder| render.scene_pass(baseline_mode, backdrop, background, encoder),
+        );
+        if sorted {
+            graph.add_pass(
+                "Pixel Sort",
+                &[scene],
+                &[sorted_scene],
+                |render, encoder| {
+                    if let Some(pixel_sort_pipeline) = &render.pixel_sort_pipeline {
+                        pixel_sort_pipeline.dispatch(encoder);
+                    }
+                },
+            );
+        }
+        if warped {
+            graph.add_pass(
+                "Field Warp",
+                &[scene, sorted_scene],
+                &[warped_scene],
+                |render, encoder| {
+                    if let Some(field_warp_pipeline) = &render.field_warp_pipeline {
+                        field_warp_pipeline.dispatch(encoder);
+                    }
+                },
+            );
+        }
+        graph.add_pass("Art-Net", &[scene], &[], |render, encoder| {
+            render.art_net.record(
+                &mut render.ui.art_net,
+                &render.off_screen_texture,
+                encoder,
+                &render.device,
+            )
+        });
+        let hud_view = post_view.clone();
+        graph.add_pass(
+            "Post",
+            &[scene, sorted_scene, warped_scene],
+            &[surface],
+            move |render, encoder| render.post_pass(&post_view, viewport, true, encoder),
         );
+        if hud {
+            graph.add_pass("HUD", &[surface], &[surface], move |render, encoder| {
+                render.hud_pipeline.draw(&hud_view, viewport, encoder)
+            });
+        }
+        if let Some(ui_layer_pipeline) = &self.ui_layer_pipeline {
+            ui_layer_pipeline.update(self.ui.color_space.sdr_white, &self.queue);
+        }
+        graph.add_pass("UI", &[surface], &[surface], move |render, encoder| {
+            let ui_view = match &render.ui_layer_pipeline {
+                Some(ui_layer_pipeline) => ui_layer_pipeline.begin(encoder),
+                None => &texture_view,
+            };
+            render.ui.render(
+                &render.window,
+                &render.device,
+                &render.queue,
+                ui_view,
+                encoder,
+                &mut render.audio,
+            );
+            if let Some(ui_layer_pipeline) = &render.ui_layer_pipeline {
+                ui_layer_pipeline.composite(&texture_view, encoder);
+            }
+        });
+
+        if let Some(route_view) = self.routes.view(RouteStage::Post, post_format) {
+            graph.add_pass(
+                "Route Post",
+                &[scene, sorted_scene, warped_scene],
+                &[],
+                move |render, encoder| render.post_pass(&route_view, viewport, false, encoder),
+            );
+        }
+        if let Some(route_view) = self.routes.view(RouteStage::Composite, post_format)
+            && let Some(ui_view) = self.routes.view(RouteStage::Composite, self.config.format)
+        {
+            graph.add_pass(
+                "Route Post + UI",
+                &[scene, sorted_scene, warped_scene, surface],
+                &[],
+                move |render, encoder| {
+                    render.post_pass(&route_view, viewport, false, encoder);
+                    // The UI pass has already drawn this frame's layer.
+                    match &render.ui_layer_pipeline {
+                        Some(ui_layer_pipeline) => ui_layer_pipeline.composite(&ui_view, encoder),
+                        None => render.ui.paint_over(&render.window, &ui_view, encoder),
+                    }
+                },
+            );
+        }
+
+        if let Err(err) = graph.execute(self, &mut encoder) {
+            self.errors.push(format!("Render graph error: {err}"));
+        }
 
         self.queue.submit(std::iter::once(encoder.finish()));
+
+        if let Some(err) = pollster::block_on(self.device.pop_error_scope()) {
+            self.errors.push(format!("Frame validation error: {err}"));
+        }
+
+        self.uploads.recall();
+        self.art_net.submitted();
+        if let Some(spectrum_pipeline) = &mut self.spectrum_pipeline {
+            spectrum_pipeline.submitted();
+        }
         surface_texture.present();
 
         Ok(())
     }
 }
+
+// The HTTP API is unauthenticated, so it may only point textures below the working directory.
+#[cfg(feature = "http")]
+fn remote_texture_error(path: &str) -> Option<String> {
+    use std::path::Component;
+
+    let path = Path::new(path.trim());
+    let contained = path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+
+    (!contained).then(|| {
+        format!(
+            "Texture path `{}` must be relative and stay inside the working directory",
+            path.display()
+        )
+    })
+}
@@ -0,0 +1,132 @@
+use anyhow::{Result, bail};
+use wgpu::CommandEncoder;
+
+// Resources are owned by the passes that use them; the graph only orders passes by what they
+// read and write.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct ResourceId(usize);
+
+type PassFn<C> = Box<dyn FnOnce(&mut C, &mut CommandEncoder)>;
+
+struct Pass<C> {
+    name: &'static str,
+    reads: Vec<ResourceId>,
+    writes: Vec<ResourceId>,
+    run: PassFn<C>,
+}
+
+pub(crate) struct RenderGraph<C> {
+    resources: Vec<&'static str>,
+    passes: Vec<Pass<C>>,
+}
+
+impl<C> RenderGraph<C> {
+    pub(crate) fn new() -> Self {
+        Self {
+            resources: Vec::new(),
+            passes: Vec::new(),
+        }
+    }
+
+    pub(crate) fn import(&mut self, name: &'static str) -> ResourceId {
+        self.resources.push(name);
+        ResourceId(self.resources.len() - 1)
+    }
+
+    pub(crate) fn add_pass(
+        &mut self,
+        name: &'static str,
+        reads: &[ResourceId],
+        writes: &[ResourceId],
+        run: impl FnOnce(&mut C, &mut CommandEncoder) + 'static,
+    ) {
+        self.passes.push(Pass {
+            name,
+            reads: reads.to_vec(),
+            writes: writes.to_vec(),
+            run: Box::new(run),
+        });
+    }
+
+    // Writers of a resource keep their insertion order; pure readers run after every writer.
+    fn schedule(&self) -> Result<Vec<usize>> {
+        let count = self.passes.len();
+        let mut edges = vec![Vec::new(); count];
+        let mut incoming = vec![0; count];
+
+        for resource in 0..self.resources.len() {
+            let resource = ResourceId(resource);
+            let writers = (0..count)
+                .filter(|&pass| self.passes[pass].writes.contains(&resource))
+                .collect::<Vec<_>>();
+            let readers = (0..count).filter(|&pass| {
+                self.passes[pass].reads.contains(&resource) && !writers.contains(&pass)
+            });
+
+            let mut add_edge = |from: usize, to: usize| {
+                edges[from].push(to);
+                incoming[to] += 1;
+            };
+            for pair in writers.windows(2) {
+                add_edge(pair[0], pair[1]);
+            }
+            for reader in readers {
+                for &writer in &writers {
+                    add_edge(writer, reader);
+                }
+            }
+        }
+
+        let mut order = Vec::with_capacity(count);
+        let mut scheduled = vec![false; count];
+        while order.len() < count {
+            let Some(next) = (0..count).find(|&pass| !scheduled[pass] && incoming[pass] == 0)
+            else {
+                let blocked = (0..count)
+                    .filter(|&pass| !scheduled[pass])
+                    .collect::<Vec<_>>();
+                let passes = blocked
+                    .iter()
+                    .map(|&pass| self.passes[pass].name)
+                    .collect::<Vec<_>>();
+                let mut resources = blocked
+                    .iter()
+                    .flat_map(|&pass| &self.passes[pass].writes)
+                    .map(|resource| self.resources[resource.0])
+                    .collect::<Vec<_>>();
+                resources.sort_unstable();
+                resources.dedup();
+                bail!(
+                    "Render graph has a cycle between {} over {}",
+                    passes.join(", "),
+                    resources.join(", ")
+                );
+            };
+
+            scheduled[next] = true;
+            order.push(next);
+            for &to in &edges[next] {
+                incoming[to] -= 1;
+            }
+        }
+
+        Ok(order)
+    }
+
+    pub(crate) fn execute(self, context: &mut C, encoder: &mut CommandEncoder) -> Result<()> {
+        let order = self.schedule()?;
+        let mut passes = self.passes.into_iter().map(Some).collect::<Vec<_>>();
+
+        for index in order {
+            let Some(pass) = passes[index].take() else {
+                continue;
+            };
+
+            encoder.push_debug_group(pass.name);
+            (pass.run)(context, encoder);
+            encoder.pop_debug_group();
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use std::time::{Duration, Instant};
+
+use egui::{ClippedPrimitive, FullOutput, ViewportId};
+use egui_wgpu::ScreenDescriptor;
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct ScreenKey {
+    pub(crate) size: [u32; 2],
+    pub(crate) pixels_per_point: f32,
+}
+
+impl ScreenKey {
+    pub(crate) fn descriptor(self) -> ScreenDescriptor {
+        ScreenDescriptor {
+            size_in_pixels: self.size,
+            pixels_per_point: self.pixels_per_point,
+        }
+    }
+}
+
+pub(crate) struct UiRepaint {
+    pub(crate) throttle: bool,
+    pub(crate) idle_fps: f32,
+}
+
+impl Default for UiRepaint {
+    fn default() -> Self {
+        Self {
+            throttle: true,
+            idle_fps: 4.,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct RepaintSchedule {
+    requested: bool,
+    deadline: Option<Instant>,
+    last_pass: Option<Instant>,
+    screen: Option<ScreenKey>,
+    primitives: Vec<ClippedPrimitive>,
+}
+
+impl RepaintSchedule {
+    pub(crate) fn request(&mut self) {
+        self.requested = true;
+    }
+
+    pub(crate) fn due(&self, settings: &UiRepaint, screen: ScreenKey) -> bool {
+        if !settings.throttle || self.requested || self.screen != Some(screen) {
+            return true;
+        }
+
+        let now = Instant::now();
+        let idle = Duration::from_secs_f32(1. / settings.idle_fps.max(0.1));
+
+        self.deadline.is_some_and(|deadline| now >= deadline)
+            || self
+                .last_pass
+                .is_none_or(|last| now.duration_since(last) >= idle)
+    }
+
+    pub(crate) fn finish(
+        &mut self,
+        output: &FullOutput,
+        screen: ScreenKey,
+        primitives: Vec<ClippedPrimitive>,
+    ) {
+        let delay = output
+            .viewport_output
+            .get(&ViewportId::ROOT)
+            .map_or(Duration::ZERO, |viewport| viewport.repaint_delay);
+        let now = Instant::now();
+
+        self.requested = false;
+        self.deadline = now.checked_add(delay);
+        self.last_pass = Some(now);
+        self.screen = Some(screen);
+        self.primitives = primitives;
+    }
+
+    pub(crate) fn primitives(&self) -> &[ClippedPrimitive] {
+        &self.primitives
+    }
+}
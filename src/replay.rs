@@ -0,0 +1,347 @@
+use std::{
+    collections::VecDeque,
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicU8, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result, anyhow};
+use image::RgbaImage;
+use png::{BitDepth, ColorType, Encoder};
+use wgpu::{
+    Buffer, BufferDescriptor, BufferUsages, COPY_BYTES_PER_ROW_ALIGNMENT, Device, Extent3d,
+    MapMode, PollType, Queue, TexelCopyBufferInfo, TexelCopyBufferLayout, Texture,
+    TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    wgt::CommandEncoderDescriptor,
+};
+use winit::{event::WindowEvent, keyboard::ModifiersState};
+
+use crate::{kiosk::Chord, pipelines::downscale::DownscalePipeline};
+
+pub(crate) const HOTKEY: &str = "ctrl+shift+r";
+
+// Captures in flight at once; when all are still mapping the GPU is behind and a frame is skipped.
+const READBACK_SLOTS: usize = 3;
+
+const MAP_PENDING: u8 = 0;
+const MAP_READY: u8 = 1;
+const MAP_FAILED: u8 = 2;
+
+pub(crate) struct UiReplay {
+    pub(crate) enabled: bool,
+    pub(crate) seconds: f32,
+    pub(crate) fps: u16,
+    pub(crate) max_width: u32,
+    pub(crate) directory: String,
+    pub(crate) save_requested: bool,
+    pub(crate) buffered_secs: f32,
+    pub(crate) status: Option<String>,
+    chord: Chord,
+    modifiers: ModifiersState,
+}
+
+impl Default for UiReplay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            seconds: 10.,
+            fps: 15,
+            max_width: 640,
+            directory: "replays".to_owned(),
+            save_requested: false,
+            buffered_secs: 0.,
+            status: None,
+            chord: Chord::parse(HOTKEY).expect("replay hotkey is valid"),
+            modifiers: ModifiersState::empty(),
+        }
+    }
+}
+
+impl UiReplay {
+    pub(crate) fn handle_input(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::ModifiersChanged(modifiers) => self.modifiers = modifiers.state(),
+            WindowEvent::KeyboardInput { event, .. }
+                if self.enabled && self.chord.matches(self.modifiers, event) =>
+            {
+                self.save_requested = true;
+            }
+            _ => {}
+        }
+    }
+}
+
+struct Slot {
+    texture: Texture,
+    buffer: Buffer,
+    bytes_per_row: u32,
+    map_state: Arc<AtomicU8>,
+}
+
+impl Slot {
+    fn new(format: TextureFormat, [width, height]: [u32; 2], device: &Device) -> Self {
+        let bytes_per_row =
+            (4 * width).div_ceil(COPY_BYTES_PER_ROW_ALIGNMENT) * COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        Self {
+            texture: device.create_texture(&TextureDescriptor {
+                label: Some("Replay Frame"),
+                size: Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+                view_formats: &[],
+            }),
+            buffer: device.create_buffer(&BufferDescriptor {
+                label: Some("Replay Readback Buffer"),
+                size: (bytes_per_row * height) as u64,
+                usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            }),
+            bytes_per_row,
+            map_state: Arc::new(AtomicU8::new(MAP_READY)),
+        }
+    }
+
+    fn read(&self) -> Option<RgbaImage> {
+        let size = self.texture.size();
+        let row = 4 * size.width as usize;
+
+        let mut pixels = Vec::with_capacity(row * size.height as usize);
+        {
+            let mapped = self.buffer.slice(..).get_mapped_range();
+            for padded in mapped.chunks(self.bytes_per_row as usize) {
+                pixels.extend_from_slice(&padded[..row]);
+            }
+        }
+        self.buffer.unmap();
+
+        RgbaImage::from_raw(size.width, size.height, pixels)
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ReplayBuffer {
+    frames: VecDeque<RgbaImage>,
+    next_capture: Option<Instant>,
+    saving: Option<JoinHandle<Result<PathBuf>>>,
+    downscale: Option<DownscalePipeline>,
+    slots: Vec<Slot>,
+    in_flight: VecDeque<usize>,
+}
+
+impl ReplayBuffer {
+    pub(crate) fn update(
+        &mut self,
+        settings: &mut UiReplay,
+        device: &Device,
+        queue: &Queue,
+        texture: &Texture,
+    ) {
+        if self.saving.as_ref().is_some_and(JoinHandle::is_finished)
+            && let Some(saving) = self.saving.take()
+        {
+            settings.status = Some(match saving.join() {
+                Ok(Ok(path)) => format!("Saved replay to {}", path.display()),
+                Ok(Err(err)) => format!("Failed to save replay: {err:#}"),
+                Err(_) => "Failed to save replay: encoder panicked".to_owned(),
+            });
+        }
+
+        if !settings.enabled {
+            self.frames.clear();
+            self.next_capture = None;
+            self.slots.clear();
+            self.in_flight.clear();
+            settings.buffered_secs = 0.;
+            return;
+        }
+
+        self.receive(settings, device);
+
+        if std::mem::take(&mut settings.save_requested) {
+            self.save(settings);
+        }
+
+        let now = Instant::now();
+        let interval = Duration::from_secs_f32(1. / settings.fps.max(1) as f32);
+        if self.next_capture.is_some_and(|next| now < next) {
+            return;
+        }
+        self.next_capture = Some(now + interval);
+
+        self.record(settings, device, queue, texture);
+    }
+
+    // Finished readbacks are taken in submission order, without waiting on the ones still mapping.
+    fn receive(&mut self, settings: &mut UiReplay, device: &Device) {
+        if self.in_flight.is_empty() {
+            return;
+        }
+
+        let _ = device.poll(PollType::Poll);
+        while let Some(&i) = self.in_flight.front() {
+            match self.slots[i].map_state.load(Ordering::Acquire) {
+                MAP_PENDING => break,
+                MAP_FAILED => {
+                    settings.status = Some("Replay: failed to read back a frame".to_owned());
+                }
+                _ => {
+                    if let Some(image) = self.slots[i].read() {
+                        self.push(image, settings);
+                    }
+                }
+            }
+            self.in_flight.pop_front();
+        }
+    }
+
+    fn record(&mut self, settings: &UiReplay, device: &Device, queue: &Queue, texture: &Texture) {
+        let size = scaled_size([texture.width(), texture.height()], settings.max_width);
+        let format = DownscalePipeline::target_format(texture.format());
+
+        let Some(i) = (0..READBACK_SLOTS).find(|i| !self.in_flight.contains(i)) else {
+            return;
+        };
+        let stale = self.slots.get(i).is_none_or(|slot| {
+            slot.texture.format() != format || [slot.texture.width(), slot.texture.height()] != size
+        });
+        if stale {
+            let slot = Slot::new(format, size, device);
+            if i < self.slots.len() {
+                self.slots[i] = slot;
+            } else {
+                self.slots.push(slot);
+            }
+        }
+        let slot = &self.slots[i];
+
+        let downscale = self
+            .downscale
+            .get_or_insert_with(|| DownscalePipeline::new(device));
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("Replay Encoder"),
+        });
+        downscale.record(
+            texture,
+            &slot.texture.create_view(&Default::default()),
+            &mut encoder,
+            device,
+        );
+        encoder.copy_texture_to_buffer(
+            slot.texture.as_image_copy(),
+            TexelCopyBufferInfo {
+                buffer: &slot.buffer,
+                layout: TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(slot.bytes_per_row),
+                    rows_per_image: Some(size[1]),
+                },
+            },
+            slot.texture.size(),
+        );
+        queue.submit(std::iter::once(encoder.finish()));
+
+        slot.map_state.store(MAP_PENDING, Ordering::Release);
+        let callback_state = slot.map_state.clone();
+        slot.buffer
+            .slice(..)
+            .map_async(MapMode::Read, move |result| {
+                let state = if result.is_ok() {
+                    MAP_READY
+                } else {
+                    MAP_FAILED
+                };
+                callback_state.store(state, Ordering::Release);
+            });
+        self.in_flight.push_back(i);
+    }
+
+    fn push(&mut self, image: RgbaImage, settings: &mut UiReplay) {
+        // A resize mid-buffer would leave frames APNG can't mix, so the buffer restarts.
+        if self
+            .frames
+            .back()
+            .is_some_and(|last| last.dimensions() != image.dimensions())
+        {
+            self.frames.clear();
+        }
+
+        let capacity = (settings.seconds * settings.fps as f32).ceil().max(1.) as usize;
+        self.frames.push_back(image);
+        while self.frames.len() > capacity {
+            self.frames.pop_front();
+        }
+        settings.buffered_secs = self.frames.len() as f32 / settings.fps.max(1) as f32;
+    }
+
+    fn save(&mut self, settings: &mut UiReplay) {
+        if self.saving.is_some() {
+            settings.status = Some("A replay is still being written".to_owned());
+            return;
+        }
+        if self.frames.is_empty() {
+            settings.status = Some("Replay buffer is empty".to_owned());
+            return;
+        }
+
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let path = Path::new(settings.directory.trim()).join(format!("replay-{seconds}.apng"));
+        let frames = std::mem::take(&mut self.frames);
+        let fps = settings.fps.max(1);
+
+        settings.status = Some(format!("Writing {}...", path.display()));
+        self.saving = Some(thread::spawn(move || {
+            write_apng(&frames, fps, &path)?;
+            Ok(path)
+        }));
+    }
+}
+
+fn scaled_size([width, height]: [u32; 2], max_width: u32) -> [u32; 2] {
+    if max_width == 0 || width <= max_width {
+        return [width, height];
+    }
+
+    let scaled_height = ((height as u64 * max_width as u64) / width as u64).max(1) as u32;
+    [max_width, scaled_height]
+}
+
+fn write_apng(frames: &VecDeque<RgbaImage>, fps: u16, path: &Path) -> Result<()> {
+    let first = frames
+        .front()
+        .ok_or_else(|| anyhow!("Replay buffer is empty"))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut encoder = Encoder::new(BufWriter::new(file), first.width(), first.height());
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+    encoder.set_animated(frames.len() as u32, 0)?;
+    encoder.set_frame_delay(1, fps)?;
+
+    let mut writer = encoder.write_header()?;
+    for frame in frames {
+        writer.write_image_data(frame.as_raw())?;
+    }
+    writer.finish()?;
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use std::{f32::consts::TAU, time::Instant};
+
+use crate::ui::UiSineWaveData;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum RevealStyle {
+    #[default]
+    Grow,
+    Fade,
+    Spiral,
+}
+
+impl RevealStyle {
+    pub(crate) const ALL: [Self; 3] = [Self::Grow, Self::Fade, Self::Spiral];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Grow => "Grow",
+            Self::Fade => "Fade",
+            Self::Spiral => "Spiral",
+        }
+    }
+
+    fn shape(self, wave: &mut UiSineWaveData, envelope: f32) {
+        match self {
+            Self::Grow => {
+                wave.inner_radius *= envelope;
+                wave.amplitude *= envelope;
+            }
+            Self::Fade => wave.fade *= envelope,
+            Self::Spiral => {
+                let turn = (1. - envelope) * TAU;
+                let reach = (1. - envelope) * 0.5;
+                let [x, y] = wave.center;
+                wave.center = [x + reach * turn.cos(), y + reach * turn.sin()];
+                wave.rotation += turn;
+                wave.inner_radius *= envelope;
+                wave.fade *= envelope;
+            }
+        }
+    }
+}
+
+pub(crate) struct UiReveal {
+    pub(crate) enabled: bool,
+    pub(crate) style: RevealStyle,
+    pub(crate) intro_secs: f32,
+    pub(crate) outro_secs: f32,
+    pub(crate) stagger_secs: f32,
+    pub(crate) replaced: Option<Vec<UiSineWaveData>>,
+}
+
+impl Default for UiReveal {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            style: RevealStyle::Grow,
+            intro_secs: 1.5,
+            outro_secs: 0.5,
+            stagger_secs: 0.1,
+            replaced: None,
+        }
+    }
+}
+
+enum Stage {
+    Outro {
+        outgoing: Vec<UiSineWaveData>,
+        started: Instant,
+    },
+    Intro {
+        started: Instant,
+    },
+}
+
+#[derive(Default)]
+pub(crate) struct RevealAnimation {
+    stage: Option<Stage>,
+    envelopes: Vec<f32>,
+}
+
+impl RevealAnimation {
+    pub(crate) fn update(&mut self, settings: &mut UiReveal, waves: &mut Vec<UiSineWaveData>) {
+        if let Some(replaced) = settings.replaced.take() {
+            let started = Instant::now();
+            self.stage = settings.enabled.then(|| {
+                if settings.outro_secs > 0. && replaced.iter().any(|wave| wave.init) {
+                    Stage::Outro {
+                        outgoing: replaced,
+                        started,
+                    }
+                } else {
+                    Stage::Intro { started }
+                }
+            });
+        }
+
+        if let Some(Stage::Outro { outgoing, started }) = &self.stage {
+            let elapsed = started.elapsed().as_secs_f32();
+            let done = Self::fill(
+                &mut self.envelopes,
+                outgoing.len(),
+                elapsed,
+                settings.outro_secs,
+                settings.stagger_secs,
+            );
+            if !done {
+                waves.clone_from(outgoing);
+                for (wave, envelope) in waves.iter_mut().zip(&self.envelopes) {
+                    settings.style.shape(wave, 1. - envelope);
+                }
+                return;
+            }
+
+            self.stage = Some(Stage::Intro {
+                started: Instant::now(),
+            });
+        }
+
+        if let Some(Stage::Intro { started }) = &self.stage {
+            let elapsed = started.elapsed().as_secs_f32();
+            let done = Self::fill(
+                &mut self.envelopes,
+                waves.len(),
+                elapsed,
+                settings.intro_secs,
+                settings.stagger_secs,
+            );
+            for (wave, envelope) in waves.iter_mut().zip(&self.envelopes) {
+                settings.style.shape(wave, *envelope);
+            }
+
+            if done {
+                self.stage = None;
+            }
+        }
+    }
+
+    fn fill(
+        envelopes: &mut Vec<f32>,
+        count: usize,
+        elapsed: f32,
+        duration: f32,
+        stagger: f32,
+    ) -> bool {
+        envelopes.clear();
+        envelopes.extend((0..count).map(|i| {
+            let local = elapsed - stagger * i as f32;
+            let t = if duration > 0. {
+                (local / duration).clamp(0., 1.)
+            } else {
+                1.
+            };
+            t * t * (3. - 2. * t)
+        }));
+
+        envelopes.iter().all(|envelope| *envelope >= 1.)
+    }
+}
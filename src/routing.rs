@@ -0,0 +1,184 @@
+use wgpu::{
+    Device, Extent3d, Texture, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    TextureView, TextureViewDescriptor,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum RouteStage {
+    #[default]
+    PrePost,
+    Post,
+    Composite,
+}
+
+impl RouteStage {
+    pub(crate) const ALL: [Self; 3] = [Self::PrePost, Self::Post, Self::Composite];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::PrePost => "Pre-Post",
+            Self::Post => "Post",
+            Self::Composite => "Post + UI",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RouteOutput {
+    Window,
+    Screenshot,
+    Replay,
+    Remote,
+}
+
+impl RouteOutput {
+    pub(crate) const ALL: [Self; 4] = [Self::Window, Self::Screenshot, Self::Replay, Self::Remote];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Window => "Window",
+            Self::Screenshot => "Screenshot",
+            Self::Replay => "Replay Buffer",
+            Self::Remote => "Remote Snapshot",
+        }
+    }
+
+    pub(crate) fn available(self) -> bool {
+        self != Self::Remote || cfg!(feature = "http")
+    }
+
+    // The window is also the control surface, so it always keeps the panel on top.
+    pub(crate) fn supports(self, stage: RouteStage) -> bool {
+        self != Self::Window || stage != RouteStage::Post
+    }
+}
+
+pub(crate) struct UiRouting {
+    stages: [RouteStage; 4],
+}
+
+impl Default for UiRouting {
+    fn default() -> Self {
+        Self {
+            stages: [
+                RouteStage::Composite,
+                RouteStage::PrePost,
+                RouteStage::PrePost,
+                RouteStage::PrePost,
+            ],
+        }
+    }
+}
+
+impl UiRouting {
+    pub(crate) fn stage(&self, output: RouteOutput) -> RouteStage {
+        self.stages[output as usize]
+    }
+
+    pub(crate) fn stage_mut(&mut self, output: RouteOutput) -> &mut RouteStage {
+        &mut self.stages[output as usize]
+    }
+
+    fn captures(&self, stage: RouteStage) -> bool {
+        RouteOutput::ALL
+            .into_iter()
+            .any(|output| output != RouteOutput::Window && self.stage(output) == stage)
+    }
+}
+
+// Window-sized copies of the post and composite stages, kept only while a capture output reads
+// them. They hold the previous frame, the same as the off-screen texture when a capture runs.
+#[derive(Default)]
+pub(crate) struct RouteTargets {
+    post: Option<Texture>,
+    composite: Option<Texture>,
+    view_format: Option<TextureFormat>,
+}
+
+impl RouteTargets {
+    pub(crate) fn prepare(
+        &mut self,
+        routing: &UiRouting,
+        size: [u32; 2],
+        format: TextureFormat,
+        view_format: TextureFormat,
+        device: &Device,
+    ) {
+        if self.view_format != Some(view_format) {
+            self.post = None;
+            self.composite = None;
+            self.view_format = Some(view_format);
+        }
+
+        for (stage, target) in [
+            (RouteStage::Post, &mut self.post),
+            (RouteStage::Composite, &mut self.composite),
+        ] {
+            if !routing.captures(stage) {
+                *target = None;
+                continue;
+            }
+
+            let stale = target.as_ref().is_none_or(|texture| {
+                texture.format() != format || [texture.width(), texture.height()] != size
+            });
+            if stale {
+                *target = Some(Self::create_texture(
+                    stage,
+                    size,
+                    format,
+                    view_format,
+                    device,
+                ));
+            }
+        }
+    }
+
+    fn create_texture(
+        stage: RouteStage,
+        [width, height]: [u32; 2],
+        format: TextureFormat,
+        view_format: TextureFormat,
+        device: &Device,
+    ) -> Texture {
+        let view_formats = [view_format];
+        device.create_texture(&TextureDescriptor {
+            label: Some(stage.label()),
+            size: Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::COPY_SRC
+                | TextureUsages::TEXTURE_BINDING,
+            view_formats: if view_format == format {
+                &[]
+            } else {
+                &view_formats
+            },
+        })
+    }
+
+    pub(crate) fn texture(&self, stage: RouteStage) -> Option<&Texture> {
+        match stage {
+            RouteStage::PrePost => None,
+            RouteStage::Post => self.post.as_ref(),
+            RouteStage::Composite => self.composite.as_ref(),
+        }
+    }
+
+    pub(crate) fn view(&self, stage: RouteStage, format: TextureFormat) -> Option<TextureView> {
+        self.texture(stage).map(|texture| {
+            texture.create_view(&TextureViewDescriptor {
+                label: Some(stage.label()),
+                format: Some(format),
+                ..Default::default()
+            })
+        })
+    }
+}
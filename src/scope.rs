@@ -0,0 +1,75 @@
+use std::num::NonZero;
+
+use wgpu::{
+    BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor, BindGroupLayoutEntry,
+    BindingType, BufferBindingType, BufferUsages, Device, ShaderStages,
+    util::{BufferInitDescriptor, DeviceExt},
+};
+
+use crate::{audio::analysis::SPECTRUM_BINS, utils::BindGroupData};
+
+pub(crate) const SCOPE_SAMPLES: usize = 512;
+pub(crate) const SPECTRUM_OFFSET: u64 = (SCOPE_SAMPLES * std::mem::size_of::<f32>()) as u64;
+
+pub(crate) fn capture(waveform: &[f32], trigger: bool) -> Vec<f32> {
+    let latest = waveform.len().saturating_sub(SCOPE_SAMPLES);
+    let start = if trigger {
+        let candidates = &waveform[..(latest + 1).min(waveform.len())];
+        rising_crossing(candidates).unwrap_or(latest)
+    } else {
+        latest
+    };
+
+    let mut samples = vec![0.; SCOPE_SAMPLES];
+    for (sample, value) in samples.iter_mut().zip(waveform.iter().skip(start)) {
+        *sample = value.clamp(-1., 1.);
+    }
+
+    samples
+}
+
+fn rising_crossing(window: &[f32]) -> Option<usize> {
+    window
+        .windows(2)
+        .rposition(|pair| pair[0] < 0. && pair[1] >= 0.)
+        .map(|i| i + 1)
+}
+
+pub(crate) fn create_bind_group_data(device: &Device) -> BindGroupData {
+    let size = ((SCOPE_SAMPLES + SPECTRUM_BINS) * std::mem::size_of::<f32>()) as u64;
+
+    let buffer = device.create_buffer_init(&BufferInitDescriptor {
+        label: Some("Scope Buffer"),
+        contents: bytemuck::cast_slice(&[0f32; SCOPE_SAMPLES + SPECTRUM_BINS]),
+        usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+    });
+
+    let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+        label: Some("Scope Bind Group Layout"),
+        entries: &[BindGroupLayoutEntry {
+            binding: 0,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: NonZero::new(size),
+            },
+            count: None,
+        }],
+    });
+
+    let bind_group = device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Scope Bind Group"),
+        layout: &layout,
+        entries: &[BindGroupEntry {
+            binding: 0,
+            resource: buffer.as_entire_binding(),
+        }],
+    });
+
+    BindGroupData {
+        layout,
+        buffer,
+        bind_group,
+    }
+}
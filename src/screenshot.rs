@@ -0,0 +1,187 @@
+use std::{
+    fs::{self, File},
+    io::BufReader,
+    path::Path,
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use image::RgbaImage;
+use png::{BitDepth, ColorType, Decoder, Encoder};
+use wgpu::{
+    BufferDescriptor, BufferUsages, COPY_BYTES_PER_ROW_ALIGNMENT, Device, MapMode, PollType, Queue,
+    TexelCopyBufferInfo, TexelCopyBufferLayout, Texture, TextureFormat,
+    wgt::CommandEncoderDescriptor,
+};
+
+use crate::preset::Preset;
+
+const PRESET_KEYWORD: &str = "Sigil Preset";
+const SOFTWARE_KEYWORD: &str = "Software";
+
+enum Layout {
+    Rgba8,
+    Bgra8,
+    Rgba16Float,
+}
+
+pub(crate) fn capture(device: &Device, queue: &Queue, texture: &Texture) -> Result<RgbaImage> {
+    let layout = match texture.format() {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => Layout::Rgba8,
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => Layout::Bgra8,
+        TextureFormat::Rgba16Float => Layout::Rgba16Float,
+        format => bail!("Screenshots are not supported for {format:?} textures"),
+    };
+    let bytes_per_pixel = match layout {
+        Layout::Rgba16Float => 8,
+        _ => 4,
+    };
+
+    let size = texture.size();
+    let unpadded_bytes_per_row = bytes_per_pixel * size.width;
+    let bytes_per_row = unpadded_bytes_per_row.div_ceil(COPY_BYTES_PER_ROW_ALIGNMENT)
+        * COPY_BYTES_PER_ROW_ALIGNMENT;
+
+    let buffer = device.create_buffer(&BufferDescriptor {
+        label: Some("Screenshot Buffer"),
+        size: (bytes_per_row * size.height) as u64,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("Screenshot Encoder"),
+    });
+
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: Some(size.height),
+            },
+        },
+        size,
+    );
+
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let slice = buffer.slice(..);
+    slice.map_async(MapMode::Read, |_| {});
+    device.poll(PollType::wait_indefinitely())?;
+
+    let mut pixels = Vec::with_capacity((unpadded_bytes_per_row * size.height) as usize);
+    {
+        let mapped = slice.get_mapped_range();
+        for row in mapped.chunks(bytes_per_row as usize) {
+            pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+        }
+    }
+    buffer.unmap();
+
+    match layout {
+        Layout::Rgba8 => {}
+        Layout::Bgra8 => pixels.chunks_mut(4).for_each(|pixel| pixel.swap(0, 2)),
+        Layout::Rgba16Float => pixels = encode_half_floats(&pixels),
+    }
+
+    RgbaImage::from_raw(size.width, size.height, pixels)
+        .ok_or_else(|| anyhow::anyhow!("Screenshot buffer has an unexpected size"))
+}
+
+fn encode_half_floats(pixels: &[u8]) -> Vec<u8> {
+    pixels
+        .chunks_exact(8)
+        .flat_map(|pixel| {
+            let channel =
+                |i: usize| half_to_f32(u16::from_le_bytes([pixel[2 * i], pixel[2 * i + 1]]));
+            let encode = |value: f32| {
+                let value = value.clamp(0., 1.);
+                let value = if value <= 0.003_130_8 {
+                    value * 12.92
+                } else {
+                    1.055 * value.powf(1. / 2.4) - 0.055
+                };
+                (value * 255.).round() as u8
+            };
+
+            [
+                encode(channel(0)),
+                encode(channel(1)),
+                encode(channel(2)),
+                (channel(3).clamp(0., 1.) * 255.).round() as u8,
+            ]
+        })
+        .collect()
+}
+
+pub(crate) fn half_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 == 0 { 1. } else { -1. };
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let mantissa = f32::from(bits & 0x3ff);
+
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0. => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1. + mantissa / 1024.) * 2f32.powi(exponent - 15),
+    }
+}
+
+pub(crate) fn encode_png(image: &RgbaImage, preset: Option<&Preset>) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+
+    let mut encoder = Encoder::new(&mut bytes, image.width(), image.height());
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+    encoder.add_text_chunk(
+        SOFTWARE_KEYWORD.to_owned(),
+        format!("sigil {}", env!("CARGO_PKG_VERSION")),
+    )?;
+    if let Some(preset) = preset {
+        encoder.add_itxt_chunk(PRESET_KEYWORD.to_owned(), serde_json::to_string(preset)?)?;
+    }
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(image.as_raw())?;
+    writer.finish()?;
+
+    Ok(bytes)
+}
+
+pub(crate) fn save(image: &RgbaImage, preset: &Preset, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, encode_png(image, Some(preset))?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    preset.save(&path.with_extension("json"))
+}
+
+pub(crate) fn read_preset(path: &Path) -> Result<Preset> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let reader = Decoder::new(BufReader::new(file)).read_info()?;
+
+    let embedded = reader
+        .info()
+        .utf8_text
+        .iter()
+        .find(|chunk| chunk.keyword == PRESET_KEYWORD)
+        .map(|chunk| chunk.get_text())
+        .transpose()?;
+
+    match embedded {
+        Some(text) => Ok(Preset::parse(&text)?),
+        None => {
+            let sidecar = path.with_extension("json");
+            Preset::load(&sidecar).map_err(|err| {
+                anyhow!(
+                    "{} has no embedded preset and its sidecar could not be read: {err}",
+                    path.display()
+                )
+            })
+        }
+    }
+}
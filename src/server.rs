@@ -0,0 +1,140 @@
+use std::io::Read;
+
+use anyhow::{Result, anyhow};
+use tiny_http::{Header, Method, Request, Response, Server as HttpServer, StatusCode};
+
+// A whole scene is a few tens of kilobytes; anything far past that isn't one.
+const MAX_BODY: u64 = 1 << 20;
+
+pub(crate) enum Command {
+    GetScene,
+    PutScene(String),
+    GetWave(usize),
+    PutWave(usize, String),
+    Screenshot,
+    ListPresets,
+    GetPreset,
+    LoadPreset(String),
+}
+
+pub(crate) enum Reply {
+    Json(String),
+    Png(Vec<u8>),
+    Ok,
+    NotFound,
+    BadRequest(String),
+}
+
+pub(crate) struct Incoming {
+    request: Request,
+    command: Option<Command>,
+}
+
+impl Incoming {
+    // Answers oversized bodies with 413 on the spot, returning `None`.
+    fn new(mut request: Request) -> Option<Self> {
+        let mut body = String::new();
+        let declared = request.body_length().unwrap_or_default() as u64;
+        let read = declared <= MAX_BODY
+            && request
+                .as_reader()
+                .take(MAX_BODY + 1)
+                .read_to_string(&mut body)
+                .is_ok();
+        if declared > MAX_BODY || body.len() as u64 > MAX_BODY {
+            let response = Response::from_string("Payload Too Large").with_status_code(413);
+            if let Err(err) = request.respond(response) {
+                log::warn!("Failed to send HTTP response: {err}");
+            }
+            return None;
+        }
+
+        let segments = request
+            .url()
+            .split('?')
+            .next()
+            .unwrap_or_default()
+            .trim_matches('/')
+            .split('/')
+            .collect::<Vec<_>>();
+
+        let command = match (request.method(), segments.as_slice()) {
+            (Method::Get, ["scene"]) => Some(Command::GetScene),
+            (Method::Put, ["scene"]) if read => Some(Command::PutScene(body)),
+            (Method::Get, ["waves", index]) => index.parse().ok().map(Command::GetWave),
+            (Method::Put, ["waves", index]) if read => index
+                .parse()
+                .ok()
+                .map(|index| Command::PutWave(index, body)),
+            (Method::Get, ["screenshot"]) => Some(Command::Screenshot),
+            (Method::Get, ["presets"]) => Some(Command::ListPresets),
+            (Method::Get, ["preset"]) => Some(Command::GetPreset),
+            (Method::Put, ["preset"]) if read => Some(Command::LoadPreset(body.trim().to_owned())),
+            _ => None,
+        };
+
+        Some(Self { request, command })
+    }
+
+    pub(crate) fn take_command(&mut self) -> Option<Command> {
+        self.command.take()
+    }
+
+    pub(crate) fn respond(self, reply: Reply) {
+        let result = match reply {
+            Reply::Json(json) => self
+                .request
+                .respond(Response::from_string(json).with_header(content_type("application/json"))),
+            Reply::Png(png) => self
+                .request
+                .respond(Response::from_data(png).with_header(content_type("image/png"))),
+            Reply::Ok => self.request.respond(Response::empty(StatusCode(204))),
+            Reply::NotFound => self
+                .request
+                .respond(Response::from_string("Not Found").with_status_code(404)),
+            Reply::BadRequest(err) => self
+                .request
+                .respond(Response::from_string(err).with_status_code(400)),
+        };
+
+        if let Err(err) = result {
+            log::warn!("Failed to send HTTP response: {err}");
+        }
+    }
+}
+
+fn content_type(value: &str) -> Header {
+    Header::from_bytes("Content-Type", value).expect("Content-Type header is valid")
+}
+
+pub(crate) struct Server {
+    inner: HttpServer,
+}
+
+impl Server {
+    pub(crate) fn start(address: &str) -> Result<Self> {
+        let inner = HttpServer::http(address)
+            .map_err(|err| anyhow!("Failed to start HTTP server on {address}: {err}"))?;
+
+        log::info!("HTTP control endpoint listening on {address}");
+
+        Ok(Self { inner })
+    }
+
+    pub(crate) fn poll(&self) -> Vec<Incoming> {
+        let mut incoming = Vec::new();
+
+        loop {
+            match self.inner.try_recv() {
+                Ok(Some(request)) => incoming.extend(Incoming::new(request)),
+                Ok(None) => break,
+                Err(err) => {
+                    log::warn!("HTTP server error: {err}");
+                    break;
+                }
+            }
+        }
+
+        incoming
+    }
+}
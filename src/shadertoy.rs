@@ -0,0 +1,122 @@
+use std::fs;
+
+use anyhow::{Result, anyhow};
+#[cfg(feature = "shadertoy")]
+use naga::{
+    back::wgsl::WriterFlags,
+    front::glsl::{Frontend, Options},
+    valid::{Capabilities, ValidationFlags, Validator},
+};
+
+// Ticks are frames at the reference rate, so this turns sigil's phase into Shadertoy seconds.
+const TICKS_PER_SECOND: f32 = 60.;
+
+#[cfg(feature = "shadertoy")]
+const PRELUDE: &str = "#version 450
+layout(set = 0, binding = 0) uniform Global {
+    vec2 sigil_resolution;
+    float sigil_phase;
+    float sigil_aspect;
+    vec4 sigil_highlight;
+    float sigil_antialias;
+    float sigil_zoom;
+    vec2 sigil_pan;
+    vec4 sigil_false_color;
+};
+layout(location = 0) out vec4 sigil_color;
+
+#define iResolution vec3(sigil_resolution, 1.0)
+#define iTime sigil_phase
+#define iMouse vec4(0.0)
+";
+
+#[cfg(feature = "shadertoy")]
+const EPILOGUE: &str = "
+void main() {
+    vec4 color = vec4(0.0, 0.0, 0.0, 1.0);
+    mainImage(color, vec2(gl_FragCoord.x, sigil_resolution.y - gl_FragCoord.y));
+
+    vec3 c = clamp(color.rgb, 0.0, 1.0);
+    vec3 linear = mix(c / 12.92, pow((c + 0.055) / 1.055, vec3(2.4)), step(vec3(0.04045), c));
+    sigil_color = vec4(linear, 1.0);
+}
+";
+
+const DEFAULT_SOURCE: &str = "void mainImage(out vec4 fragColor, in vec2 fragCoord) {
+    vec2 uv = fragCoord / iResolution.xy;
+    vec3 col = 0.5 + 0.5 * cos(iTime + uv.xyx + vec3(0, 2, 4));
+    fragColor = vec4(col * 0.4, 1.0);
+}
+";
+
+pub(crate) struct UiShadertoy {
+    pub(crate) enabled: bool,
+    pub(crate) path: String,
+    pub(crate) source: String,
+    pub(crate) opacity: f32,
+    pub(crate) speed: f32,
+    pub(crate) compile: bool,
+    pub(crate) status: Option<String>,
+}
+
+impl Default for UiShadertoy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: String::new(),
+            source: DEFAULT_SOURCE.to_owned(),
+            opacity: 1.,
+            speed: 1.,
+            compile: true,
+            status: None,
+        }
+    }
+}
+
+impl UiShadertoy {
+    pub(crate) fn load_file(&mut self) {
+        match fs::read_to_string(self.path.trim()) {
+            Ok(source) => {
+                self.source = source;
+                self.compile = true;
+            }
+            Err(err) => self.status = Some(format!("`{}`: {err}", self.path.trim())),
+        }
+    }
+
+    pub(crate) fn time(&self, phase: f32) -> f32 {
+        phase * self.speed / TICKS_PER_SECOND
+    }
+}
+
+// Wraps a `mainImage` snippet in a fragment shader reading sigil's global uniform, then lets
+// naga lower it to WGSL so it goes through the same module creation as the built-in shaders.
+#[cfg(feature = "shadertoy")]
+pub(crate) fn transpile(snippet: &str) -> Result<String> {
+    if snippet.contains("iChannel") {
+        return Err(anyhow!(
+            "Texture channels (`iChannel0`..`iChannel3`) are not supported"
+        ));
+    }
+
+    let source = format!("{PRELUDE}{snippet}{EPILOGUE}");
+    let module = Frontend::default()
+        .parse(&Options::from(naga::ShaderStage::Fragment), &source)
+        .map_err(|err| anyhow!("{}", err.emit_to_string(&source)))?;
+    let info = Validator::new(ValidationFlags::all(), Capabilities::empty())
+        .validate(&module)
+        .map_err(|err| anyhow!("{}", err.emit_to_string(&source)))?;
+
+    Ok(naga::back::wgsl::write_string(
+        &module,
+        &info,
+        WriterFlags::empty(),
+    )?)
+}
+
+#[cfg(not(feature = "shadertoy"))]
+pub(crate) fn transpile(_snippet: &str) -> Result<String> {
+    Err(anyhow!(
+        "Shadertoy import requires building with the `shadertoy` feature"
+    ))
+}
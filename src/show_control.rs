@@ -0,0 +1,288 @@
+#[cfg(feature = "network")]
+use std::net::UdpSocket;
+use std::{
+    fs::File,
+    io::Read,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+#[cfg(not(feature = "network"))]
+use anyhow::bail;
+use anyhow::{Result, anyhow};
+
+use crate::ui::Ui;
+
+#[cfg(feature = "network")]
+const MAX_DATAGRAM: usize = 64 * 1024;
+const MAX_SYSEX: usize = 256;
+
+// Universal real-time SysEx: F0 7F <device> 02 <format> <command> <data> F7.
+const SYSEX_REAL_TIME: u8 = 0x7f;
+const SUB_ID_MSC: u8 = 0x02;
+const ALL_CALL: u8 = 0x7f;
+const MSC_GO: u8 = 0x01;
+const MSC_STOP: u8 = 0x02;
+const MSC_RESUME: u8 = 0x03;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum MidiMessage {
+    ProgramChange { channel: u8, program: u8 },
+    Note { channel: u8, note: u8, on: bool },
+    SysEx(Vec<u8>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ShowCommand {
+    Go(Option<usize>),
+    Stop,
+    Resume,
+}
+
+pub(crate) struct UiShowControl {
+    pub(crate) connected: bool,
+    pub(crate) device_id: u8,
+    pub(crate) program_changes: bool,
+    pub(crate) channel: Option<u8>,
+    pub(crate) stopped: bool,
+    pub(crate) status: Option<String>,
+}
+
+impl Default for UiShowControl {
+    fn default() -> Self {
+        Self {
+            connected: false,
+            device_id: 0,
+            program_changes: true,
+            channel: None,
+            stopped: false,
+            status: None,
+        }
+    }
+}
+
+pub(crate) struct ShowControl {
+    messages: Receiver<MidiMessage>,
+}
+
+impl ShowControl {
+    pub(crate) fn open(source: &str) -> Result<Self> {
+        let (sender, messages) = mpsc::channel();
+
+        match source.strip_prefix("udp:") {
+            #[cfg(feature = "network")]
+            Some(address) => {
+                let socket = UdpSocket::bind(address)
+                    .map_err(|err| anyhow!("Failed to bind `{address}`: {err}"))?;
+                thread::spawn(move || read_datagrams(&socket, &sender));
+            }
+            #[cfg(not(feature = "network"))]
+            Some(_) => bail!("`--show-control udp:` requires building with the `network` feature"),
+            None => {
+                // A raw MIDI device such as `/dev/snd/midiC1D0` reads as a plain byte stream.
+                let file = File::open(source)
+                    .map_err(|err| anyhow!("Failed to open MIDI device `{source}`: {err}"))?;
+                thread::spawn(move || read_device(file, &sender));
+            }
+        }
+
+        Ok(Self { messages })
+    }
+
+    pub(crate) fn update(&mut self, ui: &mut Ui) {
+        for message in self.messages.try_iter() {
+            if let MidiMessage::Note { channel, note, on } = message {
+                if ui
+                    .show_control
+                    .channel
+                    .is_none_or(|wanted| wanted == channel)
+                {
+                    ui.bursts.handle_note(note, on);
+                    ui.bypass.handle_note(note, on);
+                }
+                continue;
+            }
+
+            let Some(command) = command(&message, &ui.show_control) else {
+                continue;
+            };
+
+            match command {
+                ShowCommand::Go(cue) => {
+                    let cue = cue.unwrap_or_else(|| ui.project.cue.map_or(0, |cue| cue + 1));
+                    ui.show_control.stopped = false;
+                    ui.show_control.status = Some(if ui.fire_cue(cue) {
+                        format!("GO cue {}", cue + 1)
+                    } else {
+                        format!("GO cue {} ignored, no such cue", cue + 1)
+                    });
+                }
+                ShowCommand::Stop => {
+                    ui.show_control.stopped = true;
+                    ui.show_control.status = Some("STOP".to_owned());
+                }
+                ShowCommand::Resume => {
+                    ui.show_control.stopped = false;
+                    ui.show_control.status = Some("RESUME".to_owned());
+                }
+            }
+        }
+    }
+}
+
+fn command(message: &MidiMessage, settings: &UiShowControl) -> Option<ShowCommand> {
+    match message {
+        MidiMessage::ProgramChange { channel, program } => {
+            let listening = settings.program_changes
+                && settings.channel.is_none_or(|wanted| wanted == *channel);
+            listening.then_some(ShowCommand::Go(Some(*program as usize)))
+        }
+        MidiMessage::SysEx(body) => msc_command(body, settings.device_id),
+        MidiMessage::Note { .. } => None,
+    }
+}
+
+// `body` is the SysEx payload between F0 and F7; the command format byte is not checked since
+// a lighting console may address any format and sigil only follows the cue list.
+fn msc_command(body: &[u8], device_id: u8) -> Option<ShowCommand> {
+    let [
+        SYSEX_REAL_TIME,
+        device,
+        SUB_ID_MSC,
+        _format,
+        command,
+        data @ ..,
+    ] = body
+    else {
+        return None;
+    };
+    if *device != device_id && *device != ALL_CALL {
+        return None;
+    }
+
+    match *command {
+        MSC_GO => Some(ShowCommand::Go(cue_number(data))),
+        MSC_STOP => Some(ShowCommand::Stop),
+        MSC_RESUME => Some(ShowCommand::Resume),
+        _ => None,
+    }
+}
+
+// Cue numbers are ASCII like `12` or `12.5`, followed by optional list and path fields after a
+// 00 separator. Cue `N` maps to the project's Nth cue; any fractional part is dropped.
+fn cue_number(data: &[u8]) -> Option<usize> {
+    let number = data.split(|byte| *byte == 0).next()?;
+    let whole = number.split(|byte| *byte == b'.').next()?;
+    let number = std::str::from_utf8(whole).ok()?.parse::<usize>().ok()?;
+
+    number.checked_sub(1)
+}
+
+#[derive(Default)]
+struct MidiParser {
+    status: Option<u8>,
+    data: Vec<u8>,
+    sysex: Option<Vec<u8>>,
+}
+
+impl MidiParser {
+    fn feed(&mut self, byte: u8) -> Option<MidiMessage> {
+        // Real-time bytes may appear anywhere, even inside SysEx, and never break running status.
+        if byte >= 0xf8 {
+            return None;
+        }
+
+        if byte == 0xf0 {
+            self.sysex = Some(Vec::new());
+            self.status = None;
+            return None;
+        }
+        if byte == 0xf7 {
+            return self.sysex.take().map(MidiMessage::SysEx);
+        }
+        if let Some(sysex) = &mut self.sysex {
+            if byte < 0x80 {
+                if sysex.len() < MAX_SYSEX {
+                    sysex.push(byte);
+                }
+                return None;
+            }
+            // Any other status byte ends an unterminated SysEx.
+            self.sysex = None;
+        }
+
+        if byte >= 0x80 {
+            self.status = (byte < 0xf0).then_some(byte);
+            self.data.clear();
+            return None;
+        }
+
+        let status = self.status?;
+        self.data.push(byte);
+        let length = match status & 0xf0 {
+            0xc0 | 0xd0 => 1,
+            _ => 2,
+        };
+        if self.data.len() < length {
+            return None;
+        }
+
+        let data = std::mem::take(&mut self.data);
+        let channel = status & 0x0f;
+        match status & 0xf0 {
+            0xc0 => Some(MidiMessage::ProgramChange {
+                channel,
+                program: data[0],
+            }),
+            // Note On with zero velocity is how running status sends a Note Off.
+            0x90 => Some(MidiMessage::Note {
+                channel,
+                note: data[0],
+                on: data[1] > 0,
+            }),
+            0x80 => Some(MidiMessage::Note {
+                channel,
+                note: data[0],
+                on: false,
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn read_device(mut file: File, sender: &Sender<MidiMessage>) {
+    let mut parser = MidiParser::default();
+    let mut buffer = [0; 256];
+
+    while let Ok(length) = file.read(&mut buffer) {
+        if length == 0 {
+            break;
+        }
+        for &byte in &buffer[..length] {
+            if let Some(message) = parser.feed(byte)
+                && sender.send(message).is_err()
+            {
+                return;
+            }
+        }
+    }
+
+    log::info!("MIDI device closed");
+}
+
+#[cfg(feature = "network")]
+fn read_datagrams(socket: &UdpSocket, sender: &Sender<MidiMessage>) {
+    let mut buffer = vec![0; MAX_DATAGRAM];
+
+    while let Ok(length) = socket.recv(&mut buffer) {
+        // Each datagram carries whole messages, so running status doesn't leak between them.
+        let mut parser = MidiParser::default();
+        for &byte in &buffer[..length] {
+            if let Some(message) = parser.feed(byte)
+                && sender.send(message).is_err()
+            {
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use std::f32::consts::TAU;
+
+use egui::{Context, Rect};
+
+use crate::{
+    param::{Param, Unit},
+    pipelines::sine::MAX_WAVES,
+    random::hash,
+    ui::{UiSineWaveData, UiWaves},
+};
+
+const GOLDEN_ANGLE: f32 = 2.399_963;
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum SpawnPattern {
+    #[default]
+    Grid,
+    Ring,
+    Spiral,
+}
+
+pub(crate) struct Spawner {
+    pub(crate) open: bool,
+    pattern: SpawnPattern,
+    columns: usize,
+    rows: usize,
+    count: usize,
+    spread: f32,
+    radius: f32,
+    spacing: f32,
+    jitter: f32,
+    seed: u32,
+    replace: bool,
+    template: UiSineWaveData,
+}
+
+impl Default for Spawner {
+    fn default() -> Self {
+        Self {
+            open: false,
+            pattern: SpawnPattern::default(),
+            columns: 3,
+            rows: 3,
+            count: 8,
+            spread: 0.8,
+            radius: 0.3,
+            spacing: 0.08,
+            jitter: 0.,
+            seed: 1,
+            replace: true,
+            template: UiSineWaveData {
+                inner_radius: 0.1,
+                amplitude: 0.01,
+                ..UiSineWaveData::active()
+            },
+        }
+    }
+}
+
+impl Spawner {
+    fn count(&self) -> usize {
+        match self.pattern {
+            SpawnPattern::Grid => self.columns * self.rows,
+            SpawnPattern::Ring | SpawnPattern::Spiral => self.count,
+        }
+    }
+
+    fn centers(&self, aspect: f32) -> Vec<[f32; 2]> {
+        let count = self.count().min(MAX_WAVES);
+
+        (0..count)
+            .map(|i| match self.pattern {
+                SpawnPattern::Grid => {
+                    let columns = self.columns.max(1);
+                    let rows = self.rows.max(1);
+                    let x = ((i % columns) as f32 + 0.5) / columns as f32;
+                    let y = ((i / columns) as f32 + 0.5) / rows as f32;
+
+                    [0.5 + (x - 0.5) * self.spread, 0.5 + (y - 0.5) * self.spread]
+                }
+                SpawnPattern::Ring => {
+                    let angle = TAU * i as f32 / count as f32;
+
+                    [
+                        0.5 + self.radius * angle.cos() / aspect,
+                        0.5 + self.radius * angle.sin(),
+                    ]
+                }
+                SpawnPattern::Spiral => {
+                    let angle = GOLDEN_ANGLE * i as f32;
+                    let radius = self.spacing * (i as f32).sqrt();
+
+                    [
+                        0.5 + radius * angle.cos() / aspect,
+                        0.5 + radius * angle.sin(),
+                    ]
+                }
+            })
+            .collect()
+    }
+
+    fn generate(&self, aspect: f32) -> Vec<UiSineWaveData> {
+        self.centers(aspect)
+            .into_iter()
+            .enumerate()
+            .map(|(i, center)| {
+                let jitter = |channel: u32, range: f32| {
+                    1. + hash(self.seed, channel, i as i32) * range * self.jitter
+                };
+
+                UiSineWaveData {
+                    center,
+                    inner_radius: (self.template.inner_radius * jitter(0, 0.5)).clamp(0., 1.),
+                    amplitude: (self.template.amplitude * jitter(1, 0.5)).clamp(0., 0.1),
+                    thickness: (self.template.thickness * jitter(2, 0.5)).clamp(0.01, 0.1),
+                    cycles: (self.template.cycles * jitter(3, 0.5))
+                        .round()
+                        .clamp(1., 16.),
+                    speed: (self.template.speed * jitter(4, 1.)).clamp(-0.1, 0.1),
+                    ..UiSineWaveData::active()
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn show(&mut self, ctx: &Context, rect: Rect, waves: &mut UiWaves, stretch: bool) {
+        if !self.open {
+            return;
+        }
+
+        let defaults = Self::default();
+        let aspect = if stretch {
+            1.
+        } else {
+            rect.width() / rect.height().max(1.)
+        };
+
+        let mut open = true;
+        egui::Window::new("Spawn Pattern")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.pattern, SpawnPattern::Grid, "Grid");
+                    ui.selectable_value(&mut self.pattern, SpawnPattern::Ring, "Ring");
+                    ui.selectable_value(&mut self.pattern, SpawnPattern::Spiral, "Spiral");
+                });
+
+                match self.pattern {
+                    SpawnPattern::Grid => {
+                        ui.add(
+                            Param::new(&mut self.columns, 1..=8)
+                                .text("Columns")
+                                .default(defaults.columns),
+                        );
+                        ui.add(
+                            Param::new(&mut self.rows, 1..=8)
+                                .text("Rows")
+                                .default(defaults.rows),
+                        );
+                        ui.add(
+                            Param::new(&mut self.spread, 0.0..=1.0)
+                                .text("Spread")
+                                .default(defaults.spread),
+                        );
+                    }
+                    SpawnPattern::Ring => {
+                        ui.add(
+                            Param::new(&mut self.count, 1..=MAX_WAVES)
+                                .text("Count")
+                                .default(defaults.count),
+                        );
+                        ui.add(
+                            Param::new(&mut self.radius, 0.0..=0.5)
+                                .text("Radius")
+                                .default(defaults.radius),
+                        );
+                    }
+                    SpawnPattern::Spiral => {
+                        ui.add(
+                            Param::new(&mut self.count, 1..=MAX_WAVES)
+                                .text("Count")
+                                .default(defaults.count),
+                        );
+                        ui.add(
+                            Param::new(&mut self.spacing, 0.0..=0.2)
+                                .text("Spacing")
+                                .default(defaults.spacing),
+                        );
+                    }
+                }
+
+                ui.separator();
+
+                let template = &mut self.template;
+                ui.add(
+                    Param::new(&mut template.inner_radius, 0.0..=1.0)
+                        .text("Inner Radius")
+                        .default(defaults.template.inner_radius),
+                );
+                ui.add(
+                    Param::new(&mut template.amplitude, 0.0..=0.1)
+                        .text("Amplitude")
+                        .default(defaults.template.amplitude),
+                );
+                ui.add(
+                    Param::new(&mut template.thickness, 0.01..=0.1)
+                        .text("Thickness")
+                        .default(defaults.template.thickness),
+                );
+                ui.add(
+                    Param::new(&mut template.cycles, 1.0..=16.0)
+                        .text("Cycles")
+                        .unit(Unit::Cycles)
+                        .default(defaults.template.cycles),
+                );
+                ui.add(
+                    Param::new(&mut template.speed, -0.1..=0.1)
+                        .text("Speed")
+                        .default(defaults.template.speed),
+                );
+
+                ui.separator();
+
+                ui.add(
+                    Param::new(&mut self.jitter, 0.0..=1.0)
+                        .text("Jitter")
+                        .default(defaults.jitter),
+                );
+                ui.add(egui::DragValue::new(&mut self.seed).prefix("Seed: "));
+                ui.checkbox(&mut self.replace, "Replace existing waves");
+
+                let available = if self.replace {
+                    MAX_WAVES
+                } else {
+                    MAX_WAVES - waves.0.len()
+                };
+                let count = self.count();
+                if count > available {
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        format!("Only {available} of {count} waves fit (limit {MAX_WAVES})"),
+                    );
+                }
+
+                if ui.button("Spawn").clicked() {
+                    if self.replace {
+                        waves.0.clear();
+                    }
+
+                    for wave in self.generate(aspect) {
+                        if !waves.push(wave) {
+                            break;
+                        }
+                    }
+                }
+            });
+
+        self.open = open;
+    }
+}
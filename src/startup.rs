@@ -0,0 +1,154 @@
+use std::{f32::consts::TAU, fs};
+
+use crate::{
+    config,
+    preset::Preset,
+    random::{hash, time_seed},
+    ui::{RepeatPivot, SceneMode, UiRepeat, UiSineWaveData},
+};
+
+const RECENT_COUNT: usize = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Template {
+    SingleRing,
+    Concentric,
+    Mandala,
+    Interference,
+}
+
+impl Template {
+    pub(crate) const ALL: [Self; 4] = [
+        Self::SingleRing,
+        Self::Concentric,
+        Self::Mandala,
+        Self::Interference,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::SingleRing => "Single Ring",
+            Self::Concentric => "Concentric",
+            Self::Mandala => "Mandala",
+            Self::Interference => "Interference",
+        }
+    }
+
+    pub(crate) fn preset(self) -> Preset {
+        let waves = match self {
+            Self::SingleRing => vec![UiSineWaveData::active()],
+            Self::Concentric => (0..5)
+                .map(|i| UiSineWaveData {
+                    inner_radius: 0.1 + 0.09 * i as f32,
+                    cycles: (4 + 2 * i) as f32,
+                    speed: if i % 2 == 0 { 0.004 } else { -0.004 },
+                    amplitude: 0.03,
+                    ..UiSineWaveData::active()
+                })
+                .collect(),
+            Self::Mandala => vec![
+                UiSineWaveData {
+                    center: [0.5, 0.32],
+                    inner_radius: 0.12,
+                    cycles: 5.,
+                    repeat: UiRepeat {
+                        count: 8,
+                        spacing: TAU / 8.,
+                        pivot: RepeatPivot::Canvas,
+                        phase: 0.4,
+                    },
+                    ..UiSineWaveData::active()
+                },
+                UiSineWaveData {
+                    inner_radius: 0.08,
+                    cycles: 8.,
+                    amplitude: 0.02,
+                    ..UiSineWaveData::active()
+                },
+            ],
+            Self::Interference => [[0.35, 0.5], [0.65, 0.5], [0.5, 0.25]]
+                .into_iter()
+                .map(|center| UiSineWaveData {
+                    center,
+                    inner_radius: 0.2,
+                    ..UiSineWaveData::active()
+                })
+                .collect(),
+        };
+
+        Preset {
+            mode: if self == Self::Interference {
+                SceneMode::Interference
+            } else {
+                SceneMode::Rings
+            },
+            waves,
+            ..Preset::default()
+        }
+    }
+}
+
+pub(crate) fn empty_preset() -> Preset {
+    Preset {
+        waves: Vec::new(),
+        ..Preset::default()
+    }
+}
+
+pub(crate) fn random_preset() -> Preset {
+    let seed = time_seed();
+    let unit = |wave: i32, channel: u32| (hash(seed, channel, wave) + 1.) / 2.;
+
+    let count = 2 + (unit(-1, 0) * 5.) as i32;
+    let waves = (0..count)
+        .map(|i| UiSineWaveData {
+            center: [0.3 + 0.4 * unit(i, 0), 0.3 + 0.4 * unit(i, 1)],
+            inner_radius: 0.05 + 0.4 * unit(i, 2),
+            thickness: 0.005 + 0.02 * unit(i, 3),
+            amplitude: 0.01 + 0.07 * unit(i, 4),
+            cycles: (2. + 12. * unit(i, 5)).round(),
+            speed: 0.01 * (unit(i, 6) * 2. - 1.),
+            ..UiSineWaveData::active()
+        })
+        .collect();
+
+    Preset {
+        waves,
+        ..Preset::default()
+    }
+}
+
+// Saved presets, most recently written first.
+pub(crate) fn recent_presets() -> Vec<String> {
+    let dir = config::presets_dir();
+    let mut presets = Preset::list()
+        .into_iter()
+        .filter_map(|name| {
+            let modified = fs::metadata(dir.join(format!("{name}.json")))
+                .and_then(|metadata| metadata.modified())
+                .ok()?;
+            Some((modified, name))
+        })
+        .collect::<Vec<_>>();
+    presets.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+
+    presets
+        .into_iter()
+        .take(RECENT_COUNT)
+        .map(|(_, name)| name)
+        .collect()
+}
+
+#[derive(Default)]
+pub(crate) struct UiStartup {
+    pub(crate) open: bool,
+    pub(crate) recent: Vec<String>,
+    pub(crate) dont_show: bool,
+}
+
+impl UiStartup {
+    pub(crate) fn show(&mut self) {
+        self.open = true;
+        self.recent = recent_presets();
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(feature = "network")]
+use std::net::UdpSocket;
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+use anyhow::Result;
+#[cfg(feature = "network")]
+use anyhow::anyhow;
+#[cfg(not(feature = "network"))]
+use anyhow::bail;
+
+#[cfg(feature = "network")]
+const MAX_DATAGRAM: usize = 64 * 1024;
+
+pub(crate) struct DataStream {
+    updates: Receiver<(String, f32)>,
+    values: BTreeMap<String, f32>,
+}
+
+impl DataStream {
+    pub(crate) fn open(source: &str) -> Result<Self> {
+        let (sender, updates) = mpsc::channel();
+
+        match source {
+            "-" | "stdin" => {
+                thread::spawn(move || read_lines(io::stdin().lock(), &sender));
+            }
+            _ => match source.strip_prefix("udp:") {
+                #[cfg(feature = "network")]
+                Some(address) => {
+                    let socket = UdpSocket::bind(address)
+                        .map_err(|err| anyhow!("Failed to bind `{address}`: {err}"))?;
+                    thread::spawn(move || read_datagrams(&socket, &sender));
+                }
+                #[cfg(not(feature = "network"))]
+                Some(_) => bail!("`--stream udp:` requires building with the `network` feature"),
+                None => {
+                    let path = source.to_owned();
+                    thread::spawn(move || match File::open(&path) {
+                        Ok(file) => read_lines(BufReader::new(file), &sender),
+                        Err(err) => log::error!("Failed to open stream `{path}`: {err}"),
+                    });
+                }
+            },
+        }
+
+        Ok(Self {
+            updates,
+            values: BTreeMap::new(),
+        })
+    }
+
+    pub(crate) fn poll(&mut self) -> &BTreeMap<String, f32> {
+        for (name, value) in self.updates.try_iter() {
+            self.values.insert(name, value);
+        }
+
+        &self.values
+    }
+}
+
+fn read_lines(reader: impl BufRead, sender: &Sender<(String, f32)>) {
+    for line in reader.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+
+        if !parse_line(&line, sender) {
+            return;
+        }
+    }
+
+    log::info!("Data stream closed");
+}
+
+#[cfg(feature = "network")]
+fn read_datagrams(socket: &UdpSocket, sender: &Sender<(String, f32)>) {
+    let mut buffer = vec![0; MAX_DATAGRAM];
+
+    while let Ok(length) = socket.recv(&mut buffer) {
+        let text = String::from_utf8_lossy(&buffer[..length]);
+        if !text.lines().all(|line| parse_line(line, sender)) {
+            return;
+        }
+    }
+}
+
+fn parse_line(line: &str, sender: &Sender<(String, f32)>) -> bool {
+    let line = line.trim();
+    if line.is_empty() {
+        return true;
+    }
+
+    let values = match serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(line) {
+        Ok(values) => values,
+        Err(err) => {
+            log::warn!("Ignoring malformed stream line: {err}");
+            return true;
+        }
+    };
+
+    values
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value.as_f64()? as f32)))
+        .all(|update| sender.send(update).is_ok())
+}
@@ -0,0 +1,123 @@
+use std::{
+    f32::consts::TAU,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    audio::analysis::AudioFeatures,
+    ui::{StrobeMode, StrobeSource, UiStrobe},
+};
+
+pub(crate) const BEAT_ONSET: f32 = 0.5;
+
+#[derive(Clone, Copy)]
+pub(crate) struct StrobeLevel {
+    pub(crate) flash: f32,
+    pub(crate) gain: f32,
+}
+
+impl Default for StrobeLevel {
+    fn default() -> Self {
+        Self {
+            flash: 0.,
+            gain: 1.,
+        }
+    }
+}
+
+pub(crate) struct Strobe {
+    last_update: Instant,
+    phase: f32,
+    last_onset: Option<Instant>,
+    flash_until: Instant,
+    last_beat: f32,
+}
+
+impl Default for Strobe {
+    fn default() -> Self {
+        let now = Instant::now();
+
+        Self {
+            last_update: now,
+            phase: 0.,
+            last_onset: None,
+            flash_until: now,
+            last_beat: 0.,
+        }
+    }
+}
+
+impl Strobe {
+    pub(crate) fn update(&mut self, settings: &UiStrobe, audio: &AudioFeatures) -> StrobeLevel {
+        let now = Instant::now();
+        let dt = (now - self.last_update).as_secs_f32();
+        self.last_update = now;
+
+        let beat = audio.beat;
+        let rising = beat >= BEAT_ONSET && self.last_beat < BEAT_ONSET;
+        self.last_beat = beat;
+
+        if !settings.enabled {
+            return StrobeLevel::default();
+        }
+
+        let rate = settings.effective_rate();
+        let duty = settings.duty.clamp(0.05, 0.95);
+
+        let level = match settings.source {
+            StrobeSource::Lfo => {
+                let previous = self.phase;
+                self.phase = (self.phase + dt * rate).fract();
+
+                match settings.mode {
+                    StrobeMode::Flash => {
+                        if self.phase < previous && self.try_onset(settings, now) {
+                            self.flash_until = now + Duration::from_secs_f32(duty / rate);
+                        }
+                        (now < self.flash_until) as u32 as f32
+                    }
+                    StrobeMode::Brightness => 0.5 - 0.5 * (TAU * self.phase).cos(),
+                }
+            }
+            StrobeSource::Beat => {
+                if rising && self.try_onset(settings, now) {
+                    self.flash_until = now + Duration::from_secs_f32(duty / rate);
+                }
+
+                let remaining = self
+                    .flash_until
+                    .saturating_duration_since(now)
+                    .as_secs_f32();
+                match settings.mode {
+                    StrobeMode::Flash => (remaining > 0.) as u32 as f32,
+                    StrobeMode::Brightness => (remaining * rate / duty).min(1.),
+                }
+            }
+        };
+
+        let intensity = settings.intensity.clamp(0., 1.);
+        match settings.mode {
+            StrobeMode::Flash => StrobeLevel {
+                flash: level * intensity,
+                gain: 1.,
+            },
+            StrobeMode::Brightness => StrobeLevel {
+                flash: 0.,
+                gain: 1. - intensity * (1. - level),
+            },
+        }
+    }
+
+    fn try_onset(&mut self, settings: &UiStrobe, now: Instant) -> bool {
+        if settings.safe
+            && self
+                .last_onset
+                .is_some_and(|last| (now - last).as_secs_f32() < 1. / settings.safe_max_hz.max(0.1))
+        {
+            return false;
+        }
+
+        self.last_onset = Some(now);
+        true
+    }
+}
@@ -0,0 +1,154 @@
+use std::path::Path;
+
+use anyhow::Result;
+use image::{RgbaImage, imageops::FilterType};
+use wgpu::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, CommandEncoder,
+    Device, Extent3d, FilterMode, Origin3d, Queue, SamplerBindingType, SamplerDescriptor,
+    ShaderStages, TexelCopyBufferLayout, TexelCopyTextureInfo, Texture, TextureAspect,
+    TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+    TextureView, TextureViewDescriptor, TextureViewDimension,
+};
+
+pub(crate) const STROKE_TEXTURE_SIZE: u32 = 256;
+
+pub(crate) fn load_image(path: &Path) -> Result<RgbaImage> {
+    let image = image::open(path)?.into_rgba8();
+
+    Ok(image::imageops::resize(
+        &image,
+        STROKE_TEXTURE_SIZE,
+        STROKE_TEXTURE_SIZE,
+        FilterType::Triangle,
+    ))
+}
+
+pub(crate) struct StrokeTextures {
+    texture: Texture,
+    pub(crate) layout: BindGroupLayout,
+    pub(crate) bind_group: BindGroup,
+}
+
+impl StrokeTextures {
+    pub(crate) fn new(layers: u32, device: &Device) -> Self {
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("Stroke Texture Array"),
+            size: Extent3d {
+                width: STROKE_TEXTURE_SIZE,
+                height: STROKE_TEXTURE_SIZE,
+                depth_or_array_layers: layers,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8UnormSrgb,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC
+                | TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        let view = texture.create_view(&TextureViewDescriptor {
+            label: Some("Stroke Texture Array View"),
+            dimension: Some(TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Stroke Sampler"),
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::ClampToEdge,
+            min_filter: FilterMode::Linear,
+            mag_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Stroke Texture Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2Array,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Stroke Texture Bind Group"),
+            layout: &layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
+        Self {
+            texture,
+            layout,
+            bind_group,
+        }
+    }
+
+    pub(crate) fn copy_from(&self, other: &Self, encoder: &mut CommandEncoder) {
+        encoder.copy_texture_to_texture(
+            other.texture.as_image_copy(),
+            self.texture.as_image_copy(),
+            self.texture.size(),
+        );
+    }
+
+    pub(crate) fn layer_view(&self, layer: u32) -> TextureView {
+        self.texture.create_view(&TextureViewDescriptor {
+            label: Some("Stroke Texture Layer View"),
+            dimension: Some(TextureViewDimension::D2),
+            base_array_layer: layer,
+            array_layer_count: Some(1),
+            ..Default::default()
+        })
+    }
+
+    pub(crate) fn write_layer(&self, layer: u32, image: &RgbaImage, queue: &Queue) {
+        queue.write_texture(
+            TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: Origin3d {
+                    x: 0,
+                    y: 0,
+                    z: layer,
+                },
+                aspect: TextureAspect::All,
+            },
+            image.as_raw(),
+            TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * image.width()),
+                rows_per_image: Some(image.height()),
+            },
+            Extent3d {
+                width: image.width(),
+                height: image.height(),
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+}
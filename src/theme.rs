@@ -0,0 +1,112 @@
+use std::fs;
+
+use anyhow::Result;
+use egui::{
+    Color32, Context, CornerRadius, Stroke, Style, Theme, Visuals, style::default_text_styles,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+
+const BODY_SIZE: f32 = 13.;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum ThemeMode {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl ThemeMode {
+    pub(crate) const ALL: [Self; 2] = [Self::Dark, Self::Light];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Dark => "Dark",
+            Self::Light => "Light",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct UiTheme {
+    pub(crate) mode: ThemeMode,
+    pub(crate) accent: [u8; 3],
+    pub(crate) rounding: f32,
+    pub(crate) font_size: f32,
+}
+
+impl Default for UiTheme {
+    fn default() -> Self {
+        Self {
+            mode: ThemeMode::Dark,
+            accent: [0, 92, 128],
+            rounding: 2.,
+            font_size: BODY_SIZE,
+        }
+    }
+}
+
+impl UiTheme {
+    pub(crate) fn load() -> Self {
+        let path = config::theme_path();
+        let Ok(contents) = fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        serde_json::from_str(&contents).unwrap_or_else(|err| {
+            log::warn!("Ignoring theme in {}: {err}", path.display());
+            Self::default()
+        })
+    }
+
+    pub(crate) fn save(&self) -> Result<()> {
+        let path = config::theme_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+
+    pub(crate) fn apply(&self, ctx: &Context) {
+        let (theme, mut visuals) = match self.mode {
+            ThemeMode::Dark => (Theme::Dark, Visuals::dark()),
+            ThemeMode::Light => (Theme::Light, Visuals::light()),
+        };
+
+        let [r, g, b] = self.accent;
+        let accent = Color32::from_rgb(r, g, b);
+        visuals.selection.bg_fill = accent;
+        visuals.selection.stroke = Stroke::new(1., visuals.strong_text_color());
+        visuals.hyperlink_color = accent;
+
+        let radius = CornerRadius::same(self.rounding.round().clamp(0., 16.) as u8);
+        visuals.window_corner_radius = radius;
+        visuals.menu_corner_radius = radius;
+        for widget in [
+            &mut visuals.widgets.noninteractive,
+            &mut visuals.widgets.inactive,
+            &mut visuals.widgets.hovered,
+            &mut visuals.widgets.active,
+            &mut visuals.widgets.open,
+        ] {
+            widget.corner_radius = radius;
+        }
+
+        let scale = self.font_size / BODY_SIZE;
+        let mut text_styles = default_text_styles();
+        for font in text_styles.values_mut() {
+            font.size *= scale;
+        }
+
+        ctx.set_theme(theme);
+        ctx.set_style(Style {
+            visuals,
+            text_styles,
+            ..Style::default()
+        });
+    }
+}
@@ -0,0 +1,74 @@
+use std::time::{Duration, Instant};
+
+use egui::{Context, Pos2, Rect};
+
+use crate::transient::{TransientInstance, Transients};
+
+const MIN_DISTANCE: f32 = 4.;
+
+pub(crate) struct CursorTrail {
+    pub(crate) enabled: bool,
+    pub(crate) lifetime: f32,
+    pub(crate) scale: f32,
+    pub(crate) rate: f32,
+    last_spawn: Instant,
+    last_position: Option<Pos2>,
+}
+
+impl Default for CursorTrail {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            lifetime: 0.8,
+            scale: 0.04,
+            rate: 30.,
+            last_spawn: Instant::now(),
+            last_position: None,
+        }
+    }
+}
+
+impl CursorTrail {
+    pub(crate) fn update(&mut self, ctx: &Context, rect: Rect, transients: &mut Transients) {
+        if !self.enabled {
+            self.last_position = None;
+            return;
+        }
+
+        let Some(pointer) = ctx.input(|input| input.pointer.hover_pos()) else {
+            self.last_position = None;
+            return;
+        };
+
+        if ctx.is_pointer_over_area() {
+            return;
+        }
+
+        let moved = self
+            .last_position
+            .is_none_or(|last| last.distance(pointer) >= MIN_DISTANCE);
+        let interval = Duration::from_secs_f32(1. / self.rate.max(1.));
+        if !moved || self.last_spawn.elapsed() < interval {
+            return;
+        }
+
+        transients.spawn(
+            TransientInstance {
+                center: [
+                    ((pointer.x - rect.left()) / rect.width()).clamp(0., 1.),
+                    ((pointer.y - rect.top()) / rect.height()).clamp(0., 1.),
+                ],
+                inner_radius: self.scale,
+                thickness: (self.scale * 0.25).max(0.003),
+                amplitude: self.scale * 0.1,
+                cycles: 6.,
+                speed: 0.01,
+                opacity: 1.,
+            },
+            self.lifetime,
+        );
+
+        self.last_spawn = Instant::now();
+        self.last_position = Some(pointer);
+    }
+}
@@ -0,0 +1,59 @@
+use std::time::{Duration, Instant};
+
+pub(crate) const MAX_TRANSIENTS: usize = 64;
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TransientInstance {
+    pub(crate) center: [f32; 2],
+    pub(crate) inner_radius: f32,
+    pub(crate) thickness: f32,
+    pub(crate) amplitude: f32,
+    pub(crate) cycles: f32,
+    pub(crate) speed: f32,
+    pub(crate) opacity: f32,
+}
+
+struct Transient {
+    base: TransientInstance,
+    spawned: Instant,
+    lifetime: Duration,
+}
+
+#[derive(Default)]
+pub(crate) struct Transients {
+    active: Vec<Transient>,
+    frame: Vec<TransientInstance>,
+}
+
+impl Transients {
+    pub(crate) fn spawn(&mut self, instance: TransientInstance, lifetime: f32) {
+        if self.active.len() >= MAX_TRANSIENTS {
+            self.active.remove(0);
+        }
+
+        self.active.push(Transient {
+            base: instance,
+            spawned: Instant::now(),
+            lifetime: Duration::from_secs_f32(lifetime.max(0.01)),
+        });
+    }
+
+    pub(crate) fn update(&mut self) -> &[TransientInstance] {
+        let now = Instant::now();
+        self.active
+            .retain(|transient| now - transient.spawned < transient.lifetime);
+
+        self.frame.clear();
+        self.frame.extend(self.active.iter().map(|transient| {
+            let t = (now - transient.spawned).as_secs_f32() / transient.lifetime.as_secs_f32();
+
+            TransientInstance {
+                inner_radius: transient.base.inner_radius * (1. + 0.5 * t),
+                opacity: transient.base.opacity * (1. - t) * (1. - t),
+                ..transient.base
+            }
+        }));
+
+        &self.frame
+    }
+}
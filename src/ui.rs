@@ -1,225 +1,5570 @@
-use std::array;
+use std::{
+    collections::BTreeMap,
+    f32::consts::{FRAC_PI_3, PI, TAU},
+    fs,
+    hash::Hash,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::Result;
 
 use egui::{Context, ViewportId};
 use egui_wgpu::{Renderer, RendererOptions, ScreenDescriptor};
 use egui_winit::State;
+use serde::{Deserialize, Serialize};
 use wgpu::{
-    Color, CommandEncoder, Device, LoadOp, Operations, Queue, RenderPass,
-    RenderPassColorAttachment, RenderPassDescriptor, StoreOp, TextureFormat, TextureView,
+    CommandEncoder, Device, LoadOp, Operations, Queue, RenderPassColorAttachment, StoreOp,
+    TextureFormat, TextureView,
 };
 use winit::{event::WindowEvent, window::Window};
 
+use crate::{
+    audio::{
+        Audio,
+        response::{AudioBand, AudioResponse},
+        synth::UiSynth,
+    },
+    brush::{Brush, BrushSize},
+    burst::{BurstKind, TriggerPad, UiBursts},
+    bypass::{BypassBinding, BypassMode, BypassTarget, UiBypass},
+    camera::Camera,
+    canvas::{Canvas, Snap, SnapMode, WaveSelection},
+    clock_sync::{SyncRole, UiClockSync},
+    color,
+    color_space::{HdrOutput, surface_view_format},
+    config::{self, Settings},
+    debug_view::DebugView,
+    draw_stats::DrawStats,
+    easing::{Curve, Easing},
+    false_color::{FalseColorMap, FalseColorSource, UiFalseColor},
+    hot_reload::PresetWatcher,
+    hud::UiHud,
+    input::{TouchInput, UiTouch},
+    locale::{DecimalSeparator, Language, Locale, tr},
+    lock::{self, ParamLocks, RandomConstraints},
+    log_view::LogView,
+    macros::{GlobalParam, Macro, MacroCurve, MacroParam, MacroSource, MacroTarget, TargetParam},
+    metrics::{MetricsFormat, UiMetrics},
+    motion::{MAX_PATH_POINTS, MotionPath, PathLoop, PathShape},
+    param::{Param, ParamStyle, Unit},
+    physics::SpringSettings,
+    pipelines::sine::{MAX_ECHOES, MAX_REPEATS, MAX_WAVES},
+    playlist::{Playlist, PlaylistItem, PlaylistTransition, UiPlaylist},
+    power,
+    preset::Preset,
+    preset_diff::UiPresetDiff,
+    project::{Cue, Project},
+    quality, quantize,
+    ramp::{ColorRamp, ColorStop, Interpolation, MAX_STOPS},
+    random,
+    repaint::{RepaintSchedule, ScreenKey, UiRepaint},
+    replay::{self, UiReplay},
+    reveal::{RevealStyle, UiReveal},
+    routing::{RouteOutput, RouteStage, UiRouting},
+    screenshot,
+    shadertoy::UiShadertoy,
+    show_control::UiShowControl,
+    spawn::Spawner,
+    startup::{self, Template, UiStartup},
+    theme::{ThemeMode, UiTheme},
+    trail::CursorTrail,
+    transient::Transients,
+    widget::{Corner, UiWidget},
+};
+
 pub(crate) struct Ui {
     renderer: Renderer,
     state: State,
     pub(crate) waves: UiWaves,
+    pub(crate) audio: UiAudio,
+    pub(crate) synth: UiSynth,
+    pub(crate) scene: UiScene,
+    pub(crate) compare: UiCompare,
+    pub(crate) presets: UiPresets,
+    pub(crate) preset_diff: UiPresetDiff,
+    pub(crate) reveal: UiReveal,
+    pub(crate) project: UiProject,
+    pub(crate) physics: UiPhysics,
+    pub(crate) ambient: UiAmbient,
+    pub(crate) art_net: UiArtNet,
+    pub(crate) bursts: UiBursts,
+    pub(crate) bypass: UiBypass,
+    pub(crate) playlist: UiPlaylist,
+    pub(crate) show_control: UiShowControl,
+    pub(crate) metrics: UiMetrics,
+    pub(crate) clock_sync: UiClockSync,
+    pub(crate) pixel_sort: UiPixelSort,
+    pub(crate) field_warp: UiFieldWarp,
+    pub(crate) reaction_diffusion: UiReactionDiffusion,
+    pub(crate) shadertoy: UiShadertoy,
+    pub(crate) quality: UiQuality,
+    pub(crate) power: UiPower,
+    pub(crate) repaint: UiRepaint,
+    pub(crate) quantize: UiQuantize,
+    pub(crate) color_space: UiColorSpace,
+    pub(crate) display: UiDisplay,
+    pub(crate) theme: UiTheme,
+    applied_theme: Option<UiTheme>,
+    pub(crate) preview: UiPreview,
+    pub(crate) replay: UiReplay,
+    pub(crate) routing: UiRouting,
+    pub(crate) startup: UiStartup,
+    pub(crate) settings: Settings,
+    pub(crate) strobe: UiStrobe,
+    pub(crate) grade: UiGrade,
+    pub(crate) errors: UiErrors,
+    pub(crate) mode: UiMode,
+    pub(crate) macros: Vec<Macro>,
+    pub(crate) stream_values: BTreeMap<String, f32>,
+    pub(crate) hidden: bool,
+    wave_filter: String,
+    console: UiConsole,
+    canvas: Canvas,
+    pub(crate) hud: UiHud,
+    pub(crate) widget: UiWidget,
+    touch: TouchInput,
+    debug_view: DebugView,
+    log_view: LogView,
+    pub(crate) transients: Transients,
+    trail: CursorTrail,
+    brush: Brush,
+    spawner: Spawner,
+    repaint_schedule: RepaintSchedule,
 }
 
-pub(crate) struct UiWaves(pub(crate) [UiSineWaveData; 8]);
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct UiPhysics {
+    pub(crate) enabled: bool,
+    pub(crate) spring: SpringSettings,
+}
 
-impl Default for UiWaves {
+pub(crate) struct UiAmbient {
+    pub(crate) enabled: bool,
+    pub(crate) idle: bool,
+    pub(crate) idle_minutes: f32,
+    pub(crate) seed: u32,
+    pub(crate) drift_speed: f32,
+    pub(crate) intensity: f32,
+    pub(crate) cycle_presets: bool,
+    pub(crate) cycle_minutes: f32,
+    pub(crate) active: bool,
+}
+
+impl Default for UiAmbient {
     fn default() -> Self {
-        let mut waves = UiWaves(array::from_fn(|_| UiSineWaveData::default()));
+        Self {
+            enabled: false,
+            idle: false,
+            idle_minutes: 5.,
+            seed: 1,
+            drift_speed: 0.1,
+            intensity: 0.5,
+            cycle_presets: false,
+            cycle_minutes: 2.,
+            active: false,
+        }
+    }
+}
 
-        waves.0[0].init = true;
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+pub(crate) struct GpuError {
+    pub(crate) message: String,
+    pub(crate) count: u32,
+}
+
+#[derive(Default)]
+pub(crate) struct UiErrors {
+    pub(crate) entries: Vec<GpuError>,
+    pub(crate) open: bool,
+    toast: Option<Instant>,
+}
 
-        waves
+impl UiErrors {
+    pub(crate) fn record(&mut self, message: String) {
+        match self.entries.last_mut() {
+            Some(last) if last.message == message => last.count += 1,
+            _ => {
+                self.entries.push(GpuError { message, count: 1 });
+                self.toast = Some(Instant::now());
+            }
+        }
     }
 }
 
-pub(crate) struct UiSineWaveData {
-    pub(crate) amplitude: f32,
-    pub(crate) center: [f32; 2],
-    pub(crate) inner_radius: f32,
-    pub(crate) thickness: f32,
-    pub(crate) cycles: f32,
-    pub(crate) speed: f32,
-    pub(crate) init: bool,
+pub(crate) struct UiColorSpace {
+    pub(crate) srgb_surface: bool,
+    pub(crate) linear_output: bool,
+    pub(crate) srgb_available: bool,
+    pub(crate) surface_format: Option<TextureFormat>,
+    pub(crate) off_screen_format: Option<TextureFormat>,
+    pub(crate) hdr_available: Vec<HdrOutput>,
+    pub(crate) hdr: Option<HdrOutput>,
+    pub(crate) sdr_white: f32,
+    pub(crate) hdr_peak: f32,
 }
 
-impl Default for UiSineWaveData {
+impl Default for UiColorSpace {
     fn default() -> Self {
         Self {
-            amplitude: 0.05,
-            center: [0.5, 0.5],
-            inner_radius: 0.50,
-            thickness: 0.01,
-            cycles: 8.,
-            speed: 0.005,
-            init: false,
+            srgb_surface: true,
+            linear_output: true,
+            srgb_available: false,
+            surface_format: None,
+            off_screen_format: None,
+            hdr_available: Vec::new(),
+            hdr: None,
+            sdr_white: 203.,
+            hdr_peak: 1000.,
         }
     }
 }
 
-impl Ui {
-    pub(crate) fn new(device: &Device, format: TextureFormat, window: &Window) -> Self {
-        let renderer = Renderer::new(device, format, RendererOptions::default());
-        let context = Context::default();
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub(crate) enum StrobeSource {
+    #[default]
+    Lfo,
+    Beat,
+}
 
-        let state = State::new(context.clone(), ViewportId::ROOT, window, None, None, None);
-        let waves = UiWaves::default();
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub(crate) enum StrobeMode {
+    #[default]
+    Flash,
+    Brightness,
+}
 
+pub(crate) struct UiGrade {
+    pub(crate) enabled: bool,
+    pub(crate) hue_shift: f32,
+    pub(crate) saturation: f32,
+    pub(crate) contrast: f32,
+    pub(crate) lift: [f32; 3],
+    pub(crate) gamma: [f32; 3],
+    pub(crate) gain: [f32; 3],
+    pub(crate) lut_path: String,
+    pub(crate) lut_strength: f32,
+    pub(crate) lut_loaded: bool,
+    pub(crate) lut_requested: bool,
+    pub(crate) lut_error: Option<String>,
+}
+
+impl Default for UiGrade {
+    fn default() -> Self {
         Self {
-            renderer,
-            state,
-            waves,
+            enabled: false,
+            hue_shift: 0.,
+            saturation: 1.,
+            contrast: 1.,
+            lift: [0.; 3],
+            gamma: [1.; 3],
+            gain: [1.; 3],
+            lut_path: String::new(),
+            lut_strength: 1.,
+            lut_loaded: false,
+            lut_requested: false,
+            lut_error: None,
         }
     }
+}
 
-    fn begin_frame(&mut self, window: &Window) {
-        let raw_input = self.state.take_egui_input(window);
-        self.state.egui_ctx().begin_pass(raw_input);
+pub(crate) struct UiStrobe {
+    pub(crate) enabled: bool,
+    pub(crate) source: StrobeSource,
+    pub(crate) mode: StrobeMode,
+    pub(crate) rate: f32,
+    pub(crate) duty: f32,
+    pub(crate) intensity: f32,
+    pub(crate) safe: bool,
+    pub(crate) safe_max_hz: f32,
+}
+
+impl Default for UiStrobe {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            source: StrobeSource::Lfo,
+            mode: StrobeMode::Flash,
+            rate: 2.,
+            duty: 0.2,
+            intensity: 0.6,
+            safe: true,
+            safe_max_hz: 3.,
+        }
     }
+}
 
-    fn end_frame(
-        &mut self,
-        window: &Window,
-        device: &Device,
-        queue: &Queue,
-        texture_view: &TextureView,
-        encoder: &mut CommandEncoder,
-    ) {
-        let full_output = self.state.egui_ctx().end_pass();
+impl UiStrobe {
+    pub(crate) fn effective_rate(&self) -> f32 {
+        let rate = if self.safe {
+            self.rate.min(self.safe_max_hz)
+        } else {
+            self.rate
+        };
 
-        self.state
-            .handle_platform_output(window, full_output.platform_output);
+        rate.max(0.05)
+    }
+}
 
-        let tris = self
-            .state
-            .egui_ctx()
-            .tessellate(full_output.shapes, self.state.egui_ctx().pixels_per_point());
+#[derive(Default)]
+pub(crate) struct UiDisplay {
+    pub(crate) scale_scene: bool,
+    pub(crate) scale_factor: f32,
+    pub(crate) param_style: ParamStyle,
+}
 
-        for (id, image_delta) in &full_output.textures_delta.set {
-            self.renderer
-                .update_texture(device, queue, *id, image_delta);
+pub(crate) struct UiPreview {
+    pub(crate) enabled: bool,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+impl Default for UiPreview {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            width: 1920,
+            height: 1080,
+        }
+    }
+}
+
+impl UiPreview {
+    pub(crate) fn letterbox(&self, width: u32, height: u32) -> [u32; 4] {
+        if !self.enabled || self.width == 0 || self.height == 0 {
+            return [0, 0, width, height];
         }
 
-        let size = window.inner_size();
-        let screen_descriptor = ScreenDescriptor {
-            size_in_pixels: [size.width, size.height],
-            pixels_per_point: window.scale_factor() as f32,
+        let aspect = self.width as f32 / self.height as f32;
+        let (fit_width, fit_height) = if width as f32 / height.max(1) as f32 > aspect {
+            ((height as f32 * aspect).round() as u32, height)
+        } else {
+            (width, (width as f32 / aspect).round() as u32)
         };
+        let (fit_width, fit_height) = (fit_width.clamp(1, width), fit_height.clamp(1, height));
 
-        self.renderer
-            .update_buffers(device, queue, encoder, &tris, &screen_descriptor);
+        [
+            (width - fit_width) / 2,
+            (height - fit_height) / 2,
+            fit_width,
+            fit_height,
+        ]
+    }
+}
 
-        let render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            color_attachments: &[Some(RenderPassColorAttachment {
-                view: &texture_view,
-                resolve_target: None,
+pub(crate) struct UiQuality {
+    pub(crate) adaptive: bool,
+    pub(crate) target_ms: f32,
+    pub(crate) level: usize,
+    pub(crate) frame_ms: f32,
+    pub(crate) draw: DrawStats,
+    pub(crate) budget_screens: f32,
+}
 
-                ops: Operations {
-                    load: LoadOp::Load,
-                    store: StoreOp::Store,
-                },
-                depth_slice: None,
-            })],
-            label: Some("UI Render Pass"),
-            ..Default::default()
-        });
+impl Default for UiQuality {
+    fn default() -> Self {
+        Self {
+            adaptive: true,
+            target_ms: 16.6,
+            level: 0,
+            frame_ms: 0.,
+            draw: DrawStats::default(),
+            budget_screens: 64.,
+        }
+    }
+}
 
-        self.renderer.render(
-            &mut render_pass.forget_lifetime(),
-            &tris,
-            &screen_descriptor,
-        );
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub(crate) enum QuantizeDivision {
+    #[default]
+    Beat,
+    Bar,
+}
 
-        for x in &full_output.textures_delta.free {
-            self.renderer.free_texture(x)
+#[derive(Default)]
+pub(crate) struct UiQuantize {
+    pub(crate) enabled: bool,
+    pub(crate) division: QuantizeDivision,
+    pub(crate) pending: bool,
+    pub(crate) beat: u32,
+    pub(crate) apply_requested: bool,
+    pub(crate) downbeat_requested: bool,
+}
+
+pub(crate) struct UiPower {
+    pub(crate) enabled: bool,
+    pub(crate) battery_only: bool,
+    pub(crate) on_battery: bool,
+    pub(crate) active: bool,
+}
+
+impl Default for UiPower {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            battery_only: true,
+            on_battery: false,
+            active: false,
         }
     }
+}
 
-    pub(crate) fn panel(&mut self) {
-        egui::Window::new("Control Panel")
-            .resizable(true)
-            .vscroll(true)
-            .default_open(false)
-            .movable(true)
-            .show(self.state.egui_ctx(), |ui| {
-                ui.horizontal(|ui| {
-                    if let Some(pos) = self.waves.0.iter().position(|wave_data| !wave_data.init) {
-                        if ui.button("Add Wave").clicked() {
-                            self.waves.0[pos].init = true;
-                        }
-                    }
-                });
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum UiMode {
+    #[default]
+    Design,
+    Performance,
+    Console,
+}
 
-                ui.separator();
+const SWIPE_DISTANCE: f32 = 120.;
 
-                for (i, sine_wave_data) in self
-                    .waves
-                    .0
-                    .iter_mut()
-                    .filter(|wave_data| wave_data.init)
-                    .enumerate()
-                {
-                    egui::CollapsingHeader::new(format!("Wave {}", i + 1))
-                        .default_open(false)
-                        .show(ui, |ui| {
-                            ui.group(|ui| {
-                                ui.horizontal(|ui| {
-                                    ui.label("Center:");
-                                    ui.add(
-                                        egui::Slider::new(&mut sine_wave_data.center[0], 0.0..=1.0)
-                                            .text("X"),
-                                    );
-                                    ui.add(
-                                        egui::Slider::new(&mut sine_wave_data.center[1], 0.0..=1.0)
-                                            .text("Y"),
-                                    );
-                                });
+#[derive(Default)]
+struct UiConsole {
+    page: usize,
+    swipe: f32,
+}
 
-                                ui.add(
-                                    egui::Slider::new(&mut sine_wave_data.amplitude, 0.0..=0.1)
-                                        .text("Amplitude"),
-                                );
-                                ui.add(
-                                    egui::Slider::new(&mut sine_wave_data.inner_radius, 0.0..=1.0)
-                                        .text("Inner Radius"),
-                                );
-                                ui.add(
-                                    egui::Slider::new(&mut sine_wave_data.thickness, 0.01..=0.1)
-                                        .text("Thickness"),
-                                );
-                                ui.add(
-                                    egui::Slider::new(&mut sine_wave_data.cycles, 1.0..=16.0)
-                                        .step_by(1.0)
-                                        .text("Cycles"),
-                                );
-                                ui.add(
-                                    egui::Slider::new(&mut sine_wave_data.speed, -0.1..=0.1)
-                                        .text("Speed"),
-                                );
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum SortMode {
+    #[default]
+    Radius,
+    Angle,
+}
 
-                                ui.horizontal(|ui| {
-                                    if ui.button("Remove").clicked() {
-                                        sine_wave_data.init = false;
-                                    }
-                                });
-                            });
-                        });
+pub(crate) struct UiPixelSort {
+    pub(crate) enabled: bool,
+    pub(crate) available: bool,
+    pub(crate) mode: SortMode,
+    pub(crate) threshold: f32,
+    pub(crate) animate: f32,
+    pub(crate) speed: f32,
+    pub(crate) mix: f32,
+    pub(crate) descending: bool,
+}
 
-                    ui.separator();
-                }
-            });
+impl Default for UiPixelSort {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            available: false,
+            mode: SortMode::default(),
+            threshold: 0.25,
+            animate: 0.1,
+            speed: 0.02,
+            mix: 1.,
+            descending: false,
+        }
     }
+}
 
-    pub(crate) fn render(
-        &mut self,
-        window: &Window,
-        device: &Device,
-        queue: &Queue,
-        texture_view: &TextureView,
-        encoder: &mut CommandEncoder,
-    ) {
-        self.begin_frame(window);
+pub(crate) struct UiFieldWarp {
+    pub(crate) enabled: bool,
+    pub(crate) available: bool,
+    pub(crate) strength: f32,
+    pub(crate) scale: f32,
+    pub(crate) speed: f32,
+    pub(crate) steps: u32,
+}
+
+impl Default for UiFieldWarp {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            available: false,
+            strength: 0.02,
+            scale: 3.,
+            speed: 0.005,
+            steps: 8,
+        }
+    }
+}
 
-        self.panel();
+pub(crate) const REACTION_DIFFUSION_PRESETS: [(&str, f32, f32); 4] = [
+    ("Mitosis", 0.0367, 0.0649),
+    ("Coral", 0.0545, 0.062),
+    ("Worms", 0.078, 0.061),
+    ("Spots", 0.035, 0.065),
+];
 
-        self.end_frame(window, device, queue, texture_view, encoder);
+pub(crate) struct UiReactionDiffusion {
+    pub(crate) enabled: bool,
+    pub(crate) available: bool,
+    pub(crate) feed: f32,
+    pub(crate) kill: f32,
+    pub(crate) steps: u32,
+    pub(crate) background: bool,
+    pub(crate) color: [f32; 3],
+    pub(crate) intensity: f32,
+    pub(crate) thickness_mod: f32,
+    pub(crate) color_mod: f32,
+    pub(crate) reseed: bool,
+}
+
+impl Default for UiReactionDiffusion {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            available: false,
+            feed: 0.0367,
+            kill: 0.0649,
+            steps: 8,
+            background: true,
+            color: [0.2, 0.45, 0.6],
+            intensity: 0.6,
+            thickness_mod: 0.,
+            color_mod: 0.,
+            reseed: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct ArtNetPoint {
+    pub(crate) position: [f32; 2],
+    pub(crate) channel: u16,
+}
+
+pub(crate) struct UiArtNet {
+    pub(crate) enabled: bool,
+    pub(crate) target: String,
+    pub(crate) universe: u16,
+    pub(crate) rate: f32,
+    pub(crate) points: Vec<ArtNetPoint>,
+    pub(crate) strip_count: usize,
+    pub(crate) status: Option<String>,
+}
+
+impl Default for UiArtNet {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target: "255.255.255.255:6454".to_owned(),
+            universe: 0,
+            rate: 30.,
+            points: vec![ArtNetPoint {
+                position: [0.5, 0.5],
+                channel: 1,
+            }],
+            strip_count: 30,
+            status: None,
+        }
     }
+}
 
-    pub(crate) fn handle_input(&mut self, window: &Window, event: &WindowEvent) {
-        let _ = self.state.on_window_event(window, event);
+pub(crate) struct UiPresets {
+    pub(crate) name: String,
+    pub(crate) names: Vec<String>,
+    pub(crate) current: Option<String>,
+    pub(crate) saved: Option<SystemTime>,
+    pub(crate) status: Option<String>,
+    pub(crate) hot_reload: bool,
+    pub(crate) transition_secs: f32,
+    pub(crate) image_path: String,
+    pub(crate) screenshot_requested: bool,
+}
+
+impl Default for UiPresets {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            names: Vec::new(),
+            current: None,
+            saved: None,
+            status: None,
+            hot_reload: true,
+            transition_secs: 1.,
+            image_path: "sigil.png".to_owned(),
+            screenshot_requested: false,
+        }
+    }
+}
+
+impl UiPresets {
+    // The presets folder is scanned at startup, after a save and on request, never per frame.
+    pub(crate) fn refresh(&mut self) {
+        self.names = Preset::list();
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct UiProject {
+    pub(crate) path: String,
+    pub(crate) project: Option<Project>,
+    pub(crate) cue: Option<usize>,
+    pub(crate) palette: Option<String>,
+    pub(crate) status: Option<String>,
+}
+
+pub(crate) struct Baseline {
+    pub(crate) waves: UiWaves,
+    pub(crate) ramp: ColorRamp,
+    pub(crate) mode: SceneMode,
+}
+
+pub(crate) struct UiCompare {
+    pub(crate) enabled: bool,
+    pub(crate) split: f32,
+    pub(crate) baseline: Option<Baseline>,
+    pub(crate) baseline_saved: bool,
+}
+
+impl Default for UiCompare {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            split: 0.5,
+            baseline: None,
+            baseline_saved: false,
+        }
     }
 }
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SceneMode {
+    #[default]
+    Rings,
+    Interference,
+}
+
+pub(crate) struct UiCollision {
+    pub(crate) enabled: bool,
+    pub(crate) color: [f32; 3],
+    pub(crate) strength: f32,
+}
+
+impl Default for UiCollision {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            color: [1., 1., 1.],
+            strength: 0.8,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum GradientStyle {
+    #[default]
+    Vertical,
+    Horizontal,
+    Radial,
+}
+
+impl GradientStyle {
+    pub(crate) const ALL: [Self; 3] = [Self::Vertical, Self::Horizontal, Self::Radial];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Vertical => "Vertical",
+            Self::Horizontal => "Horizontal",
+            Self::Radial => "Radial",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct UiBackground {
+    pub(crate) enabled: bool,
+    pub(crate) style: GradientStyle,
+    pub(crate) ramp: ColorRamp,
+}
+
+impl Default for UiBackground {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            style: GradientStyle::default(),
+            ramp: ColorRamp {
+                stops: vec![
+                    ColorStop {
+                        position: 0.,
+                        color: [0.01, 0.01, 0.04],
+                    },
+                    ColorStop {
+                        position: 1.,
+                        color: [0., 0., 0.],
+                    },
+                ],
+                interpolation: Interpolation::Smooth,
+            },
+        }
+    }
+}
+
+pub(crate) struct UiAntialias {
+    pub(crate) enabled: bool,
+    pub(crate) width: f32,
+    pub(crate) hairline: bool,
+}
+
+impl Default for UiAntialias {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            width: 1.,
+            hairline: false,
+        }
+    }
+}
+
+impl UiAntialias {
+    pub(crate) fn effective_width(&self) -> f32 {
+        if self.enabled { self.width } else { 0. }
+    }
+}
+
+pub(crate) struct UiScene {
+    pub(crate) mode: SceneMode,
+    pub(crate) ramp: ColorRamp,
+    pub(crate) background: UiBackground,
+    pub(crate) interference_available: bool,
+    pub(crate) stretch: bool,
+    pub(crate) master_opacity: f32,
+    pub(crate) collision: UiCollision,
+    pub(crate) antialias: UiAntialias,
+    pub(crate) false_color: UiFalseColor,
+    pub(crate) camera: Camera,
+    pub(crate) selection: WaveSelection,
+}
+
+impl Default for UiScene {
+    fn default() -> Self {
+        Self {
+            mode: SceneMode::default(),
+            ramp: ColorRamp::default(),
+            background: UiBackground::default(),
+            interference_available: false,
+            stretch: false,
+            master_opacity: 1.,
+            collision: UiCollision::default(),
+            antialias: UiAntialias::default(),
+            false_color: UiFalseColor::default(),
+            camera: Camera::default(),
+            selection: WaveSelection::default(),
+        }
+    }
+}
+
+pub(crate) struct UiAudio {
+    pub(crate) path: String,
+    pub(crate) reactivity: f32,
+    pub(crate) scope_trigger: bool,
+    pub(crate) gpu_analysis: bool,
+    pub(crate) gpu_available: bool,
+}
+
+impl Default for UiAudio {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            reactivity: 0.5,
+            scope_trigger: true,
+            gpu_analysis: true,
+            gpu_available: false,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub(crate) struct UiWaves(pub(crate) Vec<UiSineWaveData>);
+
+impl Default for UiWaves {
+    fn default() -> Self {
+        UiWaves(vec![UiSineWaveData::active()])
+    }
+}
+
+impl UiWaves {
+    pub(crate) fn push(&mut self, wave: UiSineWaveData) -> bool {
+        if self.0.len() >= MAX_WAVES {
+            return false;
+        }
+
+        self.0.push(wave);
+        true
+    }
+
+    pub(crate) fn scale(&mut self, factor: f32) {
+        for wave in &mut self.0 {
+            wave.inner_radius *= factor;
+            wave.thickness *= factor;
+            wave.amplitude *= factor;
+            wave.echo.spread *= factor;
+        }
+    }
+
+    pub(crate) fn remove(&mut self, index: usize) {
+        if index >= self.0.len() {
+            return;
+        }
+
+        self.0.remove(index);
+
+        for wave in self.0.iter_mut().skip(index).filter(|wave| wave.textured) {
+            wave.textured = false;
+            wave.texture_requested = true;
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub(crate) enum WaveTag {
+    #[default]
+    None,
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl WaveTag {
+    pub(crate) const ALL: [WaveTag; 7] = [
+        WaveTag::None,
+        WaveTag::Red,
+        WaveTag::Orange,
+        WaveTag::Yellow,
+        WaveTag::Green,
+        WaveTag::Blue,
+        WaveTag::Purple,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            WaveTag::None => "None",
+            WaveTag::Red => "Red",
+            WaveTag::Orange => "Orange",
+            WaveTag::Yellow => "Yellow",
+            WaveTag::Green => "Green",
+            WaveTag::Blue => "Blue",
+            WaveTag::Purple => "Purple",
+        }
+    }
+
+    fn color(self) -> Option<egui::Color32> {
+        match self {
+            WaveTag::None => None,
+            WaveTag::Red => Some(egui::Color32::from_rgb(230, 80, 80)),
+            WaveTag::Orange => Some(egui::Color32::from_rgb(240, 150, 60)),
+            WaveTag::Yellow => Some(egui::Color32::from_rgb(230, 210, 70)),
+            WaveTag::Green => Some(egui::Color32::from_rgb(100, 200, 100)),
+            WaveTag::Blue => Some(egui::Color32::from_rgb(90, 150, 240)),
+            WaveTag::Purple => Some(egui::Color32::from_rgb(180, 110, 230)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct UiFractal {
+    pub(crate) enabled: bool,
+    pub(crate) octaves: u32,
+    pub(crate) gain: f32,
+    pub(crate) lacunarity: f32,
+}
+
+impl Default for UiFractal {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            octaves: 4,
+            gain: 0.5,
+            lacunarity: 2.,
+        }
+    }
+}
+
+impl UiFractal {
+    pub(crate) fn params(&self) -> [f32; 4] {
+        [self.octaves as f32, self.gain, self.lacunarity, 0.]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct UiOutline {
+    pub(crate) enabled: bool,
+    pub(crate) thickness: f32,
+    pub(crate) offset: f32,
+    pub(crate) color: [f32; 3],
+}
+
+impl Default for UiOutline {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            thickness: 0.004,
+            offset: 0.01,
+            color: [0.3, 0.9, 1.],
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct UiShadow {
+    pub(crate) enabled: bool,
+    pub(crate) offset: [f32; 2],
+    pub(crate) blur: f32,
+    pub(crate) opacity: f32,
+    pub(crate) color: [f32; 3],
+}
+
+impl Default for UiShadow {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            offset: [0.008, 0.012],
+            blur: 0.02,
+            opacity: 0.6,
+            color: [0.; 3],
+        }
+    }
+}
+
+impl UiShadow {
+    pub(crate) fn params(&self) -> [f32; 4] {
+        let [x, y] = self.offset;
+        [x, y, self.blur, self.opacity]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct UiSymmetry {
+    pub(crate) across_x: bool,
+    pub(crate) across_y: bool,
+    pub(crate) point: bool,
+}
+
+impl UiSymmetry {
+    // Each twin says whether it is flipped horizontally and vertically about the canvas center.
+    pub(crate) fn twins(&self) -> impl Iterator<Item = [bool; 2]> {
+        [
+            (self.across_x, [false, true]),
+            (self.across_y, [true, false]),
+            (self.point, [true, true]),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, flip)| enabled.then_some(flip))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum RepeatPivot {
+    #[default]
+    Own,
+    Canvas,
+}
+
+impl RepeatPivot {
+    pub(crate) const ALL: [Self; 2] = [Self::Own, Self::Canvas];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Own => "Own Center",
+            Self::Canvas => "Canvas Center",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct UiRepeat {
+    pub(crate) count: usize,
+    pub(crate) spacing: f32,
+    pub(crate) pivot: RepeatPivot,
+    pub(crate) phase: f32,
+}
+
+impl Default for UiRepeat {
+    fn default() -> Self {
+        Self {
+            count: 1,
+            spacing: FRAC_PI_3,
+            pivot: RepeatPivot::Own,
+            phase: 0.,
+        }
+    }
+}
+
+impl UiRepeat {
+    // Each copy is the wave turned by a multiple of `spacing`; around the canvas center its
+    // center orbits as well, measured in aspect-corrected space so the array stays circular.
+    pub(crate) fn copies(
+        &self,
+        center: [f32; 2],
+        aspect: f32,
+    ) -> impl Iterator<Item = ([f32; 2], f32)> {
+        let UiRepeat { spacing, pivot, .. } = *self;
+        (0..self.count.clamp(1, MAX_REPEATS)).map(move |i| {
+            let angle = spacing * i as f32;
+            let center = match pivot {
+                RepeatPivot::Own => center,
+                RepeatPivot::Canvas => {
+                    let (sin, cos) = angle.sin_cos();
+                    let [x, y] = [(center[0] - 0.5) * aspect, center[1] - 0.5];
+                    [0.5 + (x * cos - y * sin) / aspect, 0.5 + x * sin + y * cos]
+                }
+            };
+
+            (center, angle)
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct UiEcho {
+    pub(crate) count: usize,
+    pub(crate) spread: f32,
+    pub(crate) phase: f32,
+    pub(crate) decay: f32,
+}
+
+impl Default for UiEcho {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            spread: 0.03,
+            phase: 0.5,
+            decay: 0.6,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct UiBreathing {
+    pub(crate) depth: f32,
+    pub(crate) period: f32,
+}
+
+impl Default for UiBreathing {
+    fn default() -> Self {
+        Self {
+            depth: 0.,
+            period: 600.,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct UiSineWaveData {
+    pub(crate) name: String,
+    pub(crate) tag: WaveTag,
+    pub(crate) amplitude: f32,
+    pub(crate) center: [f32; 2],
+    pub(crate) inner_radius: f32,
+    pub(crate) thickness: f32,
+    pub(crate) cycles: f32,
+    pub(crate) speed: f32,
+    pub(crate) speed_jitter: f32,
+    pub(crate) jitter_seed: u32,
+    pub(crate) init: bool,
+    pub(crate) texture_path: String,
+    pub(crate) texture_tiling: f32,
+    pub(crate) texture_tile_length: f32,
+    pub(crate) texture_arc_length: bool,
+    pub(crate) texture_scroll: f32,
+    pub(crate) textured: bool,
+    pub(crate) spring_override: bool,
+    pub(crate) spring: SpringSettings,
+    pub(crate) echo: UiEcho,
+    pub(crate) breathing: UiBreathing,
+    pub(crate) easing: Easing,
+    pub(crate) scope: bool,
+    pub(crate) spectrum: bool,
+    pub(crate) fractal: UiFractal,
+    pub(crate) outline: UiOutline,
+    pub(crate) shadow: UiShadow,
+    pub(crate) symmetry: UiSymmetry,
+    pub(crate) repeat: UiRepeat,
+    pub(crate) locks: ParamLocks,
+    pub(crate) audio_response: AudioResponse,
+    pub(crate) rotation: f32,
+    pub(crate) motion: MotionPath,
+    #[serde(skip)]
+    pub(crate) texture_requested: bool,
+    #[serde(skip)]
+    pub(crate) texture_error: Option<String>,
+    pub(crate) opacity: f32,
+    #[serde(skip)]
+    pub(crate) fade: f32,
+}
+
+impl UiSineWaveData {
+    pub(crate) fn active() -> Self {
+        Self {
+            init: true,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn label(&self, index: usize) -> String {
+        if self.name.trim().is_empty() {
+            format!("Wave {}", index + 1)
+        } else {
+            self.name.clone()
+        }
+    }
+
+    fn matches(&self, index: usize, filter: &str) -> bool {
+        let filter = filter.trim().to_lowercase();
+        filter.is_empty()
+            || self.label(index).to_lowercase().contains(&filter)
+            || (self.tag != WaveTag::None && self.tag.label().to_lowercase().contains(&filter))
+    }
+}
+
+impl Default for UiSineWaveData {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            tag: WaveTag::None,
+            amplitude: 0.05,
+            center: [0.5, 0.5],
+            inner_radius: 0.50,
+            thickness: 0.01,
+            cycles: 8.,
+            speed: 0.005,
+            speed_jitter: 0.,
+            jitter_seed: 1,
+            init: false,
+            texture_path: String::new(),
+            texture_tiling: 4.,
+            texture_tile_length: 64.,
+            texture_arc_length: false,
+            texture_scroll: 0.,
+            textured: false,
+            spring_override: false,
+            spring: SpringSettings::default(),
+            echo: UiEcho::default(),
+            breathing: UiBreathing::default(),
+            easing: Easing::default(),
+            scope: false,
+            spectrum: false,
+            fractal: UiFractal::default(),
+            outline: UiOutline::default(),
+            shadow: UiShadow::default(),
+            symmetry: UiSymmetry::default(),
+            repeat: UiRepeat::default(),
+            locks: ParamLocks::default(),
+            audio_response: AudioResponse::default(),
+            rotation: 0.,
+            opacity: 1.,
+            motion: MotionPath::default(),
+            texture_requested: false,
+            texture_error: None,
+            fade: 1.,
+        }
+    }
+}
+
+impl Ui {
+    pub(crate) fn new(device: &Device, format: TextureFormat, window: &Window) -> Self {
+        let renderer = Renderer::new(device, format, RendererOptions::default());
+        let context = Context::default();
+        Self::restore_memory(&context);
+
+        let state = State::new(
+            context.clone(),
+            ViewportId::ROOT,
+            window,
+            Some(window.scale_factor() as f32),
+            None,
+            None,
+        );
+        let waves = UiWaves::default();
+        let audio = UiAudio::default();
+        let scene = UiScene::default();
+
+        Self {
+            renderer,
+            state,
+            waves,
+            audio,
+            synth: UiSynth::default(),
+            scene,
+            compare: UiCompare::default(),
+            presets: UiPresets {
+                names: Preset::list(),
+                ..UiPresets::default()
+            },
+            preset_diff: UiPresetDiff::default(),
+            reveal: UiReveal::default(),
+            project: UiProject::default(),
+            physics: UiPhysics::default(),
+            ambient: UiAmbient::default(),
+            art_net: UiArtNet::default(),
+            bursts: UiBursts::default(),
+            bypass: UiBypass::default(),
+            playlist: UiPlaylist::default(),
+            show_control: UiShowControl::default(),
+            metrics: UiMetrics::default(),
+            clock_sync: UiClockSync::default(),
+            pixel_sort: UiPixelSort::default(),
+            field_warp: UiFieldWarp::default(),
+            reaction_diffusion: UiReactionDiffusion::default(),
+            shadertoy: UiShadertoy::default(),
+            quality: UiQuality::default(),
+            power: UiPower::default(),
+            repaint: UiRepaint::default(),
+            quantize: UiQuantize::default(),
+            color_space: UiColorSpace::default(),
+            strobe: UiStrobe::default(),
+            grade: UiGrade::default(),
+            display: UiDisplay {
+                scale_scene: false,
+                scale_factor: window.scale_factor() as f32,
+                param_style: ParamStyle::default(),
+            },
+            theme: UiTheme::load(),
+            applied_theme: None,
+            preview: UiPreview::default(),
+            replay: UiReplay::default(),
+            routing: UiRouting::default(),
+            startup: UiStartup::default(),
+            settings: Settings::load(),
+            errors: UiErrors::default(),
+            mode: UiMode::default(),
+            macros: Macro::defaults(),
+            stream_values: BTreeMap::new(),
+            hidden: false,
+            wave_filter: String::new(),
+            console: UiConsole::default(),
+            canvas: Canvas::default(),
+            hud: UiHud::default(),
+            widget: UiWidget::default(),
+            touch: TouchInput::default(),
+            debug_view: DebugView::default(),
+            log_view: LogView::default(),
+            transients: Transients::default(),
+            trail: CursorTrail::default(),
+            brush: Brush::default(),
+            repaint_schedule: RepaintSchedule::default(),
+            spawner: Spawner::default(),
+        }
+    }
+
+    fn restore_memory(context: &Context) {
+        let path = config::ui_state_path();
+        let Ok(contents) = fs::read_to_string(&path) else {
+            return;
+        };
+
+        match serde_json::from_str::<egui::Memory>(&contents) {
+            Ok(memory) => context.memory_mut(|current| *current = memory),
+            Err(err) => log::warn!("Ignoring UI state in {}: {err}", path.display()),
+        }
+    }
+
+    pub(crate) fn save_memory(&self) -> Result<()> {
+        let path = config::ui_state_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let memory = self
+            .state
+            .egui_ctx()
+            .memory(serde_json::to_string::<egui::Memory>)?;
+        fs::write(path, memory)?;
+
+        self.settings.save()?;
+        self.theme.save()
+    }
+
+    fn begin_frame(&mut self, window: &Window) {
+        let raw_input = self.state.take_egui_input(window);
+        self.state.egui_ctx().begin_pass(raw_input);
+        self.display.param_style.store(self.state.egui_ctx());
+        Locale::new(self.settings.language, self.settings.decimal).store(self.state.egui_ctx());
+
+        if self.applied_theme.as_ref() != Some(&self.theme) {
+            self.theme.apply(self.state.egui_ctx());
+            self.applied_theme = Some(self.theme.clone());
+        }
+    }
+
+    fn end_frame(
+        &mut self,
+        window: &Window,
+        device: &Device,
+        queue: &Queue,
+        texture_view: &TextureView,
+        encoder: &mut CommandEncoder,
+    ) {
+        let mut full_output = self.state.egui_ctx().end_pass();
+
+        self.state
+            .handle_platform_output(window, std::mem::take(&mut full_output.platform_output));
+
+        let tris = self.state.egui_ctx().tessellate(
+            std::mem::take(&mut full_output.shapes),
+            self.state.egui_ctx().pixels_per_point(),
+        );
+
+        for (id, image_delta) in &full_output.textures_delta.set {
+            self.renderer
+                .update_texture(device, queue, *id, image_delta);
+        }
+
+        let screen = self.screen_key(window);
+        let screen_descriptor = screen.descriptor();
+
+        self.renderer
+            .update_buffers(device, queue, encoder, &tris, &screen_descriptor);
+        self.repaint_schedule.finish(&full_output, screen, tris);
+
+        self.paint(texture_view, encoder, &screen_descriptor);
+
+        for x in &full_output.textures_delta.free {
+            self.renderer.free_texture(x)
+        }
+    }
+
+    fn screen_key(&self, window: &Window) -> ScreenKey {
+        let size = window.inner_size();
+        ScreenKey {
+            size: [size.width, size.height],
+            pixels_per_point: self.state.egui_ctx().pixels_per_point(),
+        }
+    }
+
+    pub(crate) fn paint_over(
+        &self,
+        window: &Window,
+        texture_view: &TextureView,
+        encoder: &mut CommandEncoder,
+    ) {
+        self.paint(texture_view, encoder, &self.screen_key(window).descriptor());
+    }
+
+    fn paint(
+        &self,
+        texture_view: &TextureView,
+        encoder: &mut CommandEncoder,
+        screen_descriptor: &ScreenDescriptor,
+    ) {
+        let render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: texture_view,
+                resolve_target: None,
+
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            label: Some("UI Render Pass"),
+            ..Default::default()
+        });
+
+        self.renderer.render(
+            &mut render_pass.forget_lifetime(),
+            self.repaint_schedule.primitives(),
+            screen_descriptor,
+        );
+    }
+
+    pub(crate) fn panel(&mut self, audio: &mut Audio) {
+        match self.mode {
+            UiMode::Design => self.design_panel(audio),
+            UiMode::Performance => self.performance_panel(),
+            UiMode::Console => self.console_panel(),
+        }
+    }
+
+    fn mode_toggle(ui: &mut egui::Ui, mode: &mut UiMode) {
+        ui.selectable_value(mode, UiMode::Design, tr(ui.ctx(), "Design"));
+        ui.selectable_value(mode, UiMode::Performance, tr(ui.ctx(), "Performance"));
+        ui.selectable_value(mode, UiMode::Console, tr(ui.ctx(), "Console"));
+    }
+
+    fn performance_panel(&mut self) {
+        let ctx = self.state.egui_ctx().clone();
+
+        egui::Window::new("Performance")
+            .resizable(true)
+            .movable(true)
+            .show(&ctx, |ui| {
+                ui.style_mut().spacing.interact_size.y = 48.;
+                ui.style_mut()
+                    .text_styles
+                    .insert(egui::TextStyle::Button, egui::FontId::proportional(20.));
+
+                ui.horizontal(|ui| Self::mode_toggle(ui, &mut self.mode));
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().slider_width = 240.;
+
+                    ui.vertical(|ui| {
+                        ui.add(
+                            egui::Slider::new(&mut self.scene.master_opacity, 0.0..=1.0)
+                                .vertical()
+                                .show_value(false),
+                        );
+                        ui.label(egui::RichText::new("Master").size(18.));
+                    });
+
+                    for macro_control in &mut self.macros {
+                        ui.vertical(|ui| {
+                            ui.add(
+                                egui::Slider::new(&mut macro_control.value, 0.0..=1.0)
+                                    .vertical()
+                                    .show_value(false),
+                            );
+                            ui.label(egui::RichText::new(&macro_control.name).size(18.));
+                        });
+                    }
+                });
+            });
+    }
+
+    fn console_panel(&mut self) {
+        let ctx = self.state.egui_ctx().clone();
+        let pages = 1 + self.waves.0.len();
+        self.console.page = self.console.page.min(pages - 1);
+
+        let fill = ctx.style().visuals.panel_fill.gamma_multiply(0.9);
+        egui::CentralPanel::default()
+            .frame(egui::Frame::central_panel(&ctx.style()).fill(fill))
+            .show(&ctx, |ui| {
+                let swipe = ui.interact(
+                    ui.max_rect(),
+                    ui.id().with("console_swipe"),
+                    egui::Sense::drag(),
+                );
+
+                ui.style_mut().spacing.interact_size.y = 64.;
+                ui.style_mut().spacing.slider_width = (ui.available_width() - 220.).max(160.);
+                for (style, size) in [
+                    (egui::TextStyle::Button, 28.),
+                    (egui::TextStyle::Body, 24.),
+                    (egui::TextStyle::Heading, 36.),
+                ] {
+                    ui.style_mut()
+                        .text_styles
+                        .insert(style, egui::FontId::proportional(size));
+                }
+
+                ui.horizontal(|ui| {
+                    Self::mode_toggle(ui, &mut self.mode);
+
+                    ui.add_space(24.);
+                    if ui
+                        .add_enabled(self.console.page > 0, egui::Button::new("◀"))
+                        .clicked()
+                    {
+                        self.console.page -= 1;
+                    }
+                    for page in 0..pages {
+                        let dot = if page == self.console.page {
+                            "●"
+                        } else {
+                            "○"
+                        };
+                        if ui.selectable_label(false, dot).clicked() {
+                            self.console.page = page;
+                        }
+                    }
+                    if ui
+                        .add_enabled(self.console.page + 1 < pages, egui::Button::new("▶"))
+                        .clicked()
+                    {
+                        self.console.page += 1;
+                    }
+                });
+
+                ui.separator();
+
+                match self.console.page {
+                    0 => {
+                        ui.heading("Macros");
+                        let defaults = Macro::default();
+                        for macro_control in &mut self.macros {
+                            ui.add_enabled(
+                                macro_control.source.is_none(),
+                                Param::new(&mut macro_control.value, 0.0..=1.0)
+                                    .text(&macro_control.name)
+                                    .default(defaults.value),
+                            );
+                        }
+                    }
+                    page => {
+                        let i = page - 1;
+                        let wave = &mut self.waves.0[i];
+
+                        let mut title = egui::RichText::new(wave.label(i));
+                        if let Some(color) = wave.tag.color() {
+                            title = title.color(color);
+                        }
+                        ui.heading(title);
+
+                        let defaults = UiSineWaveData::default();
+                        ui.checkbox(&mut wave.init, "Visible");
+                        ui.add(
+                            Param::new(&mut wave.amplitude, 0.0..=0.1)
+                                .text("Amplitude")
+                                .default(defaults.amplitude),
+                        );
+                        ui.add(
+                            Param::new(&mut wave.inner_radius, 0.0..=1.0)
+                                .text("Inner Radius")
+                                .default(defaults.inner_radius),
+                        );
+                        ui.add(
+                            Param::new(&mut wave.thickness, 0.01..=0.1)
+                                .text("Thickness")
+                                .default(defaults.thickness),
+                        );
+                        ui.add(
+                            Param::new(&mut wave.cycles, 1.0..=16.0)
+                                .step_by(1.0)
+                                .text("Cycles")
+                                .unit(Unit::Cycles)
+                                .default(defaults.cycles),
+                        );
+                        ui.add(
+                            Param::new(&mut wave.speed, -0.1..=0.1)
+                                .text("Speed")
+                                .default(defaults.speed),
+                        );
+                        ui.add(
+                            Param::new(&mut wave.center[0], 0.0..=1.0)
+                                .text("X")
+                                .default(defaults.center[0]),
+                        );
+                        ui.add(
+                            Param::new(&mut wave.center[1], 0.0..=1.0)
+                                .text("Y")
+                                .default(defaults.center[1]),
+                        );
+                    }
+                }
+
+                if swipe.dragged() {
+                    self.console.swipe += swipe.drag_delta().x;
+                }
+                if swipe.drag_stopped() {
+                    let swipe = std::mem::take(&mut self.console.swipe);
+                    if swipe <= -SWIPE_DISTANCE && self.console.page + 1 < pages {
+                        self.console.page += 1;
+                    } else if swipe >= SWIPE_DISTANCE && self.console.page > 0 {
+                        self.console.page -= 1;
+                    }
+                }
+            });
+    }
+
+    fn design_panel(&mut self, audio: &mut Audio) {
+        let ctx = self.state.egui_ctx().clone();
+
+        egui::Window::new(tr(&ctx, "Control Panel"))
+            .id(egui::Id::new("Control Panel"))
+            .resizable(true)
+            .vscroll(true)
+            .default_open(false)
+            .movable(true)
+            .show(&ctx, |ui| {
+                ui.horizontal(|ui| Self::mode_toggle(ui, &mut self.mode));
+
+                ui.horizontal(|ui| {
+                    ui.add_enabled_ui(self.waves.0.len() < MAX_WAVES, |ui| {
+                        if ui.button(tr(ui.ctx(), "Add Wave")).clicked() {
+                            self.waves.push(UiSineWaveData::active());
+                        }
+                    });
+
+                    if ui.button(tr(ui.ctx(), "Spawn…")).clicked() {
+                        self.spawner.open = true;
+                    }
+
+                    ui.checkbox(&mut self.debug_view.open, tr(ui.ctx(), "Debug View"));
+                    ui.checkbox(&mut self.log_view.open, tr(ui.ctx(), "Logs"));
+
+                    if !self.errors.entries.is_empty() {
+                        let label = format!("GPU Errors ({})", self.errors.entries.len());
+                        ui.checkbox(&mut self.errors.open, label);
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(tr(ui.ctx(), "Filter Waves:"));
+                    ui.text_edit_singleline(&mut self.wave_filter);
+                    if !self.wave_filter.is_empty() && ui.button(tr(ui.ctx(), "Clear")).clicked() {
+                        self.wave_filter.clear();
+                    }
+                });
+
+                if let Some(warning) = Self::over_budget(&self.quality) {
+                    ui.colored_label(egui::Color32::YELLOW, warning);
+                }
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Stats"))
+                    .id_salt("Stats")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::stats_panel(ui, &mut self.quality);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Power"))
+                    .id_salt("Power")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::power_panel(ui, &mut self.power);
+                        ui.separator();
+                        Self::repaint_panel(ui, &mut self.repaint);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Presets"))
+                    .id_salt("Presets")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        self.presets_panel(ui);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Project"))
+                    .id_salt("Project")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        self.project_panel(ui);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Playlist"))
+                    .id_salt("Playlist")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::playlist_panel(ui, &mut self.playlist, &self.presets);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Show Control"))
+                    .id_salt("Show Control")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::show_control_panel(ui, &mut self.show_control);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Cursor Trail"))
+                    .id_salt("Cursor Trail")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::trail_panel(ui, &mut self.trail);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Brush"))
+                    .id_salt("Brush")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::brush_panel(ui, &mut self.brush, self.waves.0.len());
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Macros"))
+                    .id_salt("Macros")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::macros_panel(
+                            ui,
+                            &mut self.macros,
+                            &self.stream_values,
+                            self.waves.0.len(),
+                        );
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Trigger Pads"))
+                    .id_salt("Trigger Pads")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::bursts_panel(ui, &mut self.bursts, self.waves.0.len());
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Bypass"))
+                    .id_salt("Bypass")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::bypass_panel(ui, &mut self.bypass, self.waves.0.len());
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Scene"))
+                    .id_salt("Scene")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::scene_panel(ui, &mut self.scene);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Strobe"))
+                    .id_salt("Strobe")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::strobe_panel(ui, &mut self.strobe);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Color Grade"))
+                    .id_salt("Color Grade")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::grade_panel(ui, &mut self.grade);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Display"))
+                    .id_salt("Display")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::display_panel(ui, &mut self.display, &mut self.settings);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Theme"))
+                    .id_salt("Theme")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::theme_panel(ui, &mut self.theme);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Export Preview"))
+                    .id_salt("Export Preview")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::preview_panel(ui, &mut self.preview);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Replay Buffer"))
+                    .id_salt("Replay Buffer")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::replay_panel(ui, &mut self.replay);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Metrics Log"))
+                    .id_salt("Metrics Log")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::metrics_panel(ui, &mut self.metrics);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Routing"))
+                    .id_salt("Routing")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::routing_panel(ui, &mut self.routing);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Color Space"))
+                    .id_salt("Color Space")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::color_space_panel(ui, &mut self.color_space);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Pixel Sort"))
+                    .id_salt("Pixel Sort")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::pixel_sort_panel(ui, &mut self.pixel_sort);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Field Warp"))
+                    .id_salt("Field Warp")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::field_warp_panel(ui, &mut self.field_warp);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Reaction Diffusion"))
+                    .id_salt("Reaction Diffusion")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::reaction_diffusion_panel(ui, &mut self.reaction_diffusion);
+                    });
+
+                if cfg!(feature = "shadertoy") {
+                    ui.separator();
+
+                    egui::CollapsingHeader::new(tr(ui.ctx(), "Shadertoy Backdrop"))
+                        .id_salt("Shadertoy Backdrop")
+                        .default_open(false)
+                        .show(ui, |ui| {
+                            Self::shadertoy_panel(ui, &mut self.shadertoy);
+                        });
+                }
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Compare"))
+                    .id_salt("Compare")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::compare_panel(ui, &mut self.compare, &self.waves, &self.scene);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Physics"))
+                    .id_salt("Physics")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        ui.checkbox(&mut self.physics.enabled, "Springy Radius");
+                        ui.add_enabled_ui(self.physics.enabled, |ui| {
+                            Self::spring_panel(ui, &mut self.physics.spring);
+                        });
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Ambient"))
+                    .id_salt("Ambient")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::ambient_panel(ui, &mut self.ambient);
+                    });
+
+                if cfg!(feature = "network") {
+                    ui.separator();
+
+                    egui::CollapsingHeader::new(tr(ui.ctx(), "Art-Net"))
+                        .id_salt("Art-Net")
+                        .default_open(false)
+                        .show(ui, |ui| {
+                            Self::art_net_panel(ui, &mut self.art_net);
+                        });
+
+                    ui.separator();
+
+                    egui::CollapsingHeader::new(tr(ui.ctx(), "Clock Sync"))
+                        .id_salt("Clock Sync")
+                        .default_open(false)
+                        .show(ui, |ui| {
+                            Self::clock_sync_panel(ui, &mut self.clock_sync);
+                        });
+                }
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Snapping"))
+                    .id_salt("Snapping")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::snap_panel(ui, &mut self.canvas.snap);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Wave HUD"))
+                    .id_salt("Wave HUD")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::hud_panel(ui, &mut self.hud);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Touch & Pen"))
+                    .id_salt("Touch & Pen")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::touch_panel(ui, &mut self.touch.settings);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Audio"))
+                    .id_salt("Audio")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::audio_panel(ui, &mut self.audio, audio);
+                    });
+
+                if cfg!(feature = "audio") {
+                    ui.separator();
+
+                    egui::CollapsingHeader::new(tr(ui.ctx(), "Synth"))
+                        .id_salt("Synth")
+                        .default_open(false)
+                        .show(ui, |ui| {
+                            Self::synth_panel(ui, &mut self.synth);
+                        });
+                }
+
+                ui.separator();
+
+                let quantize_title = if self.quantize.pending {
+                    "Quantize (pending)"
+                } else {
+                    "Quantize"
+                };
+                egui::CollapsingHeader::new(quantize_title)
+                    .id_salt("quantize")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::quantize_panel(ui, &mut self.quantize);
+                    });
+
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr(ui.ctx(), "Random Constraints"))
+                    .id_salt("Random Constraints")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        Self::constraints_panel(ui, &mut self.settings.constraints);
+                    });
+
+                ui.separator();
+
+                let mut remove = None;
+                let selection = &mut self.scene.selection;
+                let reveal = std::mem::take(&mut selection.reveal);
+                let defaults = UiSineWaveData::default();
+                for (i, sine_wave_data) in self.waves.0.iter_mut().enumerate() {
+                    if !sine_wave_data.matches(i, &self.wave_filter) {
+                        continue;
+                    }
+
+                    let selected = selection.selected == Some(i);
+                    let mut label = sine_wave_data.label(i);
+                    if selection.solo == Some(i) {
+                        label.push_str(" (solo)");
+                    }
+                    let mut title = egui::RichText::new(label);
+                    if let Some(color) = sine_wave_data.tag.color() {
+                        title = title.color(color);
+                    }
+                    if selected {
+                        title = title.strong();
+                    }
+
+                    let header = egui::CollapsingHeader::new(title)
+                        .id_salt(("wave", i))
+                        .default_open(false)
+                        .open((reveal && selected).then_some(true))
+                        .show(ui, |ui| {
+                            ui.group(|ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label("Name:");
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut sine_wave_data.name)
+                                            .hint_text(format!("Wave {}", i + 1)),
+                                    );
+                                    egui::ComboBox::from_id_salt(("wave_tag", i))
+                                        .selected_text(sine_wave_data.tag.label())
+                                        .show_ui(ui, |ui| {
+                                            for tag in WaveTag::ALL {
+                                                ui.selectable_value(
+                                                    &mut sine_wave_data.tag,
+                                                    tag,
+                                                    tag.label(),
+                                                );
+                                            }
+                                        });
+                                });
+
+                                ui.horizontal(|ui| {
+                                    ui.label("Center:");
+                                    ui.add(
+                                        Param::new(&mut sine_wave_data.center[0], 0.0..=1.0)
+                                            .text("X")
+                                            .default(defaults.center[0]),
+                                    );
+                                    ui.add(
+                                        Param::new(&mut sine_wave_data.center[1], 0.0..=1.0)
+                                            .text("Y")
+                                            .default(defaults.center[1]),
+                                    );
+                                });
+
+                                ui.add(
+                                    Param::new(&mut sine_wave_data.amplitude, 0.0..=0.1)
+                                        .text("Amplitude")
+                                        .default(defaults.amplitude),
+                                );
+                                ui.add(
+                                    Param::new(&mut sine_wave_data.inner_radius, 0.0..=1.0)
+                                        .text("Inner Radius")
+                                        .default(defaults.inner_radius),
+                                );
+                                ui.add(
+                                    Param::new(&mut sine_wave_data.thickness, 0.01..=0.1)
+                                        .text("Thickness")
+                                        .default(defaults.thickness),
+                                );
+                                ui.add(
+                                    Param::new(&mut sine_wave_data.opacity, 0.0..=1.0)
+                                        .text("Opacity")
+                                        .default(defaults.opacity),
+                                );
+                                ui.add(
+                                    Param::new(&mut sine_wave_data.cycles, 1.0..=16.0)
+                                        .step_by(1.0)
+                                        .text("Cycles")
+                                        .unit(Unit::Cycles)
+                                        .default(defaults.cycles),
+                                );
+                                ui.add(
+                                    Param::new(&mut sine_wave_data.speed, -0.1..=0.1)
+                                        .text("Speed")
+                                        .default(defaults.speed),
+                                );
+                                ui.horizontal(|ui| {
+                                    ui.add(
+                                        Param::new(&mut sine_wave_data.speed_jitter, 0.0..=1.0)
+                                            .text("Speed Jitter")
+                                            .default(defaults.speed_jitter),
+                                    )
+                                    .on_hover_text(
+                                        "Let the speed wander randomly around its set value",
+                                    );
+                                    ui.add_enabled(
+                                        sine_wave_data.speed_jitter > 0.,
+                                        egui::DragValue::new(&mut sine_wave_data.jitter_seed)
+                                            .prefix("Seed: "),
+                                    );
+                                });
+                                ui.add(
+                                    Param::new(&mut sine_wave_data.rotation, -PI..=PI)
+                                        .text("Rotation")
+                                        .unit(Unit::Radians)
+                                        .default(defaults.rotation),
+                                );
+
+                                Self::locks_panel(ui, sine_wave_data, &self.settings.constraints);
+
+                                Self::texture_panel(ui, sine_wave_data);
+
+                                Self::echo_panel(ui, &mut sine_wave_data.echo);
+
+                                Self::breathing_panel(ui, &mut sine_wave_data.breathing);
+
+                                Self::symmetry_panel(ui, &mut sine_wave_data.symmetry);
+
+                                Self::repeat_panel(ui, &mut sine_wave_data.repeat);
+
+                                Self::easing_panel(ui, i, &mut sine_wave_data.easing);
+
+                                Self::motion_panel(
+                                    ui,
+                                    i,
+                                    &mut sine_wave_data.motion,
+                                    sine_wave_data.center,
+                                );
+
+                                ui.checkbox(&mut sine_wave_data.scope, "Oscilloscope");
+                                ui.checkbox(&mut sine_wave_data.spectrum, "Spectrum Ring");
+                                Self::fractal_panel(ui, &mut sine_wave_data.fractal);
+                                Self::outline_panel(ui, &mut sine_wave_data.outline);
+                                Self::shadow_panel(ui, &mut sine_wave_data.shadow);
+                                Self::audio_response_panel(
+                                    ui,
+                                    i,
+                                    &mut sine_wave_data.audio_response,
+                                );
+
+                                if self.physics.enabled {
+                                    ui.checkbox(
+                                        &mut sine_wave_data.spring_override,
+                                        "Override Spring",
+                                    );
+                                    ui.add_enabled_ui(sine_wave_data.spring_override, |ui| {
+                                        Self::spring_panel(ui, &mut sine_wave_data.spring);
+                                    });
+                                }
+
+                                ui.horizontal(|ui| {
+                                    if ui.button("Remove").clicked() {
+                                        remove = Some(i);
+                                    }
+
+                                    let solo = selection.solo == Some(i);
+                                    if ui
+                                        .selectable_label(solo, "Solo")
+                                        .on_hover_text(
+                                            "Alt+click a ring in the viewport to solo it",
+                                        )
+                                        .clicked()
+                                    {
+                                        selection.solo = (!solo).then_some(i);
+                                    }
+                                });
+                            });
+                        });
+
+                    if reveal && selected {
+                        header.header_response.scroll_to_me(Some(egui::Align::Min));
+                    }
+                    if header.header_response.clicked() {
+                        selection.selected = Some(i);
+                    }
+
+                    ui.separator();
+                }
+
+                if let Some(i) = remove {
+                    self.waves.remove(i);
+                    self.scene.selection.removed(i);
+                }
+            });
+    }
+
+    fn macros_panel(
+        ui: &mut egui::Ui,
+        macros: &mut [Macro],
+        stream_values: &BTreeMap<String, f32>,
+        wave_count: usize,
+    ) {
+        for (i, macro_control) in macros.iter_mut().enumerate() {
+            egui::CollapsingHeader::new(&macro_control.name)
+                .id_salt(("macro", i))
+                .default_open(false)
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut macro_control.name);
+                    });
+                    ui.add_enabled(
+                        macro_control.source.is_none(),
+                        Param::new(&mut macro_control.value, 0.0..=1.0)
+                            .text("Value")
+                            .default(Macro::default().value),
+                    );
+
+                    Self::macro_source_editor(ui, i, &mut macro_control.source, stream_values);
+
+                    let mut remove = None;
+                    for (j, target) in macro_control.targets.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            Self::macro_target_editor(ui, (i, j), target, wave_count);
+                            if ui.button("Remove").clicked() {
+                                remove = Some(j);
+                            }
+                        });
+                    }
+
+                    if let Some(j) = remove {
+                        macro_control.targets.remove(j);
+                    }
+
+                    if ui.button("Add Target").clicked() {
+                        macro_control
+                            .targets
+                            .push(MacroTarget::new(TargetParam::Wave(MacroParam::Amplitude)));
+                    }
+                });
+        }
+    }
+
+    fn macro_source_editor(
+        ui: &mut egui::Ui,
+        id_salt: impl Hash,
+        source: &mut Option<MacroSource>,
+        stream_values: &BTreeMap<String, f32>,
+    ) {
+        ui.horizontal(|ui| {
+            ui.label("Source:");
+            egui::ComboBox::from_id_salt(egui::Id::new(id_salt).with("source"))
+                .selected_text(source.as_ref().map_or("Manual", |source| &source.name))
+                .show_ui(ui, |ui| {
+                    if ui.selectable_label(source.is_none(), "Manual").clicked() {
+                        *source = None;
+                    }
+                    for name in stream_values.keys() {
+                        let selected = source.as_ref().is_some_and(|source| &source.name == name);
+                        if ui.selectable_label(selected, name).clicked() && !selected {
+                            *source = Some(MacroSource::new(name.clone()));
+                        }
+                    }
+                });
+        });
+
+        if let Some(source) = source {
+            ui.horizontal(|ui| {
+                ui.label("Input range:");
+                ui.add(egui::DragValue::new(&mut source.min).speed(0.01));
+                ui.add(egui::DragValue::new(&mut source.max).speed(0.01));
+                match stream_values.get(&source.name) {
+                    Some(value) => ui.label(format!("{value:.3}")),
+                    None => ui.weak("no data"),
+                };
+            });
+        }
+    }
+
+    fn macro_target_editor(
+        ui: &mut egui::Ui,
+        id_salt: impl Hash,
+        target: &mut MacroTarget,
+        wave_count: usize,
+    ) {
+        let id = egui::Id::new(id_salt);
+
+        let previous = target.param;
+        egui::ComboBox::from_id_salt(id.with("param"))
+            .selected_text(target.param.label())
+            .show_ui(ui, |ui| {
+                for param in MacroParam::ALL.map(TargetParam::Wave) {
+                    ui.selectable_value(&mut target.param, param, param.label());
+                }
+                ui.separator();
+                for param in GlobalParam::ALL.map(TargetParam::Global) {
+                    ui.selectable_value(&mut target.param, param, param.label());
+                }
+            });
+        if target.param != previous {
+            *target = MacroTarget {
+                wave: target.wave,
+                ..MacroTarget::new(target.param)
+            };
+        }
+
+        let wave_label = |wave: Option<usize>| match wave {
+            Some(i) => format!("Wave {}", i + 1),
+            None => "All Waves".to_owned(),
+        };
+        if matches!(target.param, TargetParam::Wave(_)) {
+            egui::ComboBox::from_id_salt(id.with("wave"))
+                .selected_text(wave_label(target.wave))
+                .show_ui(ui, |ui| {
+                    for wave in std::iter::once(None).chain((0..wave_count).map(Some)) {
+                        ui.selectable_value(&mut target.wave, wave, wave_label(wave));
+                    }
+                });
+        }
+
+        egui::ComboBox::from_id_salt(id.with("curve"))
+            .selected_text(target.curve.label())
+            .show_ui(ui, |ui| {
+                for curve in MacroCurve::ALL {
+                    ui.selectable_value(&mut target.curve, curve, curve.label());
+                }
+            });
+
+        let (low, high) = target.param.range();
+        let speed = (high - low) / 200.;
+        ui.add(
+            egui::DragValue::new(&mut target.min)
+                .range(low..=high)
+                .speed(speed)
+                .prefix("min "),
+        );
+        ui.add(
+            egui::DragValue::new(&mut target.max)
+                .range(low..=high)
+                .speed(speed)
+                .prefix("max "),
+        );
+    }
+
+    fn presets_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            ui.text_edit_singleline(&mut self.presets.name);
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button("Save").clicked() {
+                let name = self.presets.name.trim().to_owned();
+                let result = Preset::named_path(&name).and_then(|path| {
+                    Preset::capture(self).save(&path)?;
+                    Ok(path)
+                });
+
+                self.presets.status = Some(match result {
+                    Ok(path) => {
+                        let status = format!("Saved `{name}`");
+                        self.presets.saved = PresetWatcher::modified(&path);
+                        self.presets.current = Some(name);
+                        self.presets.refresh();
+                        status
+                    }
+                    Err(err) => format!("Failed to save preset: {err}"),
+                });
+            }
+
+            if ui.button("Load").clicked() {
+                let name = self.presets.name.trim().to_owned();
+                self.load_preset(&name);
+            }
+
+            if ui
+                .button("⟳")
+                .on_hover_text("Rescan the presets folder")
+                .clicked()
+            {
+                self.presets.refresh();
+            }
+        });
+
+        let mut selected = None;
+        for name in self.presets.names.clone() {
+            if ui
+                .selectable_label(name == self.presets.name, &name)
+                .clicked()
+            {
+                selected = Some(name);
+            }
+        }
+
+        if let Some(name) = selected {
+            self.load_preset(&name);
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("New Scene...").clicked() {
+                self.startup.show();
+            }
+            ui.checkbox(&mut self.settings.show_startup, "Ask on launch");
+        });
+
+        ui.checkbox(&mut self.presets.hot_reload, "Reload on file change")
+            .on_hover_text("Watch the loaded preset file and transition to it when it changes");
+        ui.add_enabled_ui(self.presets.hot_reload, |ui| {
+            ui.add(
+                Param::new(&mut self.presets.transition_secs, 0.0..=5.0)
+                    .text("Transition")
+                    .unit(Unit::Secs)
+                    .default(UiPresets::default().transition_secs),
+            );
+        });
+
+        egui::CollapsingHeader::new("Compare with Saved")
+            .default_open(false)
+            .show(ui, |ui| {
+                self.preset_diff_panel(ui);
+            });
+
+        ui.separator();
+        Self::reveal_panel(ui, &mut self.reveal);
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label("Image:");
+            ui.text_edit_singleline(&mut self.presets.image_path);
+        });
+        ui.horizontal(|ui| {
+            if ui
+                .button("Save Screenshot")
+                .on_hover_text("Embeds the current preset so the image can be imported later")
+                .clicked()
+            {
+                self.presets.screenshot_requested = true;
+            }
+
+            if ui.button("Import from Image").clicked() {
+                let path = self.presets.image_path.trim().to_owned();
+                self.presets.status = Some(match screenshot::read_preset(Path::new(&path)) {
+                    Ok(preset) => {
+                        preset.apply(self);
+                        format!("Imported scene from {path}")
+                    }
+                    Err(err) => format!("Failed to import image: {err}"),
+                });
+            }
+        });
+
+        if let Some(status) = &self.presets.status {
+            ui.label(status);
+        }
+    }
+
+    fn preset_diff_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("preset_diff")
+                .selected_text(&self.preset_diff.name)
+                .show_ui(ui, |ui| {
+                    for name in &self.presets.names {
+                        ui.selectable_value(&mut self.preset_diff.name, name.clone(), name);
+                    }
+                });
+            if ui.button("Compare").clicked() {
+                self.preset_diff.load();
+            }
+        });
+        if self.preset_diff.name.is_empty()
+            && let Some(current) = &self.presets.current
+        {
+            self.preset_diff.name.clone_from(current);
+        }
+
+        if let Some(status) = &self.preset_diff.status {
+            ui.colored_label(egui::Color32::RED, status);
+        }
+        if self.preset_diff.saved.is_none() {
+            return;
+        }
+
+        let current = Preset::capture(self);
+        let changes = self.preset_diff.changes(&current);
+        if changes.is_empty() {
+            ui.label("No changes");
+            return;
+        }
+
+        let mut reverted = None;
+        egui::ScrollArea::vertical()
+            .max_height(240.)
+            .show(ui, |ui| {
+                egui::Grid::new("preset_diff_rows")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Parameter");
+                        ui.strong("Current");
+                        ui.strong("Saved");
+                        ui.end_row();
+
+                        for change in &changes {
+                            ui.label(&change.label);
+                            ui.label(&change.current);
+                            ui.label(&change.saved);
+                            if ui.small_button("Revert").clicked() {
+                                reverted = Some(change.pointer.clone());
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        let Some(pointer) = reverted else {
+            return;
+        };
+        match self.preset_diff.revert(&current, &pointer) {
+            Ok(preset) => preset.restore(self),
+            Err(err) => self.preset_diff.status = Some(format!("{err:#}")),
+        }
+    }
+
+    pub(crate) fn load_preset(&mut self, name: &str) -> bool {
+        match Preset::named_path(name).and_then(|path| Preset::load(&path)) {
+            Ok(preset) => {
+                preset.apply(self);
+                self.presets.name = name.to_owned();
+                self.presets.current = Some(name.to_owned());
+                self.presets.status = Some(format!("Loaded `{name}`"));
+                true
+            }
+            Err(err) => {
+                self.presets.status = Some(format!("Failed to load preset: {err}"));
+                false
+            }
+        }
+    }
+
+    fn project_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Directory:");
+            ui.text_edit_singleline(&mut self.project.path);
+        });
+
+        ui.horizontal(|ui| {
+            let path = PathBuf::from(self.project.path.trim());
+
+            if ui.button("Save").clicked() {
+                let project = Project::capture(self, self.project.project.as_ref());
+                self.project.status = Some(match project.save(&path) {
+                    Ok(()) => format!("Saved project to {}", path.display()),
+                    Err(err) => format!("Failed to save project: {err}"),
+                });
+                self.project.project = Some(project);
+            }
+
+            if ui.button("Load").clicked() {
+                match Project::load(&path) {
+                    Ok(project) => self.open_project(project),
+                    Err(err) => {
+                        self.project.status = Some(format!("Failed to load project: {err}"))
+                    }
+                }
+            }
+        });
+
+        if let Some(status) = &self.project.status {
+            ui.label(status);
+        }
+
+        let Some(project) = &self.project.project else {
+            return;
+        };
+
+        if !project.name.is_empty() {
+            ui.label(format!("Project: {}", project.name));
+        }
+
+        let mut preset = None;
+        let mut cue = None;
+        let mut palette = None;
+        let mut shader = None;
+        let mut add_cue = false;
+
+        ui.label("Cues:");
+        for (i, entry) in project.cues.iter().enumerate() {
+            let label = if entry.label.is_empty() {
+                &entry.preset
+            } else {
+                &entry.label
+            };
+            if ui
+                .selectable_label(self.project.cue == Some(i), format!("{}. {label}", i + 1))
+                .clicked()
+            {
+                cue = Some(i);
+            }
+        }
+
+        ui.horizontal(|ui| {
+            let next = self.project.cue.map_or(0, |cue| cue + 1);
+            if ui
+                .add_enabled(next < project.cues.len(), egui::Button::new("Next Cue"))
+                .clicked()
+            {
+                cue = Some(next);
+            }
+
+            add_cue = ui.button("Add Cue").clicked();
+        });
+
+        ui.label("Presets:");
+        for name in project.presets.keys() {
+            if ui.selectable_label(false, name).clicked() {
+                preset = Some(name.clone());
+            }
+        }
+
+        ui.label("Palettes:");
+        for name in project.palettes.keys() {
+            let selected = self.project.palette.as_ref() == Some(name);
+            if ui.selectable_label(selected, name).clicked() {
+                palette = Some(name.clone());
+            }
+        }
+        let add_palette = ui
+            .button("Add Palette")
+            .on_hover_text("Stores the current color ramp as a new palette")
+            .clicked();
+
+        if !project.shaders.is_empty() {
+            ui.label("Shaders:");
+            for name in project.shaders.keys() {
+                if ui.selectable_label(false, name).clicked() {
+                    shader = Some(name.clone());
+                }
+            }
+        }
+
+        if add_cue {
+            let captured = Preset::capture(self);
+            if let Some(project) = &mut self.project.project {
+                let name = format!("cue-{}", project.cues.len() + 1);
+                project.presets.insert(name.clone(), captured);
+                project.cues.push(Cue {
+                    label: String::new(),
+                    preset: name,
+                });
+            }
+        }
+
+        if let Some(i) = cue {
+            self.fire_cue(i);
+        }
+
+        if add_palette && let Some(project) = &mut self.project.project {
+            let name = format!("palette-{}", project.palettes.len() + 1);
+            project
+                .palettes
+                .insert(name.clone(), self.scene.ramp.clone());
+            self.project.palette = Some(name);
+        }
+
+        if let Some(name) = palette
+            && let Some(ramp) = self
+                .project
+                .project
+                .as_ref()
+                .and_then(|project| project.palettes.get(&name))
+        {
+            self.scene.ramp = ramp.clone();
+            self.project.palette = Some(name);
+        }
+
+        // Edits to the selected palette follow through to the scene, and are kept on Save.
+        if let Some(name) = &self.project.palette
+            && let Some(ramp) = self
+                .project
+                .project
+                .as_mut()
+                .and_then(|project| project.palettes.get_mut(name))
+        {
+            ui.label(format!("Palette `{name}`:"));
+            let before = ramp.clone();
+            Self::ramp_editor(ui, ("project_palette", name), ramp);
+            if *ramp != before {
+                self.scene.ramp = ramp.clone();
+            }
+        }
+
+        if let Some(name) = shader
+            && let Some(source) = self
+                .project
+                .project
+                .as_ref()
+                .and_then(|project| project.shaders.get(&name))
+        {
+            self.shadertoy.source = source.clone();
+            self.shadertoy.enabled = true;
+            self.shadertoy.compile = true;
+        }
+
+        if let Some(name) = preset {
+            self.apply_project_preset(&name);
+        }
+    }
+
+    pub(crate) fn fire_cue(&mut self, i: usize) -> bool {
+        let Some(preset) = self
+            .project
+            .project
+            .as_ref()
+            .and_then(|project| project.cues.get(i))
+            .map(|cue| cue.preset.clone())
+        else {
+            return false;
+        };
+
+        self.project.cue = Some(i);
+        self.apply_project_preset(&preset);
+        true
+    }
+
+    pub(crate) fn open_project(&mut self, project: Project) {
+        let start = project.start_preset().map(str::to_owned);
+        self.project.status = Some(format!(
+            "Loaded {} presets, {} cues",
+            project.presets.len(),
+            project.cues.len()
+        ));
+        self.project.cue = (!project.cues.is_empty()).then_some(0);
+        self.project.palette = None;
+        self.project.project = Some(project);
+
+        if let Some(name) = start {
+            self.apply_project_preset(&name);
+        }
+    }
+
+    fn apply_project_preset(&mut self, name: &str) {
+        let Some(preset) = self
+            .project
+            .project
+            .as_ref()
+            .and_then(|project| project.presets.get(name))
+            .cloned()
+        else {
+            self.project.status = Some(format!("Project has no preset `{name}`"));
+            return;
+        };
+
+        preset.apply(self);
+        self.presets.name = name.to_owned();
+    }
+
+    fn ambient_panel(ui: &mut egui::Ui, ambient: &mut UiAmbient) {
+        let defaults = UiAmbient::default();
+        ui.checkbox(&mut ambient.enabled, "Auto-Evolve");
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut ambient.idle, "Start When Idle");
+            ui.add_enabled(
+                ambient.idle,
+                egui::DragValue::new(&mut ambient.idle_minutes)
+                    .range(0.5..=120.0)
+                    .suffix(" min"),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Seed:");
+            ui.add(egui::DragValue::new(&mut ambient.seed));
+        });
+        ui.add(
+            Param::new(&mut ambient.drift_speed, 0.01..=1.0)
+                .logarithmic(true)
+                .text("Drift Speed")
+                .default(defaults.drift_speed),
+        );
+        ui.add(
+            Param::new(&mut ambient.intensity, 0.0..=1.0)
+                .text("Intensity")
+                .default(defaults.intensity),
+        );
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut ambient.cycle_presets, "Cycle Presets");
+            ui.add_enabled(
+                ambient.cycle_presets,
+                egui::DragValue::new(&mut ambient.cycle_minutes)
+                    .range(0.1..=120.0)
+                    .suffix(" min"),
+            );
+        });
+
+        if ambient.active {
+            ui.label("Active: wave parameters are restored when it stops");
+        }
+    }
+
+    fn art_net_panel(ui: &mut egui::Ui, art_net: &mut UiArtNet) {
+        let defaults = UiArtNet::default();
+        ui.checkbox(&mut art_net.enabled, "Send DMX");
+
+        ui.horizontal(|ui| {
+            ui.label("Target:");
+            ui.text_edit_singleline(&mut art_net.target);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Universe:");
+            ui.add(egui::DragValue::new(&mut art_net.universe).range(0..=0x7fff));
+        });
+        ui.add(
+            Param::new(&mut art_net.rate, 1.0..=44.0)
+                .text("Rate")
+                .unit(Unit::Hz)
+                .default(defaults.rate),
+        );
+
+        ui.label("Sample Points (RGB per point):");
+
+        let mut remove = None;
+        for (i, point) in art_net.points.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut point.position[0])
+                        .range(0.0..=1.0)
+                        .speed(0.005)
+                        .prefix("x "),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut point.position[1])
+                        .range(0.0..=1.0)
+                        .speed(0.005)
+                        .prefix("y "),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut point.channel)
+                        .range(1..=510)
+                        .prefix("ch "),
+                );
+                if ui.button("Remove").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+
+        if let Some(i) = remove {
+            art_net.points.remove(i);
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Add Point").clicked() {
+                let channel = art_net
+                    .points
+                    .iter()
+                    .map(|point| point.channel + 3)
+                    .max()
+                    .unwrap_or(1)
+                    .min(510);
+
+                art_net.points.push(ArtNetPoint {
+                    position: [0.5, 0.5],
+                    channel,
+                });
+            }
+
+            ui.add(egui::DragValue::new(&mut art_net.strip_count).range(1..=170));
+            if ui
+                .button("Edge Strip")
+                .on_hover_text("Replace the points with an LED strip around the screen edge")
+                .clicked()
+            {
+                art_net.points = edge_strip(art_net.strip_count);
+            }
+        });
+
+        if let Some(status) = &art_net.status {
+            ui.colored_label(egui::Color32::RED, status);
+        }
+    }
+
+    fn bursts_panel(ui: &mut egui::Ui, bursts: &mut UiBursts, wave_count: usize) {
+        ui.checkbox(&mut bursts.enabled, "Enabled");
+
+        ui.horizontal_wrapped(|ui| {
+            for (i, pad) in bursts.pads.iter().enumerate() {
+                let button = egui::Button::new(egui::RichText::new(&pad.name).size(18.))
+                    .min_size(egui::vec2(72., 48.));
+                if ui.add_enabled(bursts.enabled, button).clicked() {
+                    bursts.fired.push(i);
+                }
+            }
+        });
+
+        let defaults = TriggerPad::new(0);
+        let mut remove = None;
+        for (i, pad) in bursts.pads.iter_mut().enumerate() {
+            egui::CollapsingHeader::new(&pad.name)
+                .id_salt(("pad", i))
+                .default_open(false)
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut pad.name);
+                    });
+
+                    let target = |wave: Option<usize>| {
+                        wave.map_or("All Waves".to_owned(), |wave| format!("Wave {}", wave + 1))
+                    };
+                    egui::ComboBox::from_id_salt(("pad_wave", i))
+                        .selected_text(target(pad.wave))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut pad.wave, None, target(None));
+                            for wave in 0..wave_count {
+                                ui.selectable_value(&mut pad.wave, Some(wave), target(Some(wave)));
+                            }
+                        });
+
+                    ui.horizontal(|ui| {
+                        for kind in BurstKind::ALL {
+                            ui.selectable_value(&mut pad.kind, kind, kind.label());
+                        }
+                    });
+                    ui.add(
+                        Param::new(&mut pad.strength, 0.0..=8.0)
+                            .text("Strength")
+                            .default(defaults.strength),
+                    );
+                    ui.add(
+                        Param::new(&mut pad.decay_secs, 0.01..=4.0)
+                            .logarithmic(true)
+                            .text("Decay")
+                            .unit(Unit::Secs)
+                            .default(defaults.decay_secs),
+                    );
+
+                    ui.horizontal(|ui| {
+                        ui.label("Key:");
+                        ui.add(egui::TextEdit::singleline(&mut pad.key).desired_width(24.));
+                        Self::note_editor(ui, &mut pad.note);
+                        ui.label("Stream:");
+                        ui.text_edit_singleline(&mut pad.source).on_hover_text(
+                            "Fires when this stream value rises past 0.5, e.g. a MIDI note bridged into the data stream",
+                        );
+                    });
+
+                    if ui.button("Remove").clicked() {
+                        remove = Some(i);
+                    }
+                });
+        }
+
+        if let Some(i) = remove {
+            bursts.pads.remove(i);
+        }
+
+        if ui.button("Add Pad").clicked() {
+            bursts.pads.push(TriggerPad::new(bursts.pads.len()));
+        }
+    }
+
+    fn note_editor(ui: &mut egui::Ui, note: &mut Option<u8>) {
+        let mut bound = note.is_some();
+        ui.checkbox(&mut bound, "Note:")
+            .on_hover_text("MIDI note number from the `--show-control` input");
+        let mut value = note.unwrap_or(60);
+        ui.add_enabled(bound, egui::DragValue::new(&mut value).range(0..=127));
+        *note = bound.then_some(value);
+    }
+
+    fn bypass_panel(ui: &mut egui::Ui, bypass: &mut UiBypass, wave_count: usize) {
+        ui.checkbox(&mut bypass.enabled, "Enabled");
+
+        let mut remove = None;
+        for (i, binding) in bypass.bindings.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt(("bypass_target", i))
+                    .selected_text(binding.target.label())
+                    .show_ui(ui, |ui| {
+                        for target in BypassTarget::EFFECTS {
+                            ui.selectable_value(&mut binding.target, target, target.label());
+                        }
+                        for wave in 0..wave_count {
+                            let target = BypassTarget::Layer(wave);
+                            ui.selectable_value(&mut binding.target, target, target.label());
+                        }
+                    });
+
+                for mode in BypassMode::ALL {
+                    ui.selectable_value(&mut binding.mode, mode, mode.label());
+                }
+
+                if binding.active() {
+                    ui.colored_label(egui::Color32::RED, "Bypassed");
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Key:");
+                ui.add(egui::TextEdit::singleline(&mut binding.key).desired_width(24.));
+                Self::note_editor(ui, &mut binding.note);
+                ui.label("Stream:");
+                ui.add(egui::TextEdit::singleline(&mut binding.source).desired_width(120.))
+                    .on_hover_text(
+                        "Bypasses while this stream value is above 0.5, e.g. a MIDI note bridged into the data stream",
+                    );
+                if binding.mode == BypassMode::Toggle {
+                    ui.checkbox(&mut binding.latched, "Latched");
+                }
+                if ui.button("Remove").clicked() {
+                    remove = Some(i);
+                }
+            });
+
+            ui.separator();
+        }
+
+        if let Some(i) = remove {
+            bypass.bindings.remove(i);
+        }
+
+        if ui.button("Add Bypass").clicked() {
+            bypass.bindings.push(BypassBinding::default());
+        }
+    }
+
+    fn show_control_panel(ui: &mut egui::Ui, show_control: &mut UiShowControl) {
+        if !show_control.connected {
+            ui.label("Start with `--show-control DEVICE` or `--show-control udp:ADDRESS`")
+                .on_hover_text("DEVICE is a raw MIDI port such as /dev/snd/midiC1D0");
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("MSC Device ID:");
+            ui.add(egui::DragValue::new(&mut show_control.device_id).range(0..=111));
+        })
+        .response
+        .on_hover_text("All-call messages (127) are always accepted");
+
+        ui.horizontal(|ui| {
+            ui.label("Channel:");
+            egui::ComboBox::from_id_salt("show_control_channel")
+                .selected_text(
+                    show_control
+                        .channel
+                        .map_or("Omni".to_owned(), |channel| format!("Ch {}", channel + 1)),
+                )
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut show_control.channel, None, "Omni");
+                    for channel in 0..16 {
+                        ui.selectable_value(
+                            &mut show_control.channel,
+                            Some(channel),
+                            format!("Ch {}", channel + 1),
+                        );
+                    }
+                });
+        })
+        .response
+        .on_hover_text("Program changes and note bindings are only taken from this channel");
+
+        ui.checkbox(&mut show_control.program_changes, "Program Changes")
+            .on_hover_text(
+                "Program 1 fires the first project cue, program 2 the second, and so on",
+            );
+
+        ui.label("GO fires the cue with the same number, or the next cue when none is given");
+
+        if show_control.stopped {
+            ui.horizontal(|ui| {
+                ui.label("Stopped");
+                if ui.button("Resume").clicked() {
+                    show_control.stopped = false;
+                }
+            });
+        }
+
+        if let Some(status) = &show_control.status {
+            ui.label(format!("Last: {status}"));
+        }
+    }
+
+    fn playlist_panel(ui: &mut egui::Ui, playlist: &mut UiPlaylist, presets: &UiPresets) {
+        let defaults = PlaylistItem::default();
+        ui.horizontal(|ui| {
+            ui.label("File:");
+            ui.text_edit_singleline(&mut playlist.path);
+        });
+
+        ui.horizontal(|ui| {
+            let path = PathBuf::from(playlist.path.trim());
+            if ui.button("Save").clicked() {
+                playlist.status = Some(match playlist.playlist.save(&path) {
+                    Ok(()) => format!("Saved playlist to {}", path.display()),
+                    Err(err) => format!("Failed to save playlist: {err}"),
+                });
+            }
+            if ui.button("Load").clicked() {
+                playlist.status = Some(match Playlist::load(&path) {
+                    Ok(loaded) => {
+                        playlist.playlist = loaded;
+                        playlist.current = None;
+                        format!("Loaded playlist from {}", path.display())
+                    }
+                    Err(err) => format!("Failed to load playlist: {err:#}"),
+                });
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.add_enabled_ui(!playlist.playlist.items.is_empty(), |ui| {
+                let label = if playlist.playing { "Stop" } else { "Play" };
+                if ui.button(label).clicked() {
+                    playlist.playing = !playlist.playing;
+                }
+                if ui
+                    .add_enabled(playlist.playing, egui::Button::new("Next"))
+                    .clicked()
+                {
+                    playlist.skip = true;
+                }
+            });
+            ui.checkbox(&mut playlist.playlist.shuffle, "Shuffle")
+                .on_hover_text("Play every preset once in a random order, then reshuffle");
+        });
+
+        let mut remove = None;
+        let mut swap = None;
+        let count = playlist.playlist.items.len();
+        for (i, item) in playlist.playlist.items.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                let playing = playlist.playing && playlist.current == Some(i);
+                ui.label(if playing { "▶" } else { " " });
+                egui::ComboBox::from_id_salt(("playlist_preset", i))
+                    .selected_text(&item.preset)
+                    .show_ui(ui, |ui| {
+                        for name in &presets.names {
+                            ui.selectable_value(&mut item.preset, name.clone(), name);
+                        }
+                    });
+                egui::ComboBox::from_id_salt(("playlist_transition", i))
+                    .selected_text(item.transition.label())
+                    .show_ui(ui, |ui| {
+                        for transition in PlaylistTransition::ALL {
+                            ui.selectable_value(
+                                &mut item.transition,
+                                transition,
+                                transition.label(),
+                            );
+                        }
+                    });
+
+                if ui.add_enabled(i > 0, egui::Button::new("⏶")).clicked() {
+                    swap = Some(i - 1);
+                }
+                if ui
+                    .add_enabled(i + 1 < count, egui::Button::new("⏷"))
+                    .clicked()
+                {
+                    swap = Some(i);
+                }
+                if ui.button("Remove").clicked() {
+                    remove = Some(i);
+                }
+            });
+
+            ui.add(
+                Param::new(&mut item.hold_secs, 1.0..=600.0)
+                    .logarithmic(true)
+                    .text("Hold")
+                    .unit(Unit::Secs)
+                    .default(defaults.hold_secs),
+            );
+            ui.add_enabled_ui(item.transition != PlaylistTransition::Cut, |ui| {
+                ui.add(
+                    Param::new(&mut item.transition_secs, 0.0..=30.0)
+                        .text("Transition")
+                        .unit(Unit::Secs)
+                        .default(defaults.transition_secs),
+                );
+            });
+
+            ui.separator();
+        }
+
+        if let Some(i) = swap {
+            playlist.playlist.items.swap(i, i + 1);
+            playlist.current = None;
+        }
+        if let Some(i) = remove {
+            playlist.playlist.items.remove(i);
+            playlist.current = None;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Add Item").clicked() {
+                playlist.playlist.items.push(PlaylistItem::default());
+            }
+            let current = presets.name.trim();
+            if ui
+                .add_enabled(!current.is_empty(), egui::Button::new("Add Current"))
+                .clicked()
+            {
+                playlist.playlist.items.push(PlaylistItem {
+                    preset: current.to_owned(),
+                    ..PlaylistItem::default()
+                });
+            }
+        });
+
+        if let Some(status) = &playlist.status {
+            ui.label(status);
+        }
+    }
+
+    fn bypass_indicator(&self) {
+        let active = self.bypass.active();
+        if active.is_empty() {
+            return;
+        }
+
+        let ctx = self.state.egui_ctx();
+        egui::Area::new(egui::Id::new("bypass_indicator"))
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0., 12.))
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(egui::Color32::RED, "BYPASS");
+                        for target in active {
+                            ui.label(target.label());
+                        }
+                    });
+                });
+            });
+    }
+
+    fn clock_sync_panel(ui: &mut egui::Ui, clock_sync: &mut UiClockSync) {
+        let defaults = UiClockSync::default();
+        ui.horizontal(|ui| {
+            for role in SyncRole::ALL {
+                ui.selectable_value(&mut clock_sync.role, role, role.label());
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Address");
+            ui.text_edit_singleline(&mut clock_sync.address);
+        });
+
+        if clock_sync.role == SyncRole::Slave {
+            ui.add(
+                Param::new(&mut clock_sync.latency_ms, -100.0..=100.0)
+                    .text("Latency")
+                    .unit(Unit::Ms)
+                    .default(defaults.latency_ms),
+            );
+
+            if clock_sync.locked {
+                ui.colored_label(egui::Color32::GREEN, "Locked to master");
+            } else {
+                ui.colored_label(egui::Color32::YELLOW, "Waiting for master");
+            }
+        }
+
+        if let Some(status) = &clock_sync.status {
+            ui.colored_label(egui::Color32::RED, status);
+        }
+    }
+
+    fn stats_panel(ui: &mut egui::Ui, quality: &mut UiQuality) {
+        let defaults = UiQuality::default();
+        let fps = 1000. / quality.frame_ms.max(0.001);
+        ui.label(format!(
+            "Frame time: {:.1} ms ({fps:.0} fps)",
+            quality.frame_ms
+        ));
+
+        let level = &quality::LEVELS[quality.level];
+        ui.label(format!(
+            "Quality: {} ({:.0}% scale{})",
+            level.name,
+            level.render_scale * 100.,
+            if level.post_effects {
+                ""
+            } else {
+                ", post effects off"
+            }
+        ));
+
+        ui.checkbox(&mut quality.adaptive, "Adaptive quality");
+        ui.add_enabled_ui(quality.adaptive, |ui| {
+            ui.add(
+                Param::new(&mut quality.target_ms, 4.0..=50.0)
+                    .text("Target")
+                    .unit(Unit::Ms)
+                    .default(defaults.target_ms),
+            );
+        });
+
+        ui.separator();
+
+        let draw = &quality.draw;
+        ui.label(format!(
+            "Draw calls: {}, instances: {}",
+            draw.draw_calls, draw.instances
+        ));
+        ui.label(format!("Ring coverage: {:.1}x screen", draw.ring_coverage))
+            .on_hover_text("Sum of ring areas relative to the screen area");
+        ui.label(format!(
+            "Shaded fragments: {:.1}x screen",
+            draw.shaded_screens
+        ))
+        .on_hover_text("Every ring instance shades a screen-sized quad");
+        ui.add(
+            Param::new(&mut quality.budget_screens, 4.0..=512.0)
+                .logarithmic(true)
+                .text("Fragment budget (screens)")
+                .default(defaults.budget_screens),
+        );
+    }
+
+    fn power_panel(ui: &mut egui::Ui, power: &mut UiPower) {
+        ui.checkbox(&mut power.enabled, "Low Power")
+            .on_hover_text("Start with `--low-power` to also prefer the low-power GPU");
+        ui.add_enabled_ui(power.enabled, |ui| {
+            ui.checkbox(&mut power.battery_only, "Only on Battery");
+        });
+
+        if power.enabled && power.battery_only {
+            ui.label(if power.on_battery {
+                "Running on battery"
+            } else {
+                "Running on external power"
+            });
+        }
+        if power.active {
+            ui.label(format!(
+                "Capped at {} FPS, {}% render scale, post effects off",
+                power::LOW_POWER_FPS,
+                power::LOW_POWER_SCALE * 100.
+            ));
+        }
+    }
+
+    fn reveal_panel(ui: &mut egui::Ui, reveal: &mut UiReveal) {
+        let defaults = UiReveal::default();
+        ui.checkbox(&mut reveal.enabled, "Animate on Load")
+            .on_hover_text("Play an outro for the old scene and an intro for the new one");
+        ui.add_enabled_ui(reveal.enabled, |ui| {
+            ui.horizontal(|ui| {
+                for style in RevealStyle::ALL {
+                    ui.selectable_value(&mut reveal.style, style, style.label());
+                }
+            });
+            ui.add(
+                Param::new(&mut reveal.intro_secs, 0.0..=10.0)
+                    .text("Intro")
+                    .unit(Unit::Secs)
+                    .default(defaults.intro_secs),
+            );
+            ui.add(
+                Param::new(&mut reveal.outro_secs, 0.0..=5.0)
+                    .text("Outro")
+                    .unit(Unit::Secs)
+                    .default(defaults.outro_secs),
+            );
+            ui.add(
+                Param::new(&mut reveal.stagger_secs, 0.0..=1.0)
+                    .text("Stagger")
+                    .unit(Unit::Secs)
+                    .default(defaults.stagger_secs),
+            );
+        });
+    }
+
+    fn repaint_panel(ui: &mut egui::Ui, repaint: &mut UiRepaint) {
+        let defaults = UiRepaint::default();
+        ui.checkbox(&mut repaint.throttle, "Throttle UI Redraw")
+            .on_hover_text("Reuse the last panel frame until input or an animation needs a redraw");
+        ui.add_enabled_ui(repaint.throttle, |ui| {
+            ui.add(
+                Param::new(&mut repaint.idle_fps, 1.0..=30.0)
+                    .logarithmic(true)
+                    .text("Idle Refresh")
+                    .unit(Unit::Hz)
+                    .default(defaults.idle_fps),
+            )
+            .on_hover_text("Keeps readouts such as meters and stats moving while idle");
+        });
+    }
+
+    fn quantize_panel(ui: &mut egui::Ui, quantize: &mut UiQuantize) {
+        ui.checkbox(&mut quantize.enabled, "Quantize Changes")
+            .on_hover_text("Hold wave edits until the next detected beat or bar");
+
+        ui.add_enabled_ui(quantize.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Apply On:");
+                ui.selectable_value(&mut quantize.division, QuantizeDivision::Beat, "Beat");
+                ui.selectable_value(&mut quantize.division, QuantizeDivision::Bar, "Bar");
+            });
+
+            ui.horizontal(|ui| {
+                for beat in 0..quantize::BEATS_PER_BAR {
+                    let color = if beat == quantize.beat {
+                        egui::Color32::from_rgb(240, 150, 60)
+                    } else {
+                        ui.visuals().weak_text_color()
+                    };
+                    ui.label(egui::RichText::new("●").color(color));
+                }
+
+                if ui.button("Set Downbeat").clicked() {
+                    quantize.downbeat_requested = true;
+                }
+            });
+
+            ui.horizontal(|ui| {
+                if quantize.pending {
+                    ui.colored_label(egui::Color32::YELLOW, "Changes pending");
+                } else {
+                    ui.label("Up to date");
+                }
+                if ui
+                    .add_enabled(quantize.pending, egui::Button::new("Apply Now"))
+                    .clicked()
+                {
+                    quantize.apply_requested = true;
+                }
+            });
+        });
+    }
+
+    fn over_budget(quality: &UiQuality) -> Option<String> {
+        (quality.draw.shaded_screens > quality.budget_screens).then(|| {
+            format!(
+                "{} instances shade {:.0} screens of fragments per frame, over the budget of {:.0}",
+                quality.draw.instances, quality.draw.shaded_screens, quality.budget_screens
+            )
+        })
+    }
+
+    fn trail_panel(ui: &mut egui::Ui, trail: &mut CursorTrail) {
+        let defaults = CursorTrail::default();
+        ui.checkbox(&mut trail.enabled, "Enabled");
+        ui.add(
+            Param::new(&mut trail.lifetime, 0.1..=3.0)
+                .text("Lifetime")
+                .unit(Unit::Secs)
+                .default(defaults.lifetime),
+        );
+        ui.add(
+            Param::new(&mut trail.scale, 0.01..=0.2)
+                .text("Scale")
+                .default(defaults.scale),
+        );
+        ui.add(
+            Param::new(&mut trail.rate, 1.0..=60.0)
+                .text("Rate")
+                .unit(Unit::Hz)
+                .default(defaults.rate),
+        );
+    }
+
+    fn brush_panel(ui: &mut egui::Ui, brush: &mut Brush, waves: usize) {
+        let defaults = Brush::default();
+        ui.checkbox(&mut brush.enabled, "Paint Mode")
+            .on_hover_text("Click-drag on the canvas to lay down a ring every few pixels");
+        ui.add_enabled_ui(brush.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Size From:");
+                for size in BrushSize::ALL {
+                    ui.selectable_value(&mut brush.size, size, size.label());
+                }
+            });
+            ui.add(
+                Param::new(&mut brush.spacing, 4.0..=128.0)
+                    .text("Spacing")
+                    .unit(Unit::Px)
+                    .default(defaults.spacing),
+            );
+            ui.add(
+                Param::new(&mut brush.min_radius, 0.0..=0.5)
+                    .text("Min Radius")
+                    .default(defaults.min_radius),
+            );
+            ui.add(
+                Param::new(&mut brush.max_radius, 0.0..=1.0)
+                    .text("Max Radius")
+                    .default(defaults.max_radius),
+            );
+            ui.add(
+                Param::new(&mut brush.min_thickness, 0.001..=0.05)
+                    .text("Min Thickness")
+                    .default(defaults.min_thickness),
+            );
+            ui.add(
+                Param::new(&mut brush.max_thickness, 0.01..=0.1)
+                    .text("Max Thickness")
+                    .default(defaults.max_thickness),
+            );
+        });
+
+        let label = format!("Slots: {waves} / {MAX_WAVES}");
+        if waves >= MAX_WAVES {
+            ui.colored_label(ui.visuals().warn_fg_color, label)
+                .on_hover_text("Remove waves to keep painting");
+        } else {
+            ui.label(label);
+        }
+    }
+
+    fn spring_panel(ui: &mut egui::Ui, spring: &mut SpringSettings) {
+        let defaults = SpringSettings::default();
+        ui.add(
+            Param::new(&mut spring.stiffness, 1.0..=500.0)
+                .logarithmic(true)
+                .text("Stiffness")
+                .default(defaults.stiffness),
+        );
+        ui.add(
+            Param::new(&mut spring.damping, 0.0..=40.0)
+                .text("Damping")
+                .default(defaults.damping),
+        );
+    }
+
+    fn texture_panel(ui: &mut egui::Ui, wave: &mut UiSineWaveData) {
+        let defaults = UiSineWaveData::default();
+        ui.horizontal(|ui| {
+            ui.label("Texture:");
+            ui.text_edit_singleline(&mut wave.texture_path);
+            if ui.button("Load").clicked() && !wave.texture_path.is_empty() {
+                wave.texture_requested = true;
+            }
+            if wave.textured && ui.button("Clear").clicked() {
+                wave.textured = false;
+            }
+        });
+
+        if let Some(err) = &wave.texture_error {
+            ui.colored_label(egui::Color32::RED, err);
+        }
+
+        ui.add_enabled_ui(wave.textured, |ui| {
+            ui.checkbox(&mut wave.texture_arc_length, "Arc Length")
+                .on_hover_text("Keep tiles the same on-screen length whatever the ring's radius");
+            if wave.texture_arc_length {
+                ui.add(
+                    Param::new(&mut wave.texture_tile_length, 4.0..=512.0)
+                        .logarithmic(true)
+                        .text("Tile Length")
+                        .unit(Unit::Px)
+                        .default(defaults.texture_tile_length),
+                );
+            } else {
+                ui.add(
+                    Param::new(&mut wave.texture_tiling, 1.0..=32.0)
+                        .step_by(1.0)
+                        .text("Tiling")
+                        .default(defaults.texture_tiling),
+                );
+            }
+            ui.add(
+                Param::new(&mut wave.texture_scroll, -0.05..=0.05)
+                    .text("Scroll")
+                    .default(defaults.texture_scroll),
+            );
+        });
+    }
+
+    fn echo_panel(ui: &mut egui::Ui, echo: &mut UiEcho) {
+        let defaults = UiEcho::default();
+        ui.add(
+            Param::new(&mut echo.count, 0..=MAX_ECHOES)
+                .text("Echoes")
+                .default(defaults.count),
+        );
+        ui.add_enabled_ui(echo.count > 0, |ui| {
+            ui.add(
+                Param::new(&mut echo.spread, -0.1..=0.1)
+                    .text("Echo Spread")
+                    .default(defaults.spread),
+            );
+            ui.add(
+                Param::new(&mut echo.phase, -PI..=PI)
+                    .text("Echo Phase")
+                    .unit(Unit::Radians)
+                    .default(defaults.phase),
+            );
+            ui.add(
+                Param::new(&mut echo.decay, 0.0..=1.0)
+                    .text("Echo Decay")
+                    .default(defaults.decay),
+            );
+        });
+    }
+
+    fn breathing_panel(ui: &mut egui::Ui, breathing: &mut UiBreathing) {
+        let defaults = UiBreathing::default();
+        ui.add(
+            Param::new(&mut breathing.depth, -0.2..=0.2)
+                .text("Breathing")
+                .default(defaults.depth),
+        )
+        .on_hover_text("Slowly grow and shrink the whole ring, on top of its wave");
+        ui.add_enabled_ui(breathing.depth != 0., |ui| {
+            ui.add(
+                Param::new(&mut breathing.period, 30.0..=3600.0)
+                    .logarithmic(true)
+                    .text("Breathing Period (frames)")
+                    .default(defaults.period),
+            );
+        });
+    }
+
+    fn audio_response_panel(ui: &mut egui::Ui, id_salt: impl Hash, response: &mut AudioResponse) {
+        let defaults = AudioResponse::default();
+        ui.checkbox(&mut response.enabled, "Own Audio Response")
+            .on_hover_text("Drive this wave from a single band through its own envelope");
+        ui.add_enabled_ui(response.enabled, |ui| {
+            egui::ComboBox::from_id_salt(("audio band", id_salt))
+                .selected_text(format!("Band: {:?}", response.band))
+                .show_ui(ui, |ui| {
+                    for band in AudioBand::ALL {
+                        ui.selectable_value(&mut response.band, band, format!("{band:?}"));
+                    }
+                });
+            ui.add(
+                Param::new(&mut response.attack, 0.0..=1.0)
+                    .logarithmic(true)
+                    .text("Attack")
+                    .unit(Unit::Secs)
+                    .default(defaults.attack),
+            );
+            ui.add(
+                Param::new(&mut response.release, 0.0..=4.0)
+                    .logarithmic(true)
+                    .text("Release")
+                    .unit(Unit::Secs)
+                    .default(defaults.release),
+            );
+            ui.add(
+                Param::new(&mut response.gain, 0.0..=4.0)
+                    .text("Gain")
+                    .default(defaults.gain),
+            );
+            ui.add(
+                Param::new(&mut response.threshold, 0.0..=0.95)
+                    .text("Gate")
+                    .default(defaults.threshold),
+            );
+        });
+    }
+
+    fn symmetry_panel(ui: &mut egui::Ui, symmetry: &mut UiSymmetry) {
+        ui.horizontal(|ui| {
+            ui.label("Mirror:");
+            ui.checkbox(&mut symmetry.across_x, "X Axis");
+            ui.checkbox(&mut symmetry.across_y, "Y Axis");
+            ui.checkbox(&mut symmetry.point, "Center");
+        });
+    }
+
+    fn locks_panel(ui: &mut egui::Ui, wave: &mut UiSineWaveData, constraints: &RandomConstraints) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Locks:");
+            for param in MacroParam::ALL {
+                let mut locked = wave.locks.contains(param);
+                if ui.toggle_value(&mut locked, param.label()).changed() {
+                    wave.locks.set(param, locked);
+                }
+            }
+        })
+        .response
+        .on_hover_text("Locked values are skipped by Randomize, preset morphs, macros and drift");
+
+        if ui
+            .button("🎲 Randomize")
+            .on_hover_text("Rolls only this wave, within the Random Constraints ranges")
+            .clicked()
+        {
+            lock::randomize(wave, random::time_seed(), constraints);
+        }
+    }
+
+    fn constraints_panel(ui: &mut egui::Ui, constraints: &mut RandomConstraints) {
+        egui::Grid::new("random_constraints")
+            .striped(true)
+            .show(ui, |ui| {
+                for param in MacroParam::ALL {
+                    let (low, high) = param.range();
+                    let (mut min, mut max) = constraints.range(param);
+                    let speed = (high - low) / 200.;
+
+                    ui.label(param.label());
+                    let changed = ui
+                        .add(
+                            egui::DragValue::new(&mut min)
+                                .range(low..=max)
+                                .speed(speed)
+                                .prefix("min "),
+                        )
+                        .changed()
+                        | ui.add(
+                            egui::DragValue::new(&mut max)
+                                .range(min..=high)
+                                .speed(speed)
+                                .prefix("max "),
+                        )
+                        .changed();
+                    if changed {
+                        constraints.set(param, min, max);
+                    }
+                    if ui
+                        .add_enabled(
+                            constraints.is_constrained(param),
+                            egui::Button::new("Reset"),
+                        )
+                        .clicked()
+                    {
+                        constraints.set(param, low, high);
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn repeat_panel(ui: &mut egui::Ui, repeat: &mut UiRepeat) {
+        let defaults = UiRepeat::default();
+        ui.add(
+            Param::new(&mut repeat.count, 1..=MAX_REPEATS)
+                .text("Radial Copies")
+                .default(defaults.count),
+        );
+        ui.add_enabled_ui(repeat.count > 1, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(
+                    Param::new(&mut repeat.spacing, -PI..=PI)
+                        .text("Spacing")
+                        .unit(Unit::Radians)
+                        .default(defaults.spacing),
+                );
+                if ui.button("Even").clicked() {
+                    repeat.spacing = TAU / repeat.count as f32;
+                }
+            });
+            ui.add(
+                Param::new(&mut repeat.phase, -PI..=PI)
+                    .text("Copy Phase")
+                    .unit(Unit::Radians)
+                    .default(defaults.phase),
+            );
+            ui.horizontal(|ui| {
+                ui.label("Around:");
+                for pivot in RepeatPivot::ALL {
+                    ui.selectable_value(&mut repeat.pivot, pivot, pivot.label());
+                }
+            });
+        });
+    }
+
+    fn fractal_panel(ui: &mut egui::Ui, fractal: &mut UiFractal) {
+        let defaults = UiFractal::default();
+        ui.checkbox(&mut fractal.enabled, "Fractal Perturbation");
+        ui.add_enabled_ui(fractal.enabled, |ui| {
+            ui.add(
+                Param::new(&mut fractal.octaves, 1..=8)
+                    .text("Octaves")
+                    .default(defaults.octaves),
+            );
+            ui.add(
+                Param::new(&mut fractal.gain, 0.0..=1.0)
+                    .text("Gain")
+                    .default(defaults.gain),
+            );
+            ui.add(
+                Param::new(&mut fractal.lacunarity, 1.0..=4.0)
+                    .text("Lacunarity")
+                    .default(defaults.lacunarity),
+            );
+        });
+    }
+
+    fn outline_panel(ui: &mut egui::Ui, outline: &mut UiOutline) {
+        let defaults = UiOutline::default();
+        ui.checkbox(&mut outline.enabled, "Outline");
+        ui.add_enabled_ui(outline.enabled, |ui| {
+            ui.add(
+                Param::new(&mut outline.thickness, 0.0005..=0.05)
+                    .text("Outline Thickness")
+                    .default(defaults.thickness),
+            );
+            ui.add(
+                Param::new(&mut outline.offset, -0.1..=0.1)
+                    .text("Outline Offset")
+                    .default(defaults.offset),
+            );
+            ui.horizontal(|ui| {
+                ui.label("Outline Color:");
+                ui.color_edit_button_rgb(&mut outline.color);
+            });
+        });
+    }
+
+    fn shadow_panel(ui: &mut egui::Ui, shadow: &mut UiShadow) {
+        let defaults = UiShadow::default();
+        ui.checkbox(&mut shadow.enabled, "Shadow");
+        ui.add_enabled_ui(shadow.enabled, |ui| {
+            let [x, y] = &mut shadow.offset;
+            ui.add(
+                Param::new(x, -0.1..=0.1)
+                    .text("Shadow Offset X")
+                    .default(defaults.offset[0]),
+            );
+            ui.add(
+                Param::new(y, -0.1..=0.1)
+                    .text("Shadow Offset Y")
+                    .default(defaults.offset[1]),
+            );
+            ui.add(
+                Param::new(&mut shadow.blur, 0.0..=0.1)
+                    .text("Shadow Blur")
+                    .default(defaults.blur),
+            );
+            ui.add(
+                Param::new(&mut shadow.opacity, 0.0..=1.0)
+                    .text("Shadow Opacity")
+                    .default(defaults.opacity),
+            );
+            ui.horizontal(|ui| {
+                ui.label("Shadow Color:");
+                ui.color_edit_button_rgb(&mut shadow.color);
+            });
+        });
+    }
+
+    fn motion_panel(
+        ui: &mut egui::Ui,
+        id_salt: impl Hash,
+        motion: &mut MotionPath,
+        center: [f32; 2],
+    ) {
+        let defaults = MotionPath::default();
+        let id = ui.make_persistent_id(("motion", id_salt));
+
+        egui::ComboBox::from_id_salt(id.with("shape"))
+            .selected_text(format!("Path: {:?}", motion.shape))
+            .show_ui(ui, |ui| {
+                for shape in PathShape::ALL {
+                    ui.selectable_value(&mut motion.shape, shape, format!("{shape:?}"));
+                }
+            });
+
+        if motion.shape == PathShape::Fixed {
+            return;
+        }
+
+        egui::ComboBox::from_id_salt(id.with("loop"))
+            .selected_text(format!("Loop: {:?}", motion.looping))
+            .show_ui(ui, |ui| {
+                for looping in PathLoop::ALL {
+                    ui.selectable_value(&mut motion.looping, looping, format!("{looping:?}"));
+                }
+            });
+        ui.add(
+            Param::new(&mut motion.speed, -1.0..=1.0)
+                .text("Path Speed")
+                .unit(Unit::Hz)
+                .default(defaults.speed),
+        );
+        ui.add(
+            Param::new(&mut motion.phase, 0.0..=1.0)
+                .text("Path Phase")
+                .default(defaults.phase),
+        );
+
+        match motion.shape {
+            PathShape::Fixed => {}
+            PathShape::Circle | PathShape::Lissajous => {
+                ui.horizontal(|ui| {
+                    ui.label("Radius:");
+                    ui.add(
+                        Param::new(&mut motion.radius[0], 0.0..=0.5)
+                            .text("X")
+                            .default(defaults.radius[0]),
+                    );
+                    ui.add(
+                        Param::new(&mut motion.radius[1], 0.0..=0.5)
+                            .text("Y")
+                            .default(defaults.radius[1]),
+                    );
+                });
+                if motion.shape == PathShape::Lissajous {
+                    ui.horizontal(|ui| {
+                        ui.label("Frequency:");
+                        ui.add(
+                            Param::new(&mut motion.frequency[0], 1.0..=8.0)
+                                .step_by(1.0)
+                                .text("X")
+                                .default(defaults.frequency[0]),
+                        );
+                        ui.add(
+                            Param::new(&mut motion.frequency[1], 1.0..=8.0)
+                                .step_by(1.0)
+                                .text("Y")
+                                .default(defaults.frequency[1]),
+                        );
+                    });
+                }
+            }
+            PathShape::Polyline => {
+                ui.horizontal(|ui| {
+                    let label = if motion.recording {
+                        "Stop Recording"
+                    } else {
+                        "Record"
+                    };
+                    if ui
+                        .button(label)
+                        .on_hover_text("Drag the wave center to draw the path")
+                        .clicked()
+                    {
+                        motion.recording = !motion.recording;
+                        if motion.recording {
+                            motion.points.clear();
+                        }
+                    }
+                    if ui.button("Add Point").clicked() && motion.points.len() < MAX_PATH_POINTS {
+                        motion.points.push(center);
+                    }
+                    if ui.button("Clear").clicked() {
+                        motion.points.clear();
+                    }
+                });
+                ui.label(format!("{} points", motion.points.len()));
+            }
+        }
+    }
+
+    fn easing_panel(ui: &mut egui::Ui, id_salt: impl Hash, easing: &mut Easing) {
+        const CURVE_HEIGHT: f32 = 80.;
+        const HANDLE_RADIUS: f32 = 5.;
+        const SEGMENTS: usize = 64;
+
+        let defaults = Easing::default();
+        let id = ui.make_persistent_id(("easing", id_salt));
+
+        egui::ComboBox::from_id_salt(id.with("curve"))
+            .selected_text(format!("Motion: {:?}", easing.curve))
+            .show_ui(ui, |ui| {
+                for curve in Curve::ALL {
+                    ui.selectable_value(&mut easing.curve, curve, format!("{curve:?}"));
+                }
+            });
+
+        if easing.curve == Curve::Linear {
+            return;
+        }
+
+        ui.add(
+            Param::new(&mut easing.period, 10.0..=1200.0)
+                .logarithmic(true)
+                .text("Period (frames)")
+                .default(defaults.period),
+        );
+
+        let width = ui.available_width().max(64.);
+        let (area, _) =
+            ui.allocate_exact_size(egui::vec2(width, CURVE_HEIGHT), egui::Sense::hover());
+        let plot = area.shrink(HANDLE_RADIUS);
+        let to_screen = |[x, y]: [f32; 2]| {
+            egui::pos2(
+                plot.left() + x * plot.width(),
+                plot.bottom() - y * plot.height(),
+            )
+        };
+
+        let painter = ui.painter_at(area);
+        let visuals = ui.visuals();
+        painter.rect_filled(area, 2., visuals.extreme_bg_color);
+
+        let points = (0..=SEGMENTS)
+            .map(|i| {
+                let t = i as f32 / SEGMENTS as f32;
+                to_screen([t, easing.sample(t)])
+            })
+            .collect();
+        painter.add(egui::Shape::line(
+            points,
+            egui::Stroke::new(1.5, visuals.strong_text_color()),
+        ));
+
+        if easing.curve != Curve::Bezier {
+            return;
+        }
+
+        let anchors = [[0., 0.], [1., 1.]];
+        for (i, control) in easing.control.iter_mut().enumerate() {
+            let handle_response = ui.interact(
+                egui::Rect::from_center_size(
+                    to_screen(*control),
+                    egui::Vec2::splat(2. * HANDLE_RADIUS),
+                ),
+                id.with(i),
+                egui::Sense::drag(),
+            );
+            if handle_response.dragged()
+                && let Some(pointer) = handle_response.interact_pointer_pos()
+            {
+                *control = [
+                    ((pointer.x - plot.left()) / plot.width()).clamp(0., 1.),
+                    (plot.bottom() - pointer.y) / plot.height(),
+                ];
+            }
+
+            let handle = to_screen(*control);
+            painter.line_segment(
+                [to_screen(anchors[i]), handle],
+                egui::Stroke::new(1., egui::Color32::DARK_GRAY),
+            );
+            painter.circle(
+                handle,
+                HANDLE_RADIUS,
+                egui::Color32::DARK_GRAY,
+                egui::Stroke::new(
+                    1.5,
+                    if handle_response.hovered() || handle_response.dragged() {
+                        egui::Color32::WHITE
+                    } else {
+                        egui::Color32::GRAY
+                    },
+                ),
+            );
+        }
+    }
+
+    fn grade_panel(ui: &mut egui::Ui, grade: &mut UiGrade) {
+        let defaults = UiGrade::default();
+        ui.checkbox(&mut grade.enabled, "Enabled");
+
+        ui.add_enabled_ui(grade.enabled, |ui| {
+            ui.add(
+                Param::new(&mut grade.hue_shift, -180.0..=180.0)
+                    .text("Hue Shift")
+                    .unit(Unit::Degrees)
+                    .default(defaults.hue_shift),
+            );
+            ui.add(
+                Param::new(&mut grade.saturation, 0.0..=2.0)
+                    .text("Saturation")
+                    .default(defaults.saturation),
+            );
+            ui.add(
+                Param::new(&mut grade.contrast, 0.0..=2.0)
+                    .text("Contrast")
+                    .default(defaults.contrast),
+            );
+
+            for (label, values, range) in [
+                ("Lift", &mut grade.lift, -0.5..=0.5),
+                ("Gamma", &mut grade.gamma, 0.2..=3.0),
+                ("Gain", &mut grade.gain, 0.0..=2.0),
+            ] {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{label}:"));
+                    for (value, channel) in values.iter_mut().zip(["R", "G", "B"]) {
+                        ui.add(
+                            egui::DragValue::new(value)
+                                .range(range.clone())
+                                .speed(0.005)
+                                .prefix(format!("{channel} ")),
+                        );
+                    }
+                });
+            }
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.label("LUT (.cube):");
+                ui.text_edit_singleline(&mut grade.lut_path);
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Load").clicked() && !grade.lut_path.trim().is_empty() {
+                    grade.lut_requested = true;
+                }
+                if ui
+                    .add_enabled(grade.lut_loaded, egui::Button::new("Clear"))
+                    .clicked()
+                {
+                    grade.lut_path.clear();
+                    grade.lut_requested = true;
+                }
+            });
+            ui.add_enabled_ui(grade.lut_loaded, |ui| {
+                ui.add(
+                    Param::new(&mut grade.lut_strength, 0.0..=1.0)
+                        .text("LUT Strength")
+                        .default(defaults.lut_strength),
+                );
+            });
+            if let Some(error) = &grade.lut_error {
+                ui.colored_label(egui::Color32::RED, error);
+            }
+
+            if ui.button("Reset").clicked() {
+                *grade = UiGrade {
+                    enabled: true,
+                    lut_path: std::mem::take(&mut grade.lut_path),
+                    lut_loaded: grade.lut_loaded,
+                    ..Default::default()
+                };
+            }
+        });
+    }
+
+    fn strobe_panel(ui: &mut egui::Ui, strobe: &mut UiStrobe) {
+        let defaults = UiStrobe::default();
+        ui.checkbox(&mut strobe.enabled, "Enabled");
+
+        ui.horizontal(|ui| {
+            ui.label("Sync:");
+            ui.selectable_value(&mut strobe.source, StrobeSource::Lfo, "LFO");
+            ui.selectable_value(&mut strobe.source, StrobeSource::Beat, "Beat");
+        });
+        ui.horizontal(|ui| {
+            ui.label("Effect:");
+            ui.selectable_value(&mut strobe.mode, StrobeMode::Flash, "Flash");
+            ui.selectable_value(&mut strobe.mode, StrobeMode::Brightness, "Brightness");
+        });
+
+        ui.add(
+            Param::new(&mut strobe.rate, 0.1..=20.0)
+                .logarithmic(true)
+                .text("Rate")
+                .unit(Unit::Hz)
+                .default(defaults.rate),
+        );
+        ui.add(
+            Param::new(&mut strobe.duty, 0.05..=0.95)
+                .text("Duty Cycle")
+                .default(defaults.duty),
+        );
+        ui.add(
+            Param::new(&mut strobe.intensity, 0.0..=1.0)
+                .text("Intensity")
+                .default(defaults.intensity),
+        );
+
+        ui.separator();
+
+        ui.checkbox(&mut strobe.safe, "Epilepsy-safe cap")
+            .on_hover_text("Limit flashes to a safe frequency regardless of the rate and beat");
+        ui.add_enabled_ui(strobe.safe, |ui| {
+            ui.add(
+                Param::new(&mut strobe.safe_max_hz, 0.5..=3.0)
+                    .text("Max Flashes")
+                    .unit(Unit::Hz)
+                    .default(defaults.safe_max_hz),
+            );
+        });
+        if strobe.safe && strobe.rate > strobe.safe_max_hz {
+            ui.label(format!("Capped at {:.1} Hz", strobe.effective_rate()));
+        }
+    }
+
+    fn routing_panel(ui: &mut egui::Ui, routing: &mut UiRouting) {
+        egui::Grid::new("routing_matrix")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("");
+                for stage in RouteStage::ALL {
+                    ui.label(stage.label());
+                }
+                ui.end_row();
+
+                for output in RouteOutput::ALL
+                    .into_iter()
+                    .filter(|output| output.available())
+                {
+                    ui.label(output.label());
+                    for stage in RouteStage::ALL {
+                        ui.add_enabled_ui(output.supports(stage), |ui| {
+                            ui.radio_value(routing.stage_mut(output), stage, "")
+                                .on_disabled_hover_text(
+                                    "The window always shows the control panel",
+                                );
+                        });
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn replay_panel(ui: &mut egui::Ui, replay: &mut UiReplay) {
+        let defaults = UiReplay::default();
+        ui.checkbox(&mut replay.enabled, "Keep Rolling Buffer")
+            .on_hover_text(format!("Press {} to save it", replay::HOTKEY));
+
+        ui.add_enabled_ui(replay.enabled, |ui| {
+            ui.add(
+                Param::new(&mut replay.seconds, 1.0..=60.0)
+                    .text("Length")
+                    .unit(Unit::Secs)
+                    .default(defaults.seconds),
+            );
+            ui.add(
+                Param::new(&mut replay.fps, 5..=60)
+                    .text("Frame Rate")
+                    .unit(Unit::Hz)
+                    .default(defaults.fps),
+            );
+            ui.add(
+                Param::new(&mut replay.max_width, 160..=1920)
+                    .text("Max Width")
+                    .unit(Unit::Px)
+                    .default(defaults.max_width),
+            );
+
+            let frames = replay.seconds * replay.fps as f32;
+            let megabytes = frames * (replay.max_width as f32).powi(2) * 9. / 16. * 4. / 1e6;
+            ui.label(format!(
+                "Buffered {:.1}s of {:.0}s (up to ~{megabytes:.0} MB at 16:9)",
+                replay.buffered_secs, replay.seconds
+            ));
+
+            ui.horizontal(|ui| {
+                ui.label("Folder:");
+                ui.text_edit_singleline(&mut replay.directory);
+            });
+            if ui.button("Save Replay").clicked() {
+                replay.save_requested = true;
+            }
+        });
+
+        if let Some(status) = &replay.status {
+            ui.label(status);
+        }
+    }
+
+    fn metrics_panel(ui: &mut egui::Ui, metrics: &mut UiMetrics) {
+        let defaults = UiMetrics::default();
+        ui.add_enabled_ui(!metrics.recording, |ui| {
+            ui.horizontal(|ui| {
+                for format in MetricsFormat::ALL {
+                    ui.selectable_value(&mut metrics.format, format, format.label());
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Folder:");
+                ui.text_edit_singleline(&mut metrics.directory);
+            });
+        });
+        ui.add(
+            Param::new(&mut metrics.rotate_mb, 1.0..=256.0)
+                .logarithmic(true)
+                .text("Rotate After (MB)")
+                .default(defaults.rotate_mb),
+        );
+
+        ui.horizontal(|ui| {
+            let label = if metrics.recording { "Stop" } else { "Start" };
+            if ui.button(label).clicked() {
+                metrics.recording = !metrics.recording;
+            }
+            if metrics.recording {
+                ui.colored_label(egui::Color32::RED, format!("● {} frames", metrics.frames));
+            }
+        });
+
+        if let Some(status) = &metrics.status {
+            ui.label(status);
+        }
+    }
+
+    fn preview_panel(ui: &mut egui::Ui, preview: &mut UiPreview) {
+        ui.checkbox(&mut preview.enabled, "Letterbox to export resolution")
+            .on_hover_text("Render the scene at the export aspect so framing matches the output");
+
+        ui.add_enabled_ui(preview.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut preview.width)
+                        .range(16..=8192)
+                        .suffix(" px"),
+                );
+                ui.label("×");
+                ui.add(
+                    egui::DragValue::new(&mut preview.height)
+                        .range(16..=8192)
+                        .suffix(" px"),
+                );
+            });
+
+            ui.horizontal(|ui| {
+                for (label, width, height) in [
+                    ("16:9", 1920, 1080),
+                    ("9:16", 1080, 1920),
+                    ("1:1", 1080, 1080),
+                    ("4:5", 1080, 1350),
+                ] {
+                    if ui.button(label).clicked() {
+                        preview.width = width;
+                        preview.height = height;
+                    }
+                }
+            });
+        });
+    }
+
+    fn display_panel(ui: &mut egui::Ui, display: &mut UiDisplay, settings: &mut Settings) {
+        ui.label(format!("Monitor scale: {:.2}x", display.scale_factor));
+
+        let mut zoom = ui.ctx().zoom_factor();
+        if ui
+            .add(
+                Param::new(&mut zoom, 0.5..=3.0)
+                    .text("UI Scale")
+                    .default(1.),
+            )
+            .changed()
+        {
+            ui.ctx().set_zoom_factor(zoom);
+        }
+
+        ui.checkbox(&mut display.scale_scene, "Scale scene with monitor DPI")
+            .on_hover_text("Keep rings the same physical size when moving between monitors");
+
+        ui.horizontal(|ui| {
+            ui.label("Parameters:");
+            for style in ParamStyle::ALL {
+                ui.selectable_value(&mut display.param_style, style, style.label());
+            }
+        })
+        .response
+        .on_hover_text("Right-click any parameter to reset it, or click its value to type one");
+
+        ui.horizontal(|ui| {
+            ui.label(tr(ui.ctx(), "Language"));
+            egui::ComboBox::from_id_salt("language")
+                .selected_text(settings.language.label())
+                .show_ui(ui, |ui| {
+                    for language in Language::ALL {
+                        ui.selectable_value(&mut settings.language, language, language.label());
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr(ui.ctx(), "Decimals"));
+            for decimal in DecimalSeparator::ALL {
+                ui.selectable_value(&mut settings.decimal, decimal, decimal.label());
+            }
+        })
+        .response
+        .on_hover_text("Typed values accept either separator");
+    }
+
+    fn theme_panel(ui: &mut egui::Ui, theme: &mut UiTheme) {
+        let defaults = UiTheme::default();
+
+        ui.horizontal(|ui| {
+            for mode in ThemeMode::ALL {
+                ui.selectable_value(&mut theme.mode, mode, mode.label());
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.color_edit_button_srgb(&mut theme.accent);
+            ui.label("Accent");
+        });
+
+        ui.add(
+            Param::new(&mut theme.rounding, 0.0..=12.0)
+                .text("Rounding")
+                .unit(Unit::Px)
+                .default(defaults.rounding)
+                .step_by(1.),
+        );
+        ui.add(
+            Param::new(&mut theme.font_size, 9.0..=24.0)
+                .text("Font Size")
+                .unit(Unit::Px)
+                .default(defaults.font_size)
+                .step_by(0.5),
+        );
+
+        if ui.button("Reset Theme").clicked() {
+            *theme = defaults;
+        }
+    }
+
+    fn color_space_panel(ui: &mut egui::Ui, color_space: &mut UiColorSpace) {
+        let format_name =
+            |format: Option<TextureFormat>| format.map_or("-".to_owned(), |f| format!("{f:?}"));
+
+        ui.label(format!(
+            "Surface: {}",
+            format_name(color_space.surface_format)
+        ));
+        ui.label(format!(
+            "View: {}",
+            format_name(
+                color_space
+                    .surface_format
+                    .map(|format| { surface_view_format(format, color_space.srgb_surface) })
+            )
+        ));
+        ui.label(format!(
+            "Off-screen: {}",
+            format_name(color_space.off_screen_format)
+        ));
+
+        ui.add_enabled_ui(color_space.srgb_available, |ui| {
+            ui.checkbox(&mut color_space.srgb_surface, "sRGB surface view")
+                .on_disabled_hover_text("The surface cannot be viewed in another format");
+        });
+
+        ui.checkbox(&mut color_space.linear_output, "Gamma-correct output")
+            .on_hover_text("Off shows the scene as if it were written without gamma handling");
+
+        ui.separator();
+        let labels = |outputs: &[HdrOutput]| {
+            outputs
+                .iter()
+                .map(|output| output.label())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        if color_space.hdr_available.is_empty() {
+            ui.label("HDR: no usable HDR surface format");
+        } else {
+            ui.label(format!(
+                "HDR-capable surface formats: {}",
+                labels(&color_space.hdr_available)
+            ))
+            .on_hover_text("The display itself must also be switched to HDR mode");
+        }
+        match color_space.hdr {
+            Some(output) => {
+                ui.label(format!("HDR output: {}", output.label())).on_hover_text(
+                    "If the display isn't in HDR mode, highlights clip and the scene looks washed out",
+                );
+            }
+            None if !color_space.hdr_available.is_empty() => {
+                ui.label("HDR output: off (restart with `--hdr auto` to enable)");
+            }
+            None => {}
+        }
+
+        let defaults = UiColorSpace::default();
+        ui.add_enabled_ui(color_space.hdr.is_some(), |ui| {
+            ui.add(
+                Param::new(&mut color_space.sdr_white, 80.0..=500.0)
+                    .text("SDR White")
+                    .unit(Unit::Nits)
+                    .default(defaults.sdr_white),
+            )
+            .on_hover_text("How bright a scene value of 1.0 is shown");
+            ui.add(
+                Param::new(&mut color_space.hdr_peak, 200.0..=10000.0)
+                    .text("Peak Brightness")
+                    .unit(Unit::Nits)
+                    .logarithmic(true)
+                    .default(defaults.hdr_peak),
+            )
+            .on_hover_text("Additive highlights are clipped here; match it to the display");
+        });
+    }
+
+    fn scene_panel(ui: &mut egui::Ui, scene: &mut UiScene) {
+        let defaults = UiScene::default();
+        ui.horizontal(|ui| {
+            ui.label("Mode:");
+            ui.selectable_value(&mut scene.mode, SceneMode::Rings, "Rings");
+            ui.add_enabled_ui(scene.interference_available, |ui| {
+                ui.selectable_value(&mut scene.mode, SceneMode::Interference, "Interference")
+                    .on_disabled_hover_text("Requires storage buffer support");
+            });
+        });
+
+        ui.checkbox(&mut scene.stretch, "Stretch to window")
+            .on_hover_text(
+                "Legacy mode: rings follow the window shape instead of staying circular",
+            );
+
+        ui.add(
+            Param::new(&mut scene.master_opacity, 0.0..=1.0)
+                .text("Master Opacity")
+                .default(defaults.master_opacity),
+        )
+        .on_hover_text("Fades the whole output towards black");
+
+        ui.horizontal(|ui| {
+            ui.label(format!("Zoom: {:.0}%", scene.camera.zoom * 100.));
+            let reset = ui
+                .add_enabled(
+                    scene.camera != Camera::default(),
+                    egui::Button::new("Reset View"),
+                )
+                .on_hover_text("Scroll to zoom, middle-drag to pan, double-click to reset");
+            if reset.clicked() {
+                scene.camera = Camera::default();
+            }
+        });
+
+        ui.add_enabled_ui(scene.mode == SceneMode::Rings, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut scene.antialias.enabled, "Antialias edges");
+                ui.add_enabled(
+                    scene.antialias.enabled,
+                    Param::new(&mut scene.antialias.width, 0.25..=4.0)
+                        .text("Width")
+                        .unit(Unit::Px)
+                        .default(defaults.antialias.width),
+                );
+            });
+            ui.checkbox(&mut scene.antialias.hairline, "Hairline strokes")
+                .on_hover_text(
+                    "Keep rings thinner than a pixel one pixel wide and fade them instead",
+                );
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut scene.collision.enabled, "Highlight collisions");
+                ui.color_edit_button_rgb(&mut scene.collision.color);
+            });
+            ui.add_enabled_ui(scene.collision.enabled, |ui| {
+                ui.add(
+                    Param::new(&mut scene.collision.strength, 0.0..=1.0)
+                        .text("Highlight Strength")
+                        .default(defaults.collision.strength),
+                );
+            });
+
+            Self::false_color_panel(ui, &mut scene.false_color);
+        });
+
+        ui.collapsing("Background Gradient", |ui| {
+            ui.checkbox(&mut scene.background.enabled, "Enabled")
+                .on_hover_text("Drawn behind the rings; interference mode covers it");
+            ui.add_enabled_ui(scene.background.enabled, |ui| {
+                ui.horizontal(|ui| {
+                    for style in GradientStyle::ALL {
+                        ui.selectable_value(&mut scene.background.style, style, style.label());
+                    }
+                });
+                Self::ramp_editor(ui, "background_ramp", &mut scene.background.ramp);
+            });
+        });
+
+        if scene.mode != SceneMode::Interference {
+            return;
+        }
+
+        ui.label("Color Ramp:");
+        Self::ramp_editor(ui, "interference_ramp", &mut scene.ramp);
+    }
+
+    fn false_color_panel(ui: &mut egui::Ui, false_color: &mut UiFalseColor) {
+        let defaults = UiFalseColor::default();
+        ui.horizontal(|ui| {
+            ui.label("False Color:");
+            for map in FalseColorMap::ALL {
+                ui.selectable_value(&mut false_color.map, map, map.label());
+            }
+        });
+
+        ui.add_enabled_ui(false_color.map != FalseColorMap::Off, |ui| {
+            ui.horizontal(|ui| {
+                for source in FalseColorSource::ALL {
+                    ui.selectable_value(&mut false_color.source, source, source.label());
+                }
+            });
+            ui.add(
+                Param::new(&mut false_color.range, 0.005..=0.5)
+                    .logarithmic(true)
+                    .text("Range")
+                    .default(defaults.range),
+            );
+            ui.add_enabled(
+                false_color.map == FalseColorMap::Bands,
+                Param::new(&mut false_color.bands, 2..=32)
+                    .text("Bands")
+                    .default(defaults.bands),
+            );
+        });
+    }
+
+    pub(crate) fn ramp_editor(ui: &mut egui::Ui, id_salt: impl Hash, ramp: &mut ColorRamp) {
+        const BAR_HEIGHT: f32 = 20.;
+        const HANDLE_SIZE: f32 = 10.;
+        const SEGMENTS: usize = 64;
+
+        let id = ui.make_persistent_id(id_salt);
+        let mut selected = ui
+            .data(|data| data.get_temp::<usize>(id))
+            .unwrap_or_default()
+            .min(ramp.stops.len().saturating_sub(1));
+
+        egui::ComboBox::from_id_salt(id.with("interpolation"))
+            .selected_text(format!("{:?}", ramp.interpolation))
+            .show_ui(ui, |ui| {
+                for interpolation in [
+                    Interpolation::Linear,
+                    Interpolation::Smooth,
+                    Interpolation::Constant,
+                ] {
+                    ui.selectable_value(
+                        &mut ramp.interpolation,
+                        interpolation,
+                        format!("{interpolation:?}"),
+                    );
+                }
+            });
+
+        let width = ui.available_width().max(64.);
+        let (bar, response) = ui.allocate_exact_size(
+            egui::vec2(width, BAR_HEIGHT + HANDLE_SIZE),
+            egui::Sense::click(),
+        );
+        let gradient = egui::Rect::from_min_max(
+            bar.min + egui::vec2(HANDLE_SIZE / 2., 0.),
+            egui::pos2(bar.right() - HANDLE_SIZE / 2., bar.top() + BAR_HEIGHT),
+        );
+
+        let painter = ui.painter_at(bar);
+        let color = |[r, g, b]: [f32; 3]| egui::Rgba::from_rgb(r, g, b).into();
+
+        let mut mesh = egui::Mesh::default();
+        for i in 0..=SEGMENTS {
+            let t = i as f32 / SEGMENTS as f32;
+            let x = gradient.left() + t * gradient.width();
+            let fill = color(ramp.sample(t));
+
+            mesh.colored_vertex(egui::pos2(x, gradient.top()), fill);
+            mesh.colored_vertex(egui::pos2(x, gradient.bottom()), fill);
+
+            if i > 0 {
+                let base = 2 * i as u32;
+                mesh.add_triangle(base - 2, base - 1, base);
+                mesh.add_triangle(base - 1, base, base + 1);
+            }
+        }
+        painter.add(mesh);
+
+        let mut handle_hit = false;
+        for (i, stop) in ramp.stops.iter_mut().enumerate() {
+            let x = gradient.left() + stop.position.clamp(0., 1.) * gradient.width();
+            let handle = egui::Rect::from_center_size(
+                egui::pos2(x, gradient.bottom() + HANDLE_SIZE / 2.),
+                egui::vec2(HANDLE_SIZE, HANDLE_SIZE),
+            );
+
+            let handle_response = ui.interact(handle, id.with(i), egui::Sense::click_and_drag());
+            if handle_response.clicked() || handle_response.drag_started() {
+                selected = i;
+            }
+            if handle_response.dragged()
+                && let Some(pointer) = handle_response.interact_pointer_pos()
+            {
+                stop.position = ((pointer.x - gradient.left()) / gradient.width()).clamp(0., 1.);
+            }
+            handle_hit |= handle_response.hovered();
+
+            let outline = if i == selected {
+                egui::Color32::WHITE
+            } else {
+                egui::Color32::DARK_GRAY
+            };
+
+            painter.vline(x, gradient.y_range(), egui::Stroke::new(1., outline));
+            painter.rect(
+                handle,
+                2.,
+                color(stop.color),
+                egui::Stroke::new(1.5, outline),
+                egui::StrokeKind::Inside,
+            );
+        }
+
+        if response.clicked()
+            && !handle_hit
+            && ramp.stops.len() < MAX_STOPS
+            && let Some(pointer) = response.interact_pointer_pos()
+        {
+            let position = ((pointer.x - gradient.left()) / gradient.width()).clamp(0., 1.);
+            ramp.stops.push(ColorStop {
+                position,
+                color: ramp.sample(position),
+            });
+            selected = ramp.stops.len() - 1;
+        }
+
+        let can_remove = ramp.stops.len() > 1;
+        let mut remove = false;
+        if let Some(stop) = ramp.stops.get_mut(selected) {
+            ui.horizontal(|ui| {
+                ui.color_edit_button_rgb(&mut stop.color);
+                ui.add(Param::new(&mut stop.position, 0.0..=1.0).text("Position"));
+                remove = can_remove && ui.button("Delete").clicked();
+            });
+
+            let [mut l, mut c, h] = color::linear_to_oklch(stop.color);
+            let mut hue = h.to_degrees();
+            ui.horizontal(|ui| {
+                let lightness = egui::DragValue::new(&mut l)
+                    .range(0.0..=1.0)
+                    .speed(0.005)
+                    .prefix("L: ");
+                let chroma = egui::DragValue::new(&mut c)
+                    .range(0.0..=0.4)
+                    .speed(0.002)
+                    .prefix("C: ");
+                let hue_value = egui::DragValue::new(&mut hue)
+                    .range(0.0..=360.0)
+                    .speed(1.0)
+                    .prefix("H: ");
+
+                if ui.add(lightness).changed()
+                    | ui.add(chroma).changed()
+                    | ui.add(hue_value).changed()
+                {
+                    stop.color = color::oklch_to_linear([l, c, hue.to_radians()]);
+                }
+            });
+        }
+
+        if remove {
+            ramp.stops.remove(selected);
+            selected = selected.saturating_sub(1);
+        }
+
+        response.on_hover_text("Click to add a stop, drag handles to move them");
+
+        ui.data_mut(|data| data.insert_temp(id, selected));
+    }
+
+    fn pixel_sort_panel(ui: &mut egui::Ui, pixel_sort: &mut UiPixelSort) {
+        let defaults = UiPixelSort::default();
+        ui.add_enabled_ui(pixel_sort.available, |ui| {
+            ui.checkbox(&mut pixel_sort.enabled, "Enabled")
+                .on_disabled_hover_text("Requires compute shader support");
+        });
+
+        ui.add_enabled_ui(pixel_sort.available && pixel_sort.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Sort:");
+                ui.selectable_value(&mut pixel_sort.mode, SortMode::Radius, "Along Radius");
+                ui.selectable_value(&mut pixel_sort.mode, SortMode::Angle, "Along Angle");
+            });
+            ui.checkbox(&mut pixel_sort.descending, "Descending");
+            ui.add(
+                Param::new(&mut pixel_sort.threshold, 0.0..=1.0)
+                    .text("Threshold")
+                    .default(defaults.threshold),
+            );
+            ui.add(
+                Param::new(&mut pixel_sort.animate, 0.0..=0.5)
+                    .text("Animate")
+                    .default(defaults.animate),
+            );
+            ui.add(
+                Param::new(&mut pixel_sort.speed, 0.0..=0.2)
+                    .text("Speed")
+                    .default(defaults.speed),
+            );
+            ui.add(
+                Param::new(&mut pixel_sort.mix, 0.0..=1.0)
+                    .text("Mix")
+                    .default(defaults.mix),
+            );
+        });
+    }
+
+    fn field_warp_panel(ui: &mut egui::Ui, warp: &mut UiFieldWarp) {
+        let defaults = UiFieldWarp::default();
+        ui.add_enabled_ui(warp.available, |ui| {
+            ui.checkbox(&mut warp.enabled, "Enabled")
+                .on_disabled_hover_text("Requires compute shader support");
+        });
+
+        ui.add_enabled_ui(warp.available && warp.enabled, |ui| {
+            ui.add(
+                Param::new(&mut warp.strength, 0.0..=0.2)
+                    .text("Strength")
+                    .default(defaults.strength),
+            );
+            ui.add(
+                Param::new(&mut warp.scale, 0.5..=20.0)
+                    .logarithmic(true)
+                    .text("Scale")
+                    .default(defaults.scale),
+            );
+            ui.add(
+                Param::new(&mut warp.speed, 0.0..=0.05)
+                    .text("Speed")
+                    .default(defaults.speed),
+            );
+            ui.add(
+                Param::new(&mut warp.steps, 1..=32)
+                    .text("Smear Steps")
+                    .default(defaults.steps),
+            );
+        });
+    }
+
+    fn shadertoy_panel(ui: &mut egui::Ui, shadertoy: &mut UiShadertoy) {
+        let defaults = UiShadertoy::default();
+        ui.checkbox(&mut shadertoy.enabled, "Enabled");
+
+        ui.add_enabled_ui(shadertoy.enabled, |ui| {
+            ui.add(
+                Param::new(&mut shadertoy.opacity, 0.0..=1.0)
+                    .text("Opacity")
+                    .default(defaults.opacity),
+            );
+            ui.add(
+                Param::new(&mut shadertoy.speed, 0.0..=4.0)
+                    .text("Speed")
+                    .default(defaults.speed),
+            )
+            .on_hover_text("Scales `iTime` against the scene clock");
+
+            ui.horizontal(|ui| {
+                ui.label("File:");
+                ui.text_edit_singleline(&mut shadertoy.path);
+                if ui.button("Load").clicked() {
+                    shadertoy.load_file();
+                }
+            });
+
+            ui.label("mainImage(out vec4 fragColor, in vec2 fragCoord):")
+                .on_hover_text(
+                    "Provides iResolution, iTime and iMouse; iChannel textures are not supported",
+                );
+            egui::ScrollArea::vertical()
+                .max_height(240.)
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut shadertoy.source)
+                            .code_editor()
+                            .desired_rows(12)
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+
+            if ui.button("Compile").clicked() {
+                shadertoy.compile = true;
+            }
+        });
+
+        if let Some(status) = &shadertoy.status {
+            ui.colored_label(egui::Color32::RED, egui::RichText::new(status).monospace());
+        }
+    }
+
+    fn reaction_diffusion_panel(ui: &mut egui::Ui, rd: &mut UiReactionDiffusion) {
+        let defaults = UiReactionDiffusion::default();
+        ui.add_enabled_ui(rd.available, |ui| {
+            ui.checkbox(&mut rd.enabled, "Enabled")
+                .on_disabled_hover_text("Requires compute shader support");
+        });
+
+        ui.add_enabled_ui(rd.available && rd.enabled, |ui| {
+            ui.horizontal(|ui| {
+                for (name, feed, kill) in REACTION_DIFFUSION_PRESETS {
+                    if ui.button(name).clicked() {
+                        rd.feed = feed;
+                        rd.kill = kill;
+                    }
+                }
+            });
+            ui.add(
+                Param::new(&mut rd.feed, 0.01..=0.1)
+                    .text("Feed Rate")
+                    .fixed_decimals(4)
+                    .default(defaults.feed),
+            );
+            ui.add(
+                Param::new(&mut rd.kill, 0.04..=0.075)
+                    .text("Kill Rate")
+                    .fixed_decimals(4)
+                    .default(defaults.kill),
+            );
+            ui.add(
+                Param::new(&mut rd.steps, 0..=32)
+                    .text("Steps / Frame")
+                    .default(defaults.steps),
+            );
+            if ui.button("Reseed").clicked() {
+                rd.reseed = true;
+            }
+
+            ui.separator();
+            ui.checkbox(&mut rd.background, "Background Layer");
+            ui.add_enabled_ui(rd.background, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Color");
+                    ui.color_edit_button_rgb(&mut rd.color);
+                });
+                ui.add(
+                    Param::new(&mut rd.intensity, 0.0..=1.0)
+                        .text("Intensity")
+                        .default(defaults.intensity),
+                );
+            });
+
+            ui.separator();
+            ui.add(
+                Param::new(&mut rd.thickness_mod, 0.0..=4.0)
+                    .text("Thickness Modulation")
+                    .default(defaults.thickness_mod),
+            );
+            ui.add(
+                Param::new(&mut rd.color_mod, 0.0..=1.0)
+                    .text("Color Modulation")
+                    .default(defaults.color_mod),
+            );
+        });
+    }
+
+    fn compare_panel(ui: &mut egui::Ui, compare: &mut UiCompare, waves: &UiWaves, scene: &UiScene) {
+        let defaults = UiCompare::default();
+        ui.horizontal(|ui| {
+            if ui.button("Save Baseline").clicked() {
+                compare.baseline = Some(Baseline {
+                    waves: waves.clone(),
+                    ramp: scene.ramp.clone(),
+                    mode: scene.mode,
+                });
+                compare.baseline_saved = true;
+                compare.enabled = true;
+            }
+
+            if compare.baseline.is_some() && ui.button("Discard").clicked() {
+                compare.baseline = None;
+                compare.enabled = false;
+            }
+        });
+
+        ui.add_enabled_ui(compare.baseline.is_some(), |ui| {
+            ui.checkbox(&mut compare.enabled, "Split Screen");
+            ui.add(
+                Param::new(&mut compare.split, 0.0..=1.0)
+                    .text("Split")
+                    .default(defaults.split),
+            );
+        });
+    }
+
+    fn scene_rect(&self, window: &Window) -> egui::Rect {
+        let size = window.inner_size();
+        let [x, y, width, height] = self
+            .preview
+            .letterbox(size.width, size.height)
+            .map(|value| value as f32 / self.state.egui_ctx().pixels_per_point());
+
+        egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(width, height))
+    }
+
+    fn compare_divider(&mut self, rect: egui::Rect) {
+        if !self.compare.enabled || self.compare.baseline.is_none() {
+            return;
+        }
+
+        let ctx = self.state.egui_ctx();
+        let x = rect.left() + self.compare.split * rect.width();
+
+        egui::Area::new(egui::Id::new("compare_divider"))
+            .fixed_pos(egui::pos2(x - 4., rect.top()))
+            .order(egui::Order::Background)
+            .show(ctx, |ui| {
+                let (response, painter) =
+                    ui.allocate_painter(egui::vec2(8., rect.height()), egui::Sense::drag());
+
+                if response.dragged()
+                    && let Some(pointer) = response.interact_pointer_pos()
+                {
+                    self.compare.split = ((pointer.x - rect.left()) / rect.width()).clamp(0., 1.);
+                }
+
+                let stroke = egui::Stroke::new(2., egui::Color32::WHITE);
+                painter.vline(x, rect.y_range(), stroke);
+
+                let font = egui::FontId::proportional(14.);
+                painter.text(
+                    egui::pos2(x - 8., rect.top() + 8.),
+                    egui::Align2::RIGHT_TOP,
+                    "Live",
+                    font.clone(),
+                    egui::Color32::WHITE,
+                );
+                painter.text(
+                    egui::pos2(x + 8., rect.top() + 8.),
+                    egui::Align2::LEFT_TOP,
+                    "Baseline",
+                    font,
+                    egui::Color32::WHITE,
+                );
+            })
+            .response
+            .on_hover_cursor(egui::CursorIcon::ResizeHorizontal);
+    }
+
+    fn touch_panel(ui: &mut egui::Ui, touch: &mut UiTouch) {
+        let defaults = UiTouch::default();
+        ui.checkbox(&mut touch.enabled, "Touch gestures")
+            .on_hover_text("Tap to select, pinch to resize, twist to rotate the selected wave");
+        ui.add_enabled_ui(touch.enabled, |ui| {
+            ui.checkbox(&mut touch.pen_draws, "Pen draws new waves")
+                .on_hover_text("Pen pressure sets the thickness of the wave being drawn");
+            ui.add_enabled_ui(touch.pen_draws, |ui| {
+                ui.add(
+                    Param::new(&mut touch.min_thickness, 0.001..=0.05)
+                        .text("Min Thickness")
+                        .default(defaults.min_thickness),
+                );
+                ui.add(
+                    Param::new(&mut touch.max_thickness, 0.01..=0.1)
+                        .text("Max Thickness")
+                        .default(defaults.max_thickness),
+                );
+            });
+        });
+    }
+
+    fn snap_panel(ui: &mut egui::Ui, snap: &mut Snap) {
+        let defaults = Snap::default();
+        ui.checkbox(&mut snap.enabled, "Snap Centers");
+
+        ui.add_enabled_ui(snap.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut snap.mode, SnapMode::Center, "Center");
+                ui.selectable_value(&mut snap.mode, SnapMode::Thirds, "Thirds");
+                ui.selectable_value(&mut snap.mode, SnapMode::Grid, "Grid");
+            });
+
+            if snap.mode == SnapMode::Grid {
+                ui.add(
+                    Param::new(&mut snap.divisions, 2..=32)
+                        .text("Divisions")
+                        .default(defaults.divisions),
+                );
+            }
+        });
+
+        ui.checkbox(&mut snap.guides, "Alignment Guides");
+    }
+
+    fn hud_panel(ui: &mut egui::Ui, hud: &mut UiHud) {
+        let defaults = UiHud::default();
+        ui.checkbox(&mut hud.enabled, "Show for selected wave")
+            .on_hover_text("Drag the handles to change radius, thickness and amplitude");
+
+        ui.add_enabled_ui(hud.enabled, |ui| {
+            ui.add(
+                Param::new(&mut hud.opacity, 0.1..=1.0)
+                    .text("Opacity")
+                    .default(defaults.opacity),
+            );
+            ui.horizontal(|ui| {
+                ui.label("Color");
+                ui.color_edit_button_rgb(&mut hud.color);
+            });
+        });
+    }
+
+    fn synth_panel(ui: &mut egui::Ui, synth: &mut UiSynth) {
+        let defaults = UiSynth::default();
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut synth.enabled, "Sonify Waves")
+                .on_hover_text("Radius sets pitch, amplitude sets volume, cycles add harmonics");
+            ui.toggle_value(&mut synth.muted, "Mute");
+        });
+
+        ui.add_enabled_ui(synth.enabled, |ui| {
+            ui.add(
+                Param::new(&mut synth.volume, 0.0..=1.0)
+                    .text("Volume")
+                    .default(defaults.volume),
+            );
+            ui.add(
+                Param::new(&mut synth.octaves, 1.0..=8.0)
+                    .text("Pitch Range")
+                    .default(defaults.octaves),
+            );
+            ui.checkbox(&mut synth.quantize, "Snap to semitones");
+        });
+
+        if let Some(status) = &synth.status {
+            ui.colored_label(egui::Color32::RED, status);
+        }
+    }
+
+    fn audio_panel(ui: &mut egui::Ui, state: &mut UiAudio, audio: &mut Audio) {
+        let defaults = UiAudio::default();
+        #[cfg(feature = "audio")]
+        {
+            use crate::audio::player::PlayerStatus;
+
+            let player = &mut audio.player;
+
+            ui.horizontal(|ui| {
+                ui.label("File:");
+                ui.text_edit_singleline(&mut state.path);
+                if ui.button("Load").clicked() && !state.path.is_empty() {
+                    player.load(state.path.trim());
+                }
+            });
+
+            match player.status() {
+                PlayerStatus::Empty => {
+                    ui.label("No track loaded");
+                }
+                PlayerStatus::Loading(path) => {
+                    ui.label(format!("Decoding {}...", path.display()));
+                }
+                PlayerStatus::Ready(path) => {
+                    ui.label(format!("Loaded {}", path.display()));
+                }
+                PlayerStatus::Failed(err) => {
+                    ui.colored_label(egui::Color32::RED, err);
+                }
+            }
+
+            ui.horizontal(|ui| {
+                if player.is_playing() {
+                    if ui.button("Pause").clicked() {
+                        player.pause();
+                    }
+                } else if ui.button("Play").clicked() {
+                    player.play();
+                }
+
+                if ui.button("Stop").clicked() {
+                    player.stop();
+                }
+
+                let mut looping = player.looping();
+                if ui.checkbox(&mut looping, "Loop").changed() {
+                    player.set_looping(looping);
+                }
+            });
+
+            let duration = player.duration();
+            let mut position = player.position();
+            let time = format!("{} / {}", format_time(position), format_time(duration));
+            let seek = ui.add(
+                egui::Slider::new(&mut position, 0.0..=duration.max(f64::EPSILON))
+                    .show_value(false)
+                    .text(time),
+            );
+            if seek.changed() {
+                player.seek(position);
+            }
+
+            let mut volume = player.volume();
+            if ui
+                .add(
+                    Param::new(&mut volume, 0.0..=1.0)
+                        .text("Volume")
+                        .default(1.),
+                )
+                .changed()
+            {
+                player.set_volume(volume);
+            }
+        }
+
+        #[cfg(not(feature = "audio"))]
+        {
+            let _ = &mut state.path;
+            ui.label("Audio playback requires building with the `audio` feature");
+        }
+
+        ui.add(
+            Param::new(&mut state.reactivity, 0.0..=1.0)
+                .text("Reactivity")
+                .default(defaults.reactivity),
+        );
+        ui.checkbox(&mut state.scope_trigger, "Oscilloscope Trigger Sync");
+        ui.add_enabled_ui(state.gpu_available, |ui| {
+            ui.checkbox(&mut state.gpu_analysis, "GPU Analysis (8192 bins)")
+                .on_disabled_hover_text("Requires compute shader support");
+        });
+
+        let features = *audio.features();
+        for (name, value) in [
+            ("Level", features.level),
+            ("Bass", features.bass),
+            ("Mid", features.mid),
+            ("Treble", features.treble),
+            ("Beat", features.beat),
+        ] {
+            ui.add(egui::ProgressBar::new(value).text(name));
+        }
+
+        let spectrum = audio.spectrum();
+        let (rect, _) =
+            ui.allocate_exact_size(egui::vec2(ui.available_width(), 48.), egui::Sense::hover());
+        let bars = 64;
+        let bar_width = rect.width() / bars as f32;
+        for (bar, bins) in spectrum.chunks(spectrum.len().div_ceil(bars)).enumerate() {
+            let magnitude = bins.iter().copied().fold(0., f32::max);
+            let x = rect.left() + bar as f32 * bar_width;
+            ui.painter().rect_filled(
+                egui::Rect::from_min_max(
+                    egui::pos2(x, rect.bottom() - magnitude * rect.height()),
+                    egui::pos2(x + bar_width * 0.8, rect.bottom()),
+                ),
+                0.,
+                egui::Color32::LIGHT_BLUE,
+            );
+        }
+    }
+
+    pub(crate) fn render(
+        &mut self,
+        window: &Window,
+        device: &Device,
+        queue: &Queue,
+        texture_view: &TextureView,
+        encoder: &mut CommandEncoder,
+        audio: &mut Audio,
+    ) {
+        let screen = self.screen_key(window);
+        if !self.repaint_schedule.due(&self.repaint, screen) {
+            self.paint(texture_view, encoder, &screen.descriptor());
+            return;
+        }
+
+        self.begin_frame(window);
+
+        if self.hidden {
+            self.end_frame(window, device, queue, texture_view, encoder);
+            return;
+        }
+
+        if self.widget.enabled {
+            if let Some(settling) = self.widget.update(window) {
+                self.state.egui_ctx().request_repaint_after(settling);
+            }
+            self.widget_menu(window);
+            self.end_frame(window, device, queue, texture_view, encoder);
+            return;
+        }
+
+        self.panel(audio);
+        self.bypass_indicator();
+
+        let scene_rect = self.scene_rect(window);
+        self.compare_divider(scene_rect);
+        if self.mode != UiMode::Console {
+            self.scene.camera.update(self.state.egui_ctx(), scene_rect);
+        }
+        let view_rect = self.scene.camera.view_rect(scene_rect);
+
+        self.debug_view.show(self.state.egui_ctx());
+        self.log_view.show(self.state.egui_ctx());
+        self.errors_panel();
+        self.startup_dialog();
+        self.spawner.show(
+            &self.state.egui_ctx().clone(),
+            view_rect,
+            &mut self.waves,
+            self.scene.stretch,
+        );
+
+        let console = self.mode == UiMode::Console;
+        if !console && self.brush.enabled {
+            self.brush.update(
+                self.state.egui_ctx(),
+                view_rect,
+                &mut self.waves,
+                &mut self.scene.selection,
+            );
+        } else if !console && !self.touch.gesturing() {
+            self.canvas.update(
+                self.state.egui_ctx(),
+                view_rect,
+                &mut self.waves,
+                &mut self.scene.selection,
+                &mut self.hud,
+                self.scene.stretch,
+            );
+        }
+        self.touch
+            .paint(self.state.egui_ctx(), view_rect, &self.waves);
+        if !console {
+            self.trail
+                .update(self.state.egui_ctx(), view_rect, &mut self.transients);
+        }
+
+        self.end_frame(window, device, queue, texture_view, encoder);
+    }
+
+    pub(crate) fn hud_wave(&self) -> Option<&UiSineWaveData> {
+        if !self.hud.enabled || self.hidden || self.widget.enabled || self.mode == UiMode::Console {
+            return None;
+        }
+
+        let selected = self.scene.selection.selected?;
+        self.waves.0.get(selected).filter(|wave| wave.init)
+    }
+
+    fn widget_menu(&mut self, window: &Window) {
+        let ctx = self.state.egui_ctx().clone();
+        let defaults = UiWidget::default();
+        let mut preset = None;
+        let mut exit = false;
+
+        egui::CentralPanel::default()
+            .frame(egui::Frame::NONE)
+            .show(&ctx, |ui| {
+                let response = ui.interact(
+                    ui.max_rect(),
+                    ui.id().with("widget_drag"),
+                    egui::Sense::click_and_drag(),
+                );
+                if response.drag_started_by(egui::PointerButton::Primary)
+                    && let Err(err) = window.drag_window()
+                {
+                    log::warn!("Failed to drag the widget window: {err}");
+                }
+
+                response.context_menu(|ui| {
+                    ui.menu_button("Presets", |ui| {
+                        if self.presets.names.is_empty() {
+                            ui.weak("No saved presets yet");
+                        }
+                        for name in &self.presets.names {
+                            if ui.button(name).clicked() {
+                                preset = Some(name.clone());
+                                ui.close();
+                            }
+                        }
+                    });
+
+                    ui.separator();
+                    ui.add_enabled(
+                        self.widget.translucent,
+                        Param::new(&mut self.widget.opacity, 0.1..=1.0)
+                            .text("Opacity")
+                            .default(defaults.opacity),
+                    );
+                    if !self.widget.translucent {
+                        ui.weak("Transparency is not supported by this surface");
+                    }
+                    ui.add(
+                        Param::new(&mut self.widget.width, 120..=1024)
+                            .text("Width")
+                            .unit(Unit::Px)
+                            .default(defaults.width),
+                    );
+                    ui.add(
+                        Param::new(&mut self.widget.height, 120..=1024)
+                            .text("Height")
+                            .unit(Unit::Px)
+                            .default(defaults.height),
+                    );
+
+                    ui.separator();
+                    ui.checkbox(&mut self.widget.snap, "Snap to corners");
+                    for corner in Corner::ALL {
+                        if ui
+                            .selectable_label(self.widget.corner == corner, corner.label())
+                            .clicked()
+                        {
+                            self.widget.corner = corner;
+                            self.widget.placed = false;
+                        }
+                    }
+
+                    ui.separator();
+                    if ui.button("Exit Widget Mode").clicked() {
+                        exit = true;
+                        ui.close();
+                    }
+                });
+            });
+
+        if let Some(name) = preset {
+            self.load_preset(&name);
+        }
+        if exit {
+            self.widget.exit(window);
+        }
+    }
+
+    fn startup_dialog(&mut self) {
+        if !self.startup.open {
+            return;
+        }
+
+        enum Choice {
+            Recent(String),
+            Scene(Preset, &'static str),
+            Keep,
+        }
+
+        let ctx = self.state.egui_ctx().clone();
+        let mut choice = None;
+        egui::Window::new("New Scene")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(&ctx, |ui| {
+                ui.label("Recent:");
+                if self.startup.recent.is_empty() {
+                    ui.weak("No saved presets yet");
+                }
+                for name in &self.startup.recent {
+                    if ui.button(name).clicked() {
+                        choice = Some(Choice::Recent(name.clone()));
+                    }
+                }
+
+                ui.separator();
+                ui.label("Templates:");
+                ui.horizontal_wrapped(|ui| {
+                    for template in Template::ALL {
+                        if ui.button(template.label()).clicked() {
+                            choice = Some(Choice::Scene(template.preset(), template.label()));
+                        }
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Empty Scene").clicked() {
+                        choice = Some(Choice::Scene(startup::empty_preset(), "Empty Scene"));
+                    }
+                    if ui.button("Random Scene").clicked() {
+                        choice = Some(Choice::Scene(startup::random_preset(), "Random Scene"));
+                    }
+                    if ui.button("Close").clicked() {
+                        choice = Some(Choice::Keep);
+                    }
+                });
+
+                ui.separator();
+                ui.checkbox(&mut self.startup.dont_show, "Don't show this again");
+            });
+
+        let Some(choice) = choice else {
+            return;
+        };
+
+        match choice {
+            Choice::Recent(name) => {
+                self.load_preset(&name);
+            }
+            Choice::Scene(preset, label) => {
+                preset.apply(self);
+                self.presets.current = None;
+                self.presets.status = Some(format!("Started from {label}"));
+            }
+            Choice::Keep => {}
+        }
+
+        self.startup.open = false;
+        if self.startup.dont_show {
+            self.settings.show_startup = false;
+        }
+    }
+
+    fn errors_panel(&mut self) {
+        let ctx = self.state.egui_ctx().clone();
+
+        if let Some(shown) = self.errors.toast
+            && let Some(latest) = self.errors.entries.last()
+        {
+            if shown.elapsed() < TOAST_DURATION {
+                egui::Area::new(egui::Id::new("gpu_error_toast"))
+                    .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12., -12.))
+                    .show(&ctx, |ui| {
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            ui.set_max_width(360.);
+                            ui.colored_label(egui::Color32::RED, &latest.message);
+                            if ui.button("Show Log").clicked() {
+                                self.errors.open = true;
+                            }
+                        });
+                    });
+                ctx.request_repaint_after(TOAST_DURATION);
+            } else {
+                self.errors.toast = None;
+            }
+        }
+
+        if !self.errors.open {
+            return;
+        }
+
+        let mut clear = false;
+        egui::Window::new("GPU Errors")
+            .open(&mut self.errors.open)
+            .resizable(true)
+            .default_width(420.)
+            .show(&ctx, |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(300.)
+                    .show(ui, |ui| {
+                        for error in &self.errors.entries {
+                            let message = if error.count > 1 {
+                                format!("{} (x{})", error.message, error.count)
+                            } else {
+                                error.message.clone()
+                            };
+                            ui.label(message);
+                            ui.separator();
+                        }
+                    });
+
+                clear = ui.button("Clear").clicked();
+            });
+
+        if clear {
+            self.errors.entries.clear();
+            self.errors.toast = None;
+        }
+    }
+
+    pub(crate) fn set_debug_target(
+        &mut self,
+        name: &str,
+        view: &TextureView,
+        size: [u32; 2],
+        device: &Device,
+    ) {
+        self.debug_view
+            .set_target(name, view, size, &mut self.renderer, device);
+    }
+
+    pub(crate) fn handle_input(&mut self, window: &Window, event: &WindowEvent) {
+        if let WindowEvent::Touch(touch) = event {
+            let ctx = self.state.egui_ctx();
+            let pixels_per_point = ctx.pixels_per_point();
+            let point =
+                egui::pos2(touch.location.x as f32, touch.location.y as f32) / pixels_per_point;
+            let over_ui = ctx.layer_id_at(point).is_some();
+            let view_rect = self.scene.camera.view_rect(self.scene_rect(window));
+
+            // Paint mode owns the pen, so it doesn't also start a touch-drawn wave.
+            if self.brush.enabled {
+                self.brush.handle_touch(touch);
+            } else {
+                self.touch.handle(
+                    touch,
+                    pixels_per_point,
+                    view_rect,
+                    self.scene.stretch,
+                    over_ui,
+                    &mut self.waves,
+                );
+            }
+        }
+
+        if !self.state.egui_ctx().wants_keyboard_input() {
+            self.bursts.handle_input(event);
+            self.bypass.handle_input(event);
+        }
+        self.replay.handle_input(event);
+        self.widget.handle_input(event);
+
+        if self.state.on_window_event(window, event).repaint {
+            self.repaint_schedule.request();
+        }
+    }
+}
+
+fn edge_strip(count: usize) -> Vec<ArtNetPoint> {
+    const INSET: f32 = 0.02;
+
+    (0..count)
+        .map(|i| {
+            let t = 4. * i as f32 / count as f32;
+            let f = t.fract();
+            let position = match t as u32 {
+                0 => [f, 0.],
+                1 => [1., f],
+                2 => [1. - f, 1.],
+                _ => [0., 1. - f],
+            };
+
+            ArtNetPoint {
+                position: position.map(|v| INSET + v * (1. - 2. * INSET)),
+                channel: 1 + 3 * i as u16,
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "audio")]
+fn format_time(seconds: f64) -> String {
+    let seconds = seconds.max(0.) as u64;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
@@ -0,0 +1,63 @@
+use wgpu::{Buffer, BufferSize, CommandEncoder, Device, util::StagingBelt};
+
+const CHUNK_SIZE: u64 = 4096;
+
+pub(crate) struct Uploads {
+    belt: StagingBelt,
+}
+
+impl Default for Uploads {
+    fn default() -> Self {
+        Self {
+            belt: StagingBelt::new(CHUNK_SIZE),
+        }
+    }
+}
+
+impl Uploads {
+    pub(crate) fn begin<'a>(
+        &'a mut self,
+        encoder: &'a mut CommandEncoder,
+        device: &'a Device,
+    ) -> Upload<'a> {
+        encoder.push_debug_group("Uploads");
+
+        Upload {
+            belt: &mut self.belt,
+            encoder,
+            device,
+        }
+    }
+
+    pub(crate) fn finish(&mut self) {
+        self.belt.finish();
+    }
+
+    pub(crate) fn recall(&mut self) {
+        self.belt.recall();
+    }
+}
+
+pub(crate) struct Upload<'a> {
+    belt: &'a mut StagingBelt,
+    encoder: &'a mut CommandEncoder,
+    device: &'a Device,
+}
+
+impl Upload<'_> {
+    pub(crate) fn write(&mut self, target: &Buffer, offset: u64, data: &[u8]) {
+        let Some(size) = BufferSize::new(data.len() as u64) else {
+            return;
+        };
+
+        self.belt
+            .write_buffer(self.encoder, target, offset, size, self.device)
+            .copy_from_slice(data);
+    }
+}
+
+impl Drop for Upload<'_> {
+    fn drop(&mut self) {
+        self.encoder.pop_debug_group();
+    }
+}
@@ -0,0 +1,37 @@
+use anyhow::{Result, bail};
+use winit::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event_loop::ActiveEventLoop,
+    platform::x11::{ActiveEventLoopExtX11, WindowAttributesExtX11, WindowType},
+    window::{Window, WindowAttributes, WindowLevel},
+};
+
+pub(crate) fn window_attributes(event_loop: &ActiveEventLoop) -> Result<WindowAttributes> {
+    // winit can't create wlr-layer-shell surfaces, and a fullscreen window would cover the desktop.
+    if !event_loop.is_x11() {
+        bail!(
+            "`--wallpaper` is not supported on Wayland; run sigil under X11 or XWayland \
+             (e.g. with `WAYLAND_DISPLAY` unset)"
+        );
+    }
+
+    let monitor = event_loop
+        .primary_monitor()
+        .or_else(|| event_loop.available_monitors().next());
+
+    let attributes = Window::default_attributes()
+        .with_title("sigil wallpaper")
+        .with_decorations(false)
+        .with_resizable(false)
+        .with_window_level(WindowLevel::AlwaysOnBottom);
+
+    let (position, size) = monitor.map_or(
+        (PhysicalPosition::default(), PhysicalSize::new(1920, 1080)),
+        |monitor| (monitor.position(), monitor.size()),
+    );
+
+    Ok(attributes
+        .with_x11_window_type(vec![WindowType::Desktop])
+        .with_position(position)
+        .with_inner_size(size))
+}
@@ -0,0 +1,198 @@
+use std::time::{Duration, Instant};
+
+use wgpu::CompositeAlphaMode;
+use winit::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event::WindowEvent,
+    window::{Window, WindowAttributes, WindowLevel},
+};
+
+use crate::branding;
+
+const MARGIN: i32 = 12;
+const SNAP_DISTANCE: f32 = 96.;
+const SETTLE_DELAY: Duration = Duration::from_millis(250);
+const DEFAULT_SIZE: u32 = 320;
+
+pub(crate) fn alpha_mode(supported: &[CompositeAlphaMode]) -> Option<CompositeAlphaMode> {
+    [
+        CompositeAlphaMode::PreMultiplied,
+        CompositeAlphaMode::PostMultiplied,
+    ]
+    .into_iter()
+    .find(|mode| supported.contains(mode))
+}
+
+pub(crate) fn window_attributes() -> WindowAttributes {
+    Window::default_attributes()
+        .with_title(branding::TITLE)
+        .with_decorations(false)
+        .with_transparent(true)
+        .with_resizable(false)
+        .with_window_level(WindowLevel::AlwaysOnTop)
+        .with_inner_size(PhysicalSize::new(DEFAULT_SIZE, DEFAULT_SIZE))
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+impl Corner {
+    pub(crate) const ALL: [Self; 4] = [
+        Self::TopLeft,
+        Self::TopRight,
+        Self::BottomLeft,
+        Self::BottomRight,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::TopLeft => "Top Left",
+            Self::TopRight => "Top Right",
+            Self::BottomLeft => "Bottom Left",
+            Self::BottomRight => "Bottom Right",
+        }
+    }
+
+    fn position(
+        self,
+        origin: PhysicalPosition<i32>,
+        screen: PhysicalSize<u32>,
+        window: PhysicalSize<u32>,
+    ) -> PhysicalPosition<i32> {
+        let right = origin.x + screen.width as i32 - window.width as i32 - MARGIN;
+        let bottom = origin.y + screen.height as i32 - window.height as i32 - MARGIN;
+        let (x, y) = match self {
+            Self::TopLeft => (origin.x + MARGIN, origin.y + MARGIN),
+            Self::TopRight => (right, origin.y + MARGIN),
+            Self::BottomLeft => (origin.x + MARGIN, bottom),
+            Self::BottomRight => (right, bottom),
+        };
+
+        PhysicalPosition::new(x, y)
+    }
+}
+
+pub(crate) struct UiWidget {
+    pub(crate) enabled: bool,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) opacity: f32,
+    pub(crate) corner: Corner,
+    pub(crate) snap: bool,
+    pub(crate) placed: bool,
+    pub(crate) translucent: bool,
+    applied: Option<[u32; 2]>,
+    moved: Option<Instant>,
+}
+
+impl Default for UiWidget {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            width: DEFAULT_SIZE,
+            height: DEFAULT_SIZE,
+            opacity: 0.85,
+            corner: Corner::BottomRight,
+            snap: true,
+            placed: false,
+            translucent: false,
+            applied: None,
+            moved: None,
+        }
+    }
+}
+
+impl UiWidget {
+    pub(crate) fn opacity(&self) -> f32 {
+        if self.enabled && self.translucent {
+            self.opacity
+        } else {
+            1.
+        }
+    }
+
+    pub(crate) fn handle_input(&mut self, event: &WindowEvent) {
+        if !self.enabled {
+            return;
+        }
+
+        match event {
+            WindowEvent::Moved(_) => self.moved = Some(Instant::now()),
+            WindowEvent::Resized(_) => self.placed = false,
+            _ => {}
+        }
+    }
+
+    pub(crate) fn exit(&mut self, window: &Window) {
+        self.enabled = false;
+        self.applied = None;
+        window.set_decorations(true);
+        window.set_resizable(true);
+        window.set_window_level(WindowLevel::Normal);
+    }
+
+    // Window managers report moves while the drag is still in flight, so snapping waits for them to go quiet.
+    pub(crate) fn update(&mut self, window: &Window) -> Option<Duration> {
+        let size = [self.width, self.height];
+        if self.applied != Some(size) {
+            self.applied = Some(size);
+            self.placed = false;
+            let _ = window.request_inner_size(PhysicalSize::new(self.width, self.height));
+        }
+
+        if !self.placed {
+            self.placed = true;
+            self.place(window, self.corner);
+        }
+
+        let settling = SETTLE_DELAY.checked_sub(self.moved?.elapsed());
+        if settling.is_none() {
+            self.moved = None;
+            if self.snap {
+                self.snap_to_nearest(window);
+            }
+        }
+        settling
+    }
+
+    fn snap_to_nearest(&mut self, window: &Window) {
+        let (Some(monitor), Ok(position)) = (window.current_monitor(), window.outer_position())
+        else {
+            return;
+        };
+
+        let outer = window.outer_size();
+        let distance = |corner: Corner| {
+            let target = corner.position(monitor.position(), monitor.size(), outer);
+            let dx = (target.x - position.x) as f32;
+            let dy = (target.y - position.y) as f32;
+            (dx * dx + dy * dy).sqrt()
+        };
+
+        let nearest = Corner::ALL
+            .into_iter()
+            .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+            .unwrap_or_default();
+        if distance(nearest) <= SNAP_DISTANCE {
+            self.corner = nearest;
+            self.place(window, nearest);
+        }
+    }
+
+    fn place(&self, window: &Window, corner: Corner) {
+        let Some(monitor) = window.current_monitor() else {
+            return;
+        };
+
+        let target = corner.position(monitor.position(), monitor.size(), window.outer_size());
+        if window.outer_position().ok() != Some(target) {
+            window.set_outer_position(target);
+        }
+    }
+}